@@ -10,7 +10,7 @@ BUILD=${1:-stable}
 rustup update --no-self-update ${BUILD}
 
 export RUSTFLAGS="-Dwarnings"
-export PROJECTS=core data_cosmos identity messaging_servicebus storage storage_blobs storage_queues storage_datalake data_tables
+export PROJECTS=core data_cosmos identity messaging_servicebus storage storage_blobs storage_queues storage_files storage_datalake data_tables
 
 for PROJ in ${PROJECTS}; do
     cargo check --tests --features test_e2e --manifest-path sdk/$PROJ/Cargo.toml
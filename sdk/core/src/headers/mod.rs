@@ -291,6 +291,7 @@ pub const CONTENT_RANGE: HeaderName = HeaderName::from_static("content-range");
 pub const CONTENT_SECURITY_POLICY: HeaderName = HeaderName::from_static("content-security-policy");
 pub const CONTENT_TYPE: HeaderName = HeaderName::from_static("content-type");
 pub const CONTINUATION: HeaderName = HeaderName::from_static("x-ms-continuation");
+pub const COPY_ACTION: HeaderName = HeaderName::from_static("x-ms-copy-action");
 pub const COPY_COMPLETION_TIME: HeaderName = HeaderName::from_static("x-ms-copy-completion-time");
 pub const COPY_PROGRESS: HeaderName = HeaderName::from_static("x-ms-copy-progress");
 pub const COPY_SOURCE: HeaderName = HeaderName::from_static("x-ms-copy-source");
@@ -301,6 +302,10 @@ pub const CREATION_TIME: HeaderName = HeaderName::from_static("x-ms-creation-tim
 pub const DATE: HeaderName = HeaderName::from_static("date");
 pub const DELETE_SNAPSHOTS: HeaderName = HeaderName::from_static("x-ms-delete-snapshots");
 pub const DELETE_TYPE_PERMANENT: HeaderName = HeaderName::from_static("x-ms-delete-type-permanent");
+pub const DELETED_CONTAINER_NAME: HeaderName =
+    HeaderName::from_static("x-ms-deleted-container-name");
+pub const DELETED_CONTAINER_VERSION: HeaderName =
+    HeaderName::from_static("x-ms-deleted-container-version");
 pub const ETAG: HeaderName = HeaderName::from_static("etag");
 pub const ERROR_CODE: HeaderName = HeaderName::from_static("x-ms-error-code");
 pub const HAS_IMMUTABILITY_POLICY: HeaderName =
@@ -315,6 +320,10 @@ pub const IF_SEQUENCE_NUMBER_EQ: HeaderName = HeaderName::from_static("x-ms-if-s
 pub const IF_SEQUENCE_NUMBER_LE: HeaderName = HeaderName::from_static("x-ms-if-sequence-number-le");
 pub const IF_SEQUENCE_NUMBER_LT: HeaderName = HeaderName::from_static("x-ms-if-sequence-number-lt");
 pub const IF_TAGS: HeaderName = HeaderName::from_static("x-ms-if-tags");
+pub const IMMUTABILITY_POLICY_MODE: HeaderName =
+    HeaderName::from_static("x-ms-immutability-policy-mode");
+pub const IMMUTABILITY_POLICY_UNTIL_DATE: HeaderName =
+    HeaderName::from_static("x-ms-immutability-policy-until-date");
 pub const ITEM_COUNT: HeaderName = HeaderName::from_static("x-ms-item-count");
 pub const ITEM_TYPE: HeaderName = HeaderName::from_static("x-ms-item-type");
 pub const KEEP_ALIVE: HeaderName = HeaderName::from_static("keep-alive");
@@ -326,6 +335,7 @@ pub const LEASE_ID: HeaderName = HeaderName::from_static("x-ms-lease-id");
 pub const LEASE_STATE: HeaderName = HeaderName::from_static("x-ms-lease-state");
 pub const LEASE_STATUS: HeaderName = HeaderName::from_static("x-ms-lease-status");
 pub const LEASE_TIME: HeaderName = HeaderName::from_static("x-ms-lease-time");
+pub const LEGAL_HOLD: HeaderName = HeaderName::from_static("x-ms-legal-hold");
 pub const LINK: HeaderName = HeaderName::from_static("link");
 pub const LOCATION: HeaderName = HeaderName::from_static("location");
 pub const MAX_ITEM_COUNT: HeaderName = HeaderName::from_static("x-ms-max-item-count");
@@ -352,6 +362,7 @@ pub const SERVER: HeaderName = HeaderName::from_static("server");
 pub const SERVER_ENCRYPTED: HeaderName = HeaderName::from_static("x-ms-server-encrypted");
 pub const SESSION_TOKEN: HeaderName = HeaderName::from_static("x-ms-session-token");
 pub const SKU_NAME: HeaderName = HeaderName::from_static("x-ms-sku-name");
+pub const SOURCE_CONTAINER_NAME: HeaderName = HeaderName::from_static("x-ms-source-container-name");
 pub const SOURCE_IF_MATCH: HeaderName = HeaderName::from_static("x-ms-source-if-match");
 pub const SOURCE_IF_MODIFIED_SINCE: HeaderName =
     HeaderName::from_static("x-ms-source-if-modified-since");
@@ -369,6 +380,7 @@ pub const ENCRYPTION_KEY: HeaderName = HeaderName::from_static("x-ms-encryption-
 pub const ENCRYPTION_KEY_SHA256: HeaderName = HeaderName::from_static("x-ms-encryption-key-sha256");
 pub const BLOB_COMMITTED_BLOCK_COUNT: HeaderName =
     HeaderName::from_static("x-ms-blob-committed-block-count");
+pub const BLOB_APPEND_OFFSET: HeaderName = HeaderName::from_static("x-ms-blob-append-offset");
 pub const AZURE_ASYNCOPERATION: HeaderName = HeaderName::from_static("azure-asyncoperation");
 pub const OPERATION_LOCATION: HeaderName = HeaderName::from_static("operation-location");
 pub const SOURCE_RANGE: HeaderName = HeaderName::from_static("x-ms-source-range");
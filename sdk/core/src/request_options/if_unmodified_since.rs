@@ -0,0 +1,30 @@
+use crate::{
+    date,
+    headers::{self, Header},
+};
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Copy)]
+pub struct IfUnmodifiedSince(OffsetDateTime);
+
+impl IfUnmodifiedSince {
+    pub fn new(time: OffsetDateTime) -> Self {
+        Self(time)
+    }
+}
+
+impl Header for IfUnmodifiedSince {
+    fn name(&self) -> headers::HeaderName {
+        headers::IF_UNMODIFIED_SINCE
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        date::to_rfc1123(&self.0).into()
+    }
+}
+
+impl From<OffsetDateTime> for IfUnmodifiedSince {
+    fn from(time: OffsetDateTime) -> Self {
+        Self::new(time)
+    }
+}
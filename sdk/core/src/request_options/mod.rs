@@ -6,6 +6,7 @@ mod if_modified_since_condition;
 mod if_sequence_number;
 mod if_source_match_condition;
 mod if_source_modified_since_condition;
+mod if_unmodified_since;
 mod lease;
 mod lease_break_period;
 mod lease_duration;
@@ -27,6 +28,7 @@ pub use if_modified_since_condition::IfModifiedSinceCondition;
 pub use if_sequence_number::IfSequenceNumber;
 pub use if_source_match_condition::IfSourceMatchCondition;
 pub use if_source_modified_since_condition::IfSourceModifiedSinceCondition;
+pub use if_unmodified_since::IfUnmodifiedSince;
 pub use lease::LeaseId;
 pub use lease_break_period::LeaseBreakPeriod;
 pub use lease_duration::LeaseDuration;
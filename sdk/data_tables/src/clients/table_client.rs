@@ -1,6 +1,17 @@
 use crate::{clients::*, operations::*};
-use azure_core::{headers::Headers, to_json, Body, Context, Method, Request, Response, Url};
+use azure_core::{
+    auth::Secret,
+    error::{Error, ErrorKind},
+    headers::Headers,
+    to_json, Body, Context, Method, Request, Response, Url,
+};
+use azure_storage::{
+    clients::{service_sas_signing_key, ServiceType},
+    prelude::TableSasPermissions,
+    shared_access_signature::{service_sas::TableSharedAccessSignature, SasToken},
+};
 use serde::{de::DeserializeOwned, Serialize};
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone)]
 pub struct TableClient {
@@ -44,6 +55,61 @@ impl TableClient {
         self.table_service_client.url()
     }
 
+    /// Create a shared access signature for the table.
+    ///
+    /// The signature can be restricted to a range of entities with its partition and row key setters.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: TableSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<TableSharedAccessSignature> {
+        let (canonicalized_resource, key) = self.signing_key().await?;
+        Ok(TableSharedAccessSignature::new(
+            key,
+            canonicalized_resource,
+            self.table_name.clone(),
+            permissions,
+            expiry,
+        ))
+    }
+
+    /// Create a shared access signature for the table, governed by one of its stored access policies.
+    pub async fn stored_access_policy_signature(
+        &self,
+        identifier: impl Into<String>,
+    ) -> azure_core::Result<TableSharedAccessSignature> {
+        let (canonicalized_resource, key) = self.signing_key().await?;
+        Ok(TableSharedAccessSignature::from_stored_access_policy(
+            key,
+            canonicalized_resource,
+            self.table_name.clone(),
+            identifier.into(),
+        ))
+    }
+
+    async fn signing_key(&self) -> azure_core::Result<(String, Secret)> {
+        service_sas_signing_key(
+            self.table_service_client.credentials(),
+            ServiceType::Table,
+            &self.table_name.to_lowercase(),
+        )
+        .await
+    }
+
+    /// The URL of the table, signed with `signature`.
+    pub fn generate_signed_table_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.path_segments_mut()
+            .map_err(|()| Error::message(ErrorKind::DataConversion, "Invalid url"))?
+            .pop()
+            .push(self.table_name());
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
     pub fn partition_key_client<PK: Into<String>>(&self, partition_key: PK) -> PartitionKeyClient {
         PartitionKeyClient::new(self.clone(), partition_key)
     }
@@ -2,7 +2,9 @@ use crate::operations::ListTablesBuilder;
 use azure_core::{
     headers::Headers, Body, ClientOptions, Context, Method, Pipeline, Request, Response, Url,
 };
-use azure_storage::{clients::ServiceType, prelude::StorageCredentials, CloudLocation};
+use azure_storage::{
+    clients::ServiceType, prelude::StorageCredentials, CloudLocation, ConnectionString,
+};
 
 use super::TableClient;
 
@@ -43,6 +45,18 @@ impl TableServiceClientBuilder {
         }
     }
 
+    /// Create a new instance of `TableServiceClientBuilder` from a storage connection string.
+    ///
+    /// The endpoint of the service, or else the account name, protocol and endpoint suffix, give
+    /// the location. The shared access signature or account key give the credentials.
+    pub fn from_connection_string(connection_string: &str) -> azure_core::Result<Self> {
+        let connection_string = ConnectionString::new(connection_string)?;
+        Ok(Self::with_location(
+            connection_string.cloud_location(ServiceType::Table)?,
+            connection_string.storage_credentials()?,
+        ))
+    }
+
     /// Use the emulator with default settings
     #[must_use]
     pub fn emulator() -> Self {
@@ -64,8 +78,12 @@ impl TableServiceClientBuilder {
             credentials,
         } = self;
         TableServiceClient {
-            pipeline: azure_storage::clients::new_pipeline_from_options(options, credentials),
+            pipeline: azure_storage::clients::new_pipeline_from_options(
+                options,
+                credentials.clone(),
+            ),
             cloud_location,
+            credentials,
         }
     }
 
@@ -104,6 +122,7 @@ impl TableServiceClientBuilder {
 pub struct TableServiceClient {
     pipeline: Pipeline,
     cloud_location: CloudLocation,
+    credentials: StorageCredentials,
 }
 
 impl TableServiceClient {
@@ -111,6 +130,11 @@ impl TableServiceClient {
         TableServiceClientBuilder::new(account, credentials).build()
     }
 
+    /// Create a new `TableServiceClient` from a storage connection string.
+    pub fn from_connection_string(connection_string: &str) -> azure_core::Result<Self> {
+        Ok(TableServiceClientBuilder::from_connection_string(connection_string)?.build())
+    }
+
     pub fn list(&self) -> ListTablesBuilder {
         ListTablesBuilder::new(self.clone())
     }
@@ -125,6 +149,10 @@ impl TableServiceClient {
         TableClient::new(self.clone(), table_name)
     }
 
+    pub(crate) fn credentials(&self) -> &StorageCredentials {
+        &self.credentials
+    }
+
     pub(crate) fn finalize_request(
         url: Url,
         method: Method,
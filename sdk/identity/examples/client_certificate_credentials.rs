@@ -4,9 +4,7 @@
 /// please make sure to set the `send_certificate_chain` option to true otherwise
 /// the authentication will fail.
 use azure_core::auth::{Secret, TokenCredential};
-use azure_identity::{
-    ClientCertificateCredential, ClientCertificateCredentialOptions, DefaultAzureCredential,
-};
+use azure_identity::{ClientCertificateCredential, ClientCertificateCredentialOptions};
 use azure_security_keyvault::KeyvaultClient;
 use std::env::var;
 use url::Url;
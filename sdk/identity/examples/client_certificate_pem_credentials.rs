@@ -0,0 +1,30 @@
+/// An example showcasing how to use a PEM encoded certificate for AAD app authentication
+/// without OpenSSL. The file is expected to contain the certificate chain, starting with the
+/// leaf certificate, followed by the unencrypted private key.
+use azure_core::auth::TokenCredential;
+use azure_identity::{ClientCertificateCredential, ClientCertificateCredentialOptions};
+use std::env::var;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt().init();
+
+    let client_id = var("CLIENT_ID").expect("Missing CLIENT_ID environment variable.");
+    let tenant_id = var("TENANT_ID").expect("Missing TENANT_ID environment variable.");
+    let cert_path = var("CERT_PATH").expect("Missing CERT_PATH environment variable.");
+    let cert = std::fs::read_to_string(cert_path)?;
+
+    let mut options = ClientCertificateCredentialOptions::default();
+    // send the x5c certificate chain, required for subject name/issuer authentication
+    options.set_send_certificate_chain(true);
+
+    let creds =
+        ClientCertificateCredential::new(tenant_id, client_id, cert, String::new(), options);
+
+    let res = creds
+        .get_token(&["https://management.azure.com/.default"])
+        .await?;
+    println!("client certificate response == {res:?}");
+
+    Ok(())
+}
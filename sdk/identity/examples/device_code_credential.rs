@@ -0,0 +1,47 @@
+use azure_core::auth::TokenCredential;
+use azure_identity::{
+    device_code_flow::DeviceCodePhaseOneResponse, DeviceCodeCredential, TokenCredentialOptions,
+};
+use clap::Parser;
+use std::error::Error;
+
+#[derive(Debug, Parser)]
+struct Args {
+    tenant_id: String,
+    client_id: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let Args {
+        tenant_id,
+        client_id,
+    } = Args::parse();
+
+    let creds = DeviceCodeCredential::new(
+        tenant_id,
+        client_id,
+        |response: &DeviceCodePhaseOneResponse| {
+            eprintln!(
+                "Go to {} and enter the code {}",
+                response.verification_uri(),
+                response.user_code()
+            );
+        },
+        TokenCredentialOptions::default(),
+    );
+
+    // The first call prompts the user to sign in.
+    let res = creds
+        .get_token(&["https://management.azure.com/.default"])
+        .await?;
+    println!("device code response == {res:?}");
+
+    // Subsequent calls for other scopes use the refresh token without prompting.
+    let res = creds
+        .get_token(&["https://storage.azure.com/.default"])
+        .await?;
+    println!("device code response == {res:?}");
+
+    Ok(())
+}
@@ -87,6 +87,21 @@ impl<'a> DeviceCodePhaseOneResponse<'a> {
         &self.message
     }
 
+    /// The code the user has to enter on the verification page.
+    pub fn user_code(&self) -> &str {
+        &self.user_code
+    }
+
+    /// The URI the user should go to in order to sign in.
+    pub fn verification_uri(&self) -> &str {
+        &self.verification_uri
+    }
+
+    /// Number of seconds before the device code expires.
+    pub fn expires_in(&self) -> u64 {
+        self.expires_in
+    }
+
     /// Polls the token endpoint while the user signs in.
     /// This will continue until either success or error is returned.
     pub fn stream(
@@ -19,6 +19,28 @@ pub async fn exchange(
     client_id: &str,
     client_secret: Option<&str>,
     refresh_token: &Secret,
+) -> azure_core::Result<RefreshTokenResponse> {
+    exchange_for_scopes(
+        http_client,
+        tenant_id,
+        client_id,
+        client_secret,
+        refresh_token,
+        &[],
+    )
+    .await
+}
+
+/// Exchange a refresh token for an access token valid for the given scopes.
+///
+/// When `scopes` is empty the token is issued for the scopes originally consented to.
+pub(crate) async fn exchange_for_scopes(
+    http_client: Arc<dyn HttpClient>,
+    tenant_id: &str,
+    client_id: &str,
+    client_secret: Option<&str>,
+    refresh_token: &Secret,
+    scopes: &[&str],
 ) -> azure_core::Result<RefreshTokenResponse> {
     let encoded = {
         let mut encoded = &mut form_urlencoded::Serializer::new(String::new());
@@ -30,6 +52,9 @@ pub async fn exchange(
         if let Some(client_secret) = client_secret {
             encoded = encoded.append_pair("client_secret", client_secret);
         };
+        if !scopes.is_empty() {
+            encoded = encoded.append_pair("scope", &scopes.join(" "));
+        }
         encoded.finish()
     };
 
@@ -1,4 +1,4 @@
-use crate::token_credentials::cache::TokenCache;
+use crate::token_credentials::{cache::TokenCache, CredentialUnavailableError};
 use async_process::Command;
 use azure_core::{
     auth::{AccessToken, Secret, TokenCredential},
@@ -208,13 +208,20 @@ impl AzureCliCredential {
             }
             Ok(az_output) => {
                 let output = String::from_utf8_lossy(&az_output.stderr);
+                // the user is not signed in to the Azure CLI
+                if output.contains("az login") {
+                    return Err(CredentialUnavailableError::new(format!(
+                        "'az account get-access-token' command failed: {output}"
+                    ))
+                    .into_error());
+                }
                 Err(Error::with_message(ErrorKind::Credential, || {
                     format!("'az account get-access-token' command failed: {output}")
                 }))
             }
             Err(e) => match e.kind() {
                 std::io::ErrorKind::NotFound => {
-                    Err(Error::message(ErrorKind::Other, "Azure CLI not installed"))
+                    Err(CredentialUnavailableError::new("Azure CLI not installed").into_error())
                 }
                 error_kind => Err(Error::with_message(ErrorKind::Other, || {
                     format!("Unknown error of kind: {error_kind:?}")
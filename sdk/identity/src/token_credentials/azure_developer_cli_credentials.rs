@@ -0,0 +1,236 @@
+use crate::{
+    timeout::TimeoutExt,
+    token_credentials::{cache::TokenCache, CredentialUnavailableError},
+};
+use async_process::Command;
+use azure_core::{
+    auth::{AccessToken, Secret, TokenCredential},
+    error::{Error, ErrorKind},
+    from_json,
+};
+use serde::{Deserialize, Deserializer};
+use std::{str, time::Duration};
+use time::OffsetDateTime;
+use tracing::trace;
+
+/// The default time to wait for the Azure Developer CLI to return a token.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn deserialize_rfc3339<'de, D>(deserializer: D) -> Result<OffsetDateTime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    azure_core::date::parse_rfc3339(&s).map_err(serde::de::Error::custom)
+}
+
+/// The response from `azd auth token --output json`.
+#[derive(Debug, Clone, Deserialize)]
+struct AzdTokenResponse {
+    #[serde(rename = "token")]
+    pub access_token: Secret,
+    #[serde(rename = "expiresOn", deserialize_with = "deserialize_rfc3339")]
+    pub expires_on: OffsetDateTime,
+}
+
+/// Enables authentication to Azure Active Directory using the Azure Developer CLI (`azd`) to obtain an access token.
+///
+/// The user must have signed in with `azd auth login`.
+#[derive(Debug)]
+pub struct AzureDeveloperCliCredential {
+    tenant_id: Option<String>,
+    timeout: Duration,
+    cache: TokenCache,
+}
+
+impl Default for AzureDeveloperCliCredential {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AzureDeveloperCliCredential {
+    pub fn create() -> azure_core::Result<Self> {
+        Ok(AzureDeveloperCliCredential::new())
+    }
+
+    /// Create a new `AzureDeveloperCliCredential`
+    pub fn new() -> Self {
+        Self {
+            tenant_id: None,
+            timeout: DEFAULT_TIMEOUT,
+            cache: TokenCache::new(),
+        }
+    }
+
+    /// Request tokens from a specific tenant instead of the default tenant of the signed in account.
+    pub fn with_tenant_id<T>(mut self, tenant_id: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Set the time to wait for the Azure Developer CLI to return a token.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    async fn get_access_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        validate_args(scopes, self.tenant_id.as_deref())?;
+
+        // on window azd is a cmd and it should be called like this
+        // see https://doc.rust-lang.org/nightly/std/process/struct.Command.html
+        let program = if cfg!(target_os = "windows") {
+            "cmd"
+        } else {
+            "azd"
+        };
+        let mut args = Vec::new();
+        if cfg!(target_os = "windows") {
+            args.push("/C");
+            args.push("azd");
+        }
+        args.extend(["auth", "token", "--output", "json"]);
+        for scope in scopes {
+            args.push("--scope");
+            args.push(scope);
+        }
+        if let Some(tenant_id) = &self.tenant_id {
+            args.push("--tenant-id");
+            args.push(tenant_id);
+        }
+
+        trace!(
+            "fetching credential via Azure Developer CLI: {program} {}",
+            args.join(" "),
+        );
+
+        let output = Command::new(program)
+            .args(args)
+            .kill_on_drop(true)
+            .output()
+            .timeout(self.timeout)
+            .await
+            .map_err(|error| {
+                CredentialUnavailableError::with_source("'azd auth token' command timed out", error)
+                    .into_error()
+            })?;
+
+        match output {
+            Ok(azd_output) if azd_output.status.success() => {
+                let output = str::from_utf8(&azd_output.stdout)?;
+                let token_response: AzdTokenResponse = from_json(output)?;
+                Ok(AccessToken::new(
+                    token_response.access_token,
+                    token_response.expires_on,
+                ))
+            }
+            Ok(azd_output) => {
+                let output = String::from_utf8_lossy(&azd_output.stderr);
+                // the user is not signed in to the Azure Developer CLI
+                if output.contains("azd auth login") {
+                    return Err(CredentialUnavailableError::new(format!(
+                        "'azd auth token' command failed: {output}"
+                    ))
+                    .into_error());
+                }
+                Err(Error::with_message(ErrorKind::Credential, || {
+                    format!("'azd auth token' command failed: {output}")
+                }))
+            }
+            Err(e) => match e.kind() {
+                std::io::ErrorKind::NotFound => Err(CredentialUnavailableError::new(
+                    "Azure Developer CLI not installed",
+                )
+                .into_error()),
+                error_kind => Err(Error::with_message(ErrorKind::Other, || {
+                    format!("Unknown error of kind: {error_kind:?}")
+                })),
+            },
+        }
+    }
+}
+
+/// Check the scopes and tenant before passing them to `azd`.
+///
+/// On Windows the arguments go through `cmd`, so only characters valid in scopes and tenant ids
+/// are accepted.
+fn validate_args(scopes: &[&str], tenant_id: Option<&str>) -> azure_core::Result<()> {
+    for scope in scopes {
+        validate("scope", scope, |c| {
+            c.is_ascii_alphanumeric() || ".-_:/".contains(c)
+        })?;
+    }
+    if let Some(tenant_id) = tenant_id {
+        validate("tenant id", tenant_id, |c| {
+            c.is_ascii_alphanumeric() || ".-".contains(c)
+        })?;
+    }
+    Ok(())
+}
+
+fn validate(name: &str, value: &str, allowed: impl Fn(char) -> bool) -> azure_core::Result<()> {
+    if value.is_empty() || !value.chars().all(allowed) {
+        return Err(Error::with_message(ErrorKind::Credential, || {
+            format!("invalid {name} for AzureDeveloperCliCredential: '{value}'")
+        }));
+    }
+    Ok(())
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for AzureDeveloperCliCredential {
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        self.cache
+            .get_token(scopes, self.get_access_token(scopes))
+            .await
+    }
+
+    /// Clear the credential's cache.
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        self.cache.clear().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn read_azd_token_response() -> azure_core::Result<()> {
+        let json = br#"
+        {
+            "token": "MuchLonger_NotTheRealOne_Sv8Orn0Wq0OaXuQEg",
+            "expiresOn": "2024-01-01T19:23:16Z"
+        }
+        "#;
+        let token_response: AzdTokenResponse = from_json(json)?;
+        assert_eq!(
+            token_response.access_token.secret(),
+            "MuchLonger_NotTheRealOne_Sv8Orn0Wq0OaXuQEg"
+        );
+        assert_eq!(
+            token_response.expires_on,
+            datetime!(2024-01-01 19:23:16 UTC)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_args() {
+        assert!(validate_args(
+            &["https://management.azure.com/.default"],
+            Some("00000000-0000-0000-0000-000000000000"),
+        )
+        .is_ok());
+        assert!(validate_args(&["https://management.azure.com/.default & calc"], None).is_err());
+        assert!(validate_args(&["https://a/.default|b"], None).is_err());
+        assert!(validate_args(&["https://management.azure.com/.default"], Some("a^b")).is_err());
+        assert!(validate_args(&["https://management.azure.com/.default"], Some("")).is_err());
+    }
+}
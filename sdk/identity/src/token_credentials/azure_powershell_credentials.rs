@@ -0,0 +1,243 @@
+use crate::{
+    timeout::TimeoutExt,
+    token_credentials::{cache::TokenCache, CredentialUnavailableError},
+};
+use async_process::Command;
+use azure_core::{
+    auth::{AccessToken, Secret, TokenCredential},
+    error::{Error, ErrorKind},
+    from_json,
+};
+use serde::Deserialize;
+use std::{str, time::Duration};
+use time::OffsetDateTime;
+use tracing::trace;
+
+/// The default time to wait for Azure PowerShell to return a token.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Written by the script when the `Az.Accounts` module is not installed.
+const NO_AZ_ACCOUNTS_MODULE: &str = "NoAzAccountsModule";
+
+/// The response written by the token script.
+#[derive(Debug, Clone, Deserialize)]
+struct PowerShellTokenResponse {
+    #[serde(rename = "Token")]
+    pub access_token: Secret,
+    #[serde(rename = "ExpiresOn", with = "time::serde::timestamp")]
+    pub expires_on: OffsetDateTime,
+}
+
+/// Enables authentication to Azure Active Directory using Azure PowerShell to obtain an access token.
+///
+/// The user must have signed in with `Connect-AzAccount` and have the `Az.Accounts` module installed.
+#[derive(Debug)]
+pub struct AzurePowerShellCredential {
+    tenant_id: Option<String>,
+    timeout: Duration,
+    cache: TokenCache,
+}
+
+impl Default for AzurePowerShellCredential {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AzurePowerShellCredential {
+    pub fn create() -> azure_core::Result<Self> {
+        Ok(AzurePowerShellCredential::new())
+    }
+
+    /// Create a new `AzurePowerShellCredential`
+    pub fn new() -> Self {
+        Self {
+            tenant_id: None,
+            timeout: DEFAULT_TIMEOUT,
+            cache: TokenCache::new(),
+        }
+    }
+
+    /// Request tokens from a specific tenant instead of the default tenant of the signed in account.
+    pub fn with_tenant_id<T>(mut self, tenant_id: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Set the time to wait for Azure PowerShell to return a token.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    async fn get_access_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        let script = token_script(scopes, self.tenant_id.as_deref())?;
+
+        // pwsh is an executable on every platform, so it is spawned directly rather than through
+        // `cmd`, which would cut the multi-line script at its first newline
+        let args = ["-NoProfile", "-NonInteractive", "-Command", &script];
+
+        trace!(
+            "fetching credential via Azure PowerShell: pwsh {}",
+            args.join(" ")
+        );
+
+        let output = Command::new("pwsh")
+            .args(args)
+            .kill_on_drop(true)
+            .output()
+            .timeout(self.timeout)
+            .await
+            .map_err(|error| {
+                CredentialUnavailableError::with_source("Get-AzAccessToken timed out", error)
+                    .into_error()
+            })?;
+
+        match output {
+            Ok(pwsh_output) if pwsh_output.status.success() => {
+                let output = str::from_utf8(&pwsh_output.stdout)?;
+                if output.contains(NO_AZ_ACCOUNTS_MODULE) {
+                    return Err(CredentialUnavailableError::new(
+                        "Az.Accounts module >= 2.2.0 is not installed",
+                    )
+                    .into_error());
+                }
+                let token_response: PowerShellTokenResponse = from_json(output.trim())?;
+                Ok(AccessToken::new(
+                    token_response.access_token,
+                    token_response.expires_on,
+                ))
+            }
+            Ok(pwsh_output) => {
+                let output = String::from_utf8_lossy(&pwsh_output.stderr);
+                // the user is not signed in to Azure PowerShell
+                if output.contains("Connect-AzAccount") {
+                    return Err(CredentialUnavailableError::new(format!(
+                        "Get-AzAccessToken failed: {output}"
+                    ))
+                    .into_error());
+                }
+                Err(Error::with_message(ErrorKind::Credential, || {
+                    format!("Get-AzAccessToken failed: {output}")
+                }))
+            }
+            Err(e) => match e.kind() {
+                std::io::ErrorKind::NotFound => Err(CredentialUnavailableError::new(
+                    "PowerShell (pwsh) not installed",
+                )
+                .into_error()),
+                error_kind => Err(Error::with_message(ErrorKind::Other, || {
+                    format!("Unknown error of kind: {error_kind:?}")
+                })),
+            },
+        }
+    }
+}
+
+/// Build the PowerShell script that writes the token for `scopes` as JSON.
+///
+/// The scope and tenant are interpolated into the script, so only characters valid in
+/// resource URLs and tenant ids are accepted.
+fn token_script(scopes: &[&str], tenant_id: Option<&str>) -> azure_core::Result<String> {
+    let [scope] = scopes else {
+        return Err(Error::with_message(ErrorKind::Credential, || {
+            format!(
+                "AzurePowerShellCredential requires exactly one scope, got {}",
+                scopes.len()
+            )
+        }));
+    };
+    let resource = scope.strip_suffix("/.default").unwrap_or(scope);
+    validate("scope", resource, |c| {
+        c.is_ascii_alphanumeric() || ".-_:/".contains(c)
+    })?;
+
+    let mut command = format!("Get-AzAccessToken -ResourceUrl '{resource}'");
+    if let Some(tenant_id) = tenant_id {
+        validate("tenant id", tenant_id, |c| {
+            c.is_ascii_alphanumeric() || ".-".contains(c)
+        })?;
+        command.push_str(&format!(" -TenantId '{tenant_id}'"));
+    }
+
+    Ok(format!(
+        "$ErrorActionPreference = 'Stop'
+$m = Import-Module Az.Accounts -MinimumVersion 2.2.0 -PassThru -ErrorAction SilentlyContinue
+if (! $m) {{
+    Write-Output '{NO_AZ_ACCOUNTS_MODULE}'
+    exit
+}}
+$token = {command}
+$t = $token.Token
+if ($t -is [System.Security.SecureString]) {{
+    $t = [System.Net.NetworkCredential]::new('', $t).Password
+}}
+[PSCustomObject]@{{ Token = $t; ExpiresOn = $token.ExpiresOn.ToUnixTimeSeconds() }} | ConvertTo-Json -Compress"
+    ))
+}
+
+fn validate(name: &str, value: &str, allowed: impl Fn(char) -> bool) -> azure_core::Result<()> {
+    if value.is_empty() || !value.chars().all(allowed) {
+        return Err(Error::with_message(ErrorKind::Credential, || {
+            format!("invalid {name} for AzurePowerShellCredential: '{value}'")
+        }));
+    }
+    Ok(())
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for AzurePowerShellCredential {
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        self.cache
+            .get_token(scopes, self.get_access_token(scopes))
+            .await
+    }
+
+    /// Clear the credential's cache.
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        self.cache.clear().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn read_powershell_token_response() -> azure_core::Result<()> {
+        let json =
+            br#"{"Token":"MuchLonger_NotTheRealOne_Sv8Orn0Wq0OaXuQEg","ExpiresOn":1704136996}"#;
+        let token_response: PowerShellTokenResponse = from_json(json)?;
+        assert_eq!(
+            token_response.access_token.secret(),
+            "MuchLonger_NotTheRealOne_Sv8Orn0Wq0OaXuQEg"
+        );
+        assert_eq!(
+            token_response.expires_on,
+            datetime!(2024-01-01 19:23:16 UTC)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_token_script() -> azure_core::Result<()> {
+        let script = token_script(
+            &["https://management.azure.com/.default"],
+            Some("00000000-0000-0000-0000-000000000000"),
+        )?;
+        assert!(script.contains(
+            "Get-AzAccessToken -ResourceUrl 'https://management.azure.com' -TenantId '00000000-0000-0000-0000-000000000000'"
+        ));
+
+        assert!(token_script(&[], None).is_err());
+        assert!(token_script(&["https://a/.default", "https://b/.default"], None).is_err());
+        assert!(token_script(&["https://x'; Remove-Item *; '"], None).is_err());
+        assert!(token_script(&["https://management.azure.com/.default"], Some("a'b")).is_err());
+        Ok(())
+    }
+}
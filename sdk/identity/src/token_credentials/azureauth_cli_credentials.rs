@@ -1,4 +1,4 @@
-use crate::token_credentials::cache::TokenCache;
+use crate::token_credentials::{cache::TokenCache, CredentialUnavailableError};
 use async_process::Command;
 use azure_core::{
     auth::{AccessToken, Secret, TokenCredential},
@@ -158,7 +158,7 @@ impl AzureauthCliCredential {
 
         let output = result.map_err(|e| match e.kind() {
             std::io::ErrorKind::NotFound => {
-                Error::message(ErrorKind::Other, "azureauth CLI not installed")
+                CredentialUnavailableError::new("azureauth CLI not installed").into_error()
             }
             error_kind => Error::with_message(ErrorKind::Other, || {
                 format!("Unknown error of kind: {error_kind:?}")
@@ -0,0 +1,310 @@
+use async_lock::RwLock;
+use azure_core::{
+    auth::{AccessToken, TokenCredential},
+    error::{Error, ErrorKind},
+};
+use std::{fmt, sync::Arc};
+use tracing::{debug, trace};
+
+/// Error returned by a credential that cannot be used in the current environment,
+/// such as when a command line tool is not installed or no managed identity is available.
+///
+/// `ChainedTokenCredential` moves on to its next source when it encounters this error,
+/// any other error is treated as an authentication failure.
+#[derive(Debug, Clone)]
+pub struct CredentialUnavailableError {
+    message: String,
+    source: Option<Arc<Error>>,
+}
+
+impl CredentialUnavailableError {
+    pub fn new<M>(message: M) -> Self
+    where
+        M: Into<String>,
+    {
+        Self {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Create a new `CredentialUnavailableError` caused by `source`.
+    pub fn with_source<M>(message: M, source: Error) -> Self
+    where
+        M: Into<String>,
+    {
+        Self {
+            message: message.into(),
+            source: Some(Arc::new(source)),
+        }
+    }
+
+    /// Wrap this error in an `azure_core::Error` of kind `ErrorKind::Credential`.
+    pub fn into_error(self) -> Error {
+        Error::new(ErrorKind::Credential, self)
+    }
+}
+
+impl fmt::Display for CredentialUnavailableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "credential unavailable: {}", self.message)
+    }
+}
+
+impl std::error::Error for CredentialUnavailableError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|error| error as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// Whether the error, or any error in its source chain, is a `CredentialUnavailableError`.
+pub fn is_credential_unavailable(error: &Error) -> bool {
+    let mut current: Option<&(dyn std::error::Error + 'static)> = error
+        .get_ref()
+        .map(|e| e as &(dyn std::error::Error + 'static));
+    while let Some(error) = current {
+        if error.is::<CredentialUnavailableError>() {
+            return true;
+        }
+        current = error.source();
+    }
+    false
+}
+
+/// Provides a `TokenCredential` that tries each of its sources, in order, until one returns a token.
+///
+/// A source that fails with a `CredentialUnavailableError` is skipped. Any other error is an
+/// authentication failure, which stops the chain. When all sources fail, the returned error
+/// lists why each of them failed.
+///
+/// The first source to return a token is remembered and is used exclusively afterwards,
+/// until `clear_cache` is called.
+#[derive(Debug)]
+pub struct ChainedTokenCredential {
+    sources: Vec<Arc<dyn TokenCredential>>,
+    selected: RwLock<Option<usize>>,
+}
+
+impl ChainedTokenCredential {
+    /// Create a new `ChainedTokenCredential` from the sources to try, in order.
+    pub fn new(sources: Vec<Arc<dyn TokenCredential>>) -> Self {
+        Self {
+            sources,
+            selected: RwLock::new(None),
+        }
+    }
+
+    /// The index of the source that last returned a token, if any.
+    pub async fn selected_source(&self) -> Option<usize> {
+        *self.selected.read().await
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for ChainedTokenCredential {
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        let selected = *self.selected.read().await;
+        if let Some(index) = selected {
+            trace!("using previously selected credential source {index}");
+            return self.sources[index].get_token(scopes).await;
+        }
+
+        let mut errors = Vec::new();
+        for (index, source) in self.sources.iter().enumerate() {
+            match source.get_token(scopes).await {
+                Ok(token) => {
+                    debug!("credential source {index} returned a token");
+                    *self.selected.write().await = Some(index);
+                    return Ok(token);
+                }
+                Err(error) if is_credential_unavailable(&error) => {
+                    debug!("credential source {index} is unavailable");
+                    errors.push(error);
+                }
+                Err(error) => {
+                    debug!("credential source {index} failed to authenticate");
+                    errors.push(error);
+                    return Err(Error::with_message(ErrorKind::Credential, || {
+                        format!(
+                            "Authentication failed:\n{}",
+                            format_aggregate_error(&errors)
+                        )
+                    }));
+                }
+            }
+        }
+
+        Err(Error::with_message(ErrorKind::Credential, || {
+            if errors.is_empty() {
+                "No credential sources were provided".to_owned()
+            } else {
+                format!(
+                    "No credential sources were available to be used for authentication:\n{}",
+                    format_aggregate_error(&errors)
+                )
+            }
+        }))
+    }
+
+    /// Clear the cache of each source, and forget which source was selected.
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        *self.selected.write().await = None;
+        for source in &self.sources {
+            source.clear_cache().await?;
+        }
+        Ok(())
+    }
+}
+
+/// List the source chain of each error, one error per line.
+pub(crate) fn format_aggregate_error(errors: &[Error]) -> String {
+    use std::error::Error;
+    errors
+        .iter()
+        .map(|e| {
+            let mut current: Option<&dyn Error> = Some(e);
+            let mut stack = vec![];
+            while let Some(err) = current.take() {
+                stack.push(err.to_string());
+                current = err.source();
+            }
+            stack.join(" - ")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::{auth::Secret, error::ResultExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use time::OffsetDateTime;
+
+    #[derive(Debug)]
+    enum Outcome {
+        Token,
+        Unavailable,
+        Failed,
+    }
+
+    #[derive(Debug)]
+    struct MockCredential {
+        outcome: Outcome,
+        calls: AtomicUsize,
+    }
+
+    impl MockCredential {
+        fn new(outcome: Outcome) -> Arc<Self> {
+            Arc::new(Self {
+                outcome,
+                calls: AtomicUsize::new(0),
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl TokenCredential for MockCredential {
+        async fn get_token(&self, _scopes: &[&str]) -> azure_core::Result<AccessToken> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match self.outcome {
+                Outcome::Token => Ok(AccessToken::new(
+                    Secret::new("token"),
+                    OffsetDateTime::now_utc(),
+                )),
+                Outcome::Unavailable => {
+                    Err(CredentialUnavailableError::new("mock not installed").into_error())
+                        .context(ErrorKind::Credential, "mock context")
+                }
+                Outcome::Failed => Err(Error::message(ErrorKind::Credential, "mock failed")),
+            }
+        }
+
+        async fn clear_cache(&self) -> azure_core::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_skips_unavailable_and_remembers_source() -> azure_core::Result<()> {
+        let unavailable = MockCredential::new(Outcome::Unavailable);
+        let token = MockCredential::new(Outcome::Token);
+        let credential = ChainedTokenCredential::new(vec![unavailable.clone(), token.clone()]);
+
+        credential.get_token(&["scope"]).await?;
+        credential.get_token(&["scope"]).await?;
+        assert_eq!(credential.selected_source().await, Some(1));
+        assert_eq!(unavailable.calls(), 1);
+        assert_eq!(token.calls(), 2);
+
+        credential.clear_cache().await?;
+        assert_eq!(credential.selected_source().await, None);
+        credential.get_token(&["scope"]).await?;
+        assert_eq!(unavailable.calls(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stops_on_authentication_failure() {
+        let failed = MockCredential::new(Outcome::Failed);
+        let token = MockCredential::new(Outcome::Token);
+        let credential = ChainedTokenCredential::new(vec![failed.clone(), token.clone()]);
+
+        let error = credential.get_token(&["scope"]).await.unwrap_err();
+        assert!(!is_credential_unavailable(&error));
+        assert!(error.to_string().contains("mock failed"));
+        assert_eq!(token.calls(), 0);
+        assert_eq!(credential.selected_source().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_aggregates_unavailable_errors() {
+        let credential = ChainedTokenCredential::new(vec![
+            MockCredential::new(Outcome::Unavailable),
+            MockCredential::new(Outcome::Unavailable),
+        ]);
+
+        let error = credential.get_token(&["scope"]).await.unwrap_err();
+        let message = error.to_string();
+        assert!(message.starts_with("No credential sources were available"));
+        // one line per source
+        assert_eq!(message.lines().skip(1).count(), 2);
+        assert!(message
+            .lines()
+            .skip(1)
+            .all(|line| line.contains("mock not installed")));
+    }
+
+    #[test]
+    fn test_is_credential_unavailable() {
+        let error = CredentialUnavailableError::new("not installed").into_error();
+        assert!(is_credential_unavailable(&error));
+        let error = error.context("outer context");
+        assert!(is_credential_unavailable(&error));
+        assert!(!is_credential_unavailable(&Error::message(
+            ErrorKind::Credential,
+            "failed"
+        )));
+    }
+
+    #[test]
+    fn test_credential_unavailable_keeps_source() {
+        let source = Error::message(ErrorKind::Io, "connection refused");
+        let error =
+            CredentialUnavailableError::with_source("endpoint not reachable", source).into_error();
+        assert!(is_credential_unavailable(&error));
+        let unavailable = error
+            .get_ref()
+            .and_then(|error| error.downcast_ref::<CredentialUnavailableError>())
+            .unwrap();
+        let source = std::error::Error::source(unavailable).unwrap();
+        assert_eq!(source.to_string(), "connection refused");
+    }
+}
@@ -0,0 +1,103 @@
+//! Certificate parsing and signing using OpenSSL.
+
+use super::{is_encrypted_pem, leaf_first};
+use azure_core::error::{Error, ErrorKind};
+use openssl::{
+    error::ErrorStack,
+    hash::{hash, MessageDigest},
+    pkcs12::Pkcs12,
+    pkey::{PKey, Private},
+    sign::Signer,
+    x509::X509,
+};
+
+/// A certificate chain and the private key of the leaf certificate.
+pub(crate) struct CertificateKey {
+    certificates: Vec<Vec<u8>>,
+    pkey: PKey<Private>,
+}
+
+impl CertificateKey {
+    pub(crate) fn from_pkcs12(der: &[u8], password: &str) -> azure_core::Result<Self> {
+        let pkcs12 = Pkcs12::from_der(der)
+            .map_err(openssl_error)?
+            .parse2(password)
+            .map_err(openssl_error)?;
+
+        let Some(cert) = pkcs12.cert else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Certificate not found",
+            ));
+        };
+
+        let Some(pkey) = pkcs12.pkey else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Private key not found",
+            ));
+        };
+
+        let mut certificates = vec![cert];
+        certificates.extend(pkcs12.ca.into_iter().flatten());
+        Self::new(certificates, pkey)
+    }
+
+    /// Read the certificates and the private key of a PEM file, decrypting the key with
+    /// `password` if it is encrypted.
+    pub(crate) fn from_pem(pem: &str, password: &str) -> azure_core::Result<Self> {
+        let certificates = X509::stack_from_pem(pem.as_bytes()).map_err(openssl_error)?;
+        if certificates.is_empty() {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Certificate not found",
+            ));
+        }
+        if is_encrypted_pem(pem) && password.is_empty() {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "the PEM private key is encrypted, a password is required",
+            ));
+        }
+        let pkey = PKey::private_key_from_pem_passphrase(pem.as_bytes(), password.as_bytes())
+            .map_err(openssl_error)?;
+        Self::new(certificates, pkey)
+    }
+
+    fn new(certificates: Vec<X509>, pkey: PKey<Private>) -> azure_core::Result<Self> {
+        let certificates = leaf_first(certificates, |cert| {
+            cert.public_key()
+                .map(|key| key.public_eq(&pkey))
+                .unwrap_or(false)
+        })?
+        .iter()
+        .map(|cert| cert.to_der().map_err(openssl_error))
+        .collect::<azure_core::Result<Vec<_>>>()?;
+        Ok(Self { certificates, pkey })
+    }
+
+    /// The DER encoded certificates, starting with the leaf certificate.
+    pub(crate) fn certificates(&self) -> &[Vec<u8>] {
+        &self.certificates
+    }
+
+    /// Sign with RSASSA-PKCS1-v1_5 using SHA-256.
+    pub(crate) fn sign(&self, data: &[u8]) -> azure_core::Result<Vec<u8>> {
+        let sign = || -> Result<Vec<u8>, ErrorStack> {
+            let mut signer = Signer::new(MessageDigest::sha256(), &self.pkey)?;
+            signer.update(data)?;
+            signer.sign_to_vec()
+        };
+        sign().map_err(openssl_error)
+    }
+}
+
+pub(crate) fn sha1(data: &[u8]) -> azure_core::Result<Vec<u8>> {
+    Ok(hash(MessageDigest::sha1(), data)
+        .map_err(openssl_error)?
+        .to_vec())
+}
+
+fn openssl_error(err: ErrorStack) -> azure_core::error::Error {
+    Error::new(ErrorKind::Credential, err)
+}
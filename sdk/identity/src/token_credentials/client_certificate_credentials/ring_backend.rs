@@ -0,0 +1,148 @@
+//! Certificate parsing and signing without OpenSSL, using `p12`, `rustls-pemfile` and `ring`.
+
+use super::{is_encrypted_pem, leaf_first};
+use azure_core::error::{Error, ErrorKind};
+use ring::{
+    digest::{digest, SHA1_FOR_LEGACY_USE_ONLY},
+    rand::SystemRandom,
+    signature::{RsaKeyPair, RSA_PKCS1_SHA256},
+};
+use rustls_pemfile::Item;
+
+/// A certificate chain and the private key of the leaf certificate.
+pub(crate) struct CertificateKey {
+    certificates: Vec<Vec<u8>>,
+    key_pair: RsaKeyPair,
+}
+
+impl CertificateKey {
+    pub(crate) fn from_pkcs12(der: &[u8], password: &str) -> azure_core::Result<Self> {
+        let pfx = p12::PFX::parse(der).map_err(|e| {
+            Error::with_message(ErrorKind::Credential, || {
+                format!("unable to parse PKCS12 certificate: {e:?}")
+            })
+        })?;
+        if !pfx.verify_mac(password) {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "invalid PKCS12 certificate password",
+            ));
+        }
+
+        let certificates = pfx.cert_x509_bags(password).map_err(|e| {
+            Error::with_message(ErrorKind::Credential, || {
+                format!("unable to read PKCS12 certificates: {e:?}")
+            })
+        })?;
+        if certificates.is_empty() {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Certificate not found",
+            ));
+        }
+
+        let keys = pfx.key_bags(password).map_err(|e| {
+            Error::with_message(ErrorKind::Credential, || {
+                format!("unable to read PKCS12 private key: {e:?}")
+            })
+        })?;
+        let Some(key) = keys.first() else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Private key not found",
+            ));
+        };
+        let key_pair = RsaKeyPair::from_pkcs8(key).map_err(key_rejected)?;
+
+        Self::new(certificates, key_pair)
+    }
+
+    /// Read the certificates and the private key of a PEM file.
+    ///
+    /// Encrypted private keys are only supported with OpenSSL, with the `client_certificate`
+    /// feature.
+    pub(crate) fn from_pem(pem: &str, _password: &str) -> azure_core::Result<Self> {
+        if is_encrypted_pem(pem) {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "encrypted PEM private keys require the client_certificate feature, decrypt the key or use a PKCS12 certificate",
+            ));
+        }
+        let mut certificates = Vec::new();
+        let mut key_pair = None;
+        for item in rustls_pemfile::read_all(&mut pem.as_bytes()) {
+            let item = item.map_err(|e| {
+                Error::full(ErrorKind::Credential, e, "unable to parse PEM certificate")
+            })?;
+            match item {
+                Item::X509Certificate(cert) => certificates.push(cert.to_vec()),
+                Item::Pkcs8Key(key) if key_pair.is_none() => {
+                    key_pair =
+                        Some(RsaKeyPair::from_pkcs8(key.secret_pkcs8_der()).map_err(key_rejected)?);
+                }
+                Item::Pkcs1Key(key) if key_pair.is_none() => {
+                    key_pair =
+                        Some(RsaKeyPair::from_der(key.secret_pkcs1_der()).map_err(key_rejected)?);
+                }
+                _ => {}
+            }
+        }
+        if certificates.is_empty() {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Certificate not found",
+            ));
+        }
+        let Some(key_pair) = key_pair else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Private key not found",
+            ));
+        };
+        Self::new(certificates, key_pair)
+    }
+
+    fn new(certificates: Vec<Vec<u8>>, key_pair: RsaKeyPair) -> azure_core::Result<Self> {
+        // the DER encoded RSA public key is embedded as is in the certificate of the key
+        let public_key = key_pair.public().as_ref();
+        let certificates = leaf_first(certificates, |cert| {
+            cert.windows(public_key.len())
+                .any(|window| window == public_key)
+        })?;
+        Ok(Self {
+            certificates,
+            key_pair,
+        })
+    }
+
+    /// The DER encoded certificates, starting with the leaf certificate.
+    pub(crate) fn certificates(&self) -> &[Vec<u8>] {
+        &self.certificates
+    }
+
+    /// Sign with RSASSA-PKCS1-v1_5 using SHA-256.
+    pub(crate) fn sign(&self, data: &[u8]) -> azure_core::Result<Vec<u8>> {
+        let mut signature = vec![0; self.key_pair.public().modulus_len()];
+        self.key_pair
+            .sign(
+                &RSA_PKCS1_SHA256,
+                &SystemRandom::new(),
+                data,
+                &mut signature,
+            )
+            .map_err(|_| {
+                Error::message(ErrorKind::Credential, "signing client assertion failed")
+            })?;
+        Ok(signature)
+    }
+}
+
+pub(crate) fn sha1(data: &[u8]) -> azure_core::Result<Vec<u8>> {
+    Ok(digest(&SHA1_FOR_LEGACY_USE_ONLY, data).as_ref().to_vec())
+}
+
+fn key_rejected(err: ring::error::KeyRejected) -> Error {
+    Error::with_message(ErrorKind::Credential, || {
+        format!("unsupported RSA private key: {err}")
+    })
+}
@@ -1,9 +1,11 @@
-#[cfg(not(target_arch = "wasm32"))]
-use crate::AzureCliCredential;
 use crate::{
-    timeout::TimeoutExt, token_credentials::cache::TokenCache, AppServiceManagedIdentityCredential,
-    EnvironmentCredential, TokenCredentialOptions, VirtualMachineManagedIdentityCredential,
+    timeout::TimeoutExt,
+    token_credentials::{cache::TokenCache, chained_token_credential::format_aggregate_error},
+    AppServiceManagedIdentityCredential, EnvironmentCredential, TokenCredentialOptions,
+    VirtualMachineManagedIdentityCredential,
 };
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{AzureCliCredential, AzureDeveloperCliCredential, AzurePowerShellCredential};
 use azure_core::{
     auth::{AccessToken, TokenCredential},
     error::{Error, ErrorKind, ResultExt},
@@ -18,6 +20,10 @@ pub struct DefaultAzureCredentialBuilder {
     include_virtual_machine_managed_identity_credential: bool,
     #[cfg(not(target_arch = "wasm32"))]
     include_azure_cli_credential: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    include_azure_powershell_credential: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    include_azure_developer_cli_credential: bool,
 }
 
 impl Default for DefaultAzureCredentialBuilder {
@@ -29,6 +35,10 @@ impl Default for DefaultAzureCredentialBuilder {
             include_virtual_machine_managed_identity_credential: true,
             #[cfg(not(target_arch = "wasm32"))]
             include_azure_cli_credential: true,
+            #[cfg(not(target_arch = "wasm32"))]
+            include_azure_powershell_credential: true,
+            #[cfg(not(target_arch = "wasm32"))]
+            include_azure_developer_cli_credential: true,
         }
     }
 }
@@ -82,6 +92,20 @@ impl DefaultAzureCredentialBuilder {
         self
     }
 
+    /// Exclude using credential from Azure PowerShell
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn exclude_azure_powershell_credential(&mut self) -> &mut Self {
+        self.include_azure_powershell_credential = false;
+        self
+    }
+
+    /// Exclude using credential from the Azure Developer CLI
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn exclude_azure_developer_cli_credential(&mut self) -> &mut Self {
+        self.include_azure_developer_cli_credential = false;
+        self
+    }
+
     /// Get a list of the credential types to include.
     fn included(&self) -> Vec<DefaultAzureCredentialType> {
         let mut sources = Vec::new();
@@ -98,6 +122,14 @@ impl DefaultAzureCredentialBuilder {
         if self.include_azure_cli_credential {
             sources.push(DefaultAzureCredentialType::AzureCli);
         }
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.include_azure_powershell_credential {
+            sources.push(DefaultAzureCredentialType::AzurePowerShell);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.include_azure_developer_cli_credential {
+            sources.push(DefaultAzureCredentialType::AzureDeveloperCli);
+        }
         sources
     }
 
@@ -138,6 +170,18 @@ impl DefaultAzureCredentialBuilder {
                         sources.push(DefaultAzureCredentialKind::AzureCli(credential));
                     }
                 }
+                #[cfg(not(target_arch = "wasm32"))]
+                DefaultAzureCredentialType::AzurePowerShell => {
+                    if let Ok(credential) = AzurePowerShellCredential::create() {
+                        sources.push(DefaultAzureCredentialKind::AzurePowerShell(credential));
+                    }
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                DefaultAzureCredentialType::AzureDeveloperCli => {
+                    if let Ok(credential) = AzureDeveloperCliCredential::create() {
+                        sources.push(DefaultAzureCredentialKind::AzureDeveloperCli(credential));
+                    }
+                }
             }
         }
         if sources.is_empty() {
@@ -167,6 +211,10 @@ enum DefaultAzureCredentialType {
     VirtualMachine,
     #[cfg(not(target_arch = "wasm32"))]
     AzureCli,
+    #[cfg(not(target_arch = "wasm32"))]
+    AzurePowerShell,
+    #[cfg(not(target_arch = "wasm32"))]
+    AzureDeveloperCli,
 }
 
 /// Types of `TokenCredential` supported by `DefaultAzureCredential`
@@ -181,6 +229,12 @@ pub(crate) enum DefaultAzureCredentialKind {
     #[cfg(not(target_arch = "wasm32"))]
     /// `TokenCredential` from Azure CLI.
     AzureCli(AzureCliCredential),
+    #[cfg(not(target_arch = "wasm32"))]
+    /// `TokenCredential` from Azure PowerShell.
+    AzurePowerShell(AzurePowerShellCredential),
+    #[cfg(not(target_arch = "wasm32"))]
+    /// `TokenCredential` from the Azure Developer CLI.
+    AzureDeveloperCli(AzureDeveloperCliCredential),
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -222,6 +276,20 @@ impl TokenCredential for DefaultAzureCredentialKind {
                     "error getting token credential from Azure CLI",
                 )
             }
+            #[cfg(not(target_arch = "wasm32"))]
+            DefaultAzureCredentialKind::AzurePowerShell(credential) => {
+                credential.get_token(scopes).await.context(
+                    ErrorKind::Credential,
+                    "error getting token credential from Azure PowerShell",
+                )
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            DefaultAzureCredentialKind::AzureDeveloperCli(credential) => {
+                credential.get_token(scopes).await.context(
+                    ErrorKind::Credential,
+                    "error getting token credential from Azure Developer CLI",
+                )
+            }
         }
     }
 
@@ -235,6 +303,14 @@ impl TokenCredential for DefaultAzureCredentialKind {
             }
             #[cfg(not(target_arch = "wasm32"))]
             DefaultAzureCredentialKind::AzureCli(credential) => credential.clear_cache().await,
+            #[cfg(not(target_arch = "wasm32"))]
+            DefaultAzureCredentialKind::AzurePowerShell(credential) => {
+                credential.clear_cache().await
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            DefaultAzureCredentialKind::AzureDeveloperCli(credential) => {
+                credential.clear_cache().await
+            }
         }
     }
 }
@@ -245,6 +321,8 @@ impl TokenCredential for DefaultAzureCredentialKind {
 /// - `EnvironmentCredential`
 /// - `ManagedIdentityCredential`
 /// - `AzureCliCredential`
+/// - `AzurePowerShellCredential`
+/// - `AzureDeveloperCliCredential`
 /// Consult the documentation of these credential types for more information on how they attempt authentication.
 #[derive(Debug)]
 pub struct DefaultAzureCredential {
@@ -314,23 +392,6 @@ impl TokenCredential for DefaultAzureCredential {
     }
 }
 
-fn format_aggregate_error(errors: &[Error]) -> String {
-    use std::error::Error;
-    errors
-        .iter()
-        .map(|e| {
-            let mut current: Option<&dyn Error> = Some(e);
-            let mut stack = vec![];
-            while let Some(err) = current.take() {
-                stack.push(err.to_string());
-                current = err.source();
-            }
-            stack.join(" - ")
-        })
-        .collect::<Vec<String>>()
-        .join("\n")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -382,6 +443,8 @@ mod tests {
                 DefaultAzureCredentialType::AppService,
                 DefaultAzureCredentialType::VirtualMachine,
                 DefaultAzureCredentialType::AzureCli,
+                DefaultAzureCredentialType::AzurePowerShell,
+                DefaultAzureCredentialType::AzureDeveloperCli,
             ]
         );
     }
@@ -397,6 +460,8 @@ mod tests {
                 DefaultAzureCredentialType::Environment,
                 DefaultAzureCredentialType::AppService,
                 DefaultAzureCredentialType::AzureCli,
+                DefaultAzureCredentialType::AzurePowerShell,
+                DefaultAzureCredentialType::AzureDeveloperCli,
             ]
         );
     }
@@ -412,6 +477,8 @@ mod tests {
                 DefaultAzureCredentialType::AppService,
                 DefaultAzureCredentialType::VirtualMachine,
                 DefaultAzureCredentialType::AzureCli,
+                DefaultAzureCredentialType::AzurePowerShell,
+                DefaultAzureCredentialType::AzureDeveloperCli,
             ]
         );
         Ok(())
@@ -428,6 +495,26 @@ mod tests {
                 DefaultAzureCredentialType::Environment,
                 DefaultAzureCredentialType::AppService,
                 DefaultAzureCredentialType::VirtualMachine,
+                DefaultAzureCredentialType::AzurePowerShell,
+                DefaultAzureCredentialType::AzureDeveloperCli,
+            ]
+        );
+    }
+
+    /// test excluding the azure powershell and azure developer cli credentials
+    #[test]
+    fn test_exclude_azure_powershell_and_developer_cli_credential() {
+        let mut builder = DefaultAzureCredentialBuilder::new();
+        builder
+            .exclude_azure_powershell_credential()
+            .exclude_azure_developer_cli_credential();
+        assert_eq!(
+            builder.included(),
+            vec![
+                DefaultAzureCredentialType::Environment,
+                DefaultAzureCredentialType::AppService,
+                DefaultAzureCredentialType::VirtualMachine,
+                DefaultAzureCredentialType::AzureCli,
             ]
         );
     }
@@ -442,6 +529,8 @@ mod tests {
             vec![
                 DefaultAzureCredentialType::Environment,
                 DefaultAzureCredentialType::AzureCli,
+                DefaultAzureCredentialType::AzurePowerShell,
+                DefaultAzureCredentialType::AzureDeveloperCli,
             ]
         );
     }
@@ -0,0 +1,207 @@
+use crate::{
+    device_code_flow::{self, DeviceCodePhaseOneResponse},
+    refresh_token,
+    token_credentials::cache::TokenCache,
+    TokenCredentialOptions,
+};
+use async_lock::RwLock;
+use azure_core::{
+    auth::{AccessToken, Secret, TokenCredential},
+    error::{Error, ErrorKind, ResultExt},
+    HttpClient,
+};
+use futures::StreamExt;
+use std::{fmt, sync::Arc, time::Duration};
+use time::OffsetDateTime;
+use tracing::{debug, trace};
+
+/// The scope required for Azure Active Directory to issue a refresh token.
+const OFFLINE_ACCESS_SCOPE: &str = "offline_access";
+
+/// A callback used to display the device code instructions to the user.
+pub type DeviceCodePrompt = Arc<dyn Fn(&DeviceCodePhaseOneResponse) + Send + Sync>;
+
+/// Enables interactive authentication to Azure Active Directory on devices without a browser
+/// using the [device code flow](https://docs.microsoft.com/azure/active-directory/develop/v2-oauth2-device-code).
+///
+/// The first time a token is requested, the user is asked (through the prompt callback) to visit
+/// a URL and enter a code. The resulting access token is cached and the refresh token is used to
+/// silently acquire new access tokens afterwards, including for other scopes.
+pub struct DeviceCodeCredential {
+    http_client: Arc<dyn HttpClient>,
+    tenant_id: String,
+    client_id: String,
+    prompt: DeviceCodePrompt,
+    refresh_token: RwLock<Option<Secret>>,
+    cache: TokenCache,
+}
+
+impl fmt::Debug for DeviceCodeCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeviceCodeCredential")
+            .field("tenant_id", &self.tenant_id)
+            .field("client_id", &self.client_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DeviceCodeCredential {
+    /// Create a new `DeviceCodeCredential`
+    ///
+    /// `prompt` is called to display the device code instructions to the user, such as
+    /// [`DeviceCodePhaseOneResponse::message`].
+    pub fn new<T, C, F>(
+        tenant_id: T,
+        client_id: C,
+        prompt: F,
+        options: impl Into<TokenCredentialOptions>,
+    ) -> Self
+    where
+        T: Into<String>,
+        C: Into<String>,
+        F: Fn(&DeviceCodePhaseOneResponse) + Send + Sync + 'static,
+    {
+        let options = options.into();
+        Self {
+            http_client: options.http_client(),
+            tenant_id: tenant_id.into(),
+            client_id: client_id.into(),
+            prompt: Arc::new(prompt),
+            refresh_token: RwLock::new(None),
+            cache: TokenCache::new(),
+        }
+    }
+
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        let refresh_token = self.refresh_token.read().await.clone();
+        if let Some(refresh_token) = refresh_token {
+            match self.refresh(scopes, &refresh_token).await {
+                Ok(token) => return Ok(token),
+                Err(error) => {
+                    debug!("refreshing device code token failed, signing in again: {error}");
+                }
+            }
+        }
+        self.authenticate(scopes).await
+    }
+
+    /// Silently acquire a new access token using the stored refresh token.
+    async fn refresh(
+        &self,
+        scopes: &[&str],
+        refresh_token: &Secret,
+    ) -> azure_core::Result<AccessToken> {
+        trace!("exchanging device code refresh token");
+        let scopes = with_offline_access(scopes);
+        let response = refresh_token::exchange_for_scopes(
+            self.http_client.clone(),
+            &self.tenant_id,
+            &self.client_id,
+            None,
+            refresh_token,
+            &scopes,
+        )
+        .await?;
+        *self.refresh_token.write().await = Some(response.refresh_token().clone());
+        Ok(AccessToken::new(
+            response.access_token().clone(),
+            OffsetDateTime::now_utc() + Duration::from_secs(response.expires_in()),
+        ))
+    }
+
+    /// Run the device code flow, prompting the user to sign in.
+    async fn authenticate(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        let scopes = with_offline_access(scopes);
+        let phase_one = device_code_flow::start(
+            self.http_client.clone(),
+            self.tenant_id.as_str(),
+            &self.client_id,
+            &scopes,
+        )
+        .await
+        .context(
+            ErrorKind::Credential,
+            "failed to start the device code flow",
+        )?;
+
+        (self.prompt)(&phase_one);
+
+        let mut stream = phase_one.stream();
+        let mut last_error = None;
+        let authorization = loop {
+            match stream.next().await {
+                Some(Ok(authorization)) => break authorization,
+                // `authorization_pending` errors are returned while the user has not yet signed in
+                Some(Err(error)) => last_error = Some(error),
+                None => {
+                    return Err(last_error.unwrap_or_else(|| {
+                        Error::message(
+                            ErrorKind::Credential,
+                            "device code flow ended without an authorization",
+                        )
+                    }))
+                }
+            }
+        };
+
+        *self.refresh_token.write().await = authorization.refresh_token().cloned();
+        Ok(AccessToken::new(
+            authorization.access_token().clone(),
+            OffsetDateTime::now_utc() + Duration::from_secs(authorization.expires_in),
+        ))
+    }
+}
+
+/// Add the `offline_access` scope so that a refresh token is issued.
+fn with_offline_access<'a>(scopes: &[&'a str]) -> Vec<&'a str> {
+    let mut scopes = scopes.to_vec();
+    if !scopes.contains(&OFFLINE_ACCESS_SCOPE) {
+        scopes.push(OFFLINE_ACCESS_SCOPE);
+    }
+    scopes
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for DeviceCodeCredential {
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        self.cache.get_token(scopes, self.get_token(scopes)).await
+    }
+
+    /// Clear the credential's cache.
+    ///
+    /// The refresh token is kept, so new access tokens can still be acquired without prompting.
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        self.cache.clear().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn require_send<T: Send>(_t: T) {}
+
+    #[test]
+    fn ensure_that_get_token_is_send() {
+        let credential = DeviceCodeCredential::new(
+            "UNUSED",
+            "UNUSED",
+            |_: &DeviceCodePhaseOneResponse| {},
+            TokenCredentialOptions::default(),
+        );
+        require_send(TokenCredential::get_token(&credential, &["UNUSED"]));
+    }
+
+    #[test]
+    fn test_with_offline_access() {
+        assert_eq!(
+            with_offline_access(&["https://storage.azure.com/.default"]),
+            vec!["https://storage.azure.com/.default", "offline_access"]
+        );
+        assert_eq!(
+            with_offline_access(&["offline_access", "openid"]),
+            vec!["offline_access", "openid"]
+        );
+    }
+}
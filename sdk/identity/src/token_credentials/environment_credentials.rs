@@ -1,7 +1,8 @@
-#[cfg(feature = "client_certificate")]
+#[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
 pub use crate::token_credentials::ClientCertificateCredential;
 use crate::token_credentials::{
-    ClientSecretCredential, TokenCredentialOptions, WorkloadIdentityCredential,
+    ClientSecretCredential, TokenCredentialOptions, UsernamePasswordCredential,
+    WorkloadIdentityCredential,
 };
 use azure_core::{
     auth::{AccessToken, TokenCredential},
@@ -12,8 +13,9 @@ use azure_core::{
 pub(crate) enum EnvironmentCredentialKind {
     ClientSecret(ClientSecretCredential),
     WorkloadIdentity(WorkloadIdentityCredential),
-    #[cfg(feature = "client_certificate")]
+    #[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
     ClientCertificate(ClientCertificateCredential),
+    UsernamePassword(UsernamePasswordCredential),
 }
 
 /// Enables authentication with Workflows Identity if either `AZURE_FEDERATED_TOKEN` or `AZURE_FEDERATED_TOKEN_FILE` is set,
@@ -28,8 +30,13 @@ pub(crate) enum EnvironmentCredentialKind {
 /// | `AZURE_CLIENT_ID`                   | The client(application) ID of an App Registration in the tenant. |
 /// | `AZURE_CLIENT_SECRET`               | A client secret that was generated for the App Registration. |
 /// | `AZURE_FEDERATED_TOKEN_FILE`        | Path to an federated token file. Variable is present in pods with aks workload identities. |
+/// | `AZURE_CLIENT_CERTIFICATE_PATH`     | Path to a PEM or PKCS12 certificate file. Requires the `client_certificate` or `client_certificate_rustls` feature. |
+/// | `AZURE_CLIENT_CERTIFICATE_PASSWORD` | The password of the PKCS12 certificate file, if any. |
+/// | `AZURE_CLIENT_SEND_CERTIFICATE_CHAIN` | Set to `true` to send the certificate chain, for subject name/issuer authentication. |
+/// | `AZURE_USERNAME`                    | The username of a user in the tenant. |
+/// | `AZURE_PASSWORD`                    | The password of the user. |
 ///
-/// This credential ultimately uses a `WorkloadIdentityCredential` or a`ClientSecretCredential` to perform the authentication using
+/// This credential ultimately uses a `WorkloadIdentityCredential`, a `ClientSecretCredential` or a `UsernamePasswordCredential` to perform the authentication using
 /// these details.
 /// Please consult the documentation of that class for more details.
 #[derive(Debug)]
@@ -52,12 +59,17 @@ impl EnvironmentCredential {
                 source: EnvironmentCredentialKind::ClientSecret(credential),
             });
         }
-        #[cfg(feature = "client_certificate")]
+        #[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
         if let Ok(credential) = ClientCertificateCredential::create(options.clone()) {
             return Ok(Self {
                 source: EnvironmentCredentialKind::ClientCertificate(credential),
             });
         }
+        if let Ok(credential) = UsernamePasswordCredential::create(options.clone()) {
+            return Ok(Self {
+                source: EnvironmentCredentialKind::UsernamePassword(credential),
+            });
+        }
         Err(Error::message(
             ErrorKind::Credential,
             "no valid environment credential providers",
@@ -81,10 +93,13 @@ impl TokenCredential for EnvironmentCredential {
             EnvironmentCredentialKind::WorkloadIdentity(credential) => {
                 credential.get_token(scopes).await
             }
-            #[cfg(feature = "client_certificate")]
+            #[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
             EnvironmentCredentialKind::ClientCertificate(credential) => {
                 credential.get_token(scopes).await
             }
+            EnvironmentCredentialKind::UsernamePassword(credential) => {
+                credential.get_token(scopes).await
+            }
         }
     }
 
@@ -94,10 +109,13 @@ impl TokenCredential for EnvironmentCredential {
             EnvironmentCredentialKind::WorkloadIdentity(credential) => {
                 credential.clear_cache().await
             }
-            #[cfg(feature = "client_certificate")]
+            #[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
             EnvironmentCredentialKind::ClientCertificate(credential) => {
                 credential.clear_cache().await
             }
+            EnvironmentCredentialKind::UsernamePassword(credential) => {
+                credential.clear_cache().await
+            }
         }
     }
 }
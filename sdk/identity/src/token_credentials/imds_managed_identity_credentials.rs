@@ -1,4 +1,7 @@
-use crate::{token_credentials::cache::TokenCache, TokenCredentialOptions};
+use crate::{
+    token_credentials::{cache::TokenCache, CredentialUnavailableError},
+    TokenCredentialOptions,
+};
 use azure_core::{
     auth::{AccessToken, Secret, TokenCredential},
     error::{Error, ErrorKind},
@@ -88,7 +91,21 @@ impl ImdsManagedIdentityCredential {
             req.insert_header(self.secret_header.clone(), val);
         };
 
-        let rsp = self.http_client.execute_request(&req).await?;
+        // the endpoint is not reachable outside of an Azure hosted environment
+        let rsp = self
+            .http_client
+            .execute_request(&req)
+            .await
+            .map_err(|error| {
+                CredentialUnavailableError::with_source(
+                    format!(
+                        "managed identity endpoint {} is not reachable",
+                        self.endpoint
+                    ),
+                    error,
+                )
+                .into_error()
+            })?;
 
         let (rsp_status, rsp_headers, rsp_body) = rsp.deconstruct();
         let rsp_body = rsp_body.collect().await?;
@@ -96,10 +113,10 @@ impl ImdsManagedIdentityCredential {
         if !rsp_status.is_success() {
             match rsp_status {
                 StatusCode::BadRequest => {
-                    return Err(Error::message(
-                        ErrorKind::Credential,
+                    return Err(CredentialUnavailableError::new(
                         "the requested identity has not been assigned to this resource",
-                    ))
+                    )
+                    .into_error())
                 }
                 StatusCode::BadGateway | StatusCode::GatewayTimeout => {
                     return Err(Error::message(
@@ -3,39 +3,62 @@
 //! Supported means currently include:
 //! * The environment
 //! * Azure CLI credentials cache
+//! * Azure Developer CLI
+//! * Azure PowerShell
 //! * Managed identity
 //! * Client secret
+//! * A chain of any of the above
+//! * Device code
+//! * On-behalf-of
+//! * Username and password
 mod app_service_managed_identity_credential;
 #[cfg(not(target_arch = "wasm32"))]
 mod azure_cli_credentials;
+#[cfg(not(target_arch = "wasm32"))]
+mod azure_developer_cli_credentials;
+#[cfg(not(target_arch = "wasm32"))]
+mod azure_powershell_credentials;
 #[cfg(feature = "azureauth_cli")]
 #[cfg(not(target_arch = "wasm32"))]
 mod azureauth_cli_credentials;
 mod cache;
-#[cfg(feature = "client_certificate")]
+mod chained_token_credential;
+#[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
 mod client_certificate_credentials;
 mod client_secret_credentials;
 mod default_credentials;
+mod device_code_credentials;
 mod environment_credentials;
 mod imds_managed_identity_credentials;
+mod on_behalf_of_credentials;
 mod options;
 mod specific_azure_credential;
+mod token_request;
+mod username_password_credentials;
 mod virtual_machine_managed_identity_credential;
 mod workload_identity_credentials;
 
 pub use app_service_managed_identity_credential::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub use azure_cli_credentials::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use azure_developer_cli_credentials::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use azure_powershell_credentials::*;
 #[cfg(feature = "azureauth_cli")]
 #[cfg(not(target_arch = "wasm32"))]
 pub use azureauth_cli_credentials::*;
-#[cfg(feature = "client_certificate")]
+pub use chained_token_credential::*;
+#[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
 pub use client_certificate_credentials::*;
 pub use client_secret_credentials::*;
 pub use default_credentials::*;
+pub use device_code_credentials::*;
 pub use environment_credentials::*;
 pub(crate) use imds_managed_identity_credentials::*;
+pub use on_behalf_of_credentials::*;
 pub use options::*;
 pub use specific_azure_credential::*;
+pub use username_password_credentials::*;
 pub use virtual_machine_managed_identity_credential::*;
 pub use workload_identity_credentials::*;
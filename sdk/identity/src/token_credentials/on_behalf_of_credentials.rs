@@ -0,0 +1,267 @@
+#[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
+use crate::token_credentials::client_certificate_credentials::{
+    ClientCertificate, ClientCertificateCredentialOptions,
+};
+use crate::token_credentials::{
+    cache::TokenCache,
+    token_request::{request_token, token_url},
+};
+use crate::TokenCredentialOptions;
+use azure_core::{
+    auth::{AccessToken, Secret, TokenCredential},
+    error::{ErrorKind, ResultExt},
+    HttpClient, Url,
+};
+use std::sync::Arc;
+use url::form_urlencoded;
+
+/// How the middle-tier application authenticates itself to Azure Active Directory.
+#[derive(Debug)]
+enum ClientCredential {
+    Secret(Secret),
+    Assertion(Secret),
+    #[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
+    Certificate(ClientCertificate),
+}
+
+/// Enables authentication to Azure Active Directory using the
+/// [on-behalf-of flow](https://learn.microsoft.com/azure/active-directory/develop/v2-oauth2-on-behalf-of-flow).
+///
+/// A middle-tier service uses the access token it received from a signed-in user (the user assertion)
+/// to request tokens for downstream services on behalf of that user.
+/// The service itself authenticates with a client secret, a client assertion or a client certificate.
+#[derive(Debug)]
+pub struct OnBehalfOfCredential {
+    http_client: Arc<dyn HttpClient>,
+    authority_host: Url,
+    tenant_id: String,
+    client_id: String,
+    client_credential: ClientCredential,
+    user_assertion: Secret,
+    cache: TokenCache,
+}
+
+impl OnBehalfOfCredential {
+    fn new(
+        tenant_id: String,
+        client_id: String,
+        client_credential: ClientCredential,
+        user_assertion: Secret,
+        options: TokenCredentialOptions,
+    ) -> azure_core::Result<Self> {
+        Ok(Self {
+            http_client: options.http_client(),
+            authority_host: options.authority_host()?,
+            tenant_id,
+            client_id,
+            client_credential,
+            user_assertion,
+            cache: TokenCache::new(),
+        })
+    }
+
+    /// Create a new `OnBehalfOfCredential` that authenticates the application with a client secret.
+    pub fn new_with_client_secret<S, A>(
+        tenant_id: String,
+        client_id: String,
+        client_secret: S,
+        user_assertion: A,
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Self>
+    where
+        S: Into<Secret>,
+        A: Into<Secret>,
+    {
+        Self::new(
+            tenant_id,
+            client_id,
+            ClientCredential::Secret(client_secret.into()),
+            user_assertion.into(),
+            options.into(),
+        )
+    }
+
+    /// Create a new `OnBehalfOfCredential` that authenticates the application with a signed client assertion.
+    pub fn new_with_client_assertion<C, A>(
+        tenant_id: String,
+        client_id: String,
+        client_assertion: C,
+        user_assertion: A,
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Self>
+    where
+        C: Into<Secret>,
+        A: Into<Secret>,
+    {
+        Self::new(
+            tenant_id,
+            client_id,
+            ClientCredential::Assertion(client_assertion.into()),
+            user_assertion.into(),
+            options.into(),
+        )
+    }
+
+    /// Create a new `OnBehalfOfCredential` that authenticates the application with a client certificate.
+    ///
+    /// The certificate is expected to be in base64 encoded PKCS12 format.
+    #[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
+    pub fn new_with_client_certificate<C, P, A>(
+        tenant_id: String,
+        client_id: String,
+        client_certificate: C,
+        client_certificate_pass: P,
+        user_assertion: A,
+        options: impl Into<ClientCertificateCredentialOptions>,
+    ) -> azure_core::Result<Self>
+    where
+        C: Into<Secret>,
+        P: Into<Secret>,
+        A: Into<Secret>,
+    {
+        let options = options.into();
+        Self::new(
+            tenant_id,
+            client_id,
+            ClientCredential::Certificate(ClientCertificate::new(
+                client_certificate.into(),
+                client_certificate_pass.into(),
+                options.send_certificate_chain(),
+            )),
+            user_assertion.into(),
+            options.options().clone(),
+        )
+    }
+
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        let url = token_url(&self.authority_host, &self.tenant_id)?;
+
+        let encoded = {
+            let mut encoded = form_urlencoded::Serializer::new(String::new());
+            encoded
+                .append_pair("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer")
+                .append_pair("client_id", &self.client_id)
+                .append_pair("assertion", self.user_assertion.secret())
+                .append_pair("scope", &scopes.join(" "))
+                .append_pair("requested_token_use", "on_behalf_of");
+            match &self.client_credential {
+                ClientCredential::Secret(client_secret) => {
+                    encoded.append_pair("client_secret", client_secret.secret());
+                }
+                ClientCredential::Assertion(client_assertion) => {
+                    encoded
+                        .append_pair(
+                            "client_assertion_type",
+                            "urn:ietf:params:oauth:client-assertion-type:jwt-bearer",
+                        )
+                        .append_pair("client_assertion", client_assertion.secret());
+                }
+                #[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
+                ClientCredential::Certificate(certificate) => {
+                    let client_assertion = certificate.client_assertion(&self.client_id, &url)?;
+                    encoded
+                        .append_pair(
+                            "client_assertion_type",
+                            "urn:ietf:params:oauth:client-assertion-type:jwt-bearer",
+                        )
+                        .append_pair("client_assertion", &client_assertion);
+                }
+            }
+            encoded.finish()
+        };
+
+        request_token(&self.http_client, url, encoded)
+            .await
+            .context(ErrorKind::Credential, "on-behalf-of request token error")
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for OnBehalfOfCredential {
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        self.cache.get_token(scopes, self.get_token(scopes)).await
+    }
+
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        self.cache.clear().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token_credentials::token_request::tests::MockTokenClient;
+
+    fn options(http_client: &Arc<MockTokenClient>) -> TokenCredentialOptions {
+        TokenCredentialOptions::from(http_client.clone() as Arc<dyn HttpClient>)
+    }
+
+    fn field<'a>(form: &'a [(String, String)], key: &str) -> Option<&'a str> {
+        form.iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[tokio::test]
+    async fn test_client_secret_request() -> azure_core::Result<()> {
+        let http_client = Arc::new(MockTokenClient::default());
+        let credential = OnBehalfOfCredential::new_with_client_secret(
+            "tenant".to_owned(),
+            "client".to_owned(),
+            "secret",
+            "user-token",
+            options(&http_client),
+        )?;
+        let token = credential
+            .get_token(&["https://graph.microsoft.com/.default", "offline_access"])
+            .await?;
+        assert_eq!(token.token.secret(), "token");
+
+        let requests = http_client.requests();
+        assert_eq!(requests.len(), 1);
+        let (url, form) = &requests[0];
+        assert_eq!(
+            url.as_str(),
+            "https://login.microsoftonline.com/tenant/oauth2/v2.0/token"
+        );
+        assert_eq!(
+            field(form, "grant_type"),
+            Some("urn:ietf:params:oauth:grant-type:jwt-bearer")
+        );
+        assert_eq!(field(form, "client_id"), Some("client"));
+        assert_eq!(field(form, "assertion"), Some("user-token"));
+        assert_eq!(
+            field(form, "scope"),
+            Some("https://graph.microsoft.com/.default offline_access")
+        );
+        assert_eq!(field(form, "requested_token_use"), Some("on_behalf_of"));
+        assert_eq!(field(form, "client_secret"), Some("secret"));
+        assert_eq!(field(form, "client_assertion"), None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_assertion_request() -> azure_core::Result<()> {
+        let http_client = Arc::new(MockTokenClient::default());
+        let credential = OnBehalfOfCredential::new_with_client_assertion(
+            "tenant".to_owned(),
+            "client".to_owned(),
+            "client-jwt",
+            "user-token",
+            options(&http_client),
+        )?;
+        credential.get_token(&["scope"]).await?;
+
+        let requests = http_client.requests();
+        let (_, form) = &requests[0];
+        assert_eq!(
+            field(form, "client_assertion_type"),
+            Some("urn:ietf:params:oauth:client-assertion-type:jwt-bearer")
+        );
+        assert_eq!(field(form, "client_assertion"), Some("client-jwt"));
+        assert_eq!(field(form, "assertion"), Some("user-token"));
+        assert_eq!(field(form, "client_secret"), None);
+        Ok(())
+    }
+}
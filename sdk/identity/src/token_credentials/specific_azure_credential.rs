@@ -1,7 +1,7 @@
 use super::options;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::AzureCliCredential;
-#[cfg(feature = "client_certificate")]
+#[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
 use crate::ClientCertificateCredential;
 use crate::{
     AppServiceManagedIdentityCredential, ClientSecretCredential, EnvironmentCredential,
@@ -24,7 +24,7 @@ pub mod azure_credential_kinds {
     pub const APP_SERVICE: &str = "appservice";
     pub const CLIENT_SECRET: &str = "clientsecret";
     pub const WORKLOAD_IDENTITY: &str = "workloadidentity";
-    #[cfg(feature = "client_certificate")]
+    #[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
     pub const CLIENT_CERTIFICATE: &str = "clientcertificate";
 }
 
@@ -64,7 +64,7 @@ pub(crate) enum SpecificAzureCredentialKind {
     AppService(AppServiceManagedIdentityCredential),
     ClientSecret(ClientSecretCredential),
     WorkloadIdentity(WorkloadIdentityCredential),
-    #[cfg(feature = "client_certificate")]
+    #[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
     ClientCertificate(ClientCertificateCredential),
 }
 
@@ -90,7 +90,7 @@ impl TokenCredential for SpecificAzureCredentialKind {
             SpecificAzureCredentialKind::WorkloadIdentity(credential) => {
                 credential.get_token(scopes).await
             }
-            #[cfg(feature = "client_certificate")]
+            #[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
             SpecificAzureCredentialKind::ClientCertificate(credential) => {
                 credential.get_token(scopes).await
             }
@@ -110,7 +110,7 @@ impl TokenCredential for SpecificAzureCredentialKind {
             SpecificAzureCredentialKind::WorkloadIdentity(credential) => {
                 credential.clear_cache().await
             }
-            #[cfg(feature = "client_certificate")]
+            #[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
             SpecificAzureCredentialKind::ClientCertificate(credential) => {
                 credential.clear_cache().await
             }
@@ -174,7 +174,7 @@ impl SpecificAzureCredential {
                             )
                         })?
                 }
-                #[cfg(feature = "client_certificate")]
+                #[cfg(any(feature = "client_certificate", feature = "client_certificate_rustls"))]
                 azure_credential_kinds::CLIENT_CERTIFICATE => {
                     ClientCertificateCredential::create(options)
                         .map(SpecificAzureCredentialKind::ClientCertificate)
@@ -245,6 +245,28 @@ mod tests {
         Ok(())
     }
 
+    /// test AZURE_CREDENTIAL_KIND of "environment" with a username and password
+    #[test]
+    fn test_environment_username_password() -> azure_core::Result<()> {
+        let credential = SpecificAzureCredential::create(test_options(
+            &[
+                ("AZURE_CREDENTIAL_KIND", "environment"),
+                ("AZURE_TENANT_ID", "1"),
+                ("AZURE_CLIENT_ID", "2"),
+                ("AZURE_USERNAME", "3"),
+                ("AZURE_PASSWORD", "4"),
+            ][..],
+        ))?;
+        match credential.source() {
+            SpecificAzureCredentialKind::Environment(credential) => match credential.source() {
+                EnvironmentCredentialKind::UsernamePassword(_) => {}
+                _ => panic!("expect username password credential"),
+            },
+            _ => panic!("expected environment credential"),
+        }
+        Ok(())
+    }
+
     /// test AZURE_CREDENTIAL_KIND of "azurecli"
     #[test]
     #[cfg(not(target_arch = "wasm32"))]
@@ -0,0 +1,124 @@
+use crate::refresh_token::RefreshTokenError;
+use azure_core::{
+    auth::{AccessToken, Secret},
+    content_type,
+    error::{Error, ErrorKind},
+    from_json, headers, HttpClient, Method, Request, Url,
+};
+use serde::Deserialize;
+use std::{sync::Arc, time::Duration};
+use time::OffsetDateTime;
+
+/// The successful response of the Azure Active Directory v2.0 token endpoint.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    expires_in: u64,
+    access_token: Secret,
+}
+
+/// Returns the v2.0 token endpoint of a tenant.
+pub(crate) fn token_url(authority_host: &Url, tenant_id: &str) -> azure_core::Result<Url> {
+    let mut url = authority_host.clone();
+    url.path_segments_mut()
+        .map_err(|_| {
+            Error::with_message(ErrorKind::Credential, || {
+                format!("invalid authority host {authority_host}")
+            })
+        })?
+        .pop_if_empty()
+        .extend(&[tenant_id, "oauth2", "v2.0", "token"]);
+    Ok(url)
+}
+
+/// Post an url encoded form to the token endpoint and return the resulting access token.
+pub(crate) async fn request_token(
+    http_client: &Arc<dyn HttpClient>,
+    url: Url,
+    form_body: String,
+) -> azure_core::Result<AccessToken> {
+    let mut req = Request::new(url, Method::Post);
+    req.insert_header(
+        headers::CONTENT_TYPE,
+        content_type::APPLICATION_X_WWW_FORM_URLENCODED,
+    );
+    req.set_body(form_body);
+
+    let rsp = http_client.execute_request(&req).await?;
+    let (rsp_status, rsp_headers, rsp_body) = rsp.deconstruct();
+    let rsp_body = rsp_body.collect().await?;
+
+    if !rsp_status.is_success() {
+        let token_error: RefreshTokenError = from_json(&rsp_body).map_err(|_| {
+            ErrorKind::http_response_from_parts(rsp_status, &rsp_headers, &rsp_body)
+        })?;
+        return Err(Error::new(ErrorKind::Credential, token_error));
+    }
+
+    let response: TokenResponse = from_json(&rsp_body)?;
+    Ok(AccessToken::new(
+        response.access_token,
+        OffsetDateTime::now_utc() + Duration::from_secs(response.expires_in),
+    ))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use azure_core::{headers::Headers, Body, BytesStream, Response, StatusCode};
+    use std::sync::Mutex;
+
+    /// The URL and the form fields of a token request.
+    pub(crate) type TokenRequest = (Url, Vec<(String, String)>);
+
+    /// An HTTP client answering every request with an access token, recording the token requests.
+    #[derive(Debug, Default)]
+    pub(crate) struct MockTokenClient {
+        requests: Mutex<Vec<TokenRequest>>,
+    }
+
+    impl MockTokenClient {
+        pub(crate) fn requests(&self) -> Vec<TokenRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl HttpClient for MockTokenClient {
+        async fn execute_request(&self, request: &Request) -> azure_core::Result<Response> {
+            assert_eq!(request.method(), &Method::Post);
+            let Body::Bytes(body) = request.body() else {
+                panic!("token requests have a form body");
+            };
+            let form = url::form_urlencoded::parse(body)
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect();
+            self.requests
+                .lock()
+                .unwrap()
+                .push((request.url().clone(), form));
+            Ok(Response::new(
+                StatusCode::Ok,
+                Headers::new(),
+                Box::pin(BytesStream::new(
+                    &br#"{"token_type":"Bearer","expires_in":3600,"access_token":"token"}"#[..],
+                )),
+            ))
+        }
+    }
+
+    #[test]
+    fn test_token_url() -> azure_core::Result<()> {
+        let url = token_url(&Url::parse("https://login.microsoftonline.com")?, "tenant")?;
+        assert_eq!(
+            url.as_str(),
+            "https://login.microsoftonline.com/tenant/oauth2/v2.0/token"
+        );
+        let url = token_url(&Url::parse("https://login.example.com/prefix/")?, "tenant")?;
+        assert_eq!(
+            url.as_str(),
+            "https://login.example.com/prefix/tenant/oauth2/v2.0/token"
+        );
+        Ok(())
+    }
+}
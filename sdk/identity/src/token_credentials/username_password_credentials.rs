@@ -0,0 +1,169 @@
+use crate::token_credentials::{
+    cache::TokenCache,
+    token_request::{request_token, token_url},
+};
+use crate::TokenCredentialOptions;
+use azure_core::{
+    auth::{AccessToken, Secret, TokenCredential},
+    error::{ErrorKind, ResultExt},
+    HttpClient, Url,
+};
+use std::sync::Arc;
+use url::form_urlencoded;
+
+const AZURE_TENANT_ID_ENV_KEY: &str = "AZURE_TENANT_ID";
+const AZURE_CLIENT_ID_ENV_KEY: &str = "AZURE_CLIENT_ID";
+const AZURE_USERNAME_ENV_KEY: &str = "AZURE_USERNAME";
+const AZURE_PASSWORD_ENV_KEY: &str = "AZURE_PASSWORD";
+
+/// Enables authentication to Azure Active Directory with a user's username and password using the
+/// [resource owner password credentials flow](https://learn.microsoft.com/azure/active-directory/develop/v2-oauth-ropc).
+///
+/// This flow is not recommended, it does not work with multi-factor authentication,
+/// personal Microsoft accounts or federated users. Prefer interactive flows such as `DeviceCodeCredential`.
+#[derive(Debug)]
+pub struct UsernamePasswordCredential {
+    http_client: Arc<dyn HttpClient>,
+    authority_host: Url,
+    tenant_id: String,
+    client_id: String,
+    username: String,
+    password: Secret,
+    cache: TokenCache,
+}
+
+impl UsernamePasswordCredential {
+    /// Create a new `UsernamePasswordCredential`
+    pub fn new<P>(
+        tenant_id: String,
+        client_id: String,
+        username: String,
+        password: P,
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Self>
+    where
+        P: Into<Secret>,
+    {
+        let options = options.into();
+        Ok(Self {
+            http_client: options.http_client(),
+            authority_host: options.authority_host()?,
+            tenant_id,
+            client_id,
+            username,
+            password: password.into(),
+            cache: TokenCache::new(),
+        })
+    }
+
+    pub fn create(
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<UsernamePasswordCredential> {
+        let options = options.into();
+        let env = options.env();
+        let tenant_id =
+            env.var(AZURE_TENANT_ID_ENV_KEY)
+                .with_context(ErrorKind::Credential, || {
+                    format!(
+                        "username password credential requires {} environment variable",
+                        AZURE_TENANT_ID_ENV_KEY
+                    )
+                })?;
+        let client_id =
+            env.var(AZURE_CLIENT_ID_ENV_KEY)
+                .with_context(ErrorKind::Credential, || {
+                    format!(
+                        "username password credential requires {} environment variable",
+                        AZURE_CLIENT_ID_ENV_KEY
+                    )
+                })?;
+        let username =
+            env.var(AZURE_USERNAME_ENV_KEY)
+                .with_context(ErrorKind::Credential, || {
+                    format!(
+                        "username password credential requires {} environment variable",
+                        AZURE_USERNAME_ENV_KEY
+                    )
+                })?;
+        let password =
+            env.var(AZURE_PASSWORD_ENV_KEY)
+                .with_context(ErrorKind::Credential, || {
+                    format!(
+                        "username password credential requires {} environment variable",
+                        AZURE_PASSWORD_ENV_KEY
+                    )
+                })?;
+
+        UsernamePasswordCredential::new(tenant_id, client_id, username, password, options)
+    }
+
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        let url = token_url(&self.authority_host, &self.tenant_id)?;
+        let encoded = form_urlencoded::Serializer::new(String::new())
+            .append_pair("grant_type", "password")
+            .append_pair("client_id", &self.client_id)
+            .append_pair("username", &self.username)
+            .append_pair("password", self.password.secret())
+            .append_pair("scope", &scopes.join(" "))
+            .finish();
+
+        request_token(&self.http_client, url, encoded)
+            .await
+            .context(
+                ErrorKind::Credential,
+                "username password request token error",
+            )
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for UsernamePasswordCredential {
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        self.cache.get_token(scopes, self.get_token(scopes)).await
+    }
+
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        self.cache.clear().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token_credentials::token_request::tests::MockTokenClient;
+
+    #[tokio::test]
+    async fn test_request() -> azure_core::Result<()> {
+        let http_client = Arc::new(MockTokenClient::default());
+        let mut options = TokenCredentialOptions::from(http_client.clone() as Arc<dyn HttpClient>);
+        options.set_authority_host("https://login.example.com/".to_owned());
+        let credential = UsernamePasswordCredential::new(
+            "tenant".to_owned(),
+            "client".to_owned(),
+            "user@example.com".to_owned(),
+            "p@ss word",
+            options,
+        )?;
+        let token = credential.get_token(&["scope"]).await?;
+        assert_eq!(token.token.secret(), "token");
+
+        let requests = http_client.requests();
+        assert_eq!(requests.len(), 1);
+        let (url, form) = &requests[0];
+        assert_eq!(
+            url.as_str(),
+            "https://login.example.com/tenant/oauth2/v2.0/token"
+        );
+        let expected = [
+            ("grant_type", "password"),
+            ("client_id", "client"),
+            ("username", "user@example.com"),
+            ("password", "p@ss word"),
+            ("scope", "scope"),
+        ]
+        .map(|(key, value)| (key.to_owned(), value.to_owned()));
+        assert_eq!(form, &expected.to_vec());
+        Ok(())
+    }
+}
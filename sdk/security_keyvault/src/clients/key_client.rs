@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use azure_core::auth::TokenCredential;
+use azure_core::{auth::TokenCredential, Url};
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
@@ -20,6 +20,11 @@ impl KeyClient {
         Self { keyvault_client }
     }
 
+    /// The URL of the vault holding the keys.
+    pub fn vault_url(&self) -> &Url {
+        &self.keyvault_client.vault_url
+    }
+
     /// Gets the public part of a stored key.
     /// The get key operation is applicable to all key types.
     /// If the requested key is symmetric, then no key material is released in the response.
@@ -101,6 +106,19 @@ impl KeyClient {
         GetRandomBytesBuilder::new(self.clone(), hsm_name.into(), count)
     }
 
+    /// Wrap a symmetric key using the specified key.
+    ///
+    /// The WRAP operation supports encryption of a symmetric key using a key
+    /// encryption key that has previously been stored in an Azure Key Vault.
+    ///
+    /// This operation requires the keys/wrapKey permission.
+    pub fn wrap_key<N>(&self, name: N, wrap_key_parameters: WrapKeyParameters) -> WrapKeyBuilder
+    where
+        N: Into<String>,
+    {
+        WrapKeyBuilder::new(self.clone(), name.into(), wrap_key_parameters)
+    }
+
     pub fn unwrap_key<N>(
         &self,
         name: N,
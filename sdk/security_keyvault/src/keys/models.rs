@@ -411,6 +411,23 @@ pub struct GetRandomBytesResult {
     pub result: Vec<u8>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WrapKeyParameters {
+    pub encrypt_parameters_encryption: CryptographParamtersEncryption,
+    #[serde(serialize_with = "ser_base64", deserialize_with = "deser_base64")]
+    pub plaintext: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WrapKeyResult {
+    #[serde(skip)]
+    pub algorithm: EncryptionAlgorithm,
+    #[serde(rename = "kid")]
+    pub key_id: String,
+    #[serde(rename = "value", deserialize_with = "deser_base64")]
+    pub result: Vec<u8>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct UnwrapKeyParameters {
     pub decrypt_parameters_encryption: CryptographParamtersEncryption,
@@ -4,9 +4,11 @@ mod get_key;
 mod get_random_bytes;
 mod sign;
 mod unwrap_key;
+mod wrap_key;
 pub use decrypt::*;
 pub use encrypt::*;
 pub use get_key::*;
 pub use get_random_bytes::*;
 pub use sign::*;
 pub use unwrap_key::*;
+pub use wrap_key::*;
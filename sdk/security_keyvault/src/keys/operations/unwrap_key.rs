@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use azure_core::{headers::Headers, Method};
+use azure_core::{base64, headers::Headers, Method};
 use serde_json::{Map, Value};
 
 operation! {
@@ -23,7 +23,9 @@ impl UnwrapKeyBuilder {
             let mut request_body = Map::new();
             request_body.insert(
                 "value".to_owned(),
-                Value::String(String::from_utf8(self.unwrap_key_parameters.ciphertext)?),
+                Value::String(base64::encode_url_safe(
+                    self.unwrap_key_parameters.ciphertext,
+                )),
             );
 
             let algorithm = match self.unwrap_key_parameters.decrypt_parameters_encryption {
@@ -0,0 +1,80 @@
+use crate::prelude::*;
+use azure_core::{base64, headers::Headers, Method};
+use serde_json::{Map, Value};
+
+operation! {
+    WrapKey,
+    client: KeyClient,
+    name: String,
+    wrap_key_parameters: WrapKeyParameters,
+    ?version: String
+}
+
+impl WrapKeyBuilder {
+    pub fn into_future(self) -> WrapKey {
+        Box::pin(async move {
+            // POST {vaultBaseUrl}/keys/{key-name}/{key-version}/wrapkey?api-version=7.2
+            let version = self.version.unwrap_or_default();
+            let mut uri = self.client.keyvault_client.vault_url.clone();
+            let path = format!("keys/{}/{}/wrapkey", self.name, version);
+
+            uri.set_path(&path);
+
+            let mut request_body = Map::new();
+            request_body.insert(
+                "value".to_owned(),
+                Value::String(base64::encode_url_safe(self.wrap_key_parameters.plaintext)),
+            );
+
+            let algorithm = match self.wrap_key_parameters.encrypt_parameters_encryption {
+                CryptographParamtersEncryption::Rsa(RsaEncryptionParameters { algorithm }) => {
+                    request_body.insert("alg".to_owned(), serde_json::to_value(&algorithm)?);
+                    algorithm
+                }
+                CryptographParamtersEncryption::AesGcm(AesGcmEncryptionParameters {
+                    algorithm,
+                    iv,
+                    authentication_tag,
+                    additional_authenticated_data,
+                }) => {
+                    request_body.insert("alg".to_owned(), serde_json::to_value(&algorithm)?);
+                    request_body.insert("iv".to_owned(), serde_json::to_value(iv)?);
+                    request_body
+                        .insert("tag".to_owned(), serde_json::to_value(authentication_tag)?);
+                    if let Some(aad) = additional_authenticated_data {
+                        request_body.insert("aad".to_owned(), serde_json::to_value(aad)?);
+                    };
+                    algorithm
+                }
+                CryptographParamtersEncryption::AesCbc(AesCbcEncryptionParameters {
+                    algorithm,
+                    iv,
+                }) => {
+                    request_body.insert("alg".to_owned(), serde_json::to_value(&algorithm)?);
+                    request_body.insert("iv".to_owned(), serde_json::to_value(iv)?);
+                    algorithm
+                }
+            };
+
+            let headers = Headers::new();
+            let mut request = KeyvaultClient::finalize_request(
+                uri,
+                Method::Post,
+                headers,
+                Some(Value::Object(request_body).to_string().into()),
+            );
+
+            let mut result: WrapKeyResult = self
+                .client
+                .keyvault_client
+                .send(&self.context, &mut request)
+                .await?
+                .json()
+                .await?;
+            result.algorithm = algorithm;
+            Ok(result)
+        })
+    }
+}
+
+type WrapKeyResponse = WrapKeyResult;
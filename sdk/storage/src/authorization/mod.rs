@@ -1,12 +1,13 @@
 mod authorization_policy;
 
+use self::authorization_policy::authorize;
 pub(crate) use self::authorization_policy::AuthorizationPolicy;
-use crate::clients::{EMULATOR_ACCOUNT, EMULATOR_ACCOUNT_KEY};
+use crate::clients::{ServiceType, EMULATOR_ACCOUNT, EMULATOR_ACCOUNT_KEY};
 use async_lock::RwLock;
 use azure_core::{
     auth::{Secret, TokenCredential},
     error::{ErrorKind, ResultExt},
-    Url,
+    Request, Url,
 };
 use std::{
     mem::replace,
@@ -147,6 +148,17 @@ impl StorageCredentials {
 
         Ok(())
     }
+
+    /// Authorize a request with these credentials, as the client pipeline does before sending it.
+    ///
+    /// This is needed for requests embedded in another request, such as the sub-requests of a batch.
+    pub async fn authorize_request(
+        &self,
+        request: &mut Request,
+        service_type: ServiceType,
+    ) -> azure_core::Result<()> {
+        authorize(self, request, Some(service_type)).await
+    }
 }
 
 impl std::fmt::Debug for StorageCredentials {
@@ -7,6 +7,7 @@ use crate::{
     StorageCredentials,
 };
 use azure_core::{
+    auth::Secret,
     date,
     error::{Error, ErrorKind},
     headers::*,
@@ -30,7 +31,7 @@ const AZURE_VERSION: HeaderValue = HeaderValue::from_static("2022-11-02");
 pub enum ServiceType {
     Blob,
     Queue,
-    // File,
+    File,
     Table,
     DataLake,
 }
@@ -40,6 +41,7 @@ impl ServiceType {
         match self {
             ServiceType::Blob => "blob",
             ServiceType::Queue => "queue",
+            ServiceType::File => "file",
             ServiceType::Table => "table",
             ServiceType::DataLake => "dfs",
         }
@@ -71,6 +73,24 @@ pub async fn shared_access_signature(
     ))
 }
 
+/// The canonicalized resource of a service shared access signature for `path`, such as
+/// `container/blob`, and the account key to sign it with.
+pub async fn service_sas_signing_key(
+    storage_credentials: &StorageCredentials,
+    service_type: ServiceType,
+    path: &str,
+) -> Result<(String, Secret), Error> {
+    let creds = storage_credentials.0.read().await;
+    let StorageCredentialsInner::Key(account, key) = creds.deref() else {
+        return Err(Error::message(
+            ErrorKind::Credential,
+            "Shared access signature generation - SAS can be generated with access_key clients",
+        ));
+    };
+    let canonicalized_resource = format!("/{}/{account}/{path}", service_type.subdomain());
+    Ok((canonicalized_resource, key.clone()))
+}
+
 pub fn finalize_request(
     url: Url,
     method: Method,
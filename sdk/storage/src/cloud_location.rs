@@ -49,6 +49,23 @@ impl CloudLocation {
         };
         Ok(Url::parse(&url)?)
     }
+
+    /// the base URL of the read-only secondary endpoint of a geo-replicated account
+    ///
+    /// Custom and emulator locations have no well-known secondary, so their primary URL is returned.
+    pub fn secondary_url(&self, service_type: ServiceType) -> azure_core::Result<Url> {
+        match self {
+            CloudLocation::Public { account } => CloudLocation::Public {
+                account: format!("{account}-secondary"),
+            }
+            .url(service_type),
+            CloudLocation::China { account } => CloudLocation::China {
+                account: format!("{account}-secondary"),
+            }
+            .url(service_type),
+            CloudLocation::Custom { .. } | CloudLocation::Emulator { .. } => self.url(service_type),
+        }
+    }
 }
 
 impl TryFrom<&Url> for CloudLocation {
@@ -130,4 +147,26 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_secondary_url() -> azure_core::Result<()> {
+        let public = CloudLocation::Public {
+            account: "test".to_owned(),
+        };
+        assert_eq!(
+            Url::parse("https://test-secondary.blob.core.windows.net")?,
+            public.secondary_url(ServiceType::Blob)?
+        );
+
+        let custom = CloudLocation::Custom {
+            account: "test".to_owned(),
+            uri: "https://example.com/test".to_owned(),
+        };
+        assert_eq!(
+            custom.url(ServiceType::Blob)?,
+            custom.secondary_url(ServiceType::Blob)?
+        );
+
+        Ok(())
+    }
 }
@@ -1,7 +1,8 @@
-use crate::StorageCredentials;
+use crate::{clients::ServiceType, CloudLocation, StorageCredentials, EMULATOR_ACCOUNT};
 use azure_core::{
     auth::Secret,
-    error::{Error, ErrorKind},
+    error::{Error, ErrorKind, ResultExt},
+    Url,
 };
 use tracing::warn;
 
@@ -198,6 +199,10 @@ impl<'a> ConnectionString<'a> {
                 account_key: Some(key),
                 ..
             } =>  Ok(StorageCredentials::access_key(*account, Secret::new((*key).to_string()))),
+            ConnectionString {
+                use_development_storage: Some(true),
+                ..
+            } => Ok(StorageCredentials::emulator()),
            _ => {
                 Err(Error::message(ErrorKind::Credential,
                     "Could not create a `StorageCredentail` from the provided connection string. Please validate that you have specified a means of authentication (key, SAS, etc.)."
@@ -205,6 +210,148 @@ impl<'a> ConnectionString<'a> {
             }
         }
     }
+
+    /// The location of a storage service of the account.
+    ///
+    /// A custom endpoint of the service takes precedence over the account name, protocol and
+    /// endpoint suffix. Data Lake Storage uses the `dfs` endpoint matching the blob endpoint.
+    /// Development storage listens on the well-known ports of the emulator, at the address of the
+    /// development storage proxy if there is one. Only the primary endpoints are used, the
+    /// secondary endpoints are ignored.
+    pub fn cloud_location(&self, service_type: ServiceType) -> azure_core::Result<CloudLocation> {
+        if self.use_development_storage == Some(true) {
+            return self.development_storage_location(service_type);
+        }
+
+        if let Some(uri) = self.endpoint(service_type)? {
+            let account = match self.account_name {
+                Some(account) => account.to_owned(),
+                None => endpoint_account(&uri)?,
+            };
+            return Ok(CloudLocation::Custom { account, uri });
+        }
+
+        let account = self.account_name.ok_or_else(|| {
+            Error::with_message(ErrorKind::Other, || {
+                format!(
+                    "connection string has neither an {ACCOUNT_NAME_KEY_NAME} nor an endpoint for the {} service",
+                    service_type.subdomain()
+                )
+            })
+        })?;
+        let protocol = self
+            .default_endpoints_protocol
+            .as_ref()
+            .unwrap_or(&EndpointProtocol::Https);
+        let account = account.to_owned();
+        Ok(match (protocol, self.endpoint_suffix) {
+            (EndpointProtocol::Https, None | Some("core.windows.net")) => {
+                CloudLocation::Public { account }
+            }
+            (EndpointProtocol::Https, Some("core.chinacloudapi.cn")) => {
+                CloudLocation::China { account }
+            }
+            (protocol, endpoint_suffix) => CloudLocation::Custom {
+                uri: format!(
+                    "{protocol}://{account}.{}.{}",
+                    service_type.subdomain(),
+                    endpoint_suffix.unwrap_or("core.windows.net")
+                ),
+                account,
+            },
+        })
+    }
+
+    /// The custom endpoint of a service, without a trailing slash.
+    fn endpoint(&self, service_type: ServiceType) -> azure_core::Result<Option<String>> {
+        let endpoint = match service_type {
+            ServiceType::Blob => self.blob_endpoint,
+            ServiceType::Queue => self.queue_endpoint,
+            ServiceType::File => self.file_endpoint,
+            ServiceType::Table => self.table_endpoint,
+            ServiceType::DataLake => {
+                return self
+                    .blob_endpoint
+                    .map(|endpoint| {
+                        let mut url = parse_endpoint(endpoint)?;
+                        if let Some(host) = url.host_str() {
+                            let host = host.replacen(".blob.", ".dfs.", 1);
+                            url.set_host(Some(&host))
+                                .with_context(ErrorKind::Other, || {
+                                    format!("invalid connection string endpoint: {endpoint}")
+                                })?;
+                        }
+                        Ok(url.as_str().trim_end_matches('/').to_owned())
+                    })
+                    .transpose();
+            }
+        };
+        endpoint
+            .map(|endpoint| {
+                parse_endpoint(endpoint)?;
+                Ok(endpoint.trim_end_matches('/').to_owned())
+            })
+            .transpose()
+    }
+
+    fn development_storage_location(
+        &self,
+        service_type: ServiceType,
+    ) -> azure_core::Result<CloudLocation> {
+        let port = match service_type {
+            ServiceType::Blob | ServiceType::DataLake => 10000,
+            ServiceType::Queue => 10001,
+            ServiceType::Table => 10002,
+            ServiceType::File => {
+                return Err(Error::message(
+                    ErrorKind::Other,
+                    "development storage does not support the file service",
+                ))
+            }
+        };
+        let Some(proxy_uri) = self.development_storage_proxy_uri else {
+            return Ok(CloudLocation::Emulator {
+                address: "127.0.0.1".to_owned(),
+                port,
+            });
+        };
+        let proxy = parse_endpoint(proxy_uri)?;
+        let host = proxy.host_str().ok_or_else(|| {
+            Error::with_message(ErrorKind::Other, || {
+                format!("development storage proxy URI has no host: {proxy_uri}")
+            })
+        })?;
+        Ok(CloudLocation::Custom {
+            account: EMULATOR_ACCOUNT.to_owned(),
+            uri: format!("{}://{host}:{port}/{EMULATOR_ACCOUNT}", proxy.scheme()),
+        })
+    }
+}
+
+fn parse_endpoint(endpoint: &str) -> azure_core::Result<Url> {
+    Url::parse(endpoint).with_context(ErrorKind::Other, || {
+        format!("invalid connection string endpoint: {endpoint}")
+    })
+}
+
+/// The account of a custom endpoint: the first segment of the path for path-style URLs, such as
+/// those of the emulator, otherwise the first label of the host.
+fn endpoint_account(endpoint: &str) -> azure_core::Result<String> {
+    let url = parse_endpoint(endpoint)?;
+    let account = url
+        .path_segments()
+        .and_then(|mut segments| segments.next())
+        .filter(|segment| !segment.is_empty())
+        .or_else(|| match url.host() {
+            Some(url::Host::Domain(domain)) => domain.split('.').next(),
+            _ => None,
+        });
+    match account {
+        Some(account) if !account.is_empty() => Ok(account.to_owned()),
+        _ => Err(Error::with_message(ErrorKind::Other, || {
+            format!("could not find the account of the endpoint {endpoint}, please add an {ACCOUNT_NAME_KEY_NAME}")
+        })),
+    }
 }
 
 #[cfg(test)]
@@ -329,4 +476,94 @@ mod tests {
             })
         ));
     }
+
+    fn url(connection_string: &str, service_type: ServiceType) -> String {
+        ConnectionString::new(connection_string)
+            .unwrap()
+            .cloud_location(service_type)
+            .unwrap()
+            .url(service_type)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn it_resolves_cloud_locations() {
+        let account = "AccountName=a;AccountKey=b";
+        assert_eq!(
+            url(account, ServiceType::Blob),
+            "https://a.blob.core.windows.net/"
+        );
+        assert_eq!(
+            url(account, ServiceType::DataLake),
+            "https://a.dfs.core.windows.net/"
+        );
+        assert_eq!(
+            url(
+                &format!(
+                    "{account};DefaultEndpointsProtocol=http;EndpointSuffix=core.usgovcloudapi.net"
+                ),
+                ServiceType::Queue
+            ),
+            "http://a.queue.core.usgovcloudapi.net/"
+        );
+        assert_eq!(
+            url(
+                &format!("{account};EndpointSuffix=core.chinacloudapi.cn"),
+                ServiceType::Table
+            ),
+            "https://a.table.core.chinacloudapi.cn/"
+        );
+
+        let endpoints = "SharedAccessSignature=sv=2022-11-02&sig=s;BlobEndpoint=https://c.blob.core.windows.net/;QueueEndpoint=https://queues.example.com;TableEndpoint=http://127.0.0.1:10002/devstoreaccount1";
+        let connection_string = ConnectionString::new(endpoints).unwrap();
+        assert_eq!(
+            url(endpoints, ServiceType::Blob),
+            "https://c.blob.core.windows.net/"
+        );
+        assert_eq!(
+            url(endpoints, ServiceType::DataLake),
+            "https://c.dfs.core.windows.net/"
+        );
+        assert_eq!(
+            connection_string
+                .cloud_location(ServiceType::Queue)
+                .unwrap()
+                .account(),
+            "queues"
+        );
+        assert_eq!(
+            connection_string
+                .cloud_location(ServiceType::Table)
+                .unwrap()
+                .account(),
+            "devstoreaccount1"
+        );
+        assert!(connection_string.cloud_location(ServiceType::File).is_err());
+        assert!(connection_string.storage_credentials().is_ok());
+    }
+
+    #[test]
+    fn it_resolves_development_storage() {
+        let connection_string = "UseDevelopmentStorage=true";
+        assert_eq!(
+            url(connection_string, ServiceType::Blob),
+            "http://127.0.0.1:10000/devstoreaccount1"
+        );
+        assert_eq!(
+            url(connection_string, ServiceType::Queue),
+            "http://127.0.0.1:10001/devstoreaccount1"
+        );
+        assert!(ConnectionString::new(connection_string)
+            .unwrap()
+            .storage_credentials()
+            .is_ok());
+
+        let connection_string =
+            "UseDevelopmentStorage=true;DevelopmentStorageProxyUri=http://ipv4.fiddler";
+        assert_eq!(
+            url(connection_string, ServiceType::Table),
+            "http://ipv4.fiddler:10002/devstoreaccount1"
+        );
+    }
 }
@@ -221,4 +221,14 @@ mod tests {
             "BlobEndpoint=b1;BlobSecondaryEndpoint=b2;TableEndpoint=t1;TableSecondaryEndpoint=t2;QueueEndpoint=q1;QueueSecondaryEndpoint=q2;FileEndpoint=f1;FileSecondaryEndpoint=f2"
         );
     }
+
+    #[test]
+    fn it_round_trips() {
+        let connection_string = "AccountName=a;AccountKey=b;SharedAccessSignature=sv=1&sig=c;UseDevelopmentStorage=false;EndpointSuffix=d;DefaultEndpointsProtocol=http;BlobEndpoint=e;QueueEndpoint=f;FileSecondaryEndpoint=g";
+        let parsed = ConnectionString::new(connection_string).unwrap();
+        let built =
+            ConnectionStringBuilder(ConnectionString::new(connection_string).unwrap()).build();
+        assert_eq!(built, connection_string);
+        assert_eq!(ConnectionString::new(&built).unwrap(), parsed);
+    }
 }
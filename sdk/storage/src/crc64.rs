@@ -0,0 +1,83 @@
+//! The CRC64 checksum used by Azure Storage for transactional content validation.
+//!
+//! Azure Storage uses the reflected polynomial `0x9A6C9329AC4BC9B5`, with the
+//! register initialized and finalized with all bits set (also known as CRC-64/NVME).
+//! The `x-ms-content-crc64` header carries the checksum as base64 encoded little-endian bytes.
+
+const POLYNOMIAL: u64 = 0x9A6C_9329_AC4B_C9B5;
+
+const TABLE: [u64; 256] = {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Incrementally computes the CRC64 of data that is not available all at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crc64 {
+    state: u64,
+}
+
+impl Default for Crc64 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Crc64 {
+    pub fn new() -> Self {
+        Self { state: !0 }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        let mut crc = self.state;
+        for byte in data {
+            crc = TABLE[((crc ^ u64::from(*byte)) & 0xFF) as usize] ^ (crc >> 8);
+        }
+        self.state = crc;
+    }
+
+    pub fn finish(&self) -> u64 {
+        !self.state
+    }
+}
+
+/// Compute the CRC64 of `data`.
+pub fn checksum(data: &[u8]) -> u64 {
+    let mut crc = Crc64::new();
+    crc.update(data);
+    crc.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(checksum(b""), 0);
+        assert_eq!(checksum(b"123456789"), 0xAE8B_1486_0A79_9888);
+    }
+
+    #[test]
+    fn test_incremental() {
+        let mut crc = Crc64::new();
+        crc.update(b"1234");
+        crc.update(b"56789");
+        assert_eq!(crc.finish(), checksum(b"123456789"));
+    }
+}
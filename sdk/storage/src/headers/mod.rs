@@ -32,7 +32,17 @@ impl TryFrom<&Headers> for CommonStorageResponseHeaders {
 
 pub const CONTENT_CRC64: HeaderName = HeaderName::from_static("x-ms-content-crc64");
 pub const COPY_ID: HeaderName = HeaderName::from_static("x-ms-copy-id");
+pub const COPY_SOURCE_AUTHORIZATION: HeaderName =
+    HeaderName::from_static("x-ms-copy-source-authorization");
+pub const DEFAULT_ENCRYPTION_SCOPE: HeaderName =
+    HeaderName::from_static("x-ms-default-encryption-scope");
+pub const DENY_ENCRYPTION_SCOPE_OVERRIDE: HeaderName =
+    HeaderName::from_static("x-ms-deny-encryption-scope-override");
+pub const ENCRYPTION_SCOPE: HeaderName = HeaderName::from_static("x-ms-encryption-scope");
 pub const RENAME_SOURCE: HeaderName = HeaderName::from_static("x-ms-rename-source");
+pub const STRUCTURED_BODY: HeaderName = HeaderName::from_static("x-ms-structured-body");
+pub const STRUCTURED_CONTENT_LENGTH: HeaderName =
+    HeaderName::from_static("x-ms-structured-content-length");
 
 pub fn content_crc64_from_headers(headers: &Headers) -> azure_core::Result<ConsistencyCRC64> {
     headers.get_as(&CONTENT_CRC64)
@@ -10,6 +10,7 @@ Please use these crates for additional functionality:
 - [`azure_data_tables`](https://crates.io/crates/azure_data_tables)
 - [`azure_storage_blobs`](https://crates.io/crates/azure_storage_blobs)
 - [`azure_storage_datalake`](https://crates.io/crates/azure_storage_datalake)
+- [`azure_storage_files`](https://crates.io/crates/azure_storage_files)
 - [`azure_storage_queues`](https://crates.io/crates/azure_storage_queues)
 */
 
@@ -30,9 +31,12 @@ mod connection_string;
 mod connection_string_builder;
 mod copy_id;
 mod copy_progress;
+pub mod crc64;
 mod macros;
+pub mod object_store;
 pub mod prelude;
 pub mod shared_access_signature;
+pub mod structured_message;
 
 pub use self::connection_string::{ConnectionString, EndpointProtocol};
 pub use self::connection_string_builder::ConnectionStringBuilder;
@@ -0,0 +1,418 @@
+//! A storage interface common to blob containers, Data Lake file systems and local directories.
+//!
+//! Code written against [`ObjectStore`] can switch between Azure Blob Storage, Azure Data Lake
+//! Storage Gen2 and the local file system without knowing the specifics of any of them. The
+//! implementations for Azure are in `azure_storage_blobs` (for `ContainerClient`) and
+//! `azure_storage_datalake` (for `FileSystemClient`). [`LocalFileSystem`] stores objects in a
+//! directory, which is mostly useful in tests.
+//!
+//! Objects are named by their location, a path relative to the root of the store with segments
+//! separated by `/`.
+
+use azure_core::{
+    error::{Error, ErrorKind, ResultExt},
+    StatusCode,
+};
+use bytes::{Bytes, BytesMut};
+use futures::stream::{self, BoxStream, StreamExt};
+use std::{
+    fmt::Debug,
+    fs, io,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+use time::OffsetDateTime;
+
+/// The separator of the segments of a location.
+pub const DELIMITER: &str = "/";
+
+/// The metadata of an object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectMeta {
+    pub location: String,
+    pub last_modified: OffsetDateTime,
+    pub size: u64,
+    pub e_tag: Option<String>,
+}
+
+/// The objects and common prefixes directly below a prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListResult {
+    /// The prefixes of the objects further below, each ending with the delimiter.
+    pub common_prefixes: Vec<String>,
+    pub objects: Vec<ObjectMeta>,
+}
+
+/// A store of objects, addressed by location.
+///
+/// Listing takes a prefix as a path: the prefix `a/b` lists the objects below `a/b/`, but not
+/// `a/bc`. Operations on missing objects fail with an error for which [`is_not_found`] is true.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait ObjectStore: Send + Sync + Debug {
+    /// Create or replace an object.
+    async fn put(&self, location: &str, data: Bytes) -> azure_core::Result<()>;
+
+    async fn get(&self, location: &str) -> azure_core::Result<Bytes>;
+
+    /// Get the bytes of an object in a range, which may end after the end of the object.
+    async fn get_range(&self, location: &str, range: Range<u64>) -> azure_core::Result<Bytes>;
+
+    async fn head(&self, location: &str) -> azure_core::Result<ObjectMeta>;
+
+    async fn delete(&self, location: &str) -> azure_core::Result<()>;
+
+    /// List all the objects below a prefix, recursively.
+    fn list(&self, prefix: Option<&str>) -> BoxStream<'_, azure_core::Result<ObjectMeta>>;
+
+    /// List the objects directly below a prefix, and the prefixes of the ones further below.
+    async fn list_with_delimiter(&self, prefix: Option<&str>) -> azure_core::Result<ListResult>;
+
+    /// Copy an object, replacing the destination if it exists.
+    async fn copy(&self, from: &str, to: &str) -> azure_core::Result<()>;
+
+    /// Move an object, replacing the destination if it exists.
+    ///
+    /// Stores that support it rename atomically, the others copy the object and then delete it.
+    async fn rename(&self, from: &str, to: &str) -> azure_core::Result<()> {
+        self.copy(from, to).await?;
+        self.delete(from).await
+    }
+
+    /// Start uploading an object in parts, which only replaces the object when completed.
+    async fn put_multipart(&self, location: &str) -> azure_core::Result<Box<dyn MultipartUpload>>;
+}
+
+/// An object uploaded in parts, in order.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait MultipartUpload: Send + Debug {
+    /// Upload the next part of the object.
+    async fn put_part(&mut self, data: Bytes) -> azure_core::Result<()>;
+
+    /// Create the object from the parts uploaded.
+    async fn complete(&mut self) -> azure_core::Result<()>;
+
+    /// Give up on the upload, discarding the parts uploaded.
+    async fn abort(&mut self) -> azure_core::Result<()>;
+}
+
+/// Whether an error is due to an object, or a prefix, not existing.
+pub fn is_not_found(err: &Error) -> bool {
+    match err.kind() {
+        ErrorKind::HttpResponse {
+            status: StatusCode::NotFound,
+            ..
+        } => true,
+        ErrorKind::Io => err
+            .downcast_ref::<io::Error>()
+            .map_or(false, |err| err.kind() == io::ErrorKind::NotFound),
+        _ => false,
+    }
+}
+
+/// The prefix of the names of the objects below a location, ending with the delimiter.
+///
+/// This is empty for the root of the store.
+pub fn directory_prefix(prefix: Option<&str>) -> String {
+    match prefix.map(|prefix| prefix.trim_end_matches(DELIMITER)) {
+        Some(prefix) if !prefix.is_empty() => format!("{prefix}{DELIMITER}"),
+        _ => String::new(),
+    }
+}
+
+/// An object store in a directory of the local file system.
+///
+/// Files are read and written with blocking calls, so this is meant for tests and tools rather
+/// than for serving requests.
+#[derive(Debug, Clone)]
+pub struct LocalFileSystem {
+    root: PathBuf,
+}
+
+impl LocalFileSystem {
+    /// Store objects below `root`, which is created if it does not exist.
+    pub fn new(root: impl Into<PathBuf>) -> azure_core::Result<Self> {
+        let root = root.into();
+        fs::create_dir_all(&root).with_context(ErrorKind::Io, || {
+            format!("failed to create directory {}", root.display())
+        })?;
+        Ok(Self { root })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, location: &str) -> azure_core::Result<PathBuf> {
+        let mut path = self.root.clone();
+        for segment in location.split(DELIMITER) {
+            if segment.is_empty() || segment == "." || segment == ".." {
+                return Err(Error::with_message(ErrorKind::Other, || {
+                    format!("invalid object location: {location:?}")
+                }));
+            }
+            path.push(segment);
+        }
+        Ok(path)
+    }
+
+    fn meta(location: String, path: &Path) -> azure_core::Result<ObjectMeta> {
+        let metadata = fs::metadata(path).with_context(ErrorKind::Io, || {
+            format!("failed to stat {}", path.display())
+        })?;
+        let last_modified = metadata.modified().with_context(ErrorKind::Io, || {
+            format!("failed to stat {}", path.display())
+        })?;
+        Ok(ObjectMeta {
+            location,
+            last_modified: last_modified.into(),
+            size: metadata.len(),
+            e_tag: None,
+        })
+    }
+
+    /// The objects and directories directly in the directory of a prefix, sorted by name.
+    fn read_dir(&self, prefix: &str) -> azure_core::Result<(Vec<ObjectMeta>, Vec<String>)> {
+        let directory = match prefix.strip_suffix(DELIMITER) {
+            Some(prefix) => self.path(prefix)?,
+            None => self.root.clone(),
+        };
+        let entries = match fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Ok((Vec::new(), Vec::new()))
+            }
+            Err(err) => {
+                return Err(Error::full(
+                    ErrorKind::Io,
+                    err,
+                    format!("failed to list {}", directory.display()),
+                ))
+            }
+        };
+
+        let mut objects = Vec::new();
+        let mut directories = Vec::new();
+        for entry in entries {
+            let entry = entry.with_context(ErrorKind::Io, || {
+                format!("failed to list {}", directory.display())
+            })?;
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            let location = format!("{prefix}{name}");
+            if entry.path().is_dir() {
+                directories.push(format!("{location}{DELIMITER}"));
+            } else {
+                objects.push(Self::meta(location, &entry.path())?);
+            }
+        }
+        objects.sort_by(|a, b| a.location.cmp(&b.location));
+        directories.sort();
+        Ok((objects, directories))
+    }
+
+    fn list_recursive(
+        &self,
+        prefix: &str,
+        objects: &mut Vec<ObjectMeta>,
+    ) -> azure_core::Result<()> {
+        let (mut found, directories) = self.read_dir(prefix)?;
+        objects.append(&mut found);
+        for directory in directories {
+            self.list_recursive(&directory, objects)?;
+        }
+        Ok(())
+    }
+
+    fn create_parent(path: &Path) -> azure_core::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(ErrorKind::Io, || {
+                format!("failed to create directory {}", parent.display())
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl ObjectStore for LocalFileSystem {
+    async fn put(&self, location: &str, data: Bytes) -> azure_core::Result<()> {
+        let path = self.path(location)?;
+        Self::create_parent(&path)?;
+        fs::write(&path, data).with_context(ErrorKind::Io, || {
+            format!("failed to write {}", path.display())
+        })
+    }
+
+    async fn get(&self, location: &str) -> azure_core::Result<Bytes> {
+        let path = self.path(location)?;
+        let data = fs::read(&path).with_context(ErrorKind::Io, || {
+            format!("failed to read {}", path.display())
+        })?;
+        Ok(data.into())
+    }
+
+    async fn get_range(&self, location: &str, range: Range<u64>) -> azure_core::Result<Bytes> {
+        let data = self.get(location).await?;
+        let end = data.len().min(range.end as usize);
+        let start = end.min(range.start as usize);
+        Ok(data.slice(start..end))
+    }
+
+    async fn head(&self, location: &str) -> azure_core::Result<ObjectMeta> {
+        let path = self.path(location)?;
+        Self::meta(location.to_owned(), &path)
+    }
+
+    async fn delete(&self, location: &str) -> azure_core::Result<()> {
+        let path = self.path(location)?;
+        fs::remove_file(&path).with_context(ErrorKind::Io, || {
+            format!("failed to delete {}", path.display())
+        })
+    }
+
+    fn list(&self, prefix: Option<&str>) -> BoxStream<'_, azure_core::Result<ObjectMeta>> {
+        let mut objects = Vec::new();
+        match self.list_recursive(&directory_prefix(prefix), &mut objects) {
+            Ok(()) => stream::iter(objects.into_iter().map(Ok)).boxed(),
+            Err(err) => stream::once(async { Err(err) }).boxed(),
+        }
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&str>) -> azure_core::Result<ListResult> {
+        let (objects, common_prefixes) = self.read_dir(&directory_prefix(prefix))?;
+        Ok(ListResult {
+            common_prefixes,
+            objects,
+        })
+    }
+
+    async fn copy(&self, from: &str, to: &str) -> azure_core::Result<()> {
+        let (from, to) = (self.path(from)?, self.path(to)?);
+        Self::create_parent(&to)?;
+        fs::copy(&from, &to).with_context(ErrorKind::Io, || {
+            format!("failed to copy {} to {}", from.display(), to.display())
+        })?;
+        Ok(())
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> azure_core::Result<()> {
+        let (from, to) = (self.path(from)?, self.path(to)?);
+        Self::create_parent(&to)?;
+        fs::rename(&from, &to).with_context(ErrorKind::Io, || {
+            format!("failed to rename {} to {}", from.display(), to.display())
+        })
+    }
+
+    async fn put_multipart(&self, location: &str) -> azure_core::Result<Box<dyn MultipartUpload>> {
+        Ok(Box::new(LocalUpload {
+            store: self.clone(),
+            location: location.to_owned(),
+            data: BytesMut::new(),
+        }))
+    }
+}
+
+/// The parts are kept in memory and written when the upload completes.
+#[derive(Debug)]
+struct LocalUpload {
+    store: LocalFileSystem,
+    location: String,
+    data: BytesMut,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl MultipartUpload for LocalUpload {
+    async fn put_part(&mut self, data: Bytes) -> azure_core::Result<()> {
+        self.data.extend_from_slice(&data);
+        Ok(())
+    }
+
+    async fn complete(&mut self) -> azure_core::Result<()> {
+        let data = std::mem::take(&mut self.data).freeze();
+        self.store.put(&self.location, data).await
+    }
+
+    async fn abort(&mut self) -> azure_core::Result<()> {
+        self.data.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{executor::block_on, TryStreamExt};
+
+    fn store(name: &str) -> LocalFileSystem {
+        let root = std::env::temp_dir().join(format!(
+            "azure_storage_object_store_{name}_{}",
+            uuid::Uuid::new_v4()
+        ));
+        LocalFileSystem::new(root).unwrap()
+    }
+
+    #[test]
+    fn test_directory_prefix() {
+        assert_eq!(directory_prefix(None), "");
+        assert_eq!(directory_prefix(Some("")), "");
+        assert_eq!(directory_prefix(Some("a/b")), "a/b/");
+        assert_eq!(directory_prefix(Some("a/b/")), "a/b/");
+    }
+
+    #[test]
+    fn test_local_file_system() {
+        let store = store("local");
+        block_on(async {
+            store.put("a/b/one", Bytes::from_static(b"one")).await?;
+            store.put("a/two", Bytes::from_static(b"two")).await?;
+            store.put("ab", Bytes::from_static(b"three")).await?;
+
+            assert_eq!(store.get("a/b/one").await?, Bytes::from_static(b"one"));
+            assert_eq!(
+                store.get_range("ab", 1..10).await?,
+                Bytes::from_static(b"hree")
+            );
+            assert_eq!(store.head("a/two").await?.size, 3);
+
+            let listed: Vec<_> = store.list(Some("a")).try_collect().await?;
+            let locations: Vec<_> = listed.iter().map(|o| o.location.as_str()).collect();
+            assert_eq!(locations, ["a/two", "a/b/one"]);
+
+            let result = store.list_with_delimiter(None).await?;
+            assert_eq!(result.common_prefixes, ["a/"]);
+            assert_eq!(result.objects.len(), 1);
+            assert_eq!(result.objects[0].location, "ab");
+
+            store.copy("ab", "c/ab").await?;
+            store.rename("a/two", "c/two").await?;
+            assert!(is_not_found(&store.head("a/two").await.unwrap_err()));
+            assert_eq!(store.get("c/two").await?, Bytes::from_static(b"two"));
+
+            let mut upload = store.put_multipart("d").await?;
+            upload.put_part(Bytes::from_static(b"par")).await?;
+            upload.put_part(Bytes::from_static(b"ts")).await?;
+            assert!(is_not_found(&store.get("d").await.unwrap_err()));
+            upload.complete().await?;
+            assert_eq!(store.get("d").await?, Bytes::from_static(b"parts"));
+
+            store.delete("d").await?;
+            assert!(is_not_found(&store.delete("d").await.unwrap_err()));
+            azure_core::Result::Ok(())
+        })
+        .unwrap();
+        fs::remove_dir_all(store.root()).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_location() {
+        let store = store("invalid");
+        assert!(block_on(store.get("../escape")).is_err());
+        assert!(block_on(store.get("a//b")).is_err());
+        fs::remove_dir_all(store.root()).unwrap();
+    }
+}
@@ -2,7 +2,11 @@ pub use crate::{
     consistency::{ConsistencyCRC64, ConsistencyMD5},
     shared_access_signature::{
         account_sas::{AccountSasPermissions, AccountSasResource, AccountSasResourceType},
-        service_sas::{BlobSasPermissions, BlobSignedResource},
+        parsed_sas::ParsedSharedAccessSignature,
+        service_sas::{
+            BlobSasPermissions, BlobSignedResource, FileSasPermissions, FileSignedResource,
+            QueueSasPermissions, TableSasPermissions,
+        },
         SasProtocol, SasToken,
     },
     CopyId, IPRange, StorageCredentials,
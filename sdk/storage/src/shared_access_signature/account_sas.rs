@@ -1,6 +1,6 @@
-use crate::shared_access_signature::{format_date, SasProtocol, SasToken};
+use crate::shared_access_signature::{format_date, parse_permissions, SasProtocol, SasToken};
 use azure_core::{auth::Secret, hmac::hmac_sha256};
-use std::fmt;
+use std::{fmt, str::FromStr};
 use time::OffsetDateTime;
 use url::form_urlencoded;
 
@@ -128,6 +128,30 @@ impl fmt::Display for AccountSasPermissions {
     }
 }
 
+impl FromStr for AccountSasPermissions {
+    type Err = azure_core::Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        parse_permissions(s, |c| {
+            let permission = match c {
+                'r' => &mut permissions.read,
+                'a' => &mut permissions.add,
+                'c' => &mut permissions.create,
+                'w' => &mut permissions.write,
+                'd' => &mut permissions.delete,
+                'l' => &mut permissions.list,
+                'u' => &mut permissions.update,
+                'p' => &mut permissions.process,
+                _ => return false,
+            };
+            *permission = true;
+            true
+        })?;
+        Ok(permissions)
+    }
+}
+
 #[derive(PartialEq, Eq, Debug)]
 pub struct AccountSharedAccessSignature {
     account: String,
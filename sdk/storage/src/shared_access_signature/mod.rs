@@ -1,7 +1,9 @@
-use std::fmt;
+use azure_core::error::{Error, ErrorKind};
+use std::{fmt, str::FromStr};
 use time::OffsetDateTime;
 
 pub mod account_sas;
+pub mod parsed_sas;
 pub mod service_sas;
 
 pub trait SasToken {
@@ -23,6 +25,23 @@ pub(crate) fn format_date(d: OffsetDateTime) -> String {
     azure_core::date::to_rfc3339(&d.replace_nanosecond(0).unwrap())
 }
 
+/// Parse the `sp` field of a signature, calling `set` with each permission.
+///
+/// `set` returns false for a permission which does not apply to the resource.
+pub(crate) fn parse_permissions(
+    permissions: &str,
+    mut set: impl FnMut(char) -> bool,
+) -> azure_core::Result<()> {
+    for c in permissions.chars() {
+        if !set(c) {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid permission '{c}' in shared access signature permissions '{permissions}'")
+            }));
+        }
+    }
+    Ok(())
+}
+
 /// Specifies the protocol permitted for a request made with the SAS ([Azure documentation](https://docs.microsoft.com/rest/api/storageservices/create-service-sas#specifying-the-http-protocol)).
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum SasProtocol {
@@ -39,6 +58,20 @@ impl fmt::Display for SasProtocol {
     }
 }
 
+impl FromStr for SasProtocol {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        match s {
+            "https" => Ok(SasProtocol::Https),
+            "http,https" | "https,http" => Ok(SasProtocol::HttpHttps),
+            _ => Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid shared access signature protocol: {s}")
+            })),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
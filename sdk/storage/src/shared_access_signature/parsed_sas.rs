@@ -0,0 +1,286 @@
+use crate::shared_access_signature::SasProtocol;
+use azure_core::{
+    auth::Secret,
+    date,
+    error::{Error, ErrorKind, ResultExt},
+};
+use std::str::FromStr;
+use time::OffsetDateTime;
+use url::form_urlencoded;
+
+/// A shared access signature parsed from its query string.
+///
+/// Both service and account signatures can be parsed. The permissions are kept as the `sp`
+/// field, as their meaning depends on the service: use [`permissions_as`](Self::permissions_as)
+/// to read them as the permissions of a given service.
+///
+/// ```
+/// use azure_storage::{prelude::*, shared_access_signature::parsed_sas::ParsedSharedAccessSignature};
+///
+/// let sas: ParsedSharedAccessSignature =
+///     "sv=2022-11-02&sp=rl&sr=c&se=2030-01-01T00%3A00%3A00Z&sig=c2lnbmF0dXJl".parse()?;
+/// assert_eq!(sas.version, "2022-11-02");
+/// let permissions: BlobSasPermissions = sas.permissions_as()?.unwrap();
+/// assert!(permissions.read && permissions.list);
+/// # Ok::<(), azure_core::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedSharedAccessSignature {
+    /// The signed version (`sv`).
+    pub version: String,
+    /// The signed permissions (`sp`), absent when they come from a stored access policy.
+    pub permissions: Option<String>,
+    /// The start of the validity (`st`).
+    pub start: Option<OffsetDateTime>,
+    /// The expiry (`se`), absent when it comes from a stored access policy.
+    pub expiry: Option<OffsetDateTime>,
+    /// The stored access policy (`si`).
+    pub identifier: Option<String>,
+    /// The allowed IP addresses (`sip`).
+    pub ip: Option<String>,
+    /// The allowed protocols (`spr`).
+    pub protocol: Option<SasProtocol>,
+    /// The signed resource of a blob or file service signature (`sr`).
+    pub resource: Option<String>,
+    /// The signed services of an account signature (`ss`).
+    pub services: Option<String>,
+    /// The signed resource types of an account signature (`srt`).
+    pub resource_types: Option<String>,
+    /// The table of a table service signature (`tn`).
+    pub table_name: Option<String>,
+    /// The signed directory depth (`sdd`).
+    pub directory_depth: Option<usize>,
+    /// The signature (`sig`).
+    pub signature: Secret,
+    /// Any other field of the signature, such as user delegation key or table range fields.
+    pub other: Vec<(String, String)>,
+}
+
+impl ParsedSharedAccessSignature {
+    /// Parse and validate a shared access signature, with or without its leading `?`.
+    pub fn parse(token: &str) -> azure_core::Result<Self> {
+        let token = token.strip_prefix('?').unwrap_or(token);
+
+        let mut version = None;
+        let mut permissions = None;
+        let mut start = None;
+        let mut expiry = None;
+        let mut identifier = None;
+        let mut ip = None;
+        let mut protocol = None;
+        let mut resource = None;
+        let mut services = None;
+        let mut resource_types = None;
+        let mut table_name = None;
+        let mut directory_depth = None;
+        let mut signature = None;
+        let mut other = Vec::new();
+
+        for (name, value) in form_urlencoded::parse(token.as_bytes()) {
+            let value = value.into_owned();
+            match name.as_ref() {
+                "sv" => version = Some(value),
+                "sp" => permissions = Some(value),
+                "st" => start = Some(parse_date("st", &value)?),
+                "se" => expiry = Some(parse_date("se", &value)?),
+                "si" => identifier = Some(value),
+                "sip" => ip = Some(value),
+                "spr" => protocol = Some(value.parse()?),
+                "sr" => resource = Some(value),
+                "ss" => services = Some(value),
+                "srt" => resource_types = Some(value),
+                "tn" => table_name = Some(value),
+                "sdd" => {
+                    directory_depth =
+                        Some(value.parse().with_context(ErrorKind::DataConversion, || {
+                            format!("invalid shared access signature directory depth: {value}")
+                        })?);
+                }
+                "sig" => signature = Some(Secret::new(value)),
+                _ => other.push((name.into_owned(), value)),
+            }
+        }
+
+        let version = version.ok_or_else(|| missing("sv"))?;
+        date::parse_rfc3339(&format!("{version}T00:00:00Z"))
+            .with_context(ErrorKind::DataConversion, || {
+                format!("invalid shared access signature version: {version}")
+            })?;
+        let signature = signature.ok_or_else(|| missing("sig"))?;
+        if identifier.is_none() {
+            if expiry.is_none() {
+                return Err(missing("se"));
+            }
+            if permissions.is_none() {
+                return Err(missing("sp"));
+            }
+        }
+        if let (Some(start), Some(expiry)) = (start, expiry) {
+            if start > expiry {
+                return Err(Error::message(
+                    ErrorKind::DataConversion,
+                    "shared access signature starts after it expires",
+                ));
+            }
+        }
+        if let Some(permissions) = &permissions {
+            if !permissions.chars().all(|c| c.is_ascii_lowercase()) {
+                return Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!("invalid shared access signature permissions: {permissions}")
+                }));
+            }
+        }
+
+        Ok(Self {
+            version,
+            permissions,
+            start,
+            expiry,
+            identifier,
+            ip,
+            protocol,
+            resource,
+            services,
+            resource_types,
+            table_name,
+            directory_depth,
+            signature,
+            other,
+        })
+    }
+
+    /// Whether this is an account signature rather than a service signature.
+    pub fn is_account_sas(&self) -> bool {
+        self.services.is_some()
+    }
+
+    /// The permissions, read as the permissions of a given service, such as
+    /// `BlobSasPermissions` or `QueueSasPermissions`.
+    ///
+    /// Fails if a permission does not apply to that service.
+    pub fn permissions_as<P>(&self) -> azure_core::Result<Option<P>>
+    where
+        P: FromStr<Err = Error>,
+    {
+        self.permissions.as_deref().map(str::parse).transpose()
+    }
+
+    /// Whether the signature is valid at `time`, as far as its start and expiry tell.
+    ///
+    /// A signature taking its validity from a stored access policy is assumed valid.
+    pub fn is_valid_at(&self, time: OffsetDateTime) -> bool {
+        self.start.map_or(true, |start| start <= time)
+            && self.expiry.map_or(true, |expiry| time < expiry)
+    }
+
+    /// Whether the signature has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expiry
+            .map_or(false, |expiry| expiry <= OffsetDateTime::now_utc())
+    }
+}
+
+impl FromStr for ParsedSharedAccessSignature {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        Self::parse(s)
+    }
+}
+
+fn missing(field: &str) -> Error {
+    Error::with_message(ErrorKind::DataConversion, || {
+        format!("shared access signature is missing the {field} field")
+    })
+}
+
+/// Parse a signature date, which may omit the seconds or the whole time.
+fn parse_date(field: &str, value: &str) -> azure_core::Result<OffsetDateTime> {
+    [
+        value.to_owned(),
+        value.replacen('Z', ":00Z", 1),
+        format!("{value}T00:00:00Z"),
+    ]
+    .iter()
+    .find_map(|candidate| date::parse_rfc3339(candidate).ok())
+    .ok_or_else(|| {
+        Error::with_message(ErrorKind::DataConversion, || {
+            format!("invalid shared access signature {field} date: {value}")
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared_access_signature::{
+        service_sas::{QueueSasPermissions, QueueSharedAccessSignature},
+        SasToken,
+    };
+    use time::macros::datetime;
+
+    #[test]
+    fn test_parse_generated_token() -> azure_core::Result<()> {
+        let token = QueueSharedAccessSignature::new(
+            Secret::new("RZfi3m1W7eyQ5zD4ymSmGANVdJ2SDQmg4sE89SW104s="),
+            "/queue/account/queue".to_owned(),
+            QueueSasPermissions {
+                read: true,
+                process: true,
+                ..Default::default()
+            },
+            datetime!(2030-01-01 0:00 UTC),
+        )
+        .start(datetime!(2029-12-01 0:00 UTC))
+        .protocol(SasProtocol::Https)
+        .token()?;
+
+        let sas = ParsedSharedAccessSignature::parse(&format!("?{token}"))?;
+        assert_eq!(sas.version, "2022-11-02");
+        assert_eq!(sas.expiry, Some(datetime!(2030-01-01 0:00 UTC)));
+        assert_eq!(sas.protocol, Some(SasProtocol::Https));
+        assert!(!sas.is_account_sas());
+        assert!(sas.is_valid_at(datetime!(2029-12-24 0:00 UTC)));
+        assert!(!sas.is_valid_at(datetime!(2030-01-01 0:00 UTC)));
+
+        let permissions: QueueSasPermissions = sas.permissions_as()?.unwrap();
+        assert!(permissions.read && permissions.process && !permissions.add);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_account_token() -> azure_core::Result<()> {
+        let sas: ParsedSharedAccessSignature =
+            "sv=2018-11-09&ss=b&srt=sco&se=2019-01-01&sp=rwdl&spr=https,http&sig=c2ln".parse()?;
+        assert!(sas.is_account_sas());
+        assert_eq!(sas.resource_types.as_deref(), Some("sco"));
+        assert_eq!(sas.expiry, Some(datetime!(2019-01-01 0:00 UTC)));
+        assert!(sas.is_expired());
+        assert!(sas.permissions_as::<QueueSasPermissions>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid_token() {
+        // missing signature
+        assert!(ParsedSharedAccessSignature::parse("sv=2022-11-02&sp=r&se=2030-01-01").is_err());
+        // missing version
+        assert!(ParsedSharedAccessSignature::parse("sp=r&se=2030-01-01&sig=c2ln").is_err());
+        // missing expiry without a stored access policy
+        assert!(ParsedSharedAccessSignature::parse("sv=2022-11-02&sp=r&sig=c2ln").is_err());
+        assert!(ParsedSharedAccessSignature::parse("sv=2022-11-02&si=policy&sig=c2ln").is_ok());
+        // invalid date and protocol
+        assert!(
+            ParsedSharedAccessSignature::parse("sv=2022-11-02&sp=r&se=tomorrow&sig=c2ln").is_err()
+        );
+        assert!(ParsedSharedAccessSignature::parse(
+            "sv=2022-11-02&sp=r&se=2030-01-01&spr=ftp&sig=c2ln"
+        )
+        .is_err());
+        // starts after it expires
+        assert!(ParsedSharedAccessSignature::parse(
+            "sv=2022-11-02&sp=r&st=2030-02-01&se=2030-01-01&sig=c2ln"
+        )
+        .is_err());
+    }
+}
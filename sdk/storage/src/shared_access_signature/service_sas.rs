@@ -1,12 +1,13 @@
-use crate::shared_access_signature::{format_date, SasProtocol, SasToken};
+use crate::shared_access_signature::{format_date, parse_permissions, SasProtocol, SasToken};
 use azure_core::{auth::Secret, date::iso8601, hmac::hmac_sha256};
-use std::fmt;
+use std::{fmt, str::FromStr};
 use time::OffsetDateTime;
 use url::form_urlencoded;
 use uuid::Uuid;
 
 const SERVICE_SAS_VERSION: &str = "2022-11-02";
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum BlobSignedResource {
     Blob,         // b
     BlobVersion,  // bv
@@ -28,7 +29,7 @@ impl fmt::Display for BlobSignedResource {
 }
 
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Default)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
 pub struct BlobSasPermissions {
     pub read: bool,             // r - Container | Directory | Blob
     pub add: bool,              // a - Container | Directory | Blob
@@ -91,6 +92,35 @@ impl fmt::Display for BlobSasPermissions {
     }
 }
 
+impl FromStr for BlobSasPermissions {
+    type Err = azure_core::Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        parse_permissions(s, |c| {
+            let permission = match c {
+                'r' => &mut permissions.read,
+                'a' => &mut permissions.add,
+                'c' => &mut permissions.create,
+                'w' => &mut permissions.write,
+                'd' => &mut permissions.delete,
+                'x' => &mut permissions.delete_version,
+                'y' => &mut permissions.permanent_delete,
+                'l' => &mut permissions.list,
+                't' => &mut permissions.tags,
+                'm' => &mut permissions.move_,
+                'e' => &mut permissions.execute,
+                'o' => &mut permissions.ownership,
+                'p' => &mut permissions.permissions,
+                _ => return false,
+            };
+            *permission = true;
+            true
+        })?;
+        Ok(permissions)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct UserDeligationKey {
@@ -126,10 +156,10 @@ pub struct BlobSharedAccessSignature {
     key: SasKey,
     canonicalized_resource: String,
     resource: BlobSignedResource,
-    permissions: BlobSasPermissions, // sp
-    expiry: OffsetDateTime,          // se
-    start: Option<OffsetDateTime>,   // st
-    identifier: Option<String>,
+    permissions: Option<BlobSasPermissions>, // sp
+    expiry: Option<OffsetDateTime>,          // se
+    start: Option<OffsetDateTime>,           // st
+    identifier: Option<String>,              // si
     ip: Option<String>,
     protocol: Option<SasProtocol>,
     signed_directory_depth: Option<usize>, // sdd
@@ -150,8 +180,8 @@ impl BlobSharedAccessSignature {
             key: key.into(),
             canonicalized_resource,
             resource,
-            permissions,
-            expiry,
+            permissions: Some(permissions),
+            expiry: Some(expiry),
             start: None,
             identifier: None,
             ip: None,
@@ -160,7 +190,33 @@ impl BlobSharedAccessSignature {
         }
     }
 
+    /// Create a shared access signature whose permissions and expiry are taken from the
+    /// stored access policy `identifier` of the container.
+    ///
+    /// Permissions or an expiry not defined by the policy must be set on the signature.
+    pub fn from_stored_access_policy(
+        key: Secret,
+        canonicalized_resource: String,
+        identifier: String,
+        resource: BlobSignedResource,
+    ) -> Self {
+        Self {
+            key: SasKey::Key(key),
+            canonicalized_resource,
+            resource,
+            permissions: None,
+            expiry: None,
+            start: None,
+            identifier: Some(identifier),
+            ip: None,
+            protocol: None,
+            signed_directory_depth: None,
+        }
+    }
+
     setters! {
+        permissions: BlobSasPermissions => Some(permissions),
+        expiry: OffsetDateTime => Some(expiry),
         start: OffsetDateTime => Some(start),
         identifier: String => Some(identifier),
         ip: String => Some(ip),
@@ -170,9 +226,9 @@ impl BlobSharedAccessSignature {
 
     fn sign(&self) -> azure_core::Result<String> {
         let mut content = vec![
-            self.permissions.to_string(),
+            self.permissions.map_or(String::new(), |p| p.to_string()),
             self.start.map_or(String::new(), format_date),
-            format_date(self.expiry),
+            self.expiry.map_or(String::new(), format_date),
             self.canonicalized_resource.clone(),
         ];
 
@@ -236,17 +292,23 @@ impl SasToken for BlobSharedAccessSignature {
             ]);
         }
 
-        form.extend_pairs(&[
-            ("sv", SERVICE_SAS_VERSION),
-            ("sp", &self.permissions.to_string()),
-            ("sr", &self.resource.to_string()),
-            ("se", &format_date(self.expiry)),
-        ]);
+        form.append_pair("sv", SERVICE_SAS_VERSION);
+        if let Some(permissions) = &self.permissions {
+            form.append_pair("sp", &permissions.to_string());
+        }
+        form.append_pair("sr", &self.resource.to_string());
+        if let Some(expiry) = &self.expiry {
+            form.append_pair("se", &format_date(*expiry));
+        }
 
         if let Some(start) = &self.start {
             form.append_pair("st", &format_date(*start));
         }
 
+        if let (SasKey::Key(_), Some(identifier)) = (&self.key, &self.identifier) {
+            form.append_pair("si", identifier);
+        }
+
         if let Some(ip) = &self.ip {
             form.append_pair("sip", ip);
         }
@@ -265,6 +327,525 @@ impl SasToken for BlobSharedAccessSignature {
     }
 }
 
+/// Operations permitted on a queue by a shared access signature ([Azure documentation](https://docs.microsoft.com/rest/api/storageservices/create-service-sas#permissions-for-a-queue)).
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct QueueSasPermissions {
+    pub read: bool,    // r - peek messages and read metadata
+    pub add: bool,     // a - add messages
+    pub update: bool,  // u - update messages
+    pub process: bool, // p - get and delete messages
+}
+
+impl fmt::Display for QueueSasPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.read {
+            write!(f, "r")?;
+        };
+        if self.add {
+            write!(f, "a")?;
+        };
+        if self.update {
+            write!(f, "u")?;
+        };
+        if self.process {
+            write!(f, "p")?;
+        };
+        Ok(())
+    }
+}
+
+impl FromStr for QueueSasPermissions {
+    type Err = azure_core::Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        parse_permissions(s, |c| {
+            let permission = match c {
+                'r' => &mut permissions.read,
+                'a' => &mut permissions.add,
+                'u' => &mut permissions.update,
+                'p' => &mut permissions.process,
+                _ => return false,
+            };
+            *permission = true;
+            true
+        })?;
+        Ok(permissions)
+    }
+}
+
+/// Shared access signature for a queue ([Azure documentation](https://docs.microsoft.com/rest/api/storageservices/create-service-sas#version-2015-04-05-and-later-queue-service)).
+pub struct QueueSharedAccessSignature {
+    key: Secret,
+    canonicalized_resource: String,
+    permissions: Option<QueueSasPermissions>, // sp
+    expiry: Option<OffsetDateTime>,           // se
+    start: Option<OffsetDateTime>,            // st
+    identifier: Option<String>,               // si
+    ip: Option<String>,
+    protocol: Option<SasProtocol>,
+}
+
+impl QueueSharedAccessSignature {
+    pub fn new(
+        key: Secret,
+        canonicalized_resource: String,
+        permissions: QueueSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            permissions: Some(permissions),
+            expiry: Some(expiry),
+            start: None,
+            identifier: None,
+            ip: None,
+            protocol: None,
+        }
+    }
+
+    /// Create a shared access signature whose permissions and expiry are taken from the
+    /// stored access policy `identifier` of the queue.
+    pub fn from_stored_access_policy(
+        key: Secret,
+        canonicalized_resource: String,
+        identifier: String,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            permissions: None,
+            expiry: None,
+            start: None,
+            identifier: Some(identifier),
+            ip: None,
+            protocol: None,
+        }
+    }
+
+    setters! {
+        permissions: QueueSasPermissions => Some(permissions),
+        expiry: OffsetDateTime => Some(expiry),
+        start: OffsetDateTime => Some(start),
+        identifier: String => Some(identifier),
+        ip: String => Some(ip),
+        protocol: SasProtocol => Some(protocol),
+    }
+
+    fn sign(&self) -> azure_core::Result<String> {
+        let content = [
+            self.permissions.map_or(String::new(), |p| p.to_string()),
+            self.start.map_or(String::new(), format_date),
+            self.expiry.map_or(String::new(), format_date),
+            self.canonicalized_resource.clone(),
+            self.identifier.clone().unwrap_or_default(),
+            self.ip.clone().unwrap_or_default(),
+            self.protocol.map(|x| x.to_string()).unwrap_or_default(),
+            SERVICE_SAS_VERSION.to_string(),
+        ];
+        hmac_sha256(&content.join("\n"), &self.key)
+    }
+}
+
+impl SasToken for QueueSharedAccessSignature {
+    fn token(&self) -> azure_core::Result<String> {
+        let mut form = form_urlencoded::Serializer::new(String::new());
+        form.append_pair("sv", SERVICE_SAS_VERSION);
+        append_policy_fields(
+            &mut form,
+            self.permissions.map(|p| p.to_string()),
+            self.start,
+            self.expiry,
+            self.identifier.as_deref(),
+        );
+        append_network_fields(&mut form, self.ip.as_deref(), self.protocol);
+        form.append_pair("sig", &self.sign()?);
+        Ok(form.finish())
+    }
+}
+
+/// Operations permitted on a table by a shared access signature ([Azure documentation](https://docs.microsoft.com/rest/api/storageservices/create-service-sas#permissions-for-a-table)).
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct TableSasPermissions {
+    pub query: bool,  // r - get entities and query entities
+    pub add: bool,    // a - insert entities
+    pub update: bool, // u - update entities
+    pub delete: bool, // d - delete entities
+}
+
+impl fmt::Display for TableSasPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.query {
+            write!(f, "r")?;
+        };
+        if self.add {
+            write!(f, "a")?;
+        };
+        if self.update {
+            write!(f, "u")?;
+        };
+        if self.delete {
+            write!(f, "d")?;
+        };
+        Ok(())
+    }
+}
+
+impl FromStr for TableSasPermissions {
+    type Err = azure_core::Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        parse_permissions(s, |c| {
+            let permission = match c {
+                'r' => &mut permissions.query,
+                'a' => &mut permissions.add,
+                'u' => &mut permissions.update,
+                'd' => &mut permissions.delete,
+                _ => return false,
+            };
+            *permission = true;
+            true
+        })?;
+        Ok(permissions)
+    }
+}
+
+/// Shared access signature for a table, optionally restricted to a range of entities ([Azure documentation](https://docs.microsoft.com/rest/api/storageservices/create-service-sas#version-2015-04-05-and-later-table-service)).
+pub struct TableSharedAccessSignature {
+    key: Secret,
+    canonicalized_resource: String,
+    table_name: String,                       // tn
+    permissions: Option<TableSasPermissions>, // sp
+    expiry: Option<OffsetDateTime>,           // se
+    start: Option<OffsetDateTime>,            // st
+    identifier: Option<String>,               // si
+    ip: Option<String>,
+    protocol: Option<SasProtocol>,
+    start_partition_key: Option<String>, // spk
+    start_row_key: Option<String>,       // srk
+    end_partition_key: Option<String>,   // epk
+    end_row_key: Option<String>,         // erk
+}
+
+impl TableSharedAccessSignature {
+    pub fn new(
+        key: Secret,
+        canonicalized_resource: String,
+        table_name: String,
+        permissions: TableSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            table_name,
+            permissions: Some(permissions),
+            expiry: Some(expiry),
+            start: None,
+            identifier: None,
+            ip: None,
+            protocol: None,
+            start_partition_key: None,
+            start_row_key: None,
+            end_partition_key: None,
+            end_row_key: None,
+        }
+    }
+
+    /// Create a shared access signature whose permissions and expiry are taken from the
+    /// stored access policy `identifier` of the table.
+    pub fn from_stored_access_policy(
+        key: Secret,
+        canonicalized_resource: String,
+        table_name: String,
+        identifier: String,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            table_name,
+            permissions: None,
+            expiry: None,
+            start: None,
+            identifier: Some(identifier),
+            ip: None,
+            protocol: None,
+            start_partition_key: None,
+            start_row_key: None,
+            end_partition_key: None,
+            end_row_key: None,
+        }
+    }
+
+    setters! {
+        permissions: TableSasPermissions => Some(permissions),
+        expiry: OffsetDateTime => Some(expiry),
+        start: OffsetDateTime => Some(start),
+        identifier: String => Some(identifier),
+        ip: String => Some(ip),
+        protocol: SasProtocol => Some(protocol),
+        start_partition_key: String => Some(start_partition_key),
+        start_row_key: String => Some(start_row_key),
+        end_partition_key: String => Some(end_partition_key),
+        end_row_key: String => Some(end_row_key),
+    }
+
+    fn sign(&self) -> azure_core::Result<String> {
+        let content = [
+            self.permissions.map_or(String::new(), |p| p.to_string()),
+            self.start.map_or(String::new(), format_date),
+            self.expiry.map_or(String::new(), format_date),
+            self.canonicalized_resource.clone(),
+            self.identifier.clone().unwrap_or_default(),
+            self.ip.clone().unwrap_or_default(),
+            self.protocol.map(|x| x.to_string()).unwrap_or_default(),
+            SERVICE_SAS_VERSION.to_string(),
+            self.start_partition_key.clone().unwrap_or_default(),
+            self.start_row_key.clone().unwrap_or_default(),
+            self.end_partition_key.clone().unwrap_or_default(),
+            self.end_row_key.clone().unwrap_or_default(),
+        ];
+        hmac_sha256(&content.join("\n"), &self.key)
+    }
+}
+
+impl SasToken for TableSharedAccessSignature {
+    fn token(&self) -> azure_core::Result<String> {
+        let mut form = form_urlencoded::Serializer::new(String::new());
+        form.append_pair("sv", SERVICE_SAS_VERSION);
+        form.append_pair("tn", &self.table_name);
+        append_policy_fields(
+            &mut form,
+            self.permissions.map(|p| p.to_string()),
+            self.start,
+            self.expiry,
+            self.identifier.as_deref(),
+        );
+        append_network_fields(&mut form, self.ip.as_deref(), self.protocol);
+        for (name, value) in [
+            ("spk", &self.start_partition_key),
+            ("srk", &self.start_row_key),
+            ("epk", &self.end_partition_key),
+            ("erk", &self.end_row_key),
+        ] {
+            if let Some(value) = value {
+                form.append_pair(name, value);
+            }
+        }
+        form.append_pair("sig", &self.sign()?);
+        Ok(form.finish())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FileSignedResource {
+    File,  // f
+    Share, // s
+}
+
+impl fmt::Display for FileSignedResource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::File => write!(f, "f"),
+            Self::Share => write!(f, "s"),
+        }
+    }
+}
+
+/// Operations permitted on a file or a share by a shared access signature ([Azure documentation](https://docs.microsoft.com/rest/api/storageservices/create-service-sas#permissions-for-a-file)).
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct FileSasPermissions {
+    pub read: bool,   // r - File | Share
+    pub create: bool, // c - File | Share
+    pub write: bool,  // w - File | Share
+    pub delete: bool, // d - File | Share
+    pub list: bool,   // l - Share
+}
+
+impl fmt::Display for FileSasPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.read {
+            write!(f, "r")?;
+        };
+        if self.create {
+            write!(f, "c")?;
+        };
+        if self.write {
+            write!(f, "w")?;
+        };
+        if self.delete {
+            write!(f, "d")?;
+        };
+        if self.list {
+            write!(f, "l")?;
+        };
+        Ok(())
+    }
+}
+
+impl FromStr for FileSasPermissions {
+    type Err = azure_core::Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        parse_permissions(s, |c| {
+            let permission = match c {
+                'r' => &mut permissions.read,
+                'c' => &mut permissions.create,
+                'w' => &mut permissions.write,
+                'd' => &mut permissions.delete,
+                'l' => &mut permissions.list,
+                _ => return false,
+            };
+            *permission = true;
+            true
+        })?;
+        Ok(permissions)
+    }
+}
+
+/// Shared access signature for a file or a share ([Azure documentation](https://docs.microsoft.com/rest/api/storageservices/create-service-sas#version-2015-04-05-and-later-file-service)).
+pub struct FileSharedAccessSignature {
+    key: Secret,
+    canonicalized_resource: String,
+    resource: FileSignedResource,
+    permissions: Option<FileSasPermissions>, // sp
+    expiry: Option<OffsetDateTime>,          // se
+    start: Option<OffsetDateTime>,           // st
+    identifier: Option<String>,              // si
+    ip: Option<String>,
+    protocol: Option<SasProtocol>,
+}
+
+impl FileSharedAccessSignature {
+    pub fn new(
+        key: Secret,
+        canonicalized_resource: String,
+        permissions: FileSasPermissions,
+        expiry: OffsetDateTime,
+        resource: FileSignedResource,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            resource,
+            permissions: Some(permissions),
+            expiry: Some(expiry),
+            start: None,
+            identifier: None,
+            ip: None,
+            protocol: None,
+        }
+    }
+
+    /// Create a shared access signature whose permissions and expiry are taken from the
+    /// stored access policy `identifier` of the share.
+    pub fn from_stored_access_policy(
+        key: Secret,
+        canonicalized_resource: String,
+        identifier: String,
+        resource: FileSignedResource,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            resource,
+            permissions: None,
+            expiry: None,
+            start: None,
+            identifier: Some(identifier),
+            ip: None,
+            protocol: None,
+        }
+    }
+
+    setters! {
+        permissions: FileSasPermissions => Some(permissions),
+        expiry: OffsetDateTime => Some(expiry),
+        start: OffsetDateTime => Some(start),
+        identifier: String => Some(identifier),
+        ip: String => Some(ip),
+        protocol: SasProtocol => Some(protocol),
+    }
+
+    fn sign(&self) -> azure_core::Result<String> {
+        let content = [
+            self.permissions.map_or(String::new(), |p| p.to_string()),
+            self.start.map_or(String::new(), format_date),
+            self.expiry.map_or(String::new(), format_date),
+            self.canonicalized_resource.clone(),
+            self.identifier.clone().unwrap_or_default(),
+            self.ip.clone().unwrap_or_default(),
+            self.protocol.map(|x| x.to_string()).unwrap_or_default(),
+            SERVICE_SAS_VERSION.to_string(),
+            String::new(), // SIGNED CACHE CONTROL
+            String::new(), // SIGNED CONTENT DISPOSITION
+            String::new(), // SIGNED CONTENT ENCODING
+            String::new(), // SIGNED CONTENT LANGUAGE
+            String::new(), // SIGNED CONTENT TYPE
+        ];
+        hmac_sha256(&content.join("\n"), &self.key)
+    }
+}
+
+impl SasToken for FileSharedAccessSignature {
+    fn token(&self) -> azure_core::Result<String> {
+        let mut form = form_urlencoded::Serializer::new(String::new());
+        form.append_pair("sv", SERVICE_SAS_VERSION);
+        form.append_pair("sr", &self.resource.to_string());
+        append_policy_fields(
+            &mut form,
+            self.permissions.map(|p| p.to_string()),
+            self.start,
+            self.expiry,
+            self.identifier.as_deref(),
+        );
+        append_network_fields(&mut form, self.ip.as_deref(), self.protocol);
+        form.append_pair("sig", &self.sign()?);
+        Ok(form.finish())
+    }
+}
+
+/// Append the fields that may be taken from a stored access policy instead.
+fn append_policy_fields(
+    form: &mut form_urlencoded::Serializer<String>,
+    permissions: Option<String>,
+    start: Option<OffsetDateTime>,
+    expiry: Option<OffsetDateTime>,
+    identifier: Option<&str>,
+) {
+    if let Some(permissions) = permissions {
+        form.append_pair("sp", &permissions);
+    }
+    if let Some(start) = start {
+        form.append_pair("st", &format_date(start));
+    }
+    if let Some(expiry) = expiry {
+        form.append_pair("se", &format_date(expiry));
+    }
+    if let Some(identifier) = identifier {
+        form.append_pair("si", identifier);
+    }
+}
+
+fn append_network_fields(
+    form: &mut form_urlencoded::Serializer<String>,
+    ip: Option<&str>,
+    protocol: Option<SasProtocol>,
+) {
+    if let Some(ip) = ip {
+        form.append_pair("sip", ip);
+    }
+    if let Some(protocol) = protocol {
+        form.append_pair("spr", &protocol.to_string());
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -327,4 +908,114 @@ mod test {
         assert!(parsed.find(|(k, v)| k == "sdd" && v == "2").is_some());
         Ok(())
     }
+
+    #[test]
+    fn test_stored_access_policy_sas_token() -> azure_core::Result<()> {
+        let signed_token = BlobSharedAccessSignature::from_stored_access_policy(
+            Secret::new(MOCK_SECRET_KEY),
+            String::from(MOCK_CANONICALIZED_RESOURCE),
+            "policy".to_owned(),
+            BlobSignedResource::Container,
+        )
+        .token()?;
+
+        let parsed: Vec<_> = url::form_urlencoded::parse(signed_token.as_bytes()).collect();
+        assert!(parsed.iter().any(|(k, v)| k == "si" && v == "policy"));
+        assert!(!parsed.iter().any(|(k, _)| k == "sp" || k == "se"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_queue_sas_token() -> azure_core::Result<()> {
+        let permissions = QueueSasPermissions {
+            read: true,
+            process: true,
+            ..Default::default()
+        };
+        let signed_token = QueueSharedAccessSignature::new(
+            Secret::new(MOCK_SECRET_KEY),
+            String::from("/queue/STORAGE_ACCOUNT_NAME/QUEUE_NAME"),
+            permissions,
+            OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+        )
+        .token()?;
+
+        let expected_sig = hmac_sha256(
+            "rp\n\n1970-01-08T00:00:00Z\n/queue/STORAGE_ACCOUNT_NAME/QUEUE_NAME\n\n\n\n2022-11-02",
+            &Secret::new(MOCK_SECRET_KEY),
+        )?;
+        let expected = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("sv", "2022-11-02")
+            .append_pair("sp", "rp")
+            .append_pair("se", "1970-01-08T00:00:00Z")
+            .append_pair("sig", &expected_sig)
+            .finish();
+        assert_eq!(signed_token, expected);
+        assert_eq!("rp".parse::<QueueSasPermissions>()?, permissions);
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_sas_token() -> azure_core::Result<()> {
+        let signed_token = TableSharedAccessSignature::new(
+            Secret::new(MOCK_SECRET_KEY),
+            String::from("/table/STORAGE_ACCOUNT_NAME/table_name"),
+            String::from("table_name"),
+            TableSasPermissions {
+                query: true,
+                ..Default::default()
+            },
+            OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+        )
+        .start_partition_key("a".to_owned())
+        .end_partition_key("b".to_owned())
+        .token()?;
+
+        let expected_sig = hmac_sha256(
+            "r\n\n1970-01-08T00:00:00Z\n/table/STORAGE_ACCOUNT_NAME/table_name\n\n\n\n2022-11-02\na\n\nb\n",
+            &Secret::new(MOCK_SECRET_KEY),
+        )?;
+        let parsed: Vec<_> = url::form_urlencoded::parse(signed_token.as_bytes()).collect();
+        assert!(parsed.iter().any(|(k, v)| k == "tn" && v == "table_name"));
+        assert!(parsed.iter().any(|(k, v)| k == "spk" && v == "a"));
+        assert!(parsed.iter().any(|(k, v)| k == "epk" && v == "b"));
+        assert!(!parsed.iter().any(|(k, _)| k == "srk" || k == "erk"));
+        assert!(parsed.iter().any(|(k, v)| k == "sig" && *v == expected_sig));
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_sas_token() -> azure_core::Result<()> {
+        let signed_token = FileSharedAccessSignature::new(
+            Secret::new(MOCK_SECRET_KEY),
+            String::from("/file/STORAGE_ACCOUNT_NAME/share/dir/file"),
+            FileSasPermissions {
+                read: true,
+                ..Default::default()
+            },
+            OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+            FileSignedResource::File,
+        )
+        .token()?;
+
+        let expected_sig = hmac_sha256(
+            "r\n\n1970-01-08T00:00:00Z\n/file/STORAGE_ACCOUNT_NAME/share/dir/file\n\n\n\n2022-11-02\n\n\n\n\n",
+            &Secret::new(MOCK_SECRET_KEY),
+        )?;
+        let parsed: Vec<_> = url::form_urlencoded::parse(signed_token.as_bytes()).collect();
+        assert!(parsed.iter().any(|(k, v)| k == "sr" && v == "f"));
+        assert!(parsed.iter().any(|(k, v)| k == "sig" && *v == expected_sig));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_permissions() -> azure_core::Result<()> {
+        let permissions: BlobSasPermissions = "racwdl".parse()?;
+        assert!(permissions.read && permissions.list && !permissions.tags);
+        assert_eq!(permissions.to_string(), "racwdl");
+        assert!("rz".parse::<BlobSasPermissions>().is_err());
+        assert!("rl".parse::<TableSasPermissions>().is_err());
+        assert_eq!("rcwdl".parse::<FileSasPermissions>()?.to_string(), "rcwdl");
+        Ok(())
+    }
 }
@@ -0,0 +1,380 @@
+//! The structured message format used by Azure Storage to validate streamed content.
+//!
+//! A structured message splits the content in segments, each followed by its CRC64, and ends with
+//! the CRC64 of the whole content. This lets the checksum of a streamed body be computed while it
+//! is sent, instead of before sending it.
+//!
+//! All integers are little-endian. The message header holds the version (`u8`), the length of the
+//! whole message (`u64`), the flags (`u16`) and the number of segments (`u16`). Each segment starts
+//! with its number (`u16`, from 1) and the length of its data (`u64`).
+
+use crate::crc64::Crc64;
+use azure_core::{
+    error::{Error, ErrorKind},
+    SeekableStream,
+};
+use bytes::{Bytes, BytesMut};
+use futures::io::AsyncRead;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The value of the `x-ms-structured-body` header for a message with CRC64 checksums.
+pub const STRUCTURED_BODY_CRC64: &str = "XSM/1.0; properties=crc64";
+
+/// The first service version accepting structured messages.
+pub const SERVICE_VERSION: &str = "2025-01-05";
+
+/// The length of the segments content is split into, unless it needs more than `u16::MAX` of them.
+pub const DEFAULT_SEGMENT_LENGTH: u64 = 4 * 1024 * 1024;
+
+const VERSION: u8 = 1;
+const FLAG_CRC64: u16 = 1;
+const HEADER_LENGTH: u64 = 13;
+const SEGMENT_HEADER_LENGTH: u64 = 10;
+const CRC64_LENGTH: u64 = 8;
+
+fn segment_length(content_length: u64) -> u64 {
+    DEFAULT_SEGMENT_LENGTH.max(content_length.div_ceil(u64::from(u16::MAX)))
+}
+
+fn segment_count(content_length: u64, segment_length: u64) -> u64 {
+    content_length.div_ceil(segment_length)
+}
+
+/// The length of the structured message encoding `content_length` bytes.
+pub fn encoded_length(content_length: u64) -> u64 {
+    let segments = segment_count(content_length, segment_length(content_length));
+    HEADER_LENGTH
+        + segments * (SEGMENT_HEADER_LENGTH + CRC64_LENGTH)
+        + content_length
+        + CRC64_LENGTH
+}
+
+fn message_header(content_length: u64) -> Vec<u8> {
+    let segments = segment_count(content_length, segment_length(content_length));
+    let mut header = Vec::with_capacity(HEADER_LENGTH as usize);
+    header.push(VERSION);
+    header.extend_from_slice(&encoded_length(content_length).to_le_bytes());
+    header.extend_from_slice(&FLAG_CRC64.to_le_bytes());
+    header.extend_from_slice(&(segments as u16).to_le_bytes());
+    header
+}
+
+fn segment_header(number: u16, length: u64) -> Vec<u8> {
+    let mut header = Vec::with_capacity(SEGMENT_HEADER_LENGTH as usize);
+    header.extend_from_slice(&number.to_le_bytes());
+    header.extend_from_slice(&length.to_le_bytes());
+    header
+}
+
+/// Encode `content` as a structured message.
+pub fn encode(content: &[u8]) -> Bytes {
+    let content_length = content.len() as u64;
+    let mut message = BytesMut::with_capacity(encoded_length(content_length) as usize);
+    message.extend_from_slice(&message_header(content_length));
+    let mut message_crc = Crc64::new();
+    for (i, segment) in content
+        .chunks(segment_length(content_length) as usize)
+        .enumerate()
+    {
+        message.extend_from_slice(&segment_header(i as u16 + 1, segment.len() as u64));
+        message.extend_from_slice(segment);
+        let mut segment_crc = Crc64::new();
+        segment_crc.update(segment);
+        message_crc.update(segment);
+        message.extend_from_slice(&segment_crc.finish().to_le_bytes());
+    }
+    message.extend_from_slice(&message_crc.finish().to_le_bytes());
+    message.freeze()
+}
+
+/// Decode a structured message, validating the checksums it holds.
+pub fn decode(message: &[u8]) -> azure_core::Result<Bytes> {
+    let mut reader = MessageReader { message };
+    let version = reader.read::<1>()?[0];
+    if version != VERSION {
+        return Err(Error::with_message(ErrorKind::DataConversion, || {
+            format!("unsupported structured message version {version}")
+        }));
+    }
+    let message_length = u64::from_le_bytes(reader.read()?);
+    if message_length != message.len() as u64 {
+        return Err(Error::with_message(ErrorKind::DataConversion, || {
+            format!(
+                "structured message length is {} but its header declares {message_length}",
+                message.len()
+            )
+        }));
+    }
+    let crc64 = u16::from_le_bytes(reader.read()?) & FLAG_CRC64 != 0;
+    let segments = u16::from_le_bytes(reader.read()?);
+
+    let mut content = BytesMut::new();
+    let mut message_crc = Crc64::new();
+    for expected_number in 1..=segments {
+        let number = u16::from_le_bytes(reader.read()?);
+        if number != expected_number {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("expected structured message segment {expected_number} but found {number}")
+            }));
+        }
+        let length = u64::from_le_bytes(reader.read()?);
+        let segment = reader.read_slice(length)?;
+        if crc64 {
+            let mut segment_crc = Crc64::new();
+            segment_crc.update(segment);
+            verify_crc64(segment_crc.finish(), reader.read()?, "segment")?;
+        }
+        message_crc.update(segment);
+        content.extend_from_slice(segment);
+    }
+    if crc64 {
+        verify_crc64(message_crc.finish(), reader.read()?, "message")?;
+    }
+    if !reader.message.is_empty() {
+        return Err(Error::message(
+            ErrorKind::DataConversion,
+            "unexpected data after the end of the structured message",
+        ));
+    }
+    Ok(content.freeze())
+}
+
+fn verify_crc64(actual: u64, expected: [u8; 8], what: &str) -> azure_core::Result<()> {
+    let expected = u64::from_le_bytes(expected);
+    if actual != expected {
+        return Err(Error::with_message(ErrorKind::DataConversion, || {
+            format!("structured message {what} checksum mismatch, expected {expected:#x} but content has {actual:#x}")
+        }));
+    }
+    Ok(())
+}
+
+struct MessageReader<'a> {
+    message: &'a [u8],
+}
+
+impl<'a> MessageReader<'a> {
+    fn read_slice(&mut self, length: u64) -> azure_core::Result<&'a [u8]> {
+        if (self.message.len() as u64) < length {
+            return Err(Error::message(
+                ErrorKind::DataConversion,
+                "structured message is truncated",
+            ));
+        }
+        let (slice, rest) = self.message.split_at(length as usize);
+        self.message = rest;
+        Ok(slice)
+    }
+
+    fn read<const N: usize>(&mut self) -> azure_core::Result<[u8; N]> {
+        let slice = self.read_slice(N as u64)?;
+        Ok(slice.try_into().expect("slice has the requested length"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EncoderState {
+    MessageHeader,
+    SegmentHeader,
+    SegmentData,
+    SegmentFooter,
+    MessageFooter,
+    Done,
+}
+
+/// Encodes a stream as a structured message while it is read.
+#[derive(Debug, Clone)]
+pub struct StructuredMessageEncoder {
+    inner: Box<dyn SeekableStream>,
+    content_length: u64,
+    state: EncoderState,
+    pending: Vec<u8>,
+    pending_offset: usize,
+    segment_number: u16,
+    segment_remaining: u64,
+    content_read: u64,
+    segment_crc: Crc64,
+    message_crc: Crc64,
+}
+
+impl StructuredMessageEncoder {
+    pub fn new(inner: Box<dyn SeekableStream>) -> Self {
+        Self {
+            content_length: inner.len() as u64,
+            inner,
+            state: EncoderState::MessageHeader,
+            pending: Vec::new(),
+            pending_offset: 0,
+            segment_number: 0,
+            segment_remaining: 0,
+            content_read: 0,
+            segment_crc: Crc64::new(),
+            message_crc: Crc64::new(),
+        }
+    }
+
+    /// The length of the content being encoded.
+    pub fn content_length(&self) -> u64 {
+        self.content_length
+    }
+
+    /// Move to the next state, queueing the bytes it starts with.
+    fn advance(&mut self) {
+        let segment_length = segment_length(self.content_length);
+        let (pending, state) = match self.state {
+            EncoderState::MessageHeader => (
+                message_header(self.content_length),
+                if self.content_length == 0 {
+                    EncoderState::MessageFooter
+                } else {
+                    EncoderState::SegmentHeader
+                },
+            ),
+            EncoderState::SegmentHeader => {
+                self.segment_number += 1;
+                self.segment_remaining =
+                    segment_length.min(self.content_length - self.content_read);
+                self.segment_crc = Crc64::new();
+                (
+                    segment_header(self.segment_number, self.segment_remaining),
+                    EncoderState::SegmentData,
+                )
+            }
+            EncoderState::SegmentData => (Vec::new(), EncoderState::SegmentFooter),
+            EncoderState::SegmentFooter => (
+                self.segment_crc.finish().to_le_bytes().to_vec(),
+                if self.content_read < self.content_length {
+                    EncoderState::SegmentHeader
+                } else {
+                    EncoderState::MessageFooter
+                },
+            ),
+            EncoderState::MessageFooter => (
+                self.message_crc.finish().to_le_bytes().to_vec(),
+                EncoderState::Done,
+            ),
+            EncoderState::Done => (Vec::new(), EncoderState::Done),
+        };
+        self.pending = pending;
+        self.pending_offset = 0;
+        self.state = state;
+    }
+}
+
+impl AsyncRead for StructuredMessageEncoder {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        loop {
+            if this.pending_offset < this.pending.len() {
+                let pending = &this.pending[this.pending_offset..];
+                let len = pending.len().min(buf.len());
+                buf[..len].copy_from_slice(&pending[..len]);
+                this.pending_offset += len;
+                return Poll::Ready(Ok(len));
+            }
+            match this.state {
+                EncoderState::Done => return Poll::Ready(Ok(0)),
+                EncoderState::SegmentData if this.segment_remaining > 0 => {
+                    let len = buf.len().min(this.segment_remaining as usize);
+                    let read = match Pin::new(&mut this.inner).poll_read(cx, &mut buf[..len]) {
+                        Poll::Ready(Ok(0)) => {
+                            return Poll::Ready(Err(std::io::Error::new(
+                                std::io::ErrorKind::UnexpectedEof,
+                                "stream ended before its declared length",
+                            )))
+                        }
+                        Poll::Ready(Ok(read)) => read,
+                        other => return other,
+                    };
+                    this.segment_crc.update(&buf[..read]);
+                    this.message_crc.update(&buf[..read]);
+                    this.segment_remaining -= read as u64;
+                    this.content_read += read as u64;
+                    return Poll::Ready(Ok(read));
+                }
+                _ => this.advance(),
+            }
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl SeekableStream for StructuredMessageEncoder {
+    async fn reset(&mut self) -> azure_core::Result<()> {
+        self.inner.reset().await?;
+        self.state = EncoderState::MessageHeader;
+        self.pending.clear();
+        self.pending_offset = 0;
+        self.segment_number = 0;
+        self.segment_remaining = 0;
+        self.content_read = 0;
+        self.segment_crc = Crc64::new();
+        self.message_crc = Crc64::new();
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        encoded_length(self.content_length) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::BytesStream;
+    use futures::io::AsyncReadExt;
+
+    #[test]
+    fn test_round_trip() {
+        for content in [&b""[..], b"hello world"] {
+            let message = encode(content);
+            assert_eq!(message.len() as u64, encoded_length(content.len() as u64));
+            assert_eq!(decode(&message).unwrap(), content);
+        }
+    }
+
+    #[test]
+    fn test_decode_corrupted() {
+        let mut message = encode(b"hello world").to_vec();
+        message[HEADER_LENGTH as usize + SEGMENT_HEADER_LENGTH as usize] ^= 1;
+        assert!(decode(&message).is_err());
+
+        let message = encode(b"hello world");
+        assert!(decode(&message[..message.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_encoder_matches_encode() {
+        let content = (0..1000u32).map(|i| i as u8).collect::<Vec<_>>();
+        let mut encoder =
+            StructuredMessageEncoder::new(Box::new(BytesStream::new(content.clone())));
+        assert_eq!(encoder.len() as u64, encoded_length(content.len() as u64));
+
+        let mut encoded = Vec::new();
+        let mut buf = [0u8; 7];
+        loop {
+            let read = futures::executor::block_on(encoder.read(&mut buf)).unwrap();
+            if read == 0 {
+                break;
+            }
+            encoded.extend_from_slice(&buf[..read]);
+        }
+        assert_eq!(encoded, encode(&content));
+
+        futures::executor::block_on(encoder.reset()).unwrap();
+        let mut again = Vec::new();
+        futures::executor::block_on(encoder.read_to_end(&mut again)).unwrap();
+        assert_eq!(again, encoded);
+    }
+}
@@ -0,0 +1,63 @@
+/// This example uploads a file in parallel blocks, reporting the progress of the upload.
+use azure_core::tokio::fs::FileStreamBuilder;
+use azure_storage::prelude::*;
+use azure_storage_blobs::prelude::*;
+use clap::Parser;
+use std::path::PathBuf;
+use tokio::fs::File;
+
+#[derive(Debug, Parser)]
+struct Args {
+    /// Name of the container to upload
+    container_name: String,
+    /// Blob name
+    blob_name: String,
+    /// File path to upload
+    file_path: PathBuf,
+
+    /// Size of each block, picked from the size of the file when not set
+    #[clap(long)]
+    block_size: Option<u64>,
+
+    /// How many blocks to upload at the same time
+    #[clap(long, default_value = "8")]
+    max_concurrency: usize,
+
+    /// storage account name
+    #[clap(env = "STORAGE_ACCOUNT")]
+    account: String,
+
+    /// storage account access key
+    #[clap(env = "STORAGE_ACCESS_KEY")]
+    access_key: String,
+}
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    tracing_subscriber::fmt().init();
+    let args = Args::parse();
+
+    let storage_credentials =
+        StorageCredentials::access_key(args.account.clone(), args.access_key.clone());
+    let blob_client = BlobServiceClient::new(&args.account, storage_credentials)
+        .container_client(&args.container_name)
+        .blob_client(&args.blob_name);
+
+    let file = File::open(&args.file_path).await?;
+    let stream = FileStreamBuilder::new(file).build().await?;
+    let len = stream.stream_size;
+
+    let mut builder = blob_client
+        .upload(stream)
+        .max_concurrency(args.max_concurrency)
+        .checksum(ChecksumAlgorithm::CRC64)
+        .progress(move |uploaded| println!("uploaded {uploaded} of {len} bytes"));
+    if let Some(block_size) = args.block_size {
+        builder = builder.block_size(block_size);
+    }
+
+    let response = builder.await?;
+    println!("{response:?}");
+
+    Ok(())
+}
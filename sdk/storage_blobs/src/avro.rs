@@ -0,0 +1,694 @@
+//! A minimal reader for the Avro object container format.
+//!
+//! Azure Storage uses Avro to frame the responses of blob queries and to store
+//! change feed events. Only the subset of the specification used by those
+//! features is implemented: all schema types are supported, but the only
+//! supported codec is `null`.
+//!
+//! ref: <https://avro.apache.org/docs/1.11.1/specification/>
+
+use azure_core::error::{Error, ErrorKind};
+use serde_json::Value as Json;
+use std::collections::{HashMap, VecDeque};
+
+const MAGIC: &[u8; 4] = b"Obj\x01";
+const SYNC_MARKER_LEN: usize = 16;
+
+/// An Avro schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Schema {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+    Record {
+        name: String,
+        fields: Vec<(String, Schema)>,
+    },
+    Enum {
+        symbols: Vec<String>,
+    },
+    Array(Box<Schema>),
+    Map(Box<Schema>),
+    Union(Vec<Schema>),
+    Fixed {
+        size: usize,
+    },
+    /// A reference to a previously defined named type.
+    Named(String),
+}
+
+/// A decoded Avro value.
+///
+/// Unions decode to the value of the selected branch.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Boolean(bool),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Bytes(Vec<u8>),
+    String(String),
+    Record(String, Vec<(String, Value)>),
+    Enum(String),
+    Array(Vec<Value>),
+    Map(HashMap<String, Value>),
+    Fixed(Vec<u8>),
+}
+
+impl Value {
+    /// Get a field of a record.
+    pub(crate) fn field(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Record(_, fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(i) => Some(i64::from(*i)),
+            Value::Long(l) => Some(*l),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn into_bytes(self) -> Option<Vec<u8>> {
+        match self {
+            Value::Bytes(b) | Value::Fixed(b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+fn invalid(message: impl Into<String>) -> DecodeError {
+    DecodeError::Invalid(message.into())
+}
+
+#[derive(Debug)]
+enum DecodeError {
+    /// More data is needed to decode the value.
+    UnexpectedEof,
+    Invalid(String),
+}
+
+impl From<DecodeError> for Error {
+    fn from(error: DecodeError) -> Self {
+        match error {
+            DecodeError::UnexpectedEof => {
+                Error::message(ErrorKind::DataConversion, "unexpected end of avro data")
+            }
+            DecodeError::Invalid(message) => Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid avro data: {message}")
+            }),
+        }
+    }
+}
+
+type DecodeResult<T> = std::result::Result<T, DecodeError>;
+
+/// Named types defined by a schema, by full and short name.
+#[derive(Debug, Clone, Default)]
+struct Names(HashMap<String, Schema>);
+
+impl Schema {
+    /// Parse the JSON representation of a schema.
+    pub(crate) fn parse(json: &str) -> azure_core::Result<(Schema, HashMap<String, Schema>)> {
+        let json: Json = serde_json::from_str(json).map_err(|error| {
+            Error::full(ErrorKind::DataConversion, error, "invalid avro schema")
+        })?;
+        let mut names = Names::default();
+        let schema = parse_schema(&json, None, &mut names)?;
+        Ok((schema, names.0))
+    }
+}
+
+fn parse_schema(json: &Json, namespace: Option<&str>, names: &mut Names) -> DecodeResult<Schema> {
+    match json {
+        Json::String(name) => parse_type_name(name, namespace, names),
+        Json::Array(branches) => Ok(Schema::Union(
+            branches
+                .iter()
+                .map(|branch| parse_schema(branch, namespace, names))
+                .collect::<DecodeResult<_>>()?,
+        )),
+        Json::Object(object) => {
+            let ty = object
+                .get("type")
+                .ok_or_else(|| invalid("schema object without a type"))?;
+            let ty = match ty {
+                Json::String(ty) => ty.as_str(),
+                other => return parse_schema(other, namespace, names),
+            };
+            match ty {
+                "record" | "error" | "enum" | "fixed" => {
+                    let name = object
+                        .get("name")
+                        .and_then(Json::as_str)
+                        .ok_or_else(|| invalid("named type without a name"))?;
+                    let namespace = object.get("namespace").and_then(Json::as_str).or(namespace);
+                    let full_name = full_name(name, namespace);
+                    let namespace = full_name.rsplit_once('.').map(|(ns, _)| ns.to_owned());
+
+                    let schema = match ty {
+                        "enum" => Schema::Enum {
+                            symbols: object
+                                .get("symbols")
+                                .and_then(Json::as_array)
+                                .ok_or_else(|| invalid("enum without symbols"))?
+                                .iter()
+                                .map(|symbol| {
+                                    symbol
+                                        .as_str()
+                                        .map(str::to_owned)
+                                        .ok_or_else(|| invalid("enum symbol is not a string"))
+                                })
+                                .collect::<DecodeResult<_>>()?,
+                        },
+                        "fixed" => Schema::Fixed {
+                            size: object
+                                .get("size")
+                                .and_then(Json::as_u64)
+                                .ok_or_else(|| invalid("fixed without a size"))?
+                                as usize,
+                        },
+                        _ => {
+                            // register the name before the fields so that recursive types resolve
+                            names.insert(&full_name, Schema::Named(full_name.clone()));
+                            let fields = object
+                                .get("fields")
+                                .and_then(Json::as_array)
+                                .ok_or_else(|| invalid("record without fields"))?
+                                .iter()
+                                .map(|field| {
+                                    let name = field
+                                        .get("name")
+                                        .and_then(Json::as_str)
+                                        .ok_or_else(|| invalid("record field without a name"))?;
+                                    let ty = field
+                                        .get("type")
+                                        .ok_or_else(|| invalid("record field without a type"))?;
+                                    Ok((
+                                        name.to_owned(),
+                                        parse_schema(ty, namespace.as_deref(), names)?,
+                                    ))
+                                })
+                                .collect::<DecodeResult<_>>()?;
+                            Schema::Record {
+                                name: full_name.clone(),
+                                fields,
+                            }
+                        }
+                    };
+                    names.insert(&full_name, schema.clone());
+                    Ok(schema)
+                }
+                "array" => Ok(Schema::Array(Box::new(parse_schema(
+                    object
+                        .get("items")
+                        .ok_or_else(|| invalid("array without items"))?,
+                    namespace,
+                    names,
+                )?))),
+                "map" => Ok(Schema::Map(Box::new(parse_schema(
+                    object
+                        .get("values")
+                        .ok_or_else(|| invalid("map without values"))?,
+                    namespace,
+                    names,
+                )?))),
+                other => parse_type_name(other, namespace, names),
+            }
+        }
+        other => Err(invalid(format!("unexpected schema {other}"))),
+    }
+}
+
+fn parse_type_name(name: &str, namespace: Option<&str>, names: &Names) -> DecodeResult<Schema> {
+    Ok(match name {
+        "null" => Schema::Null,
+        "boolean" => Schema::Boolean,
+        "int" => Schema::Int,
+        "long" => Schema::Long,
+        "float" => Schema::Float,
+        "double" => Schema::Double,
+        "bytes" => Schema::Bytes,
+        "string" => Schema::String,
+        name => {
+            let full_name = full_name(name, namespace);
+            if names.0.contains_key(&full_name) {
+                Schema::Named(full_name)
+            } else if names.0.contains_key(name) {
+                Schema::Named(name.to_owned())
+            } else {
+                return Err(invalid(format!("unknown type {name}")));
+            }
+        }
+    })
+}
+
+fn full_name(name: &str, namespace: Option<&str>) -> String {
+    match namespace {
+        Some(namespace) if !name.contains('.') && !namespace.is_empty() => {
+            format!("{namespace}.{name}")
+        }
+        _ => name.to_owned(),
+    }
+}
+
+impl Names {
+    fn insert(&mut self, full_name: &str, schema: Schema) {
+        if let Some((_, short_name)) = full_name.rsplit_once('.') {
+            self.0
+                .entry(short_name.to_owned())
+                .or_insert_with(|| schema.clone());
+        }
+        self.0.insert(full_name.to_owned(), schema);
+    }
+}
+
+/// Reads values from a byte slice.
+struct Decoder<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn read_slice(&mut self, len: usize) -> DecodeResult<&'a [u8]> {
+        let end = self
+            .position
+            .checked_add(len)
+            .ok_or_else(|| invalid("length overflow"))?;
+        let slice = self
+            .data
+            .get(self.position..end)
+            .ok_or(DecodeError::UnexpectedEof)?;
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn read_long(&mut self) -> DecodeResult<i64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_slice(1)?[0];
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                // zig-zag decoding
+                return Ok((value >> 1) as i64 ^ -((value & 1) as i64));
+            }
+        }
+        Err(invalid("variable length integer is too long"))
+    }
+
+    fn read_len(&mut self) -> DecodeResult<usize> {
+        usize::try_from(self.read_long()?).map_err(|_| invalid("negative length"))
+    }
+
+    fn read_bytes(&mut self) -> DecodeResult<Vec<u8>> {
+        let len = self.read_len()?;
+        Ok(self.read_slice(len)?.to_vec())
+    }
+
+    fn read_string(&mut self) -> DecodeResult<String> {
+        String::from_utf8(self.read_bytes()?).map_err(|_| invalid("string is not valid UTF-8"))
+    }
+
+    /// Read the item count of the next block of an array or map, zero if the collection is done.
+    fn read_block_len(&mut self) -> DecodeResult<usize> {
+        let count = self.read_long()?;
+        if count < 0 {
+            // a negative count is followed by the block size in bytes
+            self.read_long()?;
+        }
+        usize::try_from(count.unsigned_abs()).map_err(|_| invalid("block too large"))
+    }
+
+    fn read_value(
+        &mut self,
+        schema: &Schema,
+        names: &HashMap<String, Schema>,
+    ) -> DecodeResult<Value> {
+        Ok(match schema {
+            Schema::Null => Value::Null,
+            Schema::Boolean => Value::Boolean(self.read_slice(1)?[0] != 0),
+            Schema::Int => Value::Int(
+                i32::try_from(self.read_long()?).map_err(|_| invalid("int out of range"))?,
+            ),
+            Schema::Long => Value::Long(self.read_long()?),
+            Schema::Float => Value::Float(f32::from_le_bytes(
+                self.read_slice(4)?.try_into().expect("slice has 4 bytes"),
+            )),
+            Schema::Double => Value::Double(f64::from_le_bytes(
+                self.read_slice(8)?.try_into().expect("slice has 8 bytes"),
+            )),
+            Schema::Bytes => Value::Bytes(self.read_bytes()?),
+            Schema::String => Value::String(self.read_string()?),
+            Schema::Record { name, fields } => Value::Record(
+                name.clone(),
+                fields
+                    .iter()
+                    .map(|(name, schema)| Ok((name.clone(), self.read_value(schema, names)?)))
+                    .collect::<DecodeResult<_>>()?,
+            ),
+            Schema::Enum { symbols } => {
+                let index = self.read_len()?;
+                Value::Enum(
+                    symbols
+                        .get(index)
+                        .cloned()
+                        .ok_or_else(|| invalid("enum index out of range"))?,
+                )
+            }
+            Schema::Array(items) => {
+                let mut values = Vec::new();
+                loop {
+                    let len = self.read_block_len()?;
+                    if len == 0 {
+                        break;
+                    }
+                    for _ in 0..len {
+                        values.push(self.read_value(items, names)?);
+                    }
+                }
+                Value::Array(values)
+            }
+            Schema::Map(values) => {
+                let mut map = HashMap::new();
+                loop {
+                    let len = self.read_block_len()?;
+                    if len == 0 {
+                        break;
+                    }
+                    for _ in 0..len {
+                        let key = self.read_string()?;
+                        map.insert(key, self.read_value(values, names)?);
+                    }
+                }
+                Value::Map(map)
+            }
+            Schema::Union(branches) => {
+                let index = self.read_len()?;
+                let branch = branches
+                    .get(index)
+                    .ok_or_else(|| invalid("union index out of range"))?;
+                self.read_value(branch, names)?
+            }
+            Schema::Fixed { size } => Value::Fixed(self.read_slice(*size)?.to_vec()),
+            Schema::Named(name) => {
+                let schema = names
+                    .get(name)
+                    .ok_or_else(|| invalid(format!("unknown type {name}")))?;
+                self.read_value(schema, names)?
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Header {
+    schema: Schema,
+    names: HashMap<String, Schema>,
+    sync_marker: [u8; SYNC_MARKER_LEN],
+}
+
+/// Incrementally decodes an Avro object container file.
+///
+/// Data is fed with [`AvroReader::push`] as it arrives and values are taken
+/// out with [`AvroReader::next`] once a whole block is available.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AvroReader {
+    buffer: Vec<u8>,
+    header: Option<Header>,
+    values: VecDeque<Value>,
+}
+
+impl AvroReader {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn push(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Whether all the data pushed so far has been consumed.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.buffer.is_empty() && self.values.is_empty()
+    }
+
+    /// Get the next value, or `None` if more data is needed.
+    pub(crate) fn next(&mut self) -> azure_core::Result<Option<Value>> {
+        loop {
+            if let Some(value) = self.values.pop_front() {
+                return Ok(Some(value));
+            }
+            if !self.read_block()? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Try to decode the header. Returns `false` if more data is needed.
+    fn read_header(&mut self) -> azure_core::Result<bool> {
+        let mut decoder = Decoder::new(&self.buffer);
+        let header = match decode_header(&mut decoder) {
+            Ok(header) => header,
+            Err(DecodeError::UnexpectedEof) => return Ok(false),
+            Err(error) => return Err(error.into()),
+        };
+        let consumed = decoder.position;
+        self.buffer.drain(..consumed);
+        self.header = Some(header);
+        Ok(true)
+    }
+
+    /// Try to decode the next block. Returns `false` if more data is needed.
+    fn read_block(&mut self) -> azure_core::Result<bool> {
+        if self.header.is_none() && !self.read_header()? {
+            return Ok(false);
+        }
+        let header = self.header.as_ref().expect("header was just read");
+
+        let mut decoder = Decoder::new(&self.buffer);
+        let block = (|| {
+            let count = decoder.read_len()?;
+            let size = decoder.read_len()?;
+            let data = decoder.read_slice(size)?;
+            let sync_marker = decoder.read_slice(SYNC_MARKER_LEN)?;
+            Ok((count, data, sync_marker))
+        })();
+        let (count, data, sync_marker) = match block {
+            Ok(block) => block,
+            Err(DecodeError::UnexpectedEof) => return Ok(false),
+            Err(error) => return Err(error.into()),
+        };
+        if sync_marker != header.sync_marker {
+            return Err(invalid("sync marker mismatch").into());
+        }
+
+        let mut values = Decoder::new(data);
+        for _ in 0..count {
+            let value = values.read_value(&header.schema, &header.names)?;
+            self.values.push_back(value);
+        }
+
+        let consumed = decoder.position;
+        self.buffer.drain(..consumed);
+        Ok(true)
+    }
+}
+
+fn decode_header(decoder: &mut Decoder) -> DecodeResult<Header> {
+    if decoder.read_slice(MAGIC.len())? != MAGIC {
+        return Err(invalid("not an avro object container file"));
+    }
+
+    let mut metadata = HashMap::new();
+    loop {
+        let len = decoder.read_block_len()?;
+        if len == 0 {
+            break;
+        }
+        for _ in 0..len {
+            let key = decoder.read_string()?;
+            metadata.insert(key, decoder.read_bytes()?);
+        }
+    }
+    let sync_marker = decoder
+        .read_slice(SYNC_MARKER_LEN)?
+        .try_into()
+        .expect("slice has the sync marker length");
+
+    match metadata.get("avro.codec").map(Vec::as_slice) {
+        None | Some(b"null") => {}
+        Some(codec) => {
+            return Err(invalid(format!(
+                "unsupported codec {}",
+                String::from_utf8_lossy(codec)
+            )))
+        }
+    }
+
+    let schema = metadata
+        .get("avro.schema")
+        .ok_or_else(|| invalid("missing schema"))?;
+    let schema = std::str::from_utf8(schema).map_err(|_| invalid("schema is not valid UTF-8"))?;
+    let (schema, names) = Schema::parse(schema).map_err(|error| invalid(error.to_string()))?;
+
+    Ok(Header {
+        schema,
+        names,
+        sync_marker,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    fn write_long(out: &mut Vec<u8>, value: i64) {
+        let mut value = ((value << 1) ^ (value >> 63)) as u64;
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                break;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    pub(crate) fn write_bytes(out: &mut Vec<u8>, value: &[u8]) {
+        write_long(out, value.len() as i64);
+        out.extend_from_slice(value);
+    }
+
+    pub(crate) fn write_union_index(out: &mut Vec<u8>, index: i64) {
+        write_long(out, index);
+    }
+
+    pub(crate) fn write_long_value(out: &mut Vec<u8>, value: i64) {
+        write_long(out, value);
+    }
+
+    /// Build a container file with one block per entry of `blocks`, each holding already encoded values.
+    pub(crate) fn container(schema: &str, blocks: &[(usize, Vec<u8>)]) -> Vec<u8> {
+        let sync = [7u8; SYNC_MARKER_LEN];
+        let mut out = MAGIC.to_vec();
+        write_long(&mut out, 2);
+        write_bytes(&mut out, b"avro.schema");
+        write_bytes(&mut out, schema.as_bytes());
+        write_bytes(&mut out, b"avro.codec");
+        write_bytes(&mut out, b"null");
+        write_long(&mut out, 0);
+        out.extend_from_slice(&sync);
+        for (count, data) in blocks {
+            write_long(&mut out, *count as i64);
+            write_long(&mut out, data.len() as i64);
+            out.extend_from_slice(data);
+            out.extend_from_slice(&sync);
+        }
+        out
+    }
+
+    #[test]
+    fn test_parse_schema() -> azure_core::Result<()> {
+        let (schema, _) = Schema::parse(
+            r#"{"type":"record","name":"a.b.Node","fields":[
+                {"name":"value","type":"long"},
+                {"name":"tags","type":{"type":"map","values":"string"}},
+                {"name":"next","type":["null","Node"]}
+            ]}"#,
+        )?;
+        assert_eq!(
+            schema,
+            Schema::Record {
+                name: "a.b.Node".to_owned(),
+                fields: vec![
+                    ("value".to_owned(), Schema::Long),
+                    ("tags".to_owned(), Schema::Map(Box::new(Schema::String))),
+                    (
+                        "next".to_owned(),
+                        Schema::Union(vec![Schema::Null, Schema::Named("a.b.Node".to_owned())])
+                    ),
+                ]
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_values() -> azure_core::Result<()> {
+        let schema = r#"{"type":"record","name":"Node","fields":[
+            {"name":"value","type":"long"},
+            {"name":"name","type":"string"},
+            {"name":"next","type":["null","Node"]}
+        ]}"#;
+
+        let mut data = Vec::new();
+        write_long(&mut data, -3);
+        write_bytes(&mut data, b"first");
+        write_long(&mut data, 1);
+        write_long(&mut data, 64);
+        write_bytes(&mut data, b"second");
+        write_long(&mut data, 0);
+
+        let file = container(schema, &[(1, data)]);
+
+        // feed the data one byte at a time
+        let mut reader = AvroReader::new();
+        let mut values = Vec::new();
+        for byte in &file {
+            reader.push(std::slice::from_ref(byte));
+            while let Some(value) = reader.next()? {
+                values.push(value);
+            }
+        }
+        assert!(reader.is_empty());
+        assert_eq!(values.len(), 1);
+
+        let value = &values[0];
+        assert_eq!(value.field("value").and_then(Value::as_i64), Some(-3));
+        assert_eq!(value.field("name").and_then(Value::as_str), Some("first"));
+        let next = value.field("next").unwrap();
+        assert_eq!(next.field("value").and_then(Value::as_i64), Some(64));
+        assert_eq!(next.field("next"), Some(&Value::Null));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bad_sync_marker() {
+        let mut file = container(r#""long""#, &[(1, vec![2])]);
+        let len = file.len();
+        file[len - 1] = 0;
+
+        let mut reader = AvroReader::new();
+        reader.push(&file);
+        assert!(reader.next().is_err());
+    }
+}
@@ -8,6 +8,8 @@ mod block_list;
 mod block_list_type;
 mod block_with_size_list;
 mod page_range_list;
+mod reader;
+mod writer;
 
 pub use blob_block_type::BlobBlockType;
 pub use blob_block_with_size::BlobBlockWithSize;
@@ -16,6 +18,8 @@ pub use block_list_type::BlockListType;
 pub use block_with_size_list::BlockWithSizeList;
 pub use lease_blob_options::{LeaseBlobOptions, LEASE_BLOB_OPTIONS_DEFAULT};
 pub use page_range_list::PageRangeList;
+pub use reader::BlobReader;
+pub use writer::{AppendBlobWriter, BlockBlobWriter, PageBlobWriter};
 
 use crate::options::{AccessTier, Snapshot, Tags, SNAPSHOT};
 use azure_core::{
@@ -222,6 +226,8 @@ impl Blob {
             .and_then(|cct| date::parse_rfc1123(cct).ok());
         let copy_status_description = h.get_optional_string(&headers::COPY_STATUS_DESCRIPTION);
         let server_encrypted = h.get_as(&headers::SERVER_ENCRYPTED)?;
+        let customer_provided_key_sha256 = h.get_optional_string(&headers::ENCRYPTION_KEY_SHA256);
+        let encryption_scope = h.get_optional_string(&azure_storage::headers::ENCRYPTION_SCOPE);
         let blob_committed_block_count = h.get_optional_as(&headers::BLOB_COMMITTED_BLOCK_COUNT)?;
 
         let mut metadata = HashMap::new();
@@ -274,14 +280,14 @@ impl Blob {
                 copy_status_description,
                 incremental_copy: None, // TODO: Not present or documentation bug?
                 server_encrypted,
-                customer_provided_key_sha256: None, // TODO
-                encryption_scope: None,             // TODO
-                access_tier_inferred: None,         // TODO: Not present
-                access_tier_change_time: None,      // TODO: Not present
-                deleted_time: None,                 // TODO
-                remaining_retention_days: None,     // TODO: Not present or documentation bug?
-                tag_count: None,                    // TODO
-                rehydrate_priority: None,           // TODO
+                customer_provided_key_sha256,
+                encryption_scope,
+                access_tier_inferred: None,     // TODO: Not present
+                access_tier_change_time: None,  // TODO: Not present
+                deleted_time: None,             // TODO
+                remaining_retention_days: None, // TODO: Not present or documentation bug?
+                tag_count: None,                // TODO
+                rehydrate_priority: None,       // TODO
                 expiry_time: None,
                 resource_type: None,
                 blob_committed_block_count,
@@ -0,0 +1,39 @@
+use crate::prelude::*;
+use azure_core::{headers::*, prelude::*, RequestId};
+use azure_storage::CopyId;
+use time::OffsetDateTime;
+
+operation! {
+    AbortCopy,
+    client: BlobClient,
+    copy_id: CopyId,
+    ?lease_id: LeaseId
+}
+
+impl AbortCopyBuilder {
+    pub fn into_future(mut self) -> AbortCopy {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut()
+                .append_pair("comp", "copy")
+                .append_pair("copyid", &self.copy_id.to_string());
+
+            let mut headers = Headers::new();
+            headers.insert(COPY_ACTION, "abort");
+            headers.add(self.lease_id);
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            AbortCopyResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(AbortCopyResponse,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
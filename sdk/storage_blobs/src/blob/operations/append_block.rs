@@ -6,38 +6,54 @@ operation! {
     client: BlobClient,
     body: Body,
     ?hash: Hash,
+    ?checksum: ChecksumAlgorithm,
     ?condition_max_size: ConditionMaxSize,
     ?condition_append_position: ConditionAppendPosition,
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl AppendBlockBuilder {
     pub fn into_future(mut self) -> AppendBlock {
         Box::pin(async move {
+            self.client.check_unencrypted_upload("Append Block")?;
+
             let mut url = self.client.url()?;
 
             url.query_pairs_mut().append_pair("comp", "appendblock");
 
             let mut headers = Headers::new();
-            headers.add(self.hash);
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
             headers.add(self.condition_max_size);
             headers.add(self.condition_append_position);
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
             headers.add(self.if_tags);
             headers.add(self.lease_id);
+            let (body, checksum) =
+                add_upload_checksum(self.body, self.hash, self.checksum, &mut headers).await?;
 
-            let mut request = BlobClient::finalize_request(
-                url,
-                azure_core::Method::Put,
-                headers,
-                Some(self.body),
-            )?;
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, Some(body))?;
+            if let Some(checksum) = &checksum {
+                checksum.prepare(&mut request);
+            }
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
+            if let Some(checksum) = &checksum {
+                checksum.verify(response.headers())?;
+            }
 
             PutBlockResponse::from_headers(response.headers())
         })
@@ -0,0 +1,94 @@
+use crate::prelude::*;
+use azure_core::{headers::*, prelude::*, RequestId, Url};
+use azure_storage::{headers::consistency_from_headers, ConsistencyCRC64, ConsistencyMD5};
+use time::OffsetDateTime;
+
+operation! {
+    AppendBlockFromUrl,
+    client: BlobClient,
+    source_url: Url,
+    ?source_range: Range,
+    ?condition_max_size: ConditionMaxSize,
+    ?condition_append_position: ConditionAppendPosition,
+    ?if_modified_since: IfModifiedSinceCondition,
+    ?if_match: IfMatchCondition,
+    ?if_tags: IfTags,
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
+}
+
+impl AppendBlockFromUrlBuilder {
+    pub fn into_future(mut self) -> AppendBlockFromUrl {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "appendblock");
+
+            let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
+            headers.insert(COPY_SOURCE, self.source_url.as_str().to_owned());
+            if let Some(range) = self.source_range {
+                headers.insert(SOURCE_RANGE, format!("{range}"));
+            }
+            headers.add(self.condition_max_size);
+            headers.add(self.condition_append_position);
+            headers.add(self.if_modified_since);
+            headers.add(self.if_match);
+            headers.add(self.if_tags);
+            headers.add(self.lease_id);
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
+
+            AppendBlockFromUrlResponse::from_headers(response.headers())
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppendBlockFromUrlResponse {
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub content_md5: Option<ConsistencyMD5>,
+    pub content_crc64: Option<ConsistencyCRC64>,
+    pub blob_append_offset: u64,
+    pub blob_committed_block_count: u32,
+    pub request_id: RequestId,
+    pub date: OffsetDateTime,
+    pub request_server_encrypted: bool,
+}
+
+impl AppendBlockFromUrlResponse {
+    pub(crate) fn from_headers(headers: &Headers) -> azure_core::Result<Self> {
+        let etag = etag_from_headers(headers)?;
+        let last_modified = last_modified_from_headers(headers)?;
+        let (content_md5, content_crc64) = consistency_from_headers(headers)?;
+        let blob_append_offset = headers.get_as(&BLOB_APPEND_OFFSET)?;
+        let blob_committed_block_count = headers.get_as(&BLOB_COMMITTED_BLOCK_COUNT)?;
+        let request_id = request_id_from_headers(headers)?;
+        let date = date_from_headers(headers)?;
+        let request_server_encrypted = request_server_encrypted_from_headers(headers)?;
+
+        Ok(Self {
+            etag,
+            last_modified,
+            content_md5,
+            content_crc64,
+            blob_append_offset,
+            blob_committed_block_count,
+            request_id,
+            date,
+            request_server_encrypted,
+        })
+    }
+}
@@ -9,7 +9,9 @@ operation! {
     ?if_sequence_number: IfSequenceNumber,
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl ClearPageBuilder {
@@ -20,6 +22,11 @@ impl ClearPageBuilder {
             url.query_pairs_mut().append_pair("comp", "page");
 
             let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
             headers.insert(PAGE_WRITE, "clear");
             headers.insert(BLOB_TYPE, "PageBlob");
             headers.add(self.ba512_range);
@@ -32,6 +39,9 @@ impl ClearPageBuilder {
                 BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
 
             ClearPageResponse::from_headers(response.headers())
         })
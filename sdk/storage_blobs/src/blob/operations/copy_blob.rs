@@ -2,7 +2,13 @@ use crate::{
     blob::{copy_status_from_headers, CopyStatus},
     prelude::*,
 };
-use azure_core::{headers::*, prelude::*, RequestId, Url};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::*,
+    prelude::*,
+    RequestId, Url,
+};
+use azure_storage::headers::COPY_SOURCE_AUTHORIZATION;
 use azure_storage::{copy_id_from_headers, CopyId};
 use time::OffsetDateTime;
 
@@ -17,10 +23,12 @@ operation! {
     ?if_tags: IfTags,
     ?access_tier: AccessTier,
     ?lease_id: LeaseId,
+    ?encryption_scope: EncryptionScope,
     ?if_source_since: IfSourceModifiedSinceCondition,
     ?if_source_match: IfSourceMatchCondition,
     ?source_lease_id: SourceLeaseId,
-    ?rehydrate_priority: RehydratePriority
+    ?rehydrate_priority: RehydratePriority,
+    ?source_authorization: String
 }
 
 impl CopyBlobBuilder {
@@ -29,6 +37,16 @@ impl CopyBlobBuilder {
             let url = self.client.url()?;
 
             let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(None, self.encryption_scope)?;
+            if encryption_key.is_some() {
+                return Err(Error::message(
+                    ErrorKind::Other,
+                    "Copy Blob does not support customer-provided keys",
+                ));
+            }
+            headers.add(encryption_scope);
             headers.insert(COPY_SOURCE, self.source_url.as_str().to_owned());
             if let Some(metadata) = &self.metadata {
                 for m in metadata.iter() {
@@ -43,6 +61,9 @@ impl CopyBlobBuilder {
             headers.add(self.if_source_since);
             headers.add(self.if_source_match);
             headers.add(self.source_lease_id);
+            if let Some(source_authorization) = &self.source_authorization {
+                headers.insert(COPY_SOURCE_AUTHORIZATION, source_authorization.clone());
+            }
             headers.add(
                 self.rehydrate_priority
                     .unwrap_or(RehydratePriority::Standard),
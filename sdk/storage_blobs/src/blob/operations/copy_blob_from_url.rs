@@ -3,6 +3,7 @@ use crate::{
     prelude::*,
 };
 use azure_core::{headers::*, prelude::*, RequestId, Url};
+use azure_storage::headers::COPY_SOURCE_AUTHORIZATION;
 use azure_storage::{
     copy_id_from_headers, headers::content_md5_from_headers_optional, ConsistencyMD5, CopyId,
 };
@@ -19,7 +20,10 @@ operation! {
     ?if_source_since: IfSourceModifiedSinceCondition,
     ?if_source_match: IfSourceMatchCondition,
     ?lease_id: LeaseId,
-    ?source_content_md5: SourceContentMD5
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope,
+    ?source_content_md5: SourceContentMD5,
+    ?source_authorization: String
 }
 
 impl CopyBlobFromUrlBuilder {
@@ -28,6 +32,11 @@ impl CopyBlobFromUrlBuilder {
             let url = self.client.url()?;
 
             let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
             headers.insert(COPY_SOURCE, self.source_url.to_string());
             headers.insert(
                 REQUIRES_SYNC,
@@ -44,11 +53,17 @@ impl CopyBlobFromUrlBuilder {
             headers.add(self.if_source_since);
             headers.add(self.if_source_match);
             headers.add(self.source_content_md5);
+            if let Some(source_authorization) = &self.source_authorization {
+                headers.insert(COPY_SOURCE_AUTHORIZATION, source_authorization.clone());
+            }
 
             let mut request =
                 BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
 
             (response.headers()).try_into()
         })
@@ -0,0 +1,815 @@
+use crate::{options::MAX_CHECKSUM_RANGE, prelude::*};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::*,
+    prelude::*,
+    RequestId, StatusCode,
+};
+use bytes::Bytes;
+use futures::{
+    io::{AsyncWrite, AsyncWriteExt},
+    stream::{FuturesUnordered, StreamExt},
+};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+use time::OffsetDateTime;
+
+const DEFAULT_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+const DEFAULT_MAX_CONCURRENCY: usize = 8;
+
+operation! {
+    /// Download a blob to a writer or a file, with concurrent ranged requests.
+    ///
+    /// Every range after the first one is requested with `If-Match` set to the ETag returned
+    /// for the first one, so the download fails rather than mixing two versions of a blob
+    /// modified in the meantime. Failed requests are retried by the pipeline of the client.
+    ///
+    /// When the download still fails, awaiting the builder (or a clone of it) again resumes
+    /// the download after the last range written in order, from the same version of the blob.
+    ///
+    /// With the `md5` feature, the downloaded content is checked against the Content-MD5 of
+    /// the blob, when it has one.
+    ///
+    /// When the client uses client-side encryption, encrypted blobs are downloaded in whole
+    /// encryption regions and decrypted, which cannot be combined with a transactional `checksum`.
+    DownloadBlob,
+    client: BlobClient,
+    target: DownloadTarget,
+    ?chunk_size: u64,
+    ?max_concurrency: usize,
+    ?checksum: ChecksumAlgorithm,
+    ?progress: TransferProgress,
+    ?blob_versioning: BlobVersioning,
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?if_modified_since: IfModifiedSinceCondition,
+    ?if_match: IfMatchCondition,
+    ?if_tags: IfTags
+}
+
+impl DownloadBlobBuilder {
+    pub fn into_future(self) -> DownloadBlob {
+        Box::pin(async move {
+            let mut chunk_size = self.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE).max(1);
+            if self.checksum.is_some() {
+                chunk_size = chunk_size.min(MAX_CHECKSUM_RANGE);
+            }
+            let max_concurrency = self
+                .max_concurrency
+                .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+                .max(1);
+
+            #[cfg(feature = "client-side-encryption")]
+            if let Some(encryption) = self.client.client_side_encryption() {
+                if self.checksum.is_some() {
+                    return Err(Error::message(
+                        ErrorKind::Other,
+                        "transactional checksums cannot be validated on client-side encrypted downloads",
+                    ));
+                }
+                chunk_size = encryption.encrypted_range(0, chunk_size).1;
+            }
+
+            let resume = self.target.resume_point()?;
+            let (headers, first) = match &resume {
+                Some(resume) => (resume.headers.clone(), None),
+                None => {
+                    let first = match self
+                        .fetch(Some(&Range::new(0, chunk_size)), self.if_match.clone())
+                        .await
+                    {
+                        // ranges cannot be requested from an empty blob
+                        Err(err)
+                            if err.as_http_error().map(|e| e.status())
+                                == Some(StatusCode::RequestedRangeNotSatisfiable) =>
+                        {
+                            self.fetch(None, self.if_match.clone()).await?
+                        }
+                        result => result?,
+                    };
+                    (first.headers, Some(first.data))
+                }
+            };
+            let content_range = headers.get_optional_as::<ContentRange, _>(&CONTENT_RANGE)?;
+            let total_length = match &content_range {
+                Some(content_range) => content_range.total_length(),
+                None => first.as_ref().map_or(0, |data| data.len() as u64),
+            };
+            let etag: String = headers.get_as(&ETAG)?;
+            let request_id = request_id_from_headers(&headers)?;
+            let date = date_from_headers(&headers)?;
+            let mut blob = Blob::from_headers(self.client.blob_name(), &headers)?;
+            // a ranged response carries the MD5 of the whole blob in its own header
+            blob.properties.content_md5 = match content_range {
+                Some(_) => headers.get_optional_as(&BLOB_CONTENT_MD5)?,
+                None => blob.properties.content_md5,
+            };
+
+            #[cfg(feature = "client-side-encryption")]
+            let decryptor = self.decryptor(&blob).await?;
+            let plaintext_length = total_length;
+            #[cfg(feature = "client-side-encryption")]
+            let plaintext_length = match &decryptor {
+                Some(decryptor) => decryptor.plaintext_length(total_length),
+                None => plaintext_length,
+            };
+            blob.properties.content_length = plaintext_length;
+
+            let start = match &resume {
+                Some(resume) => resume.offset,
+                None => first.as_ref().map_or(0, |data| data.len() as u64),
+            };
+            let mut downloaded = match &resume {
+                Some(resume) => resume.offset,
+                None => 0,
+            };
+            #[cfg(feature = "client-side-encryption")]
+            if let Some(decryptor) = &decryptor {
+                downloaded = decryptor.plaintext_offset(downloaded);
+            }
+            let hash_content = blob.properties.content_md5.is_some();
+            let mut sink = self
+                .target
+                .open(plaintext_length, resume, hash_content)
+                .await?;
+
+            let transfer = async {
+                if let Some(data) = first {
+                    let chunk = Chunk::new(0, data);
+                    #[cfg(feature = "client-side-encryption")]
+                    let chunk = chunk.decrypt(decryptor.as_ref())?;
+                    downloaded += chunk.data.len() as u64;
+                    sink.write(chunk).await?;
+                    self.report(downloaded);
+                }
+
+                let mut ranges = (start..total_length)
+                    .step_by(chunk_size as usize)
+                    .map(|start| Range::new(start, (start + chunk_size).min(total_length)));
+                let mut in_flight = FuturesUnordered::new();
+                loop {
+                    while in_flight.len() + sink.pending() < max_concurrency {
+                        let Some(range) = ranges.next() else {
+                            break;
+                        };
+                        let offset = range_start(&range);
+                        let if_match = Some(IfMatchCondition::Match(etag.clone()));
+                        let this = &self;
+                        in_flight.push(async move {
+                            let fetched = this.fetch(Some(&range), if_match).await?;
+                            Ok::<_, Error>((offset, fetched))
+                        });
+                    }
+                    match in_flight.next().await {
+                        Some(result) => {
+                            let (offset, fetched) = result?;
+                            let chunk = Chunk::new(offset, fetched.data);
+                            #[cfg(feature = "client-side-encryption")]
+                            let chunk = chunk.decrypt(decryptor.as_ref())?;
+                            downloaded += chunk.data.len() as u64;
+                            sink.write(chunk).await?;
+                            self.report(downloaded);
+                        }
+                        None => break,
+                    }
+                }
+                sink.finish().await
+            }
+            .await;
+            match transfer {
+                Ok(()) => self.target.complete(),
+                Err(err) => {
+                    self.target.suspend(sink, headers);
+                    return Err(err);
+                }
+            }
+
+            #[cfg(feature = "md5")]
+            if let Some(expected) = &blob.properties.content_md5 {
+                sink.verify_md5(expected)?;
+            }
+
+            Ok(DownloadBlobResponse {
+                blob,
+                etag,
+                content_length: plaintext_length,
+                request_id,
+                date,
+            })
+        })
+    }
+
+    /// The decryptor of a blob downloaded by a client using client-side encryption, if the blob
+    /// is encrypted.
+    #[cfg(feature = "client-side-encryption")]
+    async fn decryptor(
+        &self,
+        blob: &Blob,
+    ) -> azure_core::Result<Option<crate::encryption::ContentDecryptor>> {
+        let Some(encryption) = self.client.client_side_encryption() else {
+            return Ok(None);
+        };
+        let Some(encryption_data) = encryption.encryption_data(blob.metadata.as_ref())? else {
+            return Ok(None);
+        };
+        let decryptor = encryption.decryptor(encryption_data).await?;
+        // the ranges were aligned on the regions used by this client
+        if decryptor.encrypted_range(0, 1) != encryption.encrypted_range(0, 1) {
+            return Err(Error::message(
+                ErrorKind::Other,
+                "blob was encrypted with regions of an unsupported size",
+            ));
+        }
+        Ok(Some(decryptor))
+    }
+
+    fn report(&self, downloaded: u64) {
+        if let Some(progress) = &self.progress {
+            progress.report(downloaded);
+        }
+    }
+
+    async fn fetch(
+        &self,
+        range: Option<&Range>,
+        if_match: Option<IfMatchCondition>,
+    ) -> azure_core::Result<Fetched> {
+        let mut url = self.client.url()?;
+        self.blob_versioning.append_to_url_query(&mut url);
+
+        let mut headers = Headers::new();
+        if let Some(range) = range {
+            headers.insert(MS_RANGE, range.to_string());
+            if let Some(checksum) = &self.checksum {
+                headers.insert(checksum.range_request_header(), "true");
+            }
+        }
+        headers.add(self.lease_id);
+        let encryption_key = self
+            .client
+            .server_side_encryption_key(self.encryption_key.clone());
+        headers.add(encryption_key.as_ref());
+        headers.add(self.if_modified_since);
+        headers.add(if_match);
+        headers.add(self.if_tags.clone());
+
+        let mut request =
+            BlobClient::finalize_request(url, azure_core::Method::Get, headers, None)?;
+        let mut context = self.context.clone();
+        let response = self.client.send(&mut context, &mut request).await?;
+        if let Some(encryption_key) = &encryption_key {
+            encryption_key.verify(response.headers())?;
+        }
+        let (_, headers, body) = response.deconstruct();
+        let data = body.collect().await?;
+        if let (Some(checksum), Some(_)) = (&self.checksum, range) {
+            checksum.validate(&headers, &data)?;
+        }
+        Ok(Fetched { headers, data })
+    }
+}
+
+struct Fetched {
+    headers: Headers,
+    data: Bytes,
+}
+
+fn range_start(range: &Range) -> u64 {
+    match range {
+        Range::Range(range) => range.start,
+        Range::RangeFrom(range) => range.start,
+    }
+}
+
+type BoxedWriter = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// Where a blob downloaded with `BlobClient::download_to` is written, and how far a previous
+/// attempt got.
+#[derive(Clone)]
+pub(crate) struct DownloadTarget {
+    output: TargetOutput,
+    resume: Arc<Mutex<Option<ResumePoint>>>,
+}
+
+#[derive(Clone)]
+enum TargetOutput {
+    /// A writer can only be written to by one download of the builder at a time.
+    Writer(Arc<Mutex<Option<BoxedWriter>>>),
+    #[cfg(feature = "tokio-fs")]
+    File(std::path::PathBuf),
+}
+
+/// How far a failed download got, so that it can be resumed.
+#[derive(Clone)]
+struct ResumePoint {
+    /// The headers of the first range, which describe the version of the blob being downloaded.
+    headers: Headers,
+    /// The offset in the blob up to which every range has been written.
+    offset: u64,
+    #[cfg(feature = "md5")]
+    md5: Option<md5::Context>,
+}
+
+impl DownloadTarget {
+    pub(crate) fn writer(writer: impl AsyncWrite + Send + Unpin + 'static) -> Self {
+        Self::new(TargetOutput::Writer(Arc::new(Mutex::new(Some(Box::new(
+            writer,
+        ))))))
+    }
+
+    #[cfg(feature = "tokio-fs")]
+    pub(crate) fn file(path: std::path::PathBuf) -> Self {
+        Self::new(TargetOutput::File(path))
+    }
+
+    fn new(output: TargetOutput) -> Self {
+        Self {
+            output,
+            resume: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn resume_point(&self) -> azure_core::Result<Option<ResumePoint>> {
+        Ok(self
+            .resume
+            .lock()
+            .map_err(|_| Error::message(ErrorKind::Other, "download state lock poisoned"))?
+            .clone())
+    }
+
+    /// Open the target, after the part written by a previous attempt when resuming one.
+    ///
+    /// With `_hash_content`, the content is hashed as it is written, to compare it to the
+    /// Content-MD5 of the blob.
+    async fn open(
+        &self,
+        _len: u64,
+        resume: Option<ResumePoint>,
+        _hash_content: bool,
+    ) -> azure_core::Result<Sink> {
+        let output = match &self.output {
+            TargetOutput::Writer(writer) => {
+                let writer = writer
+                    .lock()
+                    .map_err(|_| Error::message(ErrorKind::Other, "download writer lock poisoned"))?
+                    .take()
+                    .ok_or_else(|| {
+                        Error::message(
+                            ErrorKind::Other,
+                            "download writer has already been written to",
+                        )
+                    })?;
+                Output::Writer(writer)
+            }
+            #[cfg(feature = "tokio-fs")]
+            TargetOutput::File(path) => {
+                let file = match resume {
+                    Some(_) => tokio::fs::OpenOptions::new().write(true).open(path).await?,
+                    None => tokio::fs::File::create(path).await?,
+                };
+                file.set_len(_len).await?;
+                Output::File(file)
+            }
+        };
+        Ok(match resume {
+            Some(resume) => Sink {
+                output,
+                next_offset: resume.offset,
+                pending: BTreeMap::new(),
+                #[cfg(feature = "md5")]
+                md5: resume.md5,
+            },
+            None => Sink {
+                output,
+                next_offset: 0,
+                pending: BTreeMap::new(),
+                #[cfg(feature = "md5")]
+                md5: _hash_content.then(md5::Context::new),
+            },
+        })
+    }
+
+    /// Forget about a previous attempt, once the download has completed.
+    fn complete(&self) {
+        if let Ok(mut resume) = self.resume.lock() {
+            *resume = None;
+        }
+    }
+
+    /// Keep how far a failed download got, and give the writer back for the next attempt.
+    fn suspend(&self, sink: Sink, headers: Headers) {
+        let Sink {
+            output,
+            next_offset,
+            #[cfg(feature = "md5")]
+            md5,
+            ..
+        } = sink;
+        match (&self.output, output) {
+            (TargetOutput::Writer(slot), Output::Writer(writer)) => {
+                if let Ok(mut slot) = slot.lock() {
+                    *slot = Some(writer);
+                }
+            }
+            #[cfg(feature = "tokio-fs")]
+            _ => {}
+        }
+        if let Ok(mut resume) = self.resume.lock() {
+            *resume = Some(ResumePoint {
+                headers,
+                offset: next_offset,
+                #[cfg(feature = "md5")]
+                md5,
+            });
+        }
+    }
+}
+
+impl fmt::Debug for DownloadTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.output {
+            TargetOutput::Writer(_) => f.write_str("Writer"),
+            #[cfg(feature = "tokio-fs")]
+            TargetOutput::File(path) => f.debug_tuple("File").field(path).finish(),
+        }
+    }
+}
+
+/// A downloaded range.
+struct Chunk {
+    /// The offset of the range in the blob.
+    start: u64,
+    /// The offset of the end of the range in the blob.
+    end: u64,
+    /// The offset of the content in the target, which differs from `start` for encrypted blobs.
+    #[cfg_attr(not(feature = "tokio-fs"), allow(dead_code))]
+    offset: u64,
+    /// The content to write.
+    data: Bytes,
+    /// The content of the range as stored, which is hashed to check the Content-MD5 of the blob.
+    #[cfg(feature = "md5")]
+    stored: Bytes,
+}
+
+impl Chunk {
+    fn new(start: u64, data: Bytes) -> Self {
+        Self {
+            start,
+            end: start + data.len() as u64,
+            offset: start,
+            #[cfg(feature = "md5")]
+            stored: data.clone(),
+            data,
+        }
+    }
+
+    #[cfg(feature = "client-side-encryption")]
+    fn decrypt(
+        self,
+        decryptor: Option<&crate::encryption::ContentDecryptor>,
+    ) -> azure_core::Result<Self> {
+        match decryptor {
+            Some(decryptor) => Ok(Self {
+                offset: decryptor.plaintext_offset(self.start),
+                data: decryptor.decrypt(&self.data)?,
+                ..self
+            }),
+            None => Ok(self),
+        }
+    }
+}
+
+enum Output {
+    Writer(BoxedWriter),
+    #[cfg(feature = "tokio-fs")]
+    File(tokio::fs::File),
+}
+
+/// Writes downloaded ranges, which may complete out of order.
+///
+/// A writer receives the ranges in order, the ranges downloaded ahead of the next one to write
+/// are held on to. A file receives the ranges at their position as soon as they are downloaded.
+struct Sink {
+    output: Output,
+    /// The offset in the blob up to which every range has been written.
+    next_offset: u64,
+    /// The ranges downloaded ahead of `next_offset`.
+    pending: BTreeMap<u64, Chunk>,
+    #[cfg(feature = "md5")]
+    md5: Option<md5::Context>,
+}
+
+impl Sink {
+    /// Number of downloaded ranges held on to.
+    fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    async fn write(&mut self, chunk: Chunk) -> azure_core::Result<()> {
+        match &mut self.output {
+            Output::Writer(_) => {}
+            #[cfg(feature = "tokio-fs")]
+            Output::File(file) => {
+                use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+                file.seek(std::io::SeekFrom::Start(chunk.offset))
+                    .await
+                    .map_err(write_error)?;
+                file.write_all(&chunk.data).await.map_err(write_error)?;
+            }
+        }
+        self.pending.insert(chunk.start, chunk);
+        while let Some(chunk) = self.pending.remove(&self.next_offset) {
+            match &mut self.output {
+                Output::Writer(writer) => {
+                    writer.write_all(&chunk.data).await.map_err(write_error)?;
+                }
+                #[cfg(feature = "tokio-fs")]
+                Output::File(_) => {}
+            }
+            #[cfg(feature = "md5")]
+            if let Some(md5) = &mut self.md5 {
+                md5.consume(&chunk.stored);
+            }
+            self.next_offset = chunk.end;
+        }
+        Ok(())
+    }
+
+    async fn finish(&mut self) -> azure_core::Result<()> {
+        match &mut self.output {
+            Output::Writer(writer) => writer.flush().await.map_err(write_error),
+            #[cfg(feature = "tokio-fs")]
+            Output::File(file) => {
+                use tokio::io::AsyncWriteExt;
+                file.flush().await.map_err(write_error)
+            }
+        }
+    }
+
+    /// Check the content written against the Content-MD5 of the blob.
+    #[cfg(feature = "md5")]
+    fn verify_md5(self, expected: &azure_storage::ConsistencyMD5) -> azure_core::Result<()> {
+        let Some(md5) = self.md5 else {
+            return Ok(());
+        };
+        let actual = md5.compute();
+        if actual.0 != *expected.as_slice() {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!(
+                    "content MD5 mismatch, expected {expected:?} but downloaded content has {actual:x}"
+                )
+            }));
+        }
+        Ok(())
+    }
+}
+
+fn write_error(err: std::io::Error) -> Error {
+    Error::full(ErrorKind::Io, err, "unable to write downloaded blob")
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadBlobResponse {
+    pub blob: Blob,
+    /// The ETag every range was downloaded from.
+    pub etag: String,
+    pub content_length: u64,
+    pub request_id: RequestId,
+    pub date: OffsetDateTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::{
+        base64, headers::RANGE_GET_CONTENT_CRC64, BytesStream, Policy, PolicyResult, Request,
+        Response, RetryOptions, TransportOptions,
+    };
+    use azure_storage::{crc64, headers::CONTENT_CRC64};
+
+    #[tokio::test]
+    async fn test_writer_sink_writes_in_order() -> azure_core::Result<()> {
+        let (writer, output) = SharedWriter::new();
+        let target = DownloadTarget::writer(writer);
+        let mut sink = target.open(9, None, false).await?;
+        assert!(
+            target.open(9, None, false).await.is_err(),
+            "writer consumed"
+        );
+
+        sink.write(Chunk::new(6, Bytes::from_static(b"678")))
+            .await?;
+        sink.write(Chunk::new(3, Bytes::from_static(b"345")))
+            .await?;
+        assert_eq!(sink.pending(), 2);
+        assert!(output.lock().unwrap().is_empty());
+
+        sink.write(Chunk::new(0, Bytes::from_static(b"012")))
+            .await?;
+        assert_eq!(sink.pending(), 0);
+        sink.finish().await?;
+        assert_eq!(output.lock().unwrap().as_slice(), b"012345678");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_fails_on_checksum_mismatch() {
+        let blob = MockBlob::new(b"0123456789");
+        *blob.corrupt_checksums.lock().unwrap() = true;
+        let (writer, _) = SharedWriter::new();
+        let err = blob
+            .client()
+            .download_to(writer)
+            .chunk_size(4u64)
+            .checksum(ChecksumAlgorithm::CRC64)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::DataConversion);
+        assert_eq!(blob.requests(), ["bytes=0-3"]);
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_after_failure() -> azure_core::Result<()> {
+        let blob = MockBlob::new(b"0123456789");
+        blob.fail(&[4]);
+        let (writer, output) = SharedWriter::new();
+        let download = blob
+            .client()
+            .download_to(writer)
+            .chunk_size(4u64)
+            .max_concurrency(1usize)
+            .checksum(ChecksumAlgorithm::CRC64);
+
+        assert!(download.clone().await.is_err());
+        assert_eq!(output.lock().unwrap().as_slice(), b"0123");
+        assert_eq!(blob.requests(), ["bytes=0-3", "bytes=4-7"]);
+
+        let response = download.await?;
+        assert_eq!(response.content_length, 10);
+        assert_eq!(output.lock().unwrap().as_slice(), b"0123456789");
+        assert_eq!(
+            blob.requests()[2..],
+            ["bytes=4-7".to_owned(), "bytes=8-9".to_owned()]
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "md5")]
+    #[tokio::test]
+    async fn test_download_checks_content_md5() -> azure_core::Result<()> {
+        let blob = MockBlob::new(b"0123456789");
+        *blob.content_md5.lock().unwrap() = Some(md5::compute(b"0123456789").0);
+        let (writer, _) = SharedWriter::new();
+        let response = blob.client().download_to(writer).chunk_size(4u64).await?;
+        assert!(response.blob.properties.content_md5.is_some());
+
+        *blob.content_md5.lock().unwrap() = Some(md5::compute(b"another blob").0);
+        let (writer, _) = SharedWriter::new();
+        let err = blob
+            .client()
+            .download_to(writer)
+            .chunk_size(4u64)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::DataConversion);
+        Ok(())
+    }
+
+    /// Serves ranges of a blob, failing some requests with a server error.
+    #[derive(Debug)]
+    struct MockBlob {
+        content: Bytes,
+        content_md5: Mutex<Option<[u8; 16]>>,
+        corrupt_checksums: Mutex<bool>,
+        /// Start offsets of the ranges to fail, once per entry.
+        failures: Mutex<Vec<u64>>,
+        requests: Mutex<Vec<String>>,
+    }
+
+    impl MockBlob {
+        fn new(content: &'static [u8]) -> Arc<Self> {
+            Arc::new(Self {
+                content: Bytes::from_static(content),
+                content_md5: Mutex::new(None),
+                corrupt_checksums: Mutex::new(false),
+                failures: Mutex::new(Vec::new()),
+                requests: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn client(self: &Arc<Self>) -> BlobClient {
+            ClientBuilder::emulator()
+                .retry(RetryOptions::none())
+                .transport(TransportOptions::new_custom_policy(self.clone()))
+                .blob_client("container", "blob")
+        }
+
+        fn fail(&self, starts: &[u64]) {
+            self.failures.lock().unwrap().extend_from_slice(starts);
+        }
+
+        fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Policy for MockBlob {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            let range = request.headers().get_str(&MS_RANGE)?.to_owned();
+            self.requests.lock().unwrap().push(range.clone());
+            let (start, end) = range
+                .strip_prefix("bytes=")
+                .and_then(|range| range.split_once('-'))
+                .unwrap();
+            let start: usize = start.parse().unwrap();
+            let end = (end.parse::<usize>().unwrap() + 1).min(self.content.len());
+
+            let mut failures = self.failures.lock().unwrap();
+            if let Some(index) = failures.iter().position(|&f| f == start as u64) {
+                failures.remove(index);
+                return Ok(Response::new(
+                    StatusCode::InternalServerError,
+                    Headers::new(),
+                    Box::pin(BytesStream::new_empty()),
+                ));
+            }
+
+            let data = self.content.slice(start..end);
+            let mut headers = Headers::new();
+            headers.insert(ETAG, "\"0x8DC0A\"");
+            headers.insert(LAST_MODIFIED, "Mon, 01 Jan 2024 00:00:00 GMT");
+            headers.insert(CREATION_TIME, "Mon, 01 Jan 2024 00:00:00 GMT");
+            headers.insert(DATE, "Mon, 01 Jan 2024 00:00:00 GMT");
+            headers.insert(REQUEST_ID, "00000000-0000-0000-0000-000000000000");
+            headers.insert(BLOB_TYPE, "BlockBlob");
+            headers.insert(SERVER_ENCRYPTED, "true");
+            headers.insert(CONTENT_LENGTH, data.len().to_string());
+            headers.insert(
+                CONTENT_RANGE,
+                format!("bytes {start}-{}/{}", end - 1, self.content.len()),
+            );
+            if let Some(md5) = *self.content_md5.lock().unwrap() {
+                headers.insert(BLOB_CONTENT_MD5, base64::encode(md5));
+            }
+            if request
+                .headers()
+                .get_optional_str(&RANGE_GET_CONTENT_CRC64)
+                .is_some()
+            {
+                let mut crc64 = crc64::checksum(&data);
+                if *self.corrupt_checksums.lock().unwrap() {
+                    crc64 ^= 1;
+                }
+                headers.insert(CONTENT_CRC64, base64::encode(crc64.to_le_bytes()));
+            }
+            Ok(Response::new(
+                StatusCode::PartialContent,
+                headers,
+                Box::pin(BytesStream::new(data)),
+            ))
+        }
+    }
+
+    struct SharedWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedWriter {
+        fn new() -> (Self, Arc<Mutex<Vec<u8>>>) {
+            let output = Arc::new(Mutex::new(Vec::new()));
+            (Self(output.clone()), output)
+        }
+    }
+
+    impl AsyncWrite for SharedWriter {
+        fn poll_write(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+            buf: &[u8],
+        ) -> std::task::Poll<std::io::Result<usize>> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            std::task::Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+    }
+}
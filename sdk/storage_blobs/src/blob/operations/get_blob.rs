@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{options::MAX_CHECKSUM_RANGE, prelude::*};
 use azure_core::{
     error::Error, headers::*, prelude::*, Pageable, RequestId, Response as AzureResponse,
     ResponseBody,
@@ -15,6 +15,7 @@ operation! {
     ?blob_versioning: BlobVersioning,
     ?lease_id: LeaseId,
     ?chunk_size: u64,
+    ?checksum: ChecksumAlgorithm,
     ?encryption_key: CPKInfo,
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
@@ -27,38 +28,157 @@ impl GetBlobBuilder {
             let this = self.clone();
             let mut ctx = self.context.clone();
             async move {
-                let mut url = this.client.url()?;
-
                 let range = match continuation {
                     Some(range) => range,
-                    None => initial_range(
-                        this.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
-                        this.range.clone(),
-                    ),
+                    None => initial_range(this.effective_chunk_size(), this.range.clone()),
                 };
 
-                this.blob_versioning.append_to_url_query(&mut url);
+                #[cfg(feature = "client-side-encryption")]
+                if let Some(encryption) = this.client.client_side_encryption() {
+                    if this.checksum.is_some() {
+                        return Err(Error::message(
+                            azure_core::error::ErrorKind::Other,
+                            "transactional checksums cannot be validated on client-side encrypted downloads",
+                        ));
+                    }
+                    if let Some(response) = this.get_encrypted(&mut ctx, encryption, &range).await?
+                    {
+                        return Ok(response);
+                    }
+                }
 
-                let mut headers = Headers::new();
-                for (name, value) in range.as_headers() {
-                    headers.insert(name, value);
+                let mut response = this.send(&mut ctx, &range).await?;
+                if let Some(checksum) = &this.checksum {
+                    response = validate(checksum, response).await?;
                 }
+                GetBlobResponse::try_from(this, response)
+            }
+        };
+        Pageable::new(make_request)
+    }
 
-                headers.add(this.lease_id);
-                headers.add(this.encryption_key.as_ref());
-                headers.add(this.if_modified_since);
-                headers.add(this.if_match.clone());
-                headers.add(this.if_tags.clone());
+    /// The size of the chunks, no larger than the service returns checksums for when validating them.
+    fn effective_chunk_size(&self) -> u64 {
+        let chunk_size = self.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+        match self.checksum {
+            Some(_) => chunk_size.min(MAX_CHECKSUM_RANGE),
+            None => chunk_size,
+        }
+    }
 
-                let mut request =
-                    BlobClient::finalize_request(url, azure_core::Method::Get, headers, None)?;
+    async fn send(&self, ctx: &mut Context, range: &Range) -> azure_core::Result<AzureResponse> {
+        let mut url = self.client.url()?;
+        self.blob_versioning.append_to_url_query(&mut url);
 
-                let response = this.client.send(&mut ctx, &mut request).await?;
+        let mut headers = Headers::new();
+        for (name, value) in range.as_headers() {
+            headers.insert(name, value);
+        }
+        if let Some(checksum) = &self.checksum {
+            headers.insert(checksum.range_request_header(), "true");
+        }
 
-                GetBlobResponse::try_from(this, response)
-            }
+        headers.add(self.lease_id);
+        let encryption_key = self
+            .client
+            .server_side_encryption_key(self.encryption_key.clone());
+        headers.add(encryption_key.as_ref());
+        headers.add(self.if_modified_since);
+        headers.add(self.if_match.clone());
+        headers.add(self.if_tags.clone());
+
+        let mut request =
+            BlobClient::finalize_request(url, azure_core::Method::Get, headers, None)?;
+
+        let response = self.client.send(ctx, &mut request).await?;
+        if let Some(encryption_key) = &encryption_key {
+            encryption_key.verify(response.headers())?;
+        }
+        Ok(response)
+    }
+
+    /// Get the plaintext `range` of a client-side encrypted blob.
+    ///
+    /// The whole encryption regions holding the range are downloaded and decrypted. Returns `None`
+    /// when the blob is not encrypted and unencrypted blobs are allowed, in which case the range
+    /// must be downloaded as is.
+    #[cfg(feature = "client-side-encryption")]
+    async fn get_encrypted(
+        &self,
+        ctx: &mut Context,
+        encryption: &crate::encryption::ClientSideEncryption,
+        range: &Range,
+    ) -> azure_core::Result<Option<GetBlobResponse>> {
+        use azure_core::error::ErrorKind;
+
+        let (start, end) = match range {
+            Range::Range(r) => (r.start, r.end),
+            Range::RangeFrom(r) => (r.start, u64::MAX),
         };
-        Pageable::new(make_request)
+
+        // the layout of the regions is only known once the encryption data has been read, so
+        // assume the one used when encrypting, and download again if the blob used another one.
+        let (mut encrypted_start, mut encrypted_end) = encryption.encrypted_range(start, end);
+        let mut response = self
+            .send(ctx, &Range::new(encrypted_start, encrypted_end))
+            .await?;
+        let mut blob = Blob::from_headers(self.client.blob_name(), response.headers())?;
+        let Some(encryption_data) = encryption.encryption_data(blob.metadata.as_ref())? else {
+            return Ok(None);
+        };
+        let decryptor = encryption.decryptor(encryption_data).await?;
+        if decryptor.encrypted_range(start, end) != (encrypted_start, encrypted_end) {
+            (encrypted_start, encrypted_end) = decryptor.encrypted_range(start, end);
+            response = self
+                .send(ctx, &Range::new(encrypted_start, encrypted_end))
+                .await?;
+            blob = Blob::from_headers(self.client.blob_name(), response.headers())?;
+        }
+
+        let headers = response.headers();
+        let request_id = request_id_from_headers(headers)?;
+        let date = date_from_headers(headers)?;
+        let encrypted_range: Option<ContentRange> = headers.get_optional_as(&CONTENT_RANGE)?;
+        let encrypted = response.into_body().collect().await?;
+
+        let (first, encrypted_length) = match encrypted_range {
+            Some(range) => (range.start(), range.total_length()),
+            None => (0, encrypted.len() as u64),
+        };
+        let offset = decryptor.plaintext_offset(first);
+        let length = decryptor.plaintext_length(encrypted_length);
+        let plaintext = decryptor.decrypt(&encrypted)?;
+        if start < offset || start > offset + plaintext.len() as u64 {
+            return Err(Error::message(
+                ErrorKind::DataConversion,
+                "encrypted blob content does not hold the requested range",
+            ));
+        }
+        let end = end.min(offset + plaintext.len() as u64);
+        let data = plaintext.slice((start - offset) as usize..(end - offset) as usize);
+
+        let content_range = (end > start).then(|| ContentRange::new(start, end - 1, length));
+        let remaining_range = remaining_range(
+            self.effective_chunk_size(),
+            self.range.clone(),
+            content_range,
+        );
+        blob.properties.content_length = data.len() as u64;
+        let data = AzureResponse::new(
+            azure_core::StatusCode::Ok,
+            Headers::new(),
+            Box::pin(futures::stream::once(async move { Ok(data) })),
+        )
+        .into_body();
+
+        Ok(Some(GetBlobResponse {
+            request_id,
+            blob,
+            data,
+            date,
+            content_range: content_range.map(|cr| Range::new(cr.start(), cr.end())),
+            remaining_range,
+        }))
     }
 }
 
@@ -81,11 +201,8 @@ impl GetBlobResponse {
 
         let content_range = headers.get_optional_as(&CONTENT_RANGE)?;
 
-        let remaining_range = remaining_range(
-            request.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
-            request.range,
-            content_range,
-        );
+        let remaining_range =
+            remaining_range(request.effective_chunk_size(), request.range, content_range);
         let blob = Blob::from_headers(request.client.blob_name(), headers)?;
         let data = response.into_body();
 
@@ -107,6 +224,21 @@ impl Continuable for GetBlobResponse {
     }
 }
 
+/// Collect the body of `response` and check it against the checksum returned by the service.
+async fn validate(
+    checksum: &ChecksumAlgorithm,
+    response: AzureResponse,
+) -> azure_core::Result<AzureResponse> {
+    let (status, headers, body) = response.deconstruct();
+    let data = body.collect().await?;
+    checksum.validate(&headers, &data)?;
+    Ok(AzureResponse::new(
+        status,
+        headers,
+        Box::pin(futures::stream::once(async move { Ok(data) })),
+    ))
+}
+
 // calculate the first Range for use at the beginning of the Pageable.
 fn initial_range(chunk_size: u64, request_range: Option<Range>) -> Range {
     match request_range {
@@ -9,7 +9,8 @@ operation! {
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags,
     ?blob_versioning: BlobVersioning,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo
 }
 
 impl GetPropertiesBuilder {
@@ -20,6 +21,8 @@ impl GetPropertiesBuilder {
             self.blob_versioning.append_to_url_query(&mut url);
 
             let mut headers = Headers::new();
+            let encryption_key = self.client.server_side_encryption_key(self.encryption_key);
+            headers.add(encryption_key.as_ref());
             headers.add(self.lease_id);
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
@@ -29,6 +32,9 @@ impl GetPropertiesBuilder {
                 BlobClient::finalize_request(url, azure_core::Method::Head, headers, None)?;
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
             // TODO: Fix this
             //let blob = Blob::from_headers(&blob_name, &container_name, snapshot_time, &headers)?;
             let blob = Blob::from_headers(self.client.blob_name(), response.headers())?;
@@ -0,0 +1,48 @@
+use crate::{
+    blob::{copy_status_from_headers, CopyStatus},
+    prelude::*,
+};
+use azure_core::{headers::*, prelude::*, RequestId, Url};
+use azure_storage::{copy_id_from_headers, CopyId};
+use time::OffsetDateTime;
+
+operation! {
+    IncrementalCopy,
+    client: BlobClient,
+    source_url: Url,
+    ?if_modified_since: IfModifiedSinceCondition,
+    ?if_match: IfMatchCondition,
+    ?if_tags: IfTags
+}
+
+impl IncrementalCopyBuilder {
+    pub fn into_future(mut self) -> IncrementalCopy {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "incrementalcopy");
+
+            let mut headers = Headers::new();
+            headers.insert(COPY_SOURCE, self.source_url.as_str().to_owned());
+            headers.add(self.if_modified_since);
+            headers.add(self.if_match);
+            headers.add(self.if_tags);
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            IncrementalCopyResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(IncrementalCopyResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime,
+    copy_id_from_headers => copy_id: CopyId,
+    copy_status_from_headers => copy_status: CopyStatus
+);
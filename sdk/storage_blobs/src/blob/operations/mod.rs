@@ -1,5 +1,7 @@
+mod abort_copy;
 mod acquire_lease;
 mod append_block;
+mod append_block_from_url;
 mod break_lease;
 mod change_lease;
 mod clear_page;
@@ -8,12 +10,15 @@ mod copy_blob_from_url;
 mod delete_blob;
 mod delete_blob_snapshot;
 mod delete_blob_version;
+mod delete_immutability_policy;
+mod download;
 mod get_blob;
 mod get_block_list;
 mod get_metadata;
 mod get_page_ranges;
 mod get_properties;
 mod get_tags;
+mod incremental_copy;
 mod put_append_blob;
 mod put_block;
 mod put_block_blob;
@@ -21,17 +26,25 @@ mod put_block_list;
 mod put_block_url;
 mod put_page;
 mod put_page_blob;
+mod put_page_from_url;
+mod query_blob;
 mod release_lease;
 mod renew_lease;
 mod set_blob_tier;
 mod set_expiry;
+mod set_immutability_policy;
+mod set_legal_hold;
 mod set_metadata;
 mod set_properties;
 mod set_tags;
 mod snapshot_blob;
+mod undelete_blob;
+mod upload;
 
+pub use abort_copy::*;
 pub use acquire_lease::*;
 pub use append_block::*;
+pub use append_block_from_url::*;
 pub use break_lease::*;
 pub use change_lease::*;
 pub use clear_page::*;
@@ -40,12 +53,15 @@ pub use copy_blob_from_url::*;
 pub use delete_blob::*;
 pub use delete_blob_snapshot::*;
 pub use delete_blob_version::*;
+pub use delete_immutability_policy::*;
+pub use download::*;
 pub use get_blob::*;
 pub use get_block_list::*;
 pub use get_metadata::*;
 pub use get_page_ranges::*;
 pub use get_properties::*;
 pub use get_tags::*;
+pub use incremental_copy::*;
 pub use put_append_blob::*;
 pub use put_block::*;
 pub use put_block_blob::*;
@@ -53,11 +69,17 @@ pub use put_block_list::*;
 pub use put_block_url::*;
 pub use put_page::*;
 pub use put_page_blob::*;
+pub use put_page_from_url::*;
+pub use query_blob::*;
 pub use release_lease::*;
 pub use renew_lease::*;
 pub use set_blob_tier::*;
 pub use set_expiry::*;
+pub use set_immutability_policy::*;
+pub use set_legal_hold::*;
 pub use set_metadata::*;
 pub use set_properties::*;
 pub use set_tags::*;
 pub use snapshot_blob::*;
+pub use undelete_blob::*;
+pub use upload::*;
@@ -13,7 +13,9 @@ operation! {
     ?tags: Tags,
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl PutAppendBlobBuilder {
@@ -22,6 +24,11 @@ impl PutAppendBlobBuilder {
             let url = self.client.url()?;
 
             let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
             headers.insert(BLOB_TYPE, "AppendBlob");
             headers.add(self.content_type);
             headers.add(self.content_encoding);
@@ -41,6 +48,9 @@ impl PutAppendBlobBuilder {
                 BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
             PutBlobResponse::from_headers(response.headers())
         })
     }
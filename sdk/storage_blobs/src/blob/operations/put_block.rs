@@ -4,33 +4,62 @@ use azure_storage::{headers::consistency_from_headers, ConsistencyCRC64, Consist
 use time::OffsetDateTime;
 
 operation! {
-    PutBlock,
+    PutBlock<>,
     client: BlobClient,
     block_id: BlockId,
     body: Body,
     ?hash: Hash,
-    ?lease_id: LeaseId
+    ?checksum: ChecksumAlgorithm,
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope,
+    #[skip]
+    encrypted: bool
 }
 
 impl PutBlockBuilder {
+    /// Mark the content of the block as already encrypted by the client-side encryption of the
+    /// client.
+    #[cfg(feature = "client-side-encryption")]
+    pub(crate) fn encrypted(mut self) -> Self {
+        self.encrypted = Some(true);
+        self
+    }
+
     pub fn into_future(mut self) -> PutBlock {
         Box::pin(async move {
+            if !self.encrypted.unwrap_or_default() {
+                self.client.check_unencrypted_upload("Put Block")?;
+            }
+
             let mut url = self.client.url()?;
 
             self.block_id.append_to_url_query(&mut url);
             url.query_pairs_mut().append_pair("comp", "block");
 
             let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
             headers.add(self.lease_id);
+            let (body, checksum) =
+                add_upload_checksum(self.body, self.hash, self.checksum, &mut headers).await?;
 
-            let mut request = BlobClient::finalize_request(
-                url,
-                azure_core::Method::Put,
-                headers,
-                Some(self.body),
-            )?;
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, Some(body))?;
+            if let Some(checksum) = &checksum {
+                checksum.prepare(&mut request);
+            }
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
+            if let Some(checksum) = &checksum {
+                checksum.verify(response.headers())?;
+            }
             PutBlockResponse::from_headers(response.headers())
         })
     }
@@ -8,6 +8,7 @@ operation! {
     client: BlobClient,
     body: Body,
     ?hash: Hash,
+    ?checksum: ChecksumAlgorithm,
     ?content_type: BlobContentType,
     ?content_encoding: BlobContentEncoding,
     ?content_language: BlobContentLanguage,
@@ -28,9 +29,14 @@ impl PutBlockBlobBuilder {
         Box::pin(async move {
             let url = self.client.url()?;
 
+            #[cfg(feature = "client-side-encryption")]
+            self.encrypt().await?;
+
             let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
             headers.insert(BLOB_TYPE, "BlockBlob");
-            headers.add(self.hash);
             headers.add(self.content_type);
             headers.add(self.content_encoding);
             headers.add(self.content_language);
@@ -43,25 +49,63 @@ impl PutBlockBlobBuilder {
             }
             headers.add(self.access_tier);
             headers.add(self.lease_id);
-            headers.add(self.encryption_key);
-            headers.add(self.encryption_scope);
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
             headers.add(self.if_tags);
+            let (body, checksum) =
+                add_upload_checksum(self.body, self.hash, self.checksum, &mut headers).await?;
 
-            let mut request = BlobClient::finalize_request(
-                url,
-                azure_core::Method::Put,
-                headers,
-                Some(self.body),
-            )?;
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, Some(body))?;
+            if let Some(checksum) = &checksum {
+                checksum.prepare(&mut request);
+            }
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
+            if let Some(checksum) = &checksum {
+                checksum.verify(response.headers())?;
+            }
             PutBlockBlobResponse::from_headers(response.headers())
         })
     }
 }
 
+#[cfg(feature = "client-side-encryption")]
+impl PutBlockBlobBuilder {
+    /// Replace the body with its encrypted form when the client encrypts blobs, and record how it
+    /// was encrypted in the metadata.
+    async fn encrypt(&mut self) -> azure_core::Result<()> {
+        use crate::encryption::{rehash, ENCRYPTION_DATA_METADATA};
+        use azure_core::error::{Error, ErrorKind};
+
+        let encryptor = match self.client.client_side_encryption() {
+            Some(encryption) => encryption.encryptor().await?,
+            None => return Ok(()),
+        };
+        let Body::Bytes(plaintext) = &self.body else {
+            return Err(Error::message(
+                ErrorKind::Other,
+                "client-side encryption requires the blob content in memory",
+            ));
+        };
+        let encrypted = encryptor.encrypt(plaintext)?;
+        if let Some(hash) = &self.hash {
+            self.hash = Some(rehash(hash, &encrypted)?);
+        }
+        self.metadata.get_or_insert_with(Metadata::new).insert(
+            ENCRYPTION_DATA_METADATA,
+            encryptor.encryption_data().to_owned(),
+        );
+        self.body = encrypted.into();
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PutBlockBlobResponse {
     pub etag: String,
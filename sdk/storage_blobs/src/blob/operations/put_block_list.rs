@@ -7,7 +7,7 @@ use bytes::Bytes;
 use time::OffsetDateTime;
 
 operation! {
-    PutBlockList,
+    PutBlockList<>,
     client: BlobClient,
     block_list: BlockList,
     ?content_type: BlobContentType,
@@ -19,14 +19,29 @@ operation! {
     ?access_tier: AccessTier,
     ?tags: Tags,
     ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope,
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
-    ?if_tags: IfTags
+    ?if_tags: IfTags,
+    #[skip]
+    encrypted: bool
 }
 
 impl PutBlockListBuilder {
+    /// Mark the blocks as already encrypted by the client-side encryption of the client.
+    #[cfg(feature = "client-side-encryption")]
+    pub(crate) fn encrypted(mut self) -> Self {
+        self.encrypted = Some(true);
+        self
+    }
+
     pub fn into_future(mut self) -> PutBlockList {
         Box::pin(async move {
+            if !self.encrypted.unwrap_or_default() {
+                self.client.check_unencrypted_upload("Put Block List")?;
+            }
+
             let mut url = self.client.url()?;
 
             url.query_pairs_mut().append_pair("comp", "blocklist");
@@ -43,6 +58,11 @@ impl PutBlockListBuilder {
             };
 
             let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
             #[cfg(feature = "md5")]
             headers.insert(CONTENT_MD5, &md5);
             headers.add(self.content_type);
@@ -70,6 +90,9 @@ impl PutBlockListBuilder {
             )?;
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
             PutBlockListResponse::from_headers(response.headers())
         })
     }
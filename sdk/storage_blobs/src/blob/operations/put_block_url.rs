@@ -10,7 +10,9 @@ operation! {
     url: Url,
     ?hash: Hash,
     ?range: Range,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl PutBlockUrlBuilder {
@@ -22,6 +24,11 @@ impl PutBlockUrlBuilder {
             url.query_pairs_mut().append_pair("comp", "block");
 
             let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
             headers.insert(COPY_SOURCE, self.url.to_string());
             headers.add(self.lease_id);
             if let Some(range) = self.range {
@@ -32,6 +39,9 @@ impl PutBlockUrlBuilder {
                 BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
             PutBlockUrlResponse::from_headers(response.headers())
         })
     }
@@ -9,39 +9,55 @@ operation! {
     ba512_range: BA512Range,
     content: Body,
     ?hash: Hash,
+    ?checksum: ChecksumAlgorithm,
     ?if_sequence_number: IfSequenceNumber,
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl PutPageBuilder {
     pub fn into_future(mut self) -> PutPage {
         Box::pin(async move {
+            self.client.check_unencrypted_upload("Put Page")?;
+
             let mut url = self.client.url()?;
 
             url.query_pairs_mut().append_pair("comp", "page");
 
             let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
             headers.insert(PAGE_WRITE, "update");
             headers.insert(BLOB_TYPE, "PageBlob");
             headers.add(self.ba512_range);
-            headers.add(self.hash);
             headers.add(self.if_sequence_number);
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
             headers.add(self.if_tags);
             headers.add(self.lease_id);
+            let (content, checksum) =
+                add_upload_checksum(self.content, self.hash, self.checksum, &mut headers).await?;
 
-            let mut request = BlobClient::finalize_request(
-                url,
-                azure_core::Method::Put,
-                headers,
-                Some(self.content),
-            )?;
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, Some(content))?;
+            if let Some(checksum) = &checksum {
+                checksum.prepare(&mut request);
+            }
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
+            if let Some(checksum) = &checksum {
+                checksum.verify(response.headers())?;
+            }
             PutPageResponse::from_headers(response.headers())
         })
     }
@@ -15,6 +15,8 @@ operation! {
     ?metadata: Metadata,
     ?tags: Tags,
     ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope,
     ?sequence_number: SequenceNumber
 }
 
@@ -24,6 +26,11 @@ impl PutPageBlobBuilder {
             let url = self.client.url()?;
 
             let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
             headers.insert(BLOB_TYPE, "PageBlob");
             headers.insert(BLOB_CONTENT_LENGTH, &format!("{}", self.length));
             headers.add(self.content_type);
@@ -43,6 +50,9 @@ impl PutPageBlobBuilder {
                 BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
             PutBlobResponse::from_headers(response.headers())
         })
     }
@@ -0,0 +1,55 @@
+use crate::{blob::operations::put_page::PutPageResponse, prelude::*};
+use azure_core::{headers::*, prelude::*, Url};
+
+operation! {
+    PutPageFromUrl,
+    client: BlobClient,
+    ba512_range: BA512Range,
+    source_url: Url,
+    source_range: BA512Range,
+    ?if_sequence_number: IfSequenceNumber,
+    ?if_modified_since: IfModifiedSinceCondition,
+    ?if_match: IfMatchCondition,
+    ?if_tags: IfTags,
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
+}
+
+impl PutPageFromUrlBuilder {
+    pub fn into_future(mut self) -> PutPageFromUrl {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "page");
+
+            let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
+            headers.insert(PAGE_WRITE, "update");
+            headers.add(self.ba512_range);
+            headers.insert(COPY_SOURCE, self.source_url.as_str().to_owned());
+            headers.insert(SOURCE_RANGE, self.source_range.to_string());
+            headers.add(self.if_sequence_number);
+            headers.add(self.if_modified_since);
+            headers.add(self.if_match);
+            headers.add(self.if_tags);
+            headers.add(self.lease_id);
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
+
+            PutPageResponse::from_headers(response.headers())
+        })
+    }
+}
+
+type PutPageFromUrlResponse = PutPageResponse;
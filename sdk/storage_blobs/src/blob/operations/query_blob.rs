@@ -0,0 +1,579 @@
+use crate::{avro::AvroReader, prelude::*};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::*,
+    prelude::*,
+    xml::to_xml_with_root,
+    Method, RequestId, ResponseBody,
+};
+use bytes::{Bytes, BytesMut};
+use futures::{Stream, StreamExt};
+use std::{
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
+use time::OffsetDateTime;
+
+operation! {
+    /// Apply a SQL statement to the contents of a blob and return only the matching data.
+    ///
+    /// ref: <https://docs.microsoft.com/rest/api/storageservices/query-blob-contents>
+    QueryBlob,
+    client: BlobClient,
+    expression: String,
+    ?input_serialization: QueryFormat,
+    ?output_serialization: QueryFormat,
+    ?blob_versioning: BlobVersioning,
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?if_modified_since: IfModifiedSinceCondition,
+    ?if_match: IfMatchCondition,
+    ?if_tags: IfTags
+}
+
+impl QueryBlobBuilder {
+    pub fn into_future(mut self) -> QueryBlob {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("comp", "query");
+            self.blob_versioning.append_to_url_query(&mut url);
+
+            let body = query_request_xml(
+                &self.expression,
+                self.input_serialization.as_ref(),
+                self.output_serialization.as_ref(),
+            )?;
+
+            let mut headers = Headers::new();
+            headers.insert(CONTENT_TYPE, "application/xml");
+            headers.add(self.lease_id);
+            let encryption_key = self.client.server_side_encryption_key(self.encryption_key);
+            headers.add(encryption_key.as_ref());
+            headers.add(self.if_modified_since);
+            headers.add(self.if_match.clone());
+            headers.add(self.if_tags.clone());
+
+            let mut request =
+                BlobClient::finalize_request(url, Method::Post, headers, Some(body.into()))?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
+            let (_, headers, body) = response.deconstruct();
+
+            Ok(QueryBlobResponse {
+                request_id: request_id_from_headers(&headers)?,
+                date: date_from_headers(&headers)?,
+                etag: etag_from_headers(&headers)?,
+                last_modified: last_modified_from_headers(&headers)?,
+                events: QueryEvents::new(body),
+            })
+        })
+    }
+}
+
+/// The format of the data read by a query, or of the results it returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryFormat {
+    /// Delimited text such as CSV.
+    Delimited(DelimitedTextConfiguration),
+    /// JSON records.
+    Json(JsonTextConfiguration),
+    /// Parquet, for input only.
+    Parquet,
+    /// Apache Arrow, for output only.
+    Arrow(ArrowConfiguration),
+}
+
+impl QueryFormat {
+    /// Comma separated values with a header row.
+    pub fn csv() -> Self {
+        Self::Delimited(DelimitedTextConfiguration::default())
+    }
+
+    /// Newline separated JSON records.
+    pub fn json() -> Self {
+        Self::Json(JsonTextConfiguration::default())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DelimitedTextConfiguration {
+    pub column_separator: String,
+    pub field_quote: String,
+    pub record_separator: String,
+    pub escape_char: String,
+    pub has_headers: bool,
+}
+
+impl Default for DelimitedTextConfiguration {
+    fn default() -> Self {
+        Self {
+            column_separator: ",".to_owned(),
+            field_quote: "\"".to_owned(),
+            record_separator: "\n".to_owned(),
+            escape_char: String::new(),
+            has_headers: true,
+        }
+    }
+}
+
+impl DelimitedTextConfiguration {
+    setters! {
+        column_separator: String => column_separator,
+        field_quote: String => field_quote,
+        record_separator: String => record_separator,
+        escape_char: String => escape_char,
+        has_headers: bool => has_headers,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct JsonTextConfiguration {
+    pub record_separator: String,
+}
+
+impl Default for JsonTextConfiguration {
+    fn default() -> Self {
+        Self {
+            record_separator: "\n".to_owned(),
+        }
+    }
+}
+
+impl JsonTextConfiguration {
+    setters! {
+        record_separator: String => record_separator,
+    }
+}
+
+/// The schema of query results returned in the Arrow format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ArrowConfiguration {
+    #[serde(rename = "Schema")]
+    schema: ArrowSchema,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+struct ArrowSchema {
+    #[serde(rename = "Field")]
+    fields: Vec<ArrowField>,
+}
+
+impl ArrowConfiguration {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a field to the schema.
+    pub fn field(mut self, field: ArrowField) -> Self {
+        self.schema.fields.push(field);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ArrowField {
+    #[serde(rename = "Type")]
+    pub field_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precision: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<u32>,
+}
+
+impl ArrowField {
+    /// Create a field of an Arrow type such as `int64`, `string` or `decimal`.
+    pub fn new(field_type: impl Into<String>) -> Self {
+        Self {
+            field_type: field_type.into(),
+            name: None,
+            precision: None,
+            scale: None,
+        }
+    }
+
+    setters! {
+        name: String => Some(name),
+        precision: u32 => Some(precision),
+        scale: u32 => Some(scale),
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct QueryRequest<'a> {
+    query_type: &'static str,
+    expression: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input_serialization: Option<QuerySerialization<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_serialization: Option<QuerySerialization<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct QuerySerialization<'a> {
+    format: QueryFormatXml<'a>,
+}
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "PascalCase")]
+struct QueryFormatXml<'a> {
+    #[serde(rename = "Type")]
+    format_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delimited_text_configuration: Option<&'a DelimitedTextConfiguration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    json_text_configuration: Option<&'a JsonTextConfiguration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    arrow_configuration: Option<&'a ArrowConfiguration>,
+}
+
+impl<'a> QuerySerialization<'a> {
+    fn new(format: &'a QueryFormat) -> Self {
+        let format = match format {
+            QueryFormat::Delimited(configuration) => QueryFormatXml {
+                format_type: "delimited",
+                delimited_text_configuration: Some(configuration),
+                ..Default::default()
+            },
+            QueryFormat::Json(configuration) => QueryFormatXml {
+                format_type: "json",
+                json_text_configuration: Some(configuration),
+                ..Default::default()
+            },
+            QueryFormat::Parquet => QueryFormatXml {
+                format_type: "parquet",
+                ..Default::default()
+            },
+            QueryFormat::Arrow(configuration) => QueryFormatXml {
+                format_type: "arrow",
+                arrow_configuration: Some(configuration),
+                ..Default::default()
+            },
+        };
+        Self { format }
+    }
+}
+
+fn query_request_xml(
+    expression: &str,
+    input: Option<&QueryFormat>,
+    output: Option<&QueryFormat>,
+) -> azure_core::Result<Bytes> {
+    if matches!(input, Some(QueryFormat::Arrow(_))) {
+        return Err(Error::message(
+            ErrorKind::Other,
+            "arrow is only supported as a query output format",
+        ));
+    }
+    if matches!(output, Some(QueryFormat::Parquet)) {
+        return Err(Error::message(
+            ErrorKind::Other,
+            "parquet is only supported as a query input format",
+        ));
+    }
+
+    let request = QueryRequest {
+        query_type: "SQL",
+        expression,
+        input_serialization: input.map(QuerySerialization::new),
+        output_serialization: output.map(QuerySerialization::new),
+    };
+    let mut body = BytesMut::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>");
+    body.extend(to_xml_with_root("QueryRequest", &request)?);
+    Ok(body.freeze())
+}
+
+#[derive(Debug)]
+pub struct QueryBlobResponse {
+    pub request_id: RequestId,
+    pub date: OffsetDateTime,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    /// The events that make up the result of the query.
+    pub events: QueryEvents,
+}
+
+/// An event in the result of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryEvent {
+    /// Data matching the query, in the output format.
+    Data(Bytes),
+    /// How much of the blob has been scanned so far.
+    Progress {
+        bytes_scanned: u64,
+        total_bytes: u64,
+    },
+    /// An error encountered while processing the query.
+    Error(QueryError),
+    /// The query is complete.
+    End { total_bytes: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    /// Whether the query was stopped by the error.
+    pub fatal: bool,
+    pub name: String,
+    pub description: String,
+    /// The offset in the blob where the error occurred.
+    pub position: u64,
+}
+
+impl QueryEvent {
+    fn try_from(value: crate::avro::Value) -> azure_core::Result<Self> {
+        let crate::avro::Value::Record(name, _) = &value else {
+            return Err(Error::message(
+                ErrorKind::DataConversion,
+                "query response event is not a record",
+            ));
+        };
+        let name = name.rsplit('.').next().unwrap_or_default().to_owned();
+
+        let long = |field: &str| {
+            value
+                .field(field)
+                .and_then(crate::avro::Value::as_i64)
+                .map(|v| v.max(0) as u64)
+                .ok_or_else(|| missing_field(&name, field))
+        };
+        let string = |field: &str| {
+            value
+                .field(field)
+                .and_then(crate::avro::Value::as_str)
+                .map(str::to_owned)
+                .ok_or_else(|| missing_field(&name, field))
+        };
+
+        Ok(match name.as_str() {
+            "resultData" => {
+                let data = value
+                    .field("data")
+                    .cloned()
+                    .and_then(crate::avro::Value::into_bytes)
+                    .ok_or_else(|| missing_field(&name, "data"))?;
+                QueryEvent::Data(Bytes::from(data))
+            }
+            "progress" => QueryEvent::Progress {
+                bytes_scanned: long("bytesScanned")?,
+                total_bytes: long("totalBytes")?,
+            },
+            "error" => QueryEvent::Error(QueryError {
+                fatal: value
+                    .field("fatal")
+                    .and_then(crate::avro::Value::as_bool)
+                    .ok_or_else(|| missing_field(&name, "fatal"))?,
+                name: string("name")?,
+                description: string("description")?,
+                position: long("position")?,
+            }),
+            "end" => QueryEvent::End {
+                total_bytes: long("totalBytes")?,
+            },
+            other => {
+                return Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!("unknown query response event {other}")
+                }))
+            }
+        })
+    }
+}
+
+fn missing_field(record: &str, field: &str) -> Error {
+    Error::with_message(ErrorKind::DataConversion, || {
+        format!("query response {record} event is missing {field}")
+    })
+}
+
+/// A stream of the events in the Avro encoded result of a query.
+pub struct QueryEvents {
+    body: ResponseBody,
+    reader: AvroReader,
+    done: bool,
+}
+
+impl std::fmt::Debug for QueryEvents {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryEvents").finish_non_exhaustive()
+    }
+}
+
+impl QueryEvents {
+    fn new(body: ResponseBody) -> Self {
+        Self {
+            body,
+            reader: AvroReader::new(),
+            done: false,
+        }
+    }
+
+    /// Collect the data returned by the query.
+    ///
+    /// Progress and non-fatal errors are skipped, a fatal error is returned as an error.
+    pub async fn collect_data(mut self) -> azure_core::Result<Bytes> {
+        let mut data = BytesMut::new();
+        while let Some(event) = self.next().await {
+            match event? {
+                QueryEvent::Data(bytes) => data.extend(bytes),
+                QueryEvent::Error(error) if error.fatal => {
+                    return Err(Error::with_message(ErrorKind::Other, || {
+                        format!(
+                            "query failed at position {}: {}: {}",
+                            error.position, error.name, error.description
+                        )
+                    }))
+                }
+                QueryEvent::Progress { .. } | QueryEvent::Error(_) | QueryEvent::End { .. } => {}
+            }
+        }
+        Ok(data.freeze())
+    }
+}
+
+impl Stream for QueryEvents {
+    type Item = azure_core::Result<QueryEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+            match this.reader.next() {
+                Ok(Some(value)) => return Poll::Ready(Some(QueryEvent::try_from(value))),
+                Ok(None) => {}
+                Err(error) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(error)));
+                }
+            }
+            match this.body.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(bytes))) => this.reader.push(&bytes),
+                Poll::Ready(Some(Err(error))) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(error)));
+                }
+                Poll::Ready(None) => {
+                    this.done = true;
+                    if !this.reader.is_empty() {
+                        return Poll::Ready(Some(Err(Error::message(
+                            ErrorKind::DataConversion,
+                            "query response ended in the middle of an event",
+                        ))));
+                    }
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avro::tests::{container, write_bytes, write_long_value, write_union_index};
+
+    const SCHEMA: &str = r#"[
+        {"type":"record","name":"com.microsoft.azure.storage.queryBlobContents.resultData","fields":[{"name":"data","type":"bytes"}]},
+        {"type":"record","name":"com.microsoft.azure.storage.queryBlobContents.error","fields":[{"name":"fatal","type":"boolean"},{"name":"name","type":"string"},{"name":"description","type":"string"},{"name":"position","type":"long"}]},
+        {"type":"record","name":"com.microsoft.azure.storage.queryBlobContents.progress","fields":[{"name":"bytesScanned","type":"long"},{"name":"totalBytes","type":"long"}]},
+        {"type":"record","name":"com.microsoft.azure.storage.queryBlobContents.end","fields":[{"name":"totalBytes","type":"long"}]}
+    ]"#;
+
+    #[test]
+    fn test_query_request_xml() -> azure_core::Result<()> {
+        let body = query_request_xml(
+            "SELECT * from BlobStorage",
+            Some(&QueryFormat::Delimited(
+                DelimitedTextConfiguration::default().column_separator(";"),
+            )),
+            Some(&QueryFormat::Arrow(
+                ArrowConfiguration::new().field(
+                    ArrowField::new("decimal")
+                        .name("price")
+                        .precision(4u32)
+                        .scale(2u32),
+                ),
+            )),
+        )?;
+        assert_eq!(
+            std::str::from_utf8(&body).unwrap(),
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"utf-8\"?>",
+                "<QueryRequest><QueryType>SQL</QueryType><Expression>SELECT * from BlobStorage</Expression>",
+                "<InputSerialization><Format><Type>delimited</Type><DelimitedTextConfiguration>",
+                "<ColumnSeparator>;</ColumnSeparator><FieldQuote>&quot;</FieldQuote><RecordSeparator>\n</RecordSeparator>",
+                "<EscapeChar/><HasHeaders>true</HasHeaders></DelimitedTextConfiguration></Format></InputSerialization>",
+                "<OutputSerialization><Format><Type>arrow</Type><ArrowConfiguration><Schema><Field>",
+                "<Type>decimal</Type><Name>price</Name><Precision>4</Precision><Scale>2</Scale>",
+                "</Field></Schema></ArrowConfiguration></Format></OutputSerialization></QueryRequest>"
+            )
+        );
+
+        assert!(query_request_xml("SELECT 1", Some(&QueryFormat::Parquet), None).is_ok());
+        assert!(query_request_xml("SELECT 1", None, Some(&QueryFormat::Parquet)).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_events() -> azure_core::Result<()> {
+        let mut block = Vec::new();
+        write_union_index(&mut block, 2);
+        write_long_value(&mut block, 10);
+        write_long_value(&mut block, 100);
+        write_union_index(&mut block, 0);
+        write_bytes(&mut block, b"a,b\n");
+        write_union_index(&mut block, 1);
+        block.push(0);
+        write_bytes(&mut block, b"InvalidColumnOrdinal");
+        write_bytes(&mut block, b"Column ordinal out of range");
+        write_long_value(&mut block, 42);
+        write_union_index(&mut block, 3);
+        write_long_value(&mut block, 100);
+
+        let file = Bytes::from(container(SCHEMA, &[(4, block)]));
+        // split the response in the middle of an event
+        let chunks = vec![
+            Ok(file.slice(..file.len() - 20)),
+            Ok(file.slice(file.len() - 20..)),
+        ];
+        let body = azure_core::Response::new(
+            azure_core::StatusCode::Ok,
+            Headers::new(),
+            Box::pin(futures::stream::iter(chunks)),
+        )
+        .into_body();
+
+        let events = QueryEvents::new(body)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<azure_core::Result<Vec<_>>>()?;
+        assert_eq!(
+            events,
+            vec![
+                QueryEvent::Progress {
+                    bytes_scanned: 10,
+                    total_bytes: 100
+                },
+                QueryEvent::Data(Bytes::from_static(b"a,b\n")),
+                QueryEvent::Error(QueryError {
+                    fatal: false,
+                    name: "InvalidColumnOrdinal".to_owned(),
+                    description: "Column ordinal out of range".to_owned(),
+                    position: 42,
+                }),
+                QueryEvent::End { total_bytes: 100 },
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::prelude::*;
+use azure_core::{date, headers::*, prelude::*, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    SetImmutabilityPolicy,
+    client: BlobClient,
+    expiry: OffsetDateTime,
+    ?mode: ImmutabilityPolicyMode,
+    ?if_unmodified_since: IfUnmodifiedSince
+}
+
+impl SetImmutabilityPolicyBuilder {
+    pub fn into_future(mut self) -> SetImmutabilityPolicy {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut()
+                .append_pair("comp", "immutabilityPolicies");
+
+            let mut headers = Headers::new();
+            headers.insert(
+                IMMUTABILITY_POLICY_UNTIL_DATE,
+                date::to_rfc1123(&self.expiry),
+            );
+            headers.add(self.mode);
+            headers.add(self.if_unmodified_since);
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            SetImmutabilityPolicyResponse::from_headers(response.headers())
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetImmutabilityPolicyResponse {
+    pub request_id: RequestId,
+    pub date: OffsetDateTime,
+    pub expiry: OffsetDateTime,
+    pub mode: ImmutabilityPolicyMode,
+}
+
+impl SetImmutabilityPolicyResponse {
+    pub(crate) fn from_headers(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            request_id: request_id_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+            expiry: date::parse_rfc1123(headers.get_str(&IMMUTABILITY_POLICY_UNTIL_DATE)?)?,
+            mode: headers.get_as(&IMMUTABILITY_POLICY_MODE)?,
+        })
+    }
+}
@@ -0,0 +1,46 @@
+use crate::prelude::*;
+use azure_core::{headers::*, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    SetLegalHold,
+    client: BlobClient,
+    legal_hold: bool,
+}
+
+impl SetLegalHoldBuilder {
+    pub fn into_future(mut self) -> SetLegalHold {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "legalhold");
+
+            let mut headers = Headers::new();
+            headers.insert(LEGAL_HOLD, self.legal_hold.to_string());
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            SetLegalHoldResponse::from_headers(response.headers())
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetLegalHoldResponse {
+    pub request_id: RequestId,
+    pub date: OffsetDateTime,
+    pub legal_hold: bool,
+}
+
+impl SetLegalHoldResponse {
+    pub(crate) fn from_headers(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            request_id: request_id_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+            legal_hold: headers.get_as(&LEGAL_HOLD)?,
+        })
+    }
+}
@@ -9,6 +9,8 @@ operation! {
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags,
     ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope,
     ?metadata: Metadata
 }
 
@@ -20,6 +22,11 @@ impl SetMetadataBuilder {
             url.query_pairs_mut().append_pair("comp", "metadata");
 
             let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
             headers.add(self.lease_id);
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
@@ -33,6 +40,9 @@ impl SetMetadataBuilder {
             let mut request = BlobClient::finalize_request(url, Method::Put, headers, None)?;
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
             response.headers().try_into()
         })
     }
@@ -15,7 +15,9 @@ operation! {
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl SnapshotBlobBuilder {
@@ -26,6 +28,11 @@ impl SnapshotBlobBuilder {
             url.query_pairs_mut().append_pair("comp", "snapshot");
 
             let mut headers = Headers::new();
+            let (encryption_key, encryption_scope) = self
+                .client
+                .server_side_encryption(self.encryption_key, self.encryption_scope)?;
+            headers.add(encryption_key.as_ref());
+            headers.add(encryption_scope);
             headers.add(self.lease_id);
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
@@ -39,6 +46,9 @@ impl SnapshotBlobBuilder {
             let mut request = BlobClient::finalize_request(url, Put, headers, None)?;
 
             let response = self.client.send(&mut self.context, &mut request).await?;
+            if let Some(encryption_key) = &encryption_key {
+                encryption_key.verify(response.headers())?;
+            }
             response.headers().try_into()
         })
     }
@@ -0,0 +1,598 @@
+use crate::{blob::operations::*, prelude::*};
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+    Body, RequestId,
+};
+use azure_storage::ConsistencyMD5;
+use bytes::{Bytes, BytesMut};
+use futures::{
+    io::{AsyncRead, AsyncReadExt},
+    stream::{FuturesUnordered, StreamExt},
+};
+use std::{
+    cmp::{max, min},
+    fmt,
+    sync::{Arc, Mutex},
+};
+use time::OffsetDateTime;
+use tracing::debug;
+use uuid::Uuid;
+
+const MIB: u64 = 1024 * 1024;
+/// Block size used when the size of the payload allows it.
+const DEFAULT_BLOCK_SIZE: u64 = 8 * MIB;
+/// Largest block accepted by Put Block.
+const MAX_BLOCK_SIZE: u64 = 4000 * MIB;
+/// Largest number of blocks a block blob can be made of.
+const MAX_BLOCKS: u64 = 50_000;
+const DEFAULT_SINGLE_UPLOAD_THRESHOLD: u64 = 32 * MIB;
+const DEFAULT_MAX_CONCURRENCY: usize = 8;
+const DEFAULT_MAX_MEMORY: u64 = 128 * MIB;
+
+operation! {
+    /// Upload a block blob from a body or a reader, in parallel blocks when it is large.
+    ///
+    /// Payloads no larger than `single_upload_threshold` are uploaded with a single Put Blob request.
+    /// Larger payloads are split into blocks, which are uploaded concurrently with Put Block and
+    /// committed together with Put Block List, so the blob is only replaced once every block has been uploaded.
+    /// Failed requests are retried by the retry policy of the client's pipeline.
+    UploadBlob,
+    client: BlobClient,
+    source: UploadSource,
+    ?block_size: u64,
+    ?single_upload_threshold: u64,
+    ?max_concurrency: usize,
+    ?max_memory: u64,
+    ?checksum: ChecksumAlgorithm,
+    ?progress: TransferProgress,
+    ?content_type: BlobContentType,
+    ?content_encoding: BlobContentEncoding,
+    ?content_language: BlobContentLanguage,
+    ?content_disposition: BlobContentDisposition,
+    ?metadata: Metadata,
+    ?access_tier: AccessTier,
+    ?tags: Tags,
+    ?lease_id: LeaseId,
+    ?if_modified_since: IfModifiedSinceCondition,
+    ?if_match: IfMatchCondition,
+    ?if_tags: IfTags
+}
+
+impl UploadBlobBuilder {
+    pub fn into_future(self) -> UploadBlob {
+        Box::pin(async move {
+            let len = self.source.len();
+            let block_size = self.pick_block_size(len)?;
+            let threshold = self
+                .single_upload_threshold
+                .unwrap_or(DEFAULT_SINGLE_UPLOAD_THRESHOLD);
+            let mut chunker = Chunker::new(self.source.take().await?);
+
+            if len.map_or(true, |len| len <= threshold) {
+                let head = chunker.next_chunk(threshold as usize + 1).await?;
+                if head.len() as u64 <= threshold {
+                    return self.upload_single(head).await;
+                }
+                chunker.unread(head);
+            }
+
+            let max_concurrency = self.max_concurrency.unwrap_or(DEFAULT_MAX_CONCURRENCY);
+            let concurrency = (self.max_memory.unwrap_or(DEFAULT_MAX_MEMORY) / block_size)
+                .clamp(1, max(max_concurrency, 1) as u64) as usize;
+            debug!("uploading blob in blocks of {block_size} bytes, {concurrency} at a time");
+
+            #[cfg(feature = "client-side-encryption")]
+            let encryptor = match self.client.client_side_encryption() {
+                Some(encryption) => Some(encryption.encryptor().await?),
+                None => None,
+            };
+
+            let upload_id = Uuid::new_v4();
+            let mut block_list = BlockList::default();
+            let mut in_flight = FuturesUnordered::new();
+            let mut uploaded = 0;
+            let mut end_of_source = false;
+            loop {
+                while !end_of_source && in_flight.len() < concurrency {
+                    let data = chunker.next_chunk(block_size as usize).await?;
+                    if data.is_empty() {
+                        end_of_source = true;
+                        break;
+                    }
+                    if block_list.blocks.len() as u64 == MAX_BLOCKS {
+                        return Err(Error::with_message(ErrorKind::Other, || {
+                            format!("blob exceeds {MAX_BLOCKS} blocks of {block_size} bytes")
+                        }));
+                    }
+                    let block_id =
+                        BlockId::new(format!("{upload_id}-{:05}", block_list.blocks.len()));
+                    block_list
+                        .blocks
+                        .push(BlobBlockType::Uncommitted(block_id.clone()));
+                    let len = data.len() as u64;
+                    #[cfg(feature = "client-side-encryption")]
+                    let data = match &encryptor {
+                        Some(encryptor) => encryptor.encrypt(&data)?,
+                        None => data,
+                    };
+                    let block = self.upload_block(block_id, data);
+                    in_flight.push(async move { block.await.map(|_| len) });
+                }
+                match in_flight.next().await {
+                    Some(result) => {
+                        uploaded += result?;
+                        if let Some(progress) = &self.progress {
+                            progress.report(uploaded);
+                        }
+                    }
+                    None => break,
+                }
+            }
+
+            let metadata = self.metadata.clone();
+            #[cfg(feature = "client-side-encryption")]
+            let metadata = match &encryptor {
+                Some(encryptor) => {
+                    let mut metadata = metadata.unwrap_or_default();
+                    metadata.insert(
+                        crate::encryption::ENCRYPTION_DATA_METADATA,
+                        encryptor.encryption_data().to_owned(),
+                    );
+                    Some(metadata)
+                }
+                None => metadata,
+            };
+
+            let block_count = block_list.blocks.len();
+            let response = self.commit(block_list, metadata).await?;
+            Ok(UploadBlobResponse::from_block_list(response, block_count))
+        })
+    }
+
+    /// Pick the block size, so that the payload fits in the maximum number of blocks.
+    fn pick_block_size(&self, len: Option<u64>) -> azure_core::Result<u64> {
+        let block_size = match (self.block_size, len) {
+            (Some(block_size), _) => block_size,
+            (None, Some(len)) => max(
+                DEFAULT_BLOCK_SIZE,
+                len.div_ceil(MAX_BLOCKS).next_multiple_of(MIB),
+            ),
+            (None, None) => DEFAULT_BLOCK_SIZE,
+        };
+        // encrypted blocks must hold whole encryption regions
+        #[cfg(feature = "client-side-encryption")]
+        let block_size = match self.client.client_side_encryption() {
+            Some(_) if block_size > 0 => {
+                use crate::encryption::ContentEncryptor;
+                let block_size = ContentEncryptor::align_block_size(block_size);
+                if ContentEncryptor::encrypted_length(block_size) > MAX_BLOCK_SIZE {
+                    return Err(Error::with_message(ErrorKind::Other, || {
+                        format!(
+                            "encrypted blocks of {block_size} bytes exceed {MAX_BLOCK_SIZE} bytes"
+                        )
+                    }));
+                }
+                block_size
+            }
+            _ => block_size,
+        };
+        if block_size == 0 || block_size > MAX_BLOCK_SIZE {
+            return Err(Error::with_message(ErrorKind::Other, || {
+                format!("block size must be between 1 and {MAX_BLOCK_SIZE} bytes, got {block_size}")
+            }));
+        }
+        if let Some(len) = len {
+            if len.div_ceil(block_size) > MAX_BLOCKS {
+                return Err(Error::with_message(ErrorKind::Other, || {
+                    format!("{len} bytes do not fit in {MAX_BLOCKS} blocks of {block_size} bytes")
+                }));
+            }
+        }
+        Ok(block_size)
+    }
+
+    async fn upload_single(&self, data: Bytes) -> azure_core::Result<UploadBlobResponse> {
+        let len = data.len() as u64;
+        let mut builder = self
+            .client
+            .put_block_blob(data)
+            .context(self.context.clone());
+        if let Some(checksum) = self.checksum {
+            builder = builder.checksum(checksum);
+        }
+        if let Some(content_type) = self.content_type.clone() {
+            builder = builder.content_type(content_type);
+        }
+        if let Some(content_encoding) = self.content_encoding.clone() {
+            builder = builder.content_encoding(content_encoding);
+        }
+        if let Some(content_language) = self.content_language.clone() {
+            builder = builder.content_language(content_language);
+        }
+        if let Some(content_disposition) = self.content_disposition.clone() {
+            builder = builder.content_disposition(content_disposition);
+        }
+        if let Some(metadata) = self.metadata.clone() {
+            builder = builder.metadata(metadata);
+        }
+        if let Some(access_tier) = self.access_tier {
+            builder = builder.access_tier(access_tier);
+        }
+        if let Some(tags) = self.tags.clone() {
+            builder = builder.tags(tags);
+        }
+        if let Some(lease_id) = self.lease_id {
+            builder = builder.lease_id(lease_id);
+        }
+        if let Some(if_modified_since) = self.if_modified_since {
+            builder = builder.if_modified_since(if_modified_since);
+        }
+        if let Some(if_match) = self.if_match.clone() {
+            builder = builder.if_match(if_match);
+        }
+        if let Some(if_tags) = self.if_tags.clone() {
+            builder = builder.if_tags(if_tags);
+        }
+        let response = builder.await?;
+        if let Some(progress) = &self.progress {
+            progress.report(len);
+        }
+        Ok(response.into())
+    }
+
+    /// Upload a single block.
+    fn upload_block(
+        &self,
+        block_id: BlockId,
+        data: Bytes,
+    ) -> impl std::future::Future<Output = azure_core::Result<()>> + Send + 'static {
+        let mut builder = self
+            .client
+            .put_block(block_id, data)
+            .context(self.context.clone());
+        #[cfg(feature = "client-side-encryption")]
+        if self.client.client_side_encryption().is_some() {
+            builder = builder.encrypted();
+        }
+        if let Some(checksum) = self.checksum {
+            builder = builder.checksum(checksum);
+        }
+        if let Some(lease_id) = self.lease_id {
+            builder = builder.lease_id(lease_id);
+        }
+        async move { builder.await.map(|_| ()) }
+    }
+
+    async fn commit(
+        &self,
+        block_list: BlockList,
+        metadata: Option<Metadata>,
+    ) -> azure_core::Result<PutBlockListResponse> {
+        let mut builder = self
+            .client
+            .put_block_list(block_list)
+            .context(self.context.clone());
+        #[cfg(feature = "client-side-encryption")]
+        if self.client.client_side_encryption().is_some() {
+            builder = builder.encrypted();
+        }
+        if let Some(content_type) = self.content_type.clone() {
+            builder = builder.content_type(content_type);
+        }
+        if let Some(content_encoding) = self.content_encoding.clone() {
+            builder = builder.content_encoding(content_encoding);
+        }
+        if let Some(content_language) = self.content_language.clone() {
+            builder = builder.content_language(content_language);
+        }
+        if let Some(content_disposition) = self.content_disposition.clone() {
+            builder = builder.content_disposition(content_disposition);
+        }
+        if let Some(metadata) = metadata {
+            builder = builder.metadata(metadata);
+        }
+        if let Some(access_tier) = self.access_tier {
+            builder = builder.access_tier(access_tier);
+        }
+        if let Some(tags) = self.tags.clone() {
+            builder = builder.tags(tags);
+        }
+        if let Some(lease_id) = self.lease_id {
+            builder = builder.lease_id(lease_id);
+        }
+        if let Some(if_modified_since) = self.if_modified_since {
+            builder = builder.if_modified_since(if_modified_since);
+        }
+        if let Some(if_match) = self.if_match.clone() {
+            builder = builder.if_match(if_match);
+        }
+        if let Some(if_tags) = self.if_tags.clone() {
+            builder = builder.if_tags(if_tags);
+        }
+        builder.await
+    }
+}
+
+type BoxedReader = Box<dyn AsyncRead + Send + Unpin>;
+
+/// The content of a blob uploaded with `BlobClient::upload`.
+#[derive(Clone)]
+pub(crate) enum UploadSource {
+    Body(Body),
+    /// A reader can only be consumed once, by the first upload of the builder.
+    Reader(Arc<Mutex<Option<BoxedReader>>>),
+}
+
+impl UploadSource {
+    pub(crate) fn reader(reader: impl AsyncRead + Send + Unpin + 'static) -> Self {
+        Self::Reader(Arc::new(Mutex::new(Some(Box::new(reader)))))
+    }
+
+    fn len(&self) -> Option<u64> {
+        match self {
+            Self::Body(body) => Some(body.len() as u64),
+            Self::Reader(_) => None,
+        }
+    }
+
+    async fn take(&self) -> azure_core::Result<ChunkSource> {
+        match self {
+            Self::Body(Body::Bytes(bytes)) => Ok(ChunkSource::Bytes(bytes.clone())),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Body(Body::SeekableStream(stream)) => {
+                let mut stream = stream.clone();
+                stream.reset().await?;
+                Ok(ChunkSource::Reader(Box::new(stream)))
+            }
+            Self::Reader(reader) => reader
+                .lock()
+                .map_err(|_| Error::message(ErrorKind::Other, "upload reader lock poisoned"))?
+                .take()
+                .map(ChunkSource::Reader)
+                .ok_or_else(|| {
+                    Error::message(ErrorKind::Other, "upload reader has already been consumed")
+                }),
+        }
+    }
+}
+
+impl fmt::Debug for UploadSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Body(body) => f.debug_tuple("Body").field(body).finish(),
+            Self::Reader(_) => f.write_str("Reader"),
+        }
+    }
+}
+
+enum ChunkSource {
+    Bytes(Bytes),
+    Reader(BoxedReader),
+}
+
+/// Splits an upload source into chunks.
+struct Chunker {
+    source: ChunkSource,
+    /// Data read from the source that has not been returned yet.
+    buffered: Bytes,
+}
+
+impl Chunker {
+    fn new(source: ChunkSource) -> Self {
+        Self {
+            source,
+            buffered: Bytes::new(),
+        }
+    }
+
+    /// Return the next `size` bytes, or fewer at the end of the source.
+    async fn next_chunk(&mut self, size: usize) -> azure_core::Result<Bytes> {
+        if self.buffered.len() >= size {
+            return Ok(self.buffered.split_to(size));
+        }
+        match &mut self.source {
+            ChunkSource::Bytes(bytes) if self.buffered.is_empty() => {
+                Ok(bytes.split_to(min(size, bytes.len())))
+            }
+            ChunkSource::Bytes(bytes) => {
+                let mut chunk = BytesMut::with_capacity(size);
+                chunk.extend_from_slice(&self.buffered);
+                let rest = bytes.split_to(min(size - self.buffered.len(), bytes.len()));
+                chunk.extend_from_slice(&rest);
+                self.buffered.clear();
+                Ok(chunk.freeze())
+            }
+            ChunkSource::Reader(reader) => {
+                let mut chunk = vec![0; size];
+                let mut filled = self.buffered.len();
+                chunk[..filled].copy_from_slice(&self.buffered);
+                self.buffered.clear();
+                while filled < size {
+                    let read = reader.read(&mut chunk[filled..]).await.map_err(|e| {
+                        Error::full(ErrorKind::Io, e, "unable to read from upload source")
+                    })?;
+                    if read == 0 {
+                        break;
+                    }
+                    filled += read;
+                }
+                chunk.truncate(filled);
+                Ok(chunk.into())
+            }
+        }
+    }
+
+    /// Return `chunk` again, ahead of the rest of the source.
+    fn unread(&mut self, chunk: Bytes) {
+        debug_assert!(self.buffered.is_empty());
+        self.buffered = chunk;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UploadBlobResponse {
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub content_md5: Option<ConsistencyMD5>,
+    pub request_id: RequestId,
+    pub date: OffsetDateTime,
+    pub request_server_encrypted: bool,
+    /// Number of blocks the blob was committed from, zero when it was uploaded with a single request.
+    pub block_count: usize,
+}
+
+impl UploadBlobResponse {
+    fn from_block_list(response: PutBlockListResponse, block_count: usize) -> Self {
+        Self {
+            etag: response.etag,
+            last_modified: response.last_modified,
+            content_md5: response.content_md5,
+            request_id: response.request_id,
+            date: response.date,
+            request_server_encrypted: response.request_server_encrypted,
+            block_count,
+        }
+    }
+}
+
+impl From<PutBlockBlobResponse> for UploadBlobResponse {
+    fn from(response: PutBlockBlobResponse) -> Self {
+        Self {
+            etag: response.etag,
+            last_modified: response.last_modified,
+            content_md5: response.content_md5,
+            request_id: response.request_id,
+            date: response.date,
+            request_server_encrypted: response.request_server_encrypted,
+            block_count: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builder() -> UploadBlobBuilder {
+        ClientBuilder::emulator()
+            .blob_client("container", "blob")
+            .upload(Vec::new())
+    }
+
+    #[test]
+    fn test_block_size() -> azure_core::Result<()> {
+        let builder = builder();
+        assert_eq!(builder.pick_block_size(None)?, DEFAULT_BLOCK_SIZE);
+        assert_eq!(builder.pick_block_size(Some(10))?, DEFAULT_BLOCK_SIZE);
+        // 1 TiB needs blocks of at least 21 MiB
+        assert_eq!(builder.pick_block_size(Some(1024 * 1024 * MIB))?, 21 * MIB);
+        assert!(builder
+            .pick_block_size(Some(MAX_BLOCKS * MAX_BLOCK_SIZE + 1))
+            .is_err());
+
+        let builder = builder.block_size(MIB);
+        assert_eq!(builder.pick_block_size(Some(10 * MIB))?, MIB);
+        assert!(builder.pick_block_size(Some(MAX_BLOCKS * MIB + 1)).is_err());
+        assert!(builder.block_size(0u64).pick_block_size(None).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chunk_bytes() -> azure_core::Result<()> {
+        let mut chunker = Chunker::new(ChunkSource::Bytes(Bytes::from_static(b"0123456789")));
+        let head = chunker.next_chunk(6).await?;
+        assert_eq!(head, "012345");
+        chunker.unread(head);
+        assert_eq!(chunker.next_chunk(4).await?, "0123");
+        assert_eq!(chunker.next_chunk(4).await?, "4567");
+        assert_eq!(chunker.next_chunk(4).await?, "89");
+        assert!(chunker.next_chunk(4).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chunk_reader() -> azure_core::Result<()> {
+        let source = UploadSource::reader(futures::io::Cursor::new(b"0123456789".to_vec()));
+        assert_eq!(source.len(), None);
+        let mut chunker = Chunker::new(source.take().await?);
+        assert!(source.take().await.is_err(), "reader consumed");
+
+        let head = chunker.next_chunk(3).await?;
+        chunker.unread(head);
+        assert_eq!(chunker.next_chunk(4).await?, "0123");
+        assert_eq!(chunker.next_chunk(4).await?, "4567");
+        assert_eq!(chunker.next_chunk(4).await?, "89");
+        assert!(chunker.next_chunk(4).await?.is_empty());
+        Ok(())
+    }
+
+    /// Accepts blocks and block lists, recording the kind of every request.
+    #[cfg(feature = "client-side-encryption")]
+    #[derive(Debug, Default)]
+    struct MockBlockBlob {
+        requests: Mutex<Vec<String>>,
+    }
+
+    #[cfg(feature = "client-side-encryption")]
+    #[async_trait::async_trait]
+    impl azure_core::Policy for MockBlockBlob {
+        async fn send(
+            &self,
+            _ctx: &azure_core::Context,
+            request: &mut azure_core::Request,
+            _next: &[Arc<dyn azure_core::Policy>],
+        ) -> azure_core::PolicyResult {
+            use azure_core::headers::*;
+
+            let comp = request
+                .url()
+                .query_pairs()
+                .find(|(key, _)| key == "comp")
+                .map(|(_, value)| value.into_owned())
+                .unwrap_or_default();
+            self.requests.lock().unwrap().push(comp);
+
+            let mut headers = Headers::new();
+            headers.insert(REQUEST_ID, "00000000-0000-0000-0000-000000000000");
+            headers.insert(DATE, "Mon, 19 Oct 2026 00:00:00 GMT");
+            headers.insert(ETAG, "\"etag\"");
+            headers.insert(LAST_MODIFIED, "Mon, 19 Oct 2026 00:00:00 GMT");
+            headers.insert(REQUEST_SERVER_ENCRYPTED, "true");
+            Ok(azure_core::Response::new(
+                azure_core::StatusCode::Created,
+                headers,
+                Box::pin(azure_core::BytesStream::new_empty()),
+            ))
+        }
+    }
+
+    #[cfg(feature = "client-side-encryption")]
+    #[tokio::test]
+    async fn test_encrypted_blocks() -> azure_core::Result<()> {
+        use crate::encryption::{AesKeyEncryptionKey, ClientSideEncryption};
+
+        let mock = Arc::new(MockBlockBlob::default());
+        let client = ClientBuilder::emulator()
+            .retry(azure_core::RetryOptions::none())
+            .transport(azure_core::TransportOptions::new_custom_policy(
+                mock.clone(),
+            ))
+            .client_side_encryption(ClientSideEncryption::new(Arc::new(
+                AesKeyEncryptionKey::new("key1", [7; 32]),
+            )))
+            .blob_client("container", "blob");
+
+        // the blocks are encrypted by the upload, so the operations accept them
+        client
+            .upload(vec![0; 4 * MIB as usize + 1])
+            .block_size(1u64)
+            .single_upload_threshold(0u64)
+            .await?;
+        assert_eq!(
+            *mock.requests.lock().unwrap(),
+            ["block", "block", "blocklist"]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,330 @@
+use crate::prelude::*;
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+    Etag, StatusCode,
+};
+use bytes::Bytes;
+use futures::{
+    future::BoxFuture,
+    io::{AsyncRead, AsyncSeek, SeekFrom},
+    ready,
+    stream::StreamExt,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt, io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+const DEFAULT_BLOCK_SIZE: u64 = 4 * 1024 * 1024;
+const DEFAULT_READ_AHEAD: usize = 2;
+const DEFAULT_CACHE_BLOCKS: usize = 8;
+
+/// Read a blob with `AsyncRead` and `AsyncSeek`, downloading it in blocks as they are needed.
+///
+/// The ETag of the blob is read before the first read or seek from the end, and every block is
+/// then downloaded on the condition that the blob still has it, so the content read is consistent.
+/// If the blob is modified in between, reads fail with a `412 Precondition Failed` error.
+/// Blobs encrypted client-side are decrypted, and their length is the length of their plaintext.
+///
+/// While a block is read, the `read_ahead` following ones are downloaded concurrently. The last
+/// `cache_blocks` downloaded blocks are kept, so seeking back to them does not download them again.
+pub struct BlobReader {
+    client: BlobClient,
+    block_size: u64,
+    read_ahead: usize,
+    cache_blocks: usize,
+    lease_id: Option<LeaseId>,
+    blob_versioning: Option<BlobVersioning>,
+    position: u64,
+    properties: Option<(Etag, u64)>,
+    properties_request: Option<BoxFuture<'static, azure_core::Result<(Etag, u64)>>>,
+    in_flight: HashMap<u64, BoxFuture<'static, azure_core::Result<Bytes>>>,
+    cache: BlockCache,
+}
+
+impl BlobReader {
+    pub(crate) fn new(client: BlobClient) -> Self {
+        Self {
+            client,
+            block_size: DEFAULT_BLOCK_SIZE,
+            read_ahead: DEFAULT_READ_AHEAD,
+            cache_blocks: DEFAULT_CACHE_BLOCKS,
+            lease_id: None,
+            blob_versioning: None,
+            position: 0,
+            properties: None,
+            properties_request: None,
+            in_flight: HashMap::new(),
+            cache: BlockCache::default(),
+        }
+    }
+
+    setters! {
+        block_size: u64 => block_size.max(1),
+        read_ahead: usize => read_ahead,
+        cache_blocks: usize => cache_blocks,
+        lease_id: LeaseId => Some(lease_id),
+        blob_versioning: BlobVersioning => Some(blob_versioning),
+    }
+
+    /// The position of the next byte read.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// The ETag the reader is pinned to, once it has been read.
+    pub fn etag(&self) -> Option<&Etag> {
+        self.properties.as_ref().map(|(etag, _)| etag)
+    }
+
+    /// Read the ETag and length of the blob, once.
+    fn poll_properties(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<(Etag, u64)>> {
+        if let Some(properties) = &self.properties {
+            return Poll::Ready(Ok(properties.clone()));
+        }
+        let request = self.properties_request.get_or_insert_with(|| {
+            let mut get_properties = self.client.get_properties();
+            if let Some(lease_id) = self.lease_id {
+                get_properties = get_properties.lease_id(lease_id);
+            }
+            if let Some(blob_versioning) = self.blob_versioning.clone() {
+                get_properties = get_properties.blob_versioning(blob_versioning);
+            }
+            let client = self.client.clone();
+            Box::pin(async move {
+                let blob = get_properties.await?.blob;
+                let length = client.content_length(&blob)?;
+                Ok((blob.properties.etag, length))
+            })
+        });
+        let properties = ready!(request.as_mut().poll(cx));
+        self.properties_request = None;
+        let properties = properties.map_err(into_io_error)?;
+        self.properties = Some(properties.clone());
+        Poll::Ready(Ok(properties))
+    }
+
+    /// Start downloading the block at `index` and the following ones, and cancel the downloads of
+    /// blocks outside of that window.
+    fn schedule(&mut self, index: u64, etag: &Etag, length: u64) {
+        let last = (length - 1) / self.block_size;
+        let window = index..=last.min(index + self.read_ahead as u64);
+        self.in_flight.retain(|index, _| window.contains(index));
+        for index in window {
+            if !self.cache.contains(index) && !self.in_flight.contains_key(&index) {
+                let fetch = self.fetch(index, etag, length);
+                self.in_flight.insert(index, fetch);
+            }
+        }
+    }
+
+    fn fetch(
+        &self,
+        index: u64,
+        etag: &Etag,
+        length: u64,
+    ) -> BoxFuture<'static, azure_core::Result<Bytes>> {
+        let start = index * self.block_size;
+        let end = length.min(start + self.block_size);
+        let mut get = self
+            .client
+            .get()
+            .range(start..end)
+            .chunk_size(end - start)
+            .if_match(IfMatchCondition::Match(etag.to_string()));
+        if let Some(lease_id) = self.lease_id {
+            get = get.lease_id(lease_id);
+        }
+        if let Some(blob_versioning) = self.blob_versioning.clone() {
+            get = get.blob_versioning(blob_versioning);
+        }
+
+        let etag = etag.clone();
+        Box::pin(async move {
+            let response = match get.into_stream().next().await {
+                Some(Ok(response)) => response,
+                Some(Err(err)) => {
+                    return Err(match err.kind() {
+                        ErrorKind::HttpResponse {
+                            status: StatusCode::PreconditionFailed,
+                            ..
+                        } => changed_error(&etag),
+                        _ => err,
+                    })
+                }
+                None => {
+                    return Err(Error::message(
+                        ErrorKind::Other,
+                        "getting the blob returned no response",
+                    ))
+                }
+            };
+            if response.blob.properties.etag != etag {
+                return Err(changed_error(&etag));
+            }
+            response.data.collect().await
+        })
+    }
+
+    /// Poll the downloads in flight, moving the completed blocks to the cache.
+    fn poll_in_flight(&mut self, cx: &mut Context<'_>) -> io::Result<()> {
+        let mut completed = Vec::new();
+        for (index, fetch) in &mut self.in_flight {
+            if let Poll::Ready(result) = fetch.as_mut().poll(cx) {
+                completed.push((*index, result));
+            }
+        }
+        let capacity = self.cache_blocks.max(self.read_ahead + 1);
+        for (index, result) in completed {
+            self.in_flight.remove(&index);
+            let data = result.map_err(into_io_error)?;
+            self.cache.insert(index, data, capacity);
+        }
+        Ok(())
+    }
+}
+
+impl AsyncRead for BlobReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let (etag, length) = ready!(this.poll_properties(cx))?;
+        if buf.is_empty() || this.position >= length {
+            return Poll::Ready(Ok(0));
+        }
+
+        let index = this.position / this.block_size;
+        this.schedule(index, &etag, length);
+        this.poll_in_flight(cx)?;
+
+        let Some(block) = this.cache.get(index) else {
+            return Poll::Pending;
+        };
+        let offset = (this.position - index * this.block_size) as usize;
+        if offset >= block.len() {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "blob block is shorter than expected",
+            )));
+        }
+        let read = buf.len().min(block.len() - offset);
+        buf[..read].copy_from_slice(&block[offset..offset + read]);
+        this.position += read as u64;
+        Poll::Ready(Ok(read))
+    }
+}
+
+impl AsyncSeek for BlobReader {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+        let position = match pos {
+            SeekFrom::Start(position) => Some(position),
+            SeekFrom::Current(offset) => this.position.checked_add_signed(offset),
+            SeekFrom::End(offset) => {
+                let (_, length) = ready!(this.poll_properties(cx))?;
+                length.checked_add_signed(offset)
+            }
+        };
+        let Some(position) = position else {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )));
+        };
+        this.position = position;
+        Poll::Ready(Ok(position))
+    }
+}
+
+impl fmt::Debug for BlobReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlobReader")
+            .field("client", &self.client)
+            .field("block_size", &self.block_size)
+            .field("read_ahead", &self.read_ahead)
+            .field("cache_blocks", &self.cache_blocks)
+            .field("position", &self.position)
+            .field("properties", &self.properties)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The blocks most recently downloaded, least recently used first.
+#[derive(Debug, Default)]
+struct BlockCache {
+    blocks: VecDeque<(u64, Bytes)>,
+}
+
+impl BlockCache {
+    fn contains(&self, index: u64) -> bool {
+        self.blocks.iter().any(|(i, _)| *i == index)
+    }
+
+    fn get(&mut self, index: u64) -> Option<Bytes> {
+        let position = self.blocks.iter().position(|(i, _)| *i == index)?;
+        let block = self.blocks.remove(position)?;
+        let data = block.1.clone();
+        self.blocks.push_back(block);
+        Some(data)
+    }
+
+    fn insert(&mut self, index: u64, data: Bytes, capacity: usize) {
+        self.blocks.retain(|(i, _)| *i != index);
+        self.blocks.push_back((index, data));
+        while self.blocks.len() > capacity {
+            self.blocks.pop_front();
+        }
+    }
+}
+
+fn changed_error(etag: &Etag) -> Error {
+    Error::with_message(
+        ErrorKind::http_response(
+            StatusCode::PreconditionFailed,
+            Some("ConditionNotMet".to_owned()),
+        ),
+        || format!("the blob was modified while being read, it no longer has ETag {etag}"),
+    )
+}
+
+fn into_io_error(err: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{executor::block_on, io::AsyncSeekExt};
+
+    #[test]
+    fn block_cache_evicts_least_recently_used() {
+        let mut cache = BlockCache::default();
+        cache.insert(0, Bytes::from_static(b"a"), 2);
+        cache.insert(1, Bytes::from_static(b"b"), 2);
+        assert_eq!(cache.get(0), Some(Bytes::from_static(b"a")));
+        cache.insert(2, Bytes::from_static(b"c"), 2);
+        assert!(cache.contains(0));
+        assert!(!cache.contains(1));
+        assert!(cache.contains(2));
+    }
+
+    #[test]
+    fn seek_without_request() {
+        let client = ClientBuilder::emulator().blob_client("container", "blob");
+        let mut reader = client.reader();
+        assert_eq!(block_on(reader.seek(SeekFrom::Start(10))).unwrap(), 10);
+        assert_eq!(block_on(reader.seek(SeekFrom::Current(-4))).unwrap(), 6);
+        assert!(block_on(reader.seek(SeekFrom::Current(-7))).is_err());
+        assert_eq!(reader.position(), 6);
+    }
+}
@@ -0,0 +1,784 @@
+use crate::prelude::*;
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+};
+use bytes::{Bytes, BytesMut};
+use futures::{
+    future::{self, BoxFuture},
+    io::AsyncWrite,
+    ready,
+    stream::{FuturesUnordered, StreamExt},
+    FutureExt,
+};
+use std::{
+    fmt, io, mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use uuid::Uuid;
+
+const MIB: usize = 1024 * 1024;
+const DEFAULT_BLOCK_SIZE: usize = 4 * MIB;
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+/// Largest block accepted by Put Block.
+const MAX_BLOCK_SIZE: usize = 4000 * MIB;
+/// Largest number of blocks a block blob can be made of.
+const MAX_BLOCKS: usize = 50_000;
+/// Largest block accepted by Append Block.
+const MAX_APPEND_SIZE: usize = 4 * MIB;
+/// Largest range accepted by Put Page.
+const MAX_PAGE_WRITE_SIZE: usize = 4 * MIB;
+const PAGE_SIZE: usize = 512;
+
+/// Write a block blob with `AsyncWrite`.
+///
+/// The data written is staged in blocks of `block_size` bytes, up to `max_concurrency` of them
+/// uploaded at once, and committed when the writer is closed. The blob is left unchanged if the
+/// writer is dropped before being closed.
+///
+/// Client-side encryption is not supported: writing fails when the client encrypts blobs, use
+/// [`BlobClient::upload_reader`] instead.
+#[derive(Debug)]
+pub struct BlockBlobWriter {
+    client: BlobClient,
+    block_size: usize,
+    max_concurrency: usize,
+    checksum: Option<ChecksumAlgorithm>,
+    content_type: Option<BlobContentType>,
+    metadata: Option<Metadata>,
+    access_tier: Option<AccessTier>,
+    tags: Option<Tags>,
+    lease_id: Option<LeaseId>,
+    upload_id: Uuid,
+    block_list: BlockList,
+    state: WriterState,
+}
+
+/// Write an append blob with `AsyncWrite`.
+///
+/// The data written is appended in blocks of up to `block_size` bytes, one at a time and each on the
+/// condition that the blob has the length expected, so that the blob is never written out of order.
+/// Flushing the writer appends the data buffered.
+///
+/// The blob is created, or replaced if it exists, unless `create` is set to false, in which case the
+/// data is appended to the existing blob.
+///
+/// Client-side encryption is not supported: writing fails when the client encrypts blobs.
+#[derive(Debug)]
+pub struct AppendBlobWriter {
+    client: BlobClient,
+    block_size: usize,
+    create: bool,
+    checksum: Option<ChecksumAlgorithm>,
+    content_type: Option<BlobContentType>,
+    metadata: Option<Metadata>,
+    tags: Option<Tags>,
+    lease_id: Option<LeaseId>,
+    append_position: u64,
+    state: WriterState,
+}
+
+/// Write a page blob of a given length with `AsyncWrite`, from its start.
+///
+/// The data written is uploaded in ranges of `block_size` bytes, up to `max_concurrency` of them at
+/// once. Flushing the writer uploads the whole pages buffered, and closing it pads the last page
+/// with zeros.
+///
+/// The blob is created, or replaced if it exists, unless `create` is set to false, in which case the
+/// pages of the existing blob are overwritten.
+///
+/// Client-side encryption is not supported: writing fails when the client encrypts blobs.
+#[derive(Debug)]
+pub struct PageBlobWriter {
+    client: BlobClient,
+    length: u128,
+    block_size: usize,
+    max_concurrency: usize,
+    create: bool,
+    checksum: Option<ChecksumAlgorithm>,
+    content_type: Option<BlobContentType>,
+    metadata: Option<Metadata>,
+    tags: Option<Tags>,
+    lease_id: Option<LeaseId>,
+    offset: u64,
+    state: WriterState,
+}
+
+impl BlockBlobWriter {
+    pub(crate) fn new(client: BlobClient) -> Self {
+        Self {
+            client,
+            block_size: DEFAULT_BLOCK_SIZE,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            checksum: None,
+            content_type: None,
+            metadata: None,
+            access_tier: None,
+            tags: None,
+            lease_id: None,
+            upload_id: Uuid::new_v4(),
+            block_list: BlockList::default(),
+            state: WriterState::default(),
+        }
+    }
+
+    setters! {
+        block_size: usize => block_size.clamp(1, MAX_BLOCK_SIZE),
+        max_concurrency: usize => max_concurrency.max(1),
+        checksum: ChecksumAlgorithm => Some(checksum),
+        content_type: BlobContentType => Some(content_type),
+        metadata: Metadata => Some(metadata),
+        access_tier: AccessTier => Some(access_tier),
+        tags: Tags => Some(tags),
+        lease_id: LeaseId => Some(lease_id),
+    }
+}
+
+impl AppendBlobWriter {
+    pub(crate) fn new(client: BlobClient) -> Self {
+        Self {
+            client,
+            block_size: MAX_APPEND_SIZE,
+            create: true,
+            checksum: None,
+            content_type: None,
+            metadata: None,
+            tags: None,
+            lease_id: None,
+            append_position: 0,
+            state: WriterState::default(),
+        }
+    }
+
+    setters! {
+        block_size: usize => block_size.clamp(1, MAX_APPEND_SIZE),
+        create: bool => create,
+        checksum: ChecksumAlgorithm => Some(checksum),
+        content_type: BlobContentType => Some(content_type),
+        metadata: Metadata => Some(metadata),
+        tags: Tags => Some(tags),
+        lease_id: LeaseId => Some(lease_id),
+    }
+}
+
+impl PageBlobWriter {
+    pub(crate) fn new(client: BlobClient, length: u128) -> Self {
+        Self {
+            client,
+            length,
+            block_size: MAX_PAGE_WRITE_SIZE,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            create: true,
+            checksum: None,
+            content_type: None,
+            metadata: None,
+            tags: None,
+            lease_id: None,
+            offset: 0,
+            state: WriterState::default(),
+        }
+    }
+
+    setters! {
+        block_size: usize => (block_size / PAGE_SIZE * PAGE_SIZE).clamp(PAGE_SIZE, MAX_PAGE_WRITE_SIZE),
+        max_concurrency: usize => max_concurrency.max(1),
+        create: bool => create,
+        checksum: ChecksumAlgorithm => Some(checksum),
+        content_type: BlobContentType => Some(content_type),
+        metadata: Metadata => Some(metadata),
+        tags: Tags => Some(tags),
+        lease_id: LeaseId => Some(lease_id),
+    }
+}
+
+/// How the data buffered by a writer is uploaded to a blob.
+trait Target {
+    fn block_size(&self) -> usize;
+
+    fn max_concurrency(&self) -> usize;
+
+    /// The largest number of bytes that can be written.
+    fn max_length(&self) -> Option<u64> {
+        None
+    }
+
+    /// How many of the bytes buffered are uploaded when the writer is flushed.
+    fn flush_length(&self, buffered: usize) -> usize;
+
+    /// Create the blob before anything is uploaded, returning its length.
+    fn create(&mut self) -> Option<BoxFuture<'static, azure_core::Result<u64>>>;
+
+    fn created(&mut self, _length: u64) {}
+
+    fn upload(&mut self, data: Bytes) -> BoxFuture<'static, azure_core::Result<()>>;
+
+    /// The last partial block, uploaded when the writer is closed.
+    fn last_block(&self, data: BytesMut) -> Bytes {
+        data.freeze()
+    }
+
+    fn commit(&mut self) -> Option<BoxFuture<'static, azure_core::Result<()>>> {
+        None
+    }
+}
+
+impl Target for BlockBlobWriter {
+    fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    fn flush_length(&self, _buffered: usize) -> usize {
+        // the blocks are only visible once committed, so avoid staging small ones
+        0
+    }
+
+    fn create(&mut self) -> Option<BoxFuture<'static, azure_core::Result<u64>>> {
+        check_client_side_encryption(&self.client)
+    }
+
+    fn upload(&mut self, data: Bytes) -> BoxFuture<'static, azure_core::Result<()>> {
+        let index = self.block_list.blocks.len();
+        if index == MAX_BLOCKS {
+            let block_size = self.block_size;
+            return future::ready(Err(Error::with_message(ErrorKind::Other, || {
+                format!("blob exceeds {MAX_BLOCKS} blocks of {block_size} bytes")
+            })))
+            .boxed();
+        }
+        let block_id = BlockId::new(format!("{}-{index:05}", self.upload_id));
+        self.block_list
+            .blocks
+            .push(BlobBlockType::Uncommitted(block_id.clone()));
+
+        let mut put_block = self.client.put_block(block_id, data);
+        if let Some(checksum) = self.checksum {
+            put_block = put_block.checksum(checksum);
+        }
+        if let Some(lease_id) = self.lease_id {
+            put_block = put_block.lease_id(lease_id);
+        }
+        put_block.into_future().map(|r| r.map(|_| ())).boxed()
+    }
+
+    fn commit(&mut self) -> Option<BoxFuture<'static, azure_core::Result<()>>> {
+        let mut put_block_list = self.client.put_block_list(self.block_list.clone());
+        if let Some(content_type) = self.content_type.clone() {
+            put_block_list = put_block_list.content_type(content_type);
+        }
+        if let Some(metadata) = self.metadata.clone() {
+            put_block_list = put_block_list.metadata(metadata);
+        }
+        if let Some(access_tier) = self.access_tier {
+            put_block_list = put_block_list.access_tier(access_tier);
+        }
+        if let Some(tags) = self.tags.clone() {
+            put_block_list = put_block_list.tags(tags);
+        }
+        if let Some(lease_id) = self.lease_id {
+            put_block_list = put_block_list.lease_id(lease_id);
+        }
+        Some(put_block_list.into_future().map(|r| r.map(|_| ())).boxed())
+    }
+}
+
+impl Target for AppendBlobWriter {
+    fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    fn max_concurrency(&self) -> usize {
+        // each block is appended on the condition that the previous one was
+        1
+    }
+
+    fn flush_length(&self, buffered: usize) -> usize {
+        buffered
+    }
+
+    fn create(&mut self) -> Option<BoxFuture<'static, azure_core::Result<u64>>> {
+        if let Some(unsupported) = check_client_side_encryption(&self.client) {
+            return Some(unsupported);
+        }
+        if !self.create {
+            let mut get_properties = self.client.get_properties();
+            if let Some(lease_id) = self.lease_id {
+                get_properties = get_properties.lease_id(lease_id);
+            }
+            return Some(
+                get_properties
+                    .into_future()
+                    .map(|r| r.map(|response| response.blob.properties.content_length))
+                    .boxed(),
+            );
+        }
+
+        let mut put_append_blob = self.client.put_append_blob();
+        if let Some(content_type) = self.content_type.clone() {
+            put_append_blob = put_append_blob.content_type(content_type);
+        }
+        if let Some(metadata) = self.metadata.clone() {
+            put_append_blob = put_append_blob.metadata(metadata);
+        }
+        if let Some(tags) = self.tags.clone() {
+            put_append_blob = put_append_blob.tags(tags);
+        }
+        if let Some(lease_id) = self.lease_id {
+            put_append_blob = put_append_blob.lease_id(lease_id);
+        }
+        Some(put_append_blob.into_future().map(|r| r.map(|_| 0)).boxed())
+    }
+
+    fn created(&mut self, length: u64) {
+        self.append_position = length;
+    }
+
+    fn upload(&mut self, data: Bytes) -> BoxFuture<'static, azure_core::Result<()>> {
+        let position = self.append_position;
+        self.append_position += data.len() as u64;
+
+        let mut append_block = self
+            .client
+            .append_block(data)
+            .condition_append_position(ConditionAppendPosition::new(position));
+        if let Some(checksum) = self.checksum {
+            append_block = append_block.checksum(checksum);
+        }
+        if let Some(lease_id) = self.lease_id {
+            append_block = append_block.lease_id(lease_id);
+        }
+        append_block.into_future().map(|r| r.map(|_| ())).boxed()
+    }
+}
+
+impl Target for PageBlobWriter {
+    fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    fn max_length(&self) -> Option<u64> {
+        Some(self.length.try_into().unwrap_or(u64::MAX))
+    }
+
+    fn flush_length(&self, buffered: usize) -> usize {
+        buffered / PAGE_SIZE * PAGE_SIZE
+    }
+
+    fn create(&mut self) -> Option<BoxFuture<'static, azure_core::Result<u64>>> {
+        if let Some(unsupported) = check_client_side_encryption(&self.client) {
+            return Some(unsupported);
+        }
+        if !self.create {
+            return None;
+        }
+
+        let mut put_page_blob = self.client.put_page_blob(self.length);
+        if let Some(content_type) = self.content_type.clone() {
+            put_page_blob = put_page_blob.content_type(content_type);
+        }
+        if let Some(metadata) = self.metadata.clone() {
+            put_page_blob = put_page_blob.metadata(metadata);
+        }
+        if let Some(tags) = self.tags.clone() {
+            put_page_blob = put_page_blob.tags(tags);
+        }
+        if let Some(lease_id) = self.lease_id {
+            put_page_blob = put_page_blob.lease_id(lease_id);
+        }
+        Some(put_page_blob.into_future().map(|r| r.map(|_| 0)).boxed())
+    }
+
+    fn upload(&mut self, data: Bytes) -> BoxFuture<'static, azure_core::Result<()>> {
+        let start = self.offset;
+        self.offset += data.len() as u64;
+        let range = match BA512Range::new(start, self.offset - 1) {
+            Ok(range) => range,
+            Err(err) => return future::ready(Err(err)).boxed(),
+        };
+
+        let mut put_page = self.client.put_page(range, data);
+        if let Some(checksum) = self.checksum {
+            put_page = put_page.checksum(checksum);
+        }
+        if let Some(lease_id) = self.lease_id {
+            put_page = put_page.lease_id(lease_id);
+        }
+        put_page.into_future().map(|r| r.map(|_| ())).boxed()
+    }
+
+    fn last_block(&self, mut data: BytesMut) -> Bytes {
+        let padded = data.len().div_ceil(PAGE_SIZE) * PAGE_SIZE;
+        data.resize(padded, 0);
+        data.freeze()
+    }
+}
+
+/// The data buffered by a writer and the requests in flight.
+#[derive(Default)]
+struct WriterState {
+    buffer: BytesMut,
+    written: u64,
+    creating: Option<BoxFuture<'static, azure_core::Result<u64>>>,
+    created: bool,
+    in_flight: FuturesUnordered<BoxFuture<'static, azure_core::Result<()>>>,
+    committing: Option<BoxFuture<'static, azure_core::Result<()>>>,
+    closed: bool,
+    failed: bool,
+}
+
+impl WriterState {
+    fn poll_write(
+        &mut self,
+        cx: &mut Context<'_>,
+        target: &mut dyn Target,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.check()?;
+        ready!(self.poll_create(cx, target))?;
+        ready!(self.poll_upload(cx, target, false))?;
+
+        let remaining = target.max_length().map_or(u64::MAX, |max_length| {
+            max_length.saturating_sub(self.written)
+        });
+        let len = buf
+            .len()
+            .min(target.block_size() - self.buffer.len())
+            .min(remaining.try_into().unwrap_or(usize::MAX));
+        if len == 0 && !buf.is_empty() {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "the blob cannot hold more data",
+            )));
+        }
+        self.buffer.extend_from_slice(&buf[..len]);
+        self.written += len as u64;
+
+        // start uploading the buffer if it is full
+        if let Poll::Ready(Err(err)) = self.poll_upload(cx, target, false) {
+            return Poll::Ready(Err(err));
+        }
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_flush(
+        &mut self,
+        cx: &mut Context<'_>,
+        target: &mut dyn Target,
+    ) -> Poll<io::Result<()>> {
+        self.check()?;
+        ready!(self.poll_create(cx, target))?;
+        self.poll_upload(cx, target, true)
+    }
+
+    fn poll_close(
+        &mut self,
+        cx: &mut Context<'_>,
+        target: &mut dyn Target,
+    ) -> Poll<io::Result<()>> {
+        if self.closed {
+            return Poll::Ready(Ok(()));
+        }
+        self.check()?;
+        ready!(self.poll_create(cx, target))?;
+        ready!(self.poll_upload(cx, target, true))?;
+        if !self.buffer.is_empty() {
+            let data = target.last_block(self.buffer.split());
+            self.in_flight.push(target.upload(data));
+            ready!(self.poll_upload(cx, target, true))?;
+        }
+
+        if self.committing.is_none() {
+            self.committing = target.commit();
+        }
+        if let Some(committing) = &mut self.committing {
+            let result = ready!(committing.as_mut().poll(cx));
+            self.committing = None;
+            result.map_err(into_io_error)?;
+        }
+        self.closed = true;
+        Poll::Ready(Ok(()))
+    }
+
+    fn check(&self) -> io::Result<()> {
+        if self.failed {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "a previous upload to the blob failed",
+            ));
+        }
+        if self.closed {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "the blob writer is closed",
+            ));
+        }
+        Ok(())
+    }
+
+    fn poll_create(
+        &mut self,
+        cx: &mut Context<'_>,
+        target: &mut dyn Target,
+    ) -> Poll<io::Result<()>> {
+        if self.created {
+            return Poll::Ready(Ok(()));
+        }
+        if self.creating.is_none() {
+            self.creating = target.create();
+        }
+        if let Some(creating) = &mut self.creating {
+            let result = ready!(creating.as_mut().poll(cx));
+            self.creating = None;
+            target.created(result.map_err(into_io_error)?);
+        }
+        self.created = true;
+        Poll::Ready(Ok(()))
+    }
+
+    /// Upload the full blocks buffered, as far as the concurrency allows, and poll the uploads in
+    /// flight.
+    ///
+    /// Completes once the buffer has room for more data or, when flushing, once the data that can
+    /// be flushed has been uploaded.
+    fn poll_upload(
+        &mut self,
+        cx: &mut Context<'_>,
+        target: &mut dyn Target,
+        flush: bool,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            while let Poll::Ready(Some(result)) = self.in_flight.poll_next_unpin(cx) {
+                if let Err(err) = result {
+                    self.failed = true;
+                    return Poll::Ready(Err(into_io_error(err)));
+                }
+            }
+
+            let len = if self.buffer.len() >= target.block_size() {
+                target.block_size()
+            } else if flush {
+                target.flush_length(self.buffer.len())
+            } else {
+                0
+            };
+            if len == 0 {
+                if flush && !self.in_flight.is_empty() {
+                    return Poll::Pending;
+                }
+                return Poll::Ready(Ok(()));
+            }
+            if self.in_flight.len() >= target.max_concurrency() {
+                return Poll::Pending;
+            }
+            let data = self.buffer.split_to(len).freeze();
+            self.in_flight.push(target.upload(data));
+        }
+    }
+}
+
+impl fmt::Debug for WriterState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriterState")
+            .field("buffered", &self.buffer.len())
+            .field("written", &self.written)
+            .field("in_flight", &self.in_flight.len())
+            .field("closed", &self.closed)
+            .field("failed", &self.failed)
+            .finish_non_exhaustive()
+    }
+}
+
+macro_rules! impl_async_write {
+    ($writer:ty) => {
+        impl AsyncWrite for $writer {
+            fn poll_write(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                let this = self.get_mut();
+                let mut state = mem::take(&mut this.state);
+                let result = state.poll_write(cx, this, buf);
+                this.state = state;
+                result
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+                let this = self.get_mut();
+                let mut state = mem::take(&mut this.state);
+                let result = state.poll_flush(cx, this);
+                this.state = state;
+                result
+            }
+
+            fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+                let this = self.get_mut();
+                let mut state = mem::take(&mut this.state);
+                let result = state.poll_close(cx, this);
+                this.state = state;
+                result
+            }
+        }
+    };
+}
+
+impl_async_write!(BlockBlobWriter);
+impl_async_write!(AppendBlobWriter);
+impl_async_write!(PageBlobWriter);
+
+/// Fail the writes when the client encrypts blobs, as the blocks and pages uploaded by the writers
+/// are not encrypted.
+#[cfg_attr(
+    not(feature = "client-side-encryption"),
+    allow(unused_variables, clippy::unnecessary_wraps)
+)]
+fn check_client_side_encryption(
+    client: &BlobClient,
+) -> Option<BoxFuture<'static, azure_core::Result<u64>>> {
+    #[cfg(feature = "client-side-encryption")]
+    if client.client_side_encryption().is_some() {
+        return Some(
+            future::ready(Err(Error::message(
+                ErrorKind::Other,
+                "the blob writers do not support client-side encryption",
+            )))
+            .boxed(),
+        );
+    }
+    None
+}
+
+fn into_io_error(err: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::sync::{Arc, Mutex};
+
+    /// Record the uploads instead of sending them.
+    #[derive(Default)]
+    struct Recorder {
+        block_size: usize,
+        flush_pages: bool,
+        uploads: Arc<Mutex<Vec<Bytes>>>,
+        committed: bool,
+    }
+
+    impl Target for Recorder {
+        fn block_size(&self) -> usize {
+            self.block_size
+        }
+
+        fn max_concurrency(&self) -> usize {
+            2
+        }
+
+        fn flush_length(&self, buffered: usize) -> usize {
+            if self.flush_pages {
+                buffered / PAGE_SIZE * PAGE_SIZE
+            } else {
+                0
+            }
+        }
+
+        fn create(&mut self) -> Option<BoxFuture<'static, azure_core::Result<u64>>> {
+            None
+        }
+
+        fn upload(&mut self, data: Bytes) -> BoxFuture<'static, azure_core::Result<()>> {
+            self.uploads.lock().unwrap().push(data);
+            future::ready(Ok(())).boxed()
+        }
+
+        fn commit(&mut self) -> Option<BoxFuture<'static, azure_core::Result<()>>> {
+            self.committed = true;
+            None
+        }
+    }
+
+    #[test]
+    fn writes_in_blocks() {
+        let mut target = Recorder {
+            block_size: 4,
+            ..Recorder::default()
+        };
+        let mut state = WriterState::default();
+        block_on(future::poll_fn(|cx| {
+            let mut data: &[u8] = b"hello world";
+            while !data.is_empty() {
+                let written = ready!(state.poll_write(cx, &mut target, data))?;
+                data = &data[written..];
+            }
+            state.poll_close(cx, &mut target)
+        }))
+        .unwrap();
+
+        let uploads = target.uploads.lock().unwrap().clone();
+        assert_eq!(uploads, vec!["hell", "o wo", "rld"]);
+        assert!(target.committed);
+        assert!(block_on(future::poll_fn(|cx| state.poll_write(
+            cx,
+            &mut target,
+            b"!"
+        )))
+        .is_err());
+    }
+
+    #[test]
+    fn flush_uploads_whole_pages() {
+        let mut target = Recorder {
+            block_size: 1024,
+            flush_pages: true,
+            ..Recorder::default()
+        };
+        let mut state = WriterState::default();
+        block_on(future::poll_fn(|cx| {
+            ready!(state.poll_write(cx, &mut target, &[1; 700]))?;
+            state.poll_flush(cx, &mut target)
+        }))
+        .unwrap();
+
+        let uploads = target.uploads.lock().unwrap().clone();
+        assert_eq!(uploads.len(), 1);
+        assert_eq!(uploads[0].len(), PAGE_SIZE);
+        assert_eq!(state.buffer.len(), 700 - PAGE_SIZE);
+    }
+
+    #[test]
+    fn page_blob_pads_last_page() {
+        let client = ClientBuilder::emulator().blob_client("container", "blob");
+        let writer = client.page_blob_writer(1024).block_size(1000usize);
+        assert_eq!(writer.block_size, PAGE_SIZE);
+        let last = writer.last_block(BytesMut::from(&b"abc"[..]));
+        assert_eq!(last.len(), PAGE_SIZE);
+        assert_eq!(&last[..4], b"abc\0");
+    }
+
+    #[cfg(feature = "client-side-encryption")]
+    #[test]
+    fn rejects_client_side_encryption() {
+        use crate::encryption::{AesKeyEncryptionKey, ClientSideEncryption};
+        use futures::AsyncWriteExt;
+
+        let client = ClientBuilder::emulator()
+            .client_side_encryption(ClientSideEncryption::new(Arc::new(
+                AesKeyEncryptionKey::new("key1", [7; 32]),
+            )))
+            .blob_client("container", "blob");
+
+        assert!(block_on(client.block_blob_writer().write_all(b"hello")).is_err());
+        assert!(block_on(client.append_blob_writer().write_all(b"hello")).is_err());
+        assert!(block_on(client.page_blob_writer(1024).write_all(&[1; 512])).is_err());
+    }
+}
@@ -0,0 +1,578 @@
+//! Read the blob change feed of a storage account.
+//!
+//! The change feed records the changes made to the blobs of an account as Avro files in the
+//! `$blobchangefeed` container. They are grouped in hourly segments, each split in shards holding
+//! a sequence of chunk files. Events of a given blob are always in the same shard, in order.
+//!
+//! ```no_run
+//! # async fn example(service_client: azure_storage_blobs::prelude::BlobServiceClient) -> azure_core::Result<()> {
+//! use futures::StreamExt;
+//!
+//! let mut changes = service_client.change_feed().into_stream();
+//! while let Some(change) = changes.next().await {
+//!     let change = change?;
+//!     println!("{:?} {}", change.event.event_type, change.event.subject);
+//!     // store `change.cursor.to_string()` to resume after this event
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ref: <https://docs.microsoft.com/azure/storage/blobs/storage-blob-change-feed>
+
+use crate::{
+    avro::{AvroReader, Value},
+    prelude::*,
+};
+use azure_core::{
+    date,
+    error::{Error, ErrorKind, ResultExt},
+};
+use futures::stream::{self, BoxStream, StreamExt};
+use std::{collections::HashMap, collections::VecDeque, fmt, str::FromStr};
+use time::{Date, Duration, Month, OffsetDateTime, Time};
+
+/// The container holding the change feed.
+pub const CHANGE_FEED_CONTAINER: &str = "$blobchangefeed";
+
+const SEGMENTS_METADATA: &str = "meta/segments.json";
+const SEGMENTS_PREFIX: &str = "idx/segments/";
+
+operation! {
+    #[stream]
+    /// Read the events of the change feed, oldest segment first.
+    ///
+    /// Only the segments the service has finalized are read. The time window selects whole hourly
+    /// segments: `start_time` is rounded down and `end_time` up to the hour, so events slightly
+    /// outside of the window may be returned. A `cursor` resumes reading right after the event
+    /// it was returned with, and takes precedence over `start_time`.
+    ChangeFeed,
+    client: BlobServiceClient,
+    ?start_time: OffsetDateTime,
+    ?end_time: OffsetDateTime,
+    ?cursor: ChangeFeedCursor
+}
+
+/// An event of the change feed, with the cursor to resume reading after it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeFeedEntry {
+    pub event: ChangeFeedEvent,
+    pub cursor: ChangeFeedCursor,
+}
+
+/// A change to a blob.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeFeedEvent {
+    pub id: String,
+    pub event_type: ChangeFeedEventType,
+    pub event_time: OffsetDateTime,
+    /// The storage account the event happened in.
+    pub topic: String,
+    /// The blob the event is about, as `/blobServices/default/containers/<container>/blobs/<blob>`.
+    pub subject: String,
+    pub schema_version: i64,
+    pub data: ChangeFeedEventData,
+}
+
+impl ChangeFeedEvent {
+    /// The container and blob names the event is about.
+    pub fn container_and_blob(&self) -> Option<(&str, &str)> {
+        let path = self
+            .subject
+            .strip_prefix("/blobServices/default/containers/")?;
+        path.split_once("/blobs/")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeFeedEventType {
+    BlobCreated,
+    BlobDeleted,
+    BlobPropertiesUpdated,
+    BlobSnapshotCreated,
+    BlobTierChanged,
+    BlobAsyncOperationInitiated,
+    RestorePointMarkerCreated,
+    /// An event type this version of the crate does not know about.
+    Other(String),
+}
+
+impl From<&str> for ChangeFeedEventType {
+    fn from(event_type: &str) -> Self {
+        match event_type {
+            "BlobCreated" => Self::BlobCreated,
+            "BlobDeleted" => Self::BlobDeleted,
+            "BlobPropertiesUpdated" => Self::BlobPropertiesUpdated,
+            "BlobSnapshotCreated" => Self::BlobSnapshotCreated,
+            "BlobTierChanged" => Self::BlobTierChanged,
+            "BlobAsyncOperationInitiated" => Self::BlobAsyncOperationInitiated,
+            "RestorePointMarkerCreated" => Self::RestorePointMarkerCreated,
+            other => Self::Other(other.to_owned()),
+        }
+    }
+}
+
+/// The details of a change. Which fields are set depends on the event and schema version.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ChangeFeedEventData {
+    /// The operation that triggered the event, such as `PutBlob` or `SetBlobMetadata`.
+    pub api: String,
+    pub client_request_id: Option<String>,
+    pub request_id: Option<String>,
+    pub etag: Option<String>,
+    pub content_type: Option<String>,
+    pub content_length: Option<i64>,
+    pub content_offset: Option<i64>,
+    pub blob_type: Option<String>,
+    pub blob_version: Option<String>,
+    pub container_version: Option<String>,
+    pub blob_access_tier: Option<String>,
+    pub url: Option<String>,
+    pub source_url: Option<String>,
+    pub destination_url: Option<String>,
+    pub recursive: Option<bool>,
+    pub sequencer: Option<String>,
+    pub snapshot: Option<String>,
+    /// The state of the blob before the change.
+    pub previous_info: HashMap<String, String>,
+}
+
+/// The position in the change feed, to resume reading where a previous reader left off.
+///
+/// Cursors can be stored with serde or as the string returned by `to_string`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeFeedCursor {
+    segment_path: String,
+    shard_index: usize,
+    chunk_path: String,
+    /// The number of events of the chunk already read.
+    event_index: usize,
+}
+
+impl fmt::Display for ChangeFeedCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+impl FromStr for ChangeFeedCursor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        serde_json::from_str(s).context(ErrorKind::DataConversion, "invalid change feed cursor")
+    }
+}
+
+impl ChangeFeedBuilder {
+    pub fn into_stream(self) -> BoxStream<'static, azure_core::Result<ChangeFeedEntry>> {
+        let reader = ChangeFeedReader {
+            client: self.client.container_client(CHANGE_FEED_CONTAINER),
+            start_time: self.start_time,
+            end_time: self.end_time,
+            resume: self.cursor,
+            initialized: false,
+            failed: false,
+            segments: VecDeque::new(),
+            segment_path: String::new(),
+            shards: VecDeque::new(),
+            shard_index: 0,
+            chunks: VecDeque::new(),
+            chunk_path: String::new(),
+            events: VecDeque::new(),
+            event_index: 0,
+        };
+
+        stream::unfold(reader, |mut reader| async move {
+            if reader.failed {
+                return None;
+            }
+            match reader.next().await {
+                Ok(entry) => entry.map(|entry| (Ok(entry), reader)),
+                Err(err) => {
+                    reader.failed = true;
+                    Some((Err(err), reader))
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+struct ChangeFeedReader {
+    client: ContainerClient,
+    start_time: Option<OffsetDateTime>,
+    end_time: Option<OffsetDateTime>,
+    resume: Option<ChangeFeedCursor>,
+    initialized: bool,
+    failed: bool,
+    segments: VecDeque<String>,
+    segment_path: String,
+    shards: VecDeque<(usize, String)>,
+    shard_index: usize,
+    chunks: VecDeque<String>,
+    chunk_path: String,
+    events: VecDeque<ChangeFeedEvent>,
+    event_index: usize,
+}
+
+impl ChangeFeedReader {
+    async fn next(&mut self) -> azure_core::Result<Option<ChangeFeedEntry>> {
+        if !self.initialized {
+            self.segments = self.list_segments().await?;
+            self.initialized = true;
+        }
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                self.event_index += 1;
+                let cursor = ChangeFeedCursor {
+                    segment_path: self.segment_path.clone(),
+                    shard_index: self.shard_index,
+                    chunk_path: self.chunk_path.clone(),
+                    event_index: self.event_index,
+                };
+                return Ok(Some(ChangeFeedEntry { event, cursor }));
+            }
+            if let Some(chunk_path) = self.chunks.pop_front() {
+                let skip = match self.resume.take() {
+                    Some(resume) if resume.chunk_path == chunk_path => resume.event_index,
+                    _ => 0,
+                };
+                self.events = self.read_chunk(&chunk_path).await?;
+                self.events.drain(..skip.min(self.events.len()));
+                self.chunk_path = chunk_path;
+                self.event_index = skip;
+                continue;
+            }
+            if let Some((shard_index, shard_path)) = self.shards.pop_front() {
+                let mut chunks = self.list_chunks(&shard_path).await?;
+                if let Some(resume) = &self.resume {
+                    if resume.shard_index == shard_index {
+                        chunks.retain(|chunk| *chunk >= resume.chunk_path);
+                    }
+                }
+                self.chunks = chunks;
+                self.shard_index = shard_index;
+                continue;
+            }
+            if let Some(segment_path) = self.segments.pop_front() {
+                let mut shards = self.read_segment(&segment_path).await?;
+                match &self.resume {
+                    Some(resume) if resume.segment_path == segment_path => {
+                        shards.retain(|(index, _)| *index >= resume.shard_index);
+                    }
+                    _ => self.resume = None,
+                }
+                self.shards = shards;
+                self.segment_path = segment_path;
+                continue;
+            }
+            return Ok(None);
+        }
+    }
+
+    /// List the paths of the finalized segment manifests in the time window.
+    async fn list_segments(&self) -> azure_core::Result<VecDeque<String>> {
+        let metadata = self
+            .client
+            .blob_client(SEGMENTS_METADATA)
+            .get_content()
+            .await?;
+        let metadata: SegmentsMetadata = serde_json::from_slice(&metadata)
+            .context(ErrorKind::DataConversion, "invalid change feed metadata")?;
+        let last_consumable = date::parse_rfc3339(&metadata.last_consumable)?;
+
+        let mut end = floor_hour(last_consumable);
+        if let Some(end_time) = self.end_time {
+            end = end.min(ceil_hour(end_time));
+        }
+        let start = self.start_time.map(floor_hour);
+
+        let mut segments = VecDeque::new();
+        let mut pages = self
+            .client
+            .list_blobs()
+            .prefix(SEGMENTS_PREFIX)
+            .into_stream();
+        while let Some(page) = pages.next().await {
+            for blob in page?.blobs.blobs() {
+                let Some(time) = segment_time(&blob.name) else {
+                    continue;
+                };
+                let after_resume = match &self.resume {
+                    Some(resume) => blob.name >= resume.segment_path,
+                    None => start.map_or(true, |start| time >= start),
+                };
+                if after_resume && time < end {
+                    segments.push_back(blob.name.clone());
+                }
+            }
+        }
+        Ok(segments)
+    }
+
+    /// Read the shard paths of a segment manifest.
+    async fn read_segment(
+        &self,
+        segment_path: &str,
+    ) -> azure_core::Result<VecDeque<(usize, String)>> {
+        let manifest = self.client.blob_client(segment_path).get_content().await?;
+        let manifest: SegmentManifest = serde_json::from_slice(&manifest)
+            .context(ErrorKind::DataConversion, "invalid change feed segment")?;
+        let container_prefix = format!("{CHANGE_FEED_CONTAINER}/");
+        Ok(manifest
+            .chunk_file_paths
+            .into_iter()
+            .map(|path| match path.strip_prefix(&container_prefix) {
+                Some(path) => path.to_owned(),
+                None => path,
+            })
+            .enumerate()
+            .collect())
+    }
+
+    async fn list_chunks(&self, shard_path: &str) -> azure_core::Result<VecDeque<String>> {
+        let mut chunks = Vec::new();
+        let mut pages = self
+            .client
+            .list_blobs()
+            .prefix(shard_path.to_owned())
+            .into_stream();
+        while let Some(page) = pages.next().await {
+            chunks.extend(page?.blobs.blobs().map(|blob| blob.name.clone()));
+        }
+        chunks.sort();
+        Ok(chunks.into())
+    }
+
+    async fn read_chunk(&self, chunk_path: &str) -> azure_core::Result<VecDeque<ChangeFeedEvent>> {
+        let mut reader = AvroReader::new();
+        let mut events = VecDeque::new();
+        let mut pages = self.client.blob_client(chunk_path).get().into_stream();
+        while let Some(page) = pages.next().await {
+            let mut data = page?.data;
+            while let Some(data) = data.next().await {
+                reader.push(&data?);
+                while let Some(value) = reader.next()? {
+                    events.push_back(ChangeFeedEvent::try_from(value)?);
+                }
+            }
+        }
+        if !reader.is_empty() {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("change feed chunk {chunk_path} ends with a partial block")
+            }));
+        }
+        Ok(events)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentsMetadata {
+    last_consumable: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentManifest {
+    chunk_file_paths: Vec<String>,
+}
+
+/// Parse the time of a segment from the path of its manifest, `idx/segments/YYYY/MM/DD/hhmm/meta.json`.
+fn segment_time(path: &str) -> Option<OffsetDateTime> {
+    let path = path
+        .strip_prefix(SEGMENTS_PREFIX)?
+        .strip_suffix("/meta.json")?;
+    let mut parts = path.split('/');
+    let year = parts.next()?.parse().ok()?;
+    let month: u8 = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    let hhmm = parts.next()?;
+    if parts.next().is_some() || hhmm.len() != 4 {
+        return None;
+    }
+    let hour = hhmm[..2].parse().ok()?;
+    let minute = hhmm[2..].parse().ok()?;
+    let date = Date::from_calendar_date(year, Month::try_from(month).ok()?, day).ok()?;
+    let time = Time::from_hms(hour, minute, 0).ok()?;
+    Some(date.with_time(time).assume_utc())
+}
+
+fn floor_hour(time: OffsetDateTime) -> OffsetDateTime {
+    time.replace_time(Time::from_hms(time.hour(), 0, 0).expect("hour is valid"))
+}
+
+fn ceil_hour(time: OffsetDateTime) -> OffsetDateTime {
+    let floor = floor_hour(time);
+    if floor == time {
+        floor
+    } else {
+        floor + Duration::HOUR
+    }
+}
+
+/// The text of a string or enum value.
+fn text(value: &Value) -> Option<&str> {
+    match value {
+        Value::String(s) | Value::Enum(s) => Some(s),
+        _ => None,
+    }
+}
+
+fn optional_text(value: &Value, field: &str) -> Option<String> {
+    value.field(field).and_then(text).map(ToOwned::to_owned)
+}
+
+fn required_text(value: &Value, field: &str) -> azure_core::Result<String> {
+    optional_text(value, field).ok_or_else(|| {
+        Error::with_message(ErrorKind::DataConversion, || {
+            format!("change feed event has no {field}")
+        })
+    })
+}
+
+impl TryFrom<Value> for ChangeFeedEvent {
+    type Error = Error;
+
+    fn try_from(value: Value) -> azure_core::Result<Self> {
+        let event_time = date::parse_rfc3339(&required_text(&value, "eventTime")?)?;
+        let data = value
+            .field("data")
+            .map(ChangeFeedEventData::from)
+            .unwrap_or_default();
+        Ok(Self {
+            id: required_text(&value, "id")?,
+            event_type: required_text(&value, "eventType")?.as_str().into(),
+            event_time,
+            topic: optional_text(&value, "topic").unwrap_or_default(),
+            subject: required_text(&value, "subject")?,
+            schema_version: value
+                .field("schemaVersion")
+                .and_then(Value::as_i64)
+                .unwrap_or_default(),
+            data,
+        })
+    }
+}
+
+impl From<&Value> for ChangeFeedEventData {
+    fn from(data: &Value) -> Self {
+        let previous_info = match data.field("previousInfo") {
+            Some(Value::Map(map)) => map
+                .iter()
+                .filter_map(|(k, v)| text(v).map(|v| (k.clone(), v.to_owned())))
+                .collect(),
+            _ => HashMap::new(),
+        };
+        Self {
+            api: optional_text(data, "api").unwrap_or_default(),
+            client_request_id: optional_text(data, "clientRequestId"),
+            request_id: optional_text(data, "requestId"),
+            etag: optional_text(data, "etag"),
+            content_type: optional_text(data, "contentType"),
+            content_length: data.field("contentLength").and_then(Value::as_i64),
+            content_offset: data.field("contentOffset").and_then(Value::as_i64),
+            blob_type: optional_text(data, "blobType"),
+            blob_version: optional_text(data, "blobVersion"),
+            container_version: optional_text(data, "containerVersion"),
+            blob_access_tier: optional_text(data, "blobAccessTier"),
+            url: optional_text(data, "url"),
+            source_url: optional_text(data, "sourceUrl"),
+            destination_url: optional_text(data, "destinationUrl"),
+            recursive: data.field("recursive").and_then(Value::as_bool),
+            sequencer: optional_text(data, "sequencer"),
+            snapshot: optional_text(data, "snapshot"),
+            previous_info,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avro::tests::{container, write_bytes, write_long_value, write_union_index};
+    use time::macros::datetime;
+
+    #[test]
+    fn test_segment_time() {
+        assert_eq!(
+            segment_time("idx/segments/2019/02/22/1800/meta.json"),
+            Some(datetime!(2019-02-22 18:00 UTC))
+        );
+        assert!(segment_time("idx/segments/1601/01/01/0000/meta.json").is_some());
+        assert_eq!(
+            segment_time("idx/segments/2019/02/22/1800/other.json"),
+            None
+        );
+        assert_eq!(
+            ceil_hour(datetime!(2019-02-22 18:10 UTC)),
+            datetime!(2019-02-22 19:00 UTC)
+        );
+        assert_eq!(
+            ceil_hour(datetime!(2019-02-22 18:00 UTC)),
+            datetime!(2019-02-22 18:00 UTC)
+        );
+    }
+
+    #[test]
+    fn test_cursor_round_trip() -> azure_core::Result<()> {
+        let cursor = ChangeFeedCursor {
+            segment_path: "idx/segments/2019/02/22/1800/meta.json".to_owned(),
+            shard_index: 1,
+            chunk_path: "log/01/2019/02/22/1810/00000.avro".to_owned(),
+            event_index: 42,
+        };
+        assert_eq!(cursor.to_string().parse::<ChangeFeedCursor>()?, cursor);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_event() -> azure_core::Result<()> {
+        let schema = r#"{"type":"record","name":"BlobChangeEvent","fields":[
+            {"name":"schemaVersion","type":"int"},
+            {"name":"topic","type":"string"},
+            {"name":"subject","type":"string"},
+            {"name":"eventType","type":{"type":"enum","name":"BlobChangeEventType","symbols":["UnspecifiedEventType","BlobCreated","BlobDeleted"]}},
+            {"name":"eventTime","type":"string"},
+            {"name":"id","type":"string"},
+            {"name":"data","type":{"type":"record","name":"BlobChangeEventData","fields":[
+                {"name":"api","type":"string"},
+                {"name":"contentLength","type":["null","long"]},
+                {"name":"etag","type":["null","string"]}
+            ]}}
+        ]}"#;
+
+        let mut data = Vec::new();
+        write_long_value(&mut data, 3);
+        write_bytes(
+            &mut data,
+            b"/subscriptions/s/resourceGroups/g/providers/Microsoft.Storage/storageAccounts/a",
+        );
+        write_bytes(
+            &mut data,
+            b"/blobServices/default/containers/c/blobs/dir/b.txt",
+        );
+        write_long_value(&mut data, 1);
+        write_bytes(&mut data, b"2019-02-22T18:12:01.079Z");
+        write_bytes(&mut data, b"62616073-8020-0000-00ff-233467060cc0");
+        write_bytes(&mut data, b"PutBlob");
+        write_union_index(&mut data, 1);
+        write_long_value(&mut data, 128);
+        write_union_index(&mut data, 0);
+
+        let mut reader = AvroReader::new();
+        reader.push(&container(schema, &[(1, data)]));
+        let event = ChangeFeedEvent::try_from(reader.next()?.unwrap())?;
+
+        assert_eq!(event.event_type, ChangeFeedEventType::BlobCreated);
+        assert_eq!(event.event_time, datetime!(2019-02-22 18:12:01.079 UTC));
+        assert_eq!(event.container_and_blob(), Some(("c", "dir/b.txt")));
+        assert_eq!(event.schema_version, 3);
+        assert_eq!(event.data.api, "PutBlob");
+        assert_eq!(event.data.content_length, Some(128));
+        assert_eq!(event.data.etag, None);
+        Ok(())
+    }
+}
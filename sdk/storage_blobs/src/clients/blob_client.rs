@@ -1,4 +1,8 @@
-use crate::{blob::operations::*, prelude::*};
+use crate::{
+    blob::{operations::*, AppendBlobWriter, BlobReader, BlockBlobWriter, PageBlobWriter},
+    lease::{AcquireLeaseGuardBuilder, LeaderElection, LeaseTarget},
+    prelude::*,
+};
 use azure_core::{
     error::{Error, ErrorKind},
     headers::Headers,
@@ -6,6 +10,7 @@ use azure_core::{
     Body, Method, Request, Response, StatusCode, Url,
 };
 use azure_storage::{
+    clients::{service_sas_signing_key, ServiceType},
     prelude::*,
     shared_access_signature::service_sas::{BlobSharedAccessSignature, UserDeligationKey},
     StorageCredentialsInner,
@@ -14,6 +19,9 @@ use futures::StreamExt;
 use std::ops::Deref;
 use time::OffsetDateTime;
 
+#[cfg(feature = "client-side-encryption")]
+use crate::encryption::ClientSideEncryption;
+
 /// A client for handling blobs
 ///
 /// For a full list of operations available on blobs, check out [the Azure documentation](https://docs.microsoft.com/en-us/rest/api/storageservices/operations-on-blobs).
@@ -68,6 +76,27 @@ impl BlobClient {
         Ok(blob)
     }
 
+    /// Read the blob with `AsyncRead` and `AsyncSeek`, downloading blocks as they are needed.
+    pub fn reader(&self) -> BlobReader {
+        BlobReader::new(self.clone())
+    }
+
+    /// Download a blob to a writer, with concurrent ranged requests written in order.
+    pub fn download_to(
+        &self,
+        writer: impl futures::io::AsyncWrite + Send + Unpin + 'static,
+    ) -> DownloadBlobBuilder {
+        DownloadBlobBuilder::new(self.clone(), DownloadTarget::writer(writer))
+    }
+
+    /// Download a blob to a file, with concurrent ranged requests written at their position in the file.
+    ///
+    /// The file is created, or truncated if it exists.
+    #[cfg(feature = "tokio-fs")]
+    pub fn download_to_file(&self, path: impl Into<std::path::PathBuf>) -> DownloadBlobBuilder {
+        DownloadBlobBuilder::new(self.clone(), DownloadTarget::file(path.into()))
+    }
+
     /// Get all user-defined metadata, standard HTTP properties, and system properties for the blob.
     pub fn get_properties(&self) -> GetPropertiesBuilder {
         GetPropertiesBuilder::new(self.clone())
@@ -91,6 +120,11 @@ impl BlobClient {
         SetMetadataBuilder::new(self.clone())
     }
 
+    /// Apply a SQL statement to the contents of the blob, returning only the matching data.
+    pub fn query(&self, expression: impl Into<String>) -> QueryBlobBuilder {
+        QueryBlobBuilder::new(self.clone(), expression.into())
+    }
+
     /// Set the access tier on the blob.
     pub fn set_blob_tier(&self, access_tier: AccessTier) -> SetBlobTierBuilder {
         SetBlobTierBuilder::new(self.clone(), access_tier)
@@ -121,7 +155,39 @@ impl BlobClient {
         PutBlockBlobBuilder::new(self.clone(), body.into())
     }
 
+    /// Write a block blob with `AsyncWrite`, committing it when the writer is closed.
+    pub fn block_blob_writer(&self) -> BlockBlobWriter {
+        BlockBlobWriter::new(self.clone())
+    }
+
+    /// Write an append blob with `AsyncWrite`, appending the data in order.
+    pub fn append_blob_writer(&self) -> AppendBlobWriter {
+        AppendBlobWriter::new(self.clone())
+    }
+
+    /// Write a page blob of `length` bytes with `AsyncWrite`.
+    pub fn page_blob_writer(&self, length: u128) -> PageBlobWriter {
+        PageBlobWriter::new(self.clone(), length)
+    }
+
+    /// Upload a block blob, in parallel blocks when the body is large.
+    ///
+    /// Bodies created from a `SeekableStream`, such as a `FileStream`, are read one block at a time.
+    pub fn upload(&self, body: impl Into<Body>) -> UploadBlobBuilder {
+        UploadBlobBuilder::new(self.clone(), UploadSource::Body(body.into()))
+    }
+
+    /// Upload a block blob from a reader of unknown length, in parallel blocks when it is large.
+    pub fn upload_reader(
+        &self,
+        reader: impl futures::io::AsyncRead + Send + Unpin + 'static,
+    ) -> UploadBlobBuilder {
+        UploadBlobBuilder::new(self.clone(), UploadSource::reader(reader))
+    }
+
     /// Copy the blob to a destination within the storage account.
+    ///
+    /// Fails on clients with a customer-provided key, which Copy Blob does not support.
     pub fn copy(&self, copy_source: Url) -> CopyBlobBuilder {
         CopyBlobBuilder::new(self.clone(), copy_source)
     }
@@ -131,6 +197,18 @@ impl BlobClient {
         CopyBlobFromUrlBuilder::new(self.clone(), copy_source)
     }
 
+    /// Abort a pending copy, leaving the destination blob with zero length and full metadata.
+    pub fn abort_copy(&self, copy_id: CopyId) -> AbortCopyBuilder {
+        AbortCopyBuilder::new(self.clone(), copy_id)
+    }
+
+    /// Copy the differences of a page blob snapshot to this blob.
+    ///
+    /// The source must be a page blob snapshot URL, authorized via a SAS or public.
+    pub fn incremental_copy(&self, copy_source: Url) -> IncrementalCopyBuilder {
+        IncrementalCopyBuilder::new(self.clone(), copy_source)
+    }
+
     /// Create a lease on the blob to lock for write and delete operations.
     pub fn acquire_lease<LD: Into<LeaseDuration>>(
         &self,
@@ -139,6 +217,19 @@ impl BlobClient {
         AcquireLeaseBuilder::new(self.clone(), lease_duration.into())
     }
 
+    /// Acquire a lease on the blob, and keep renewing it until it is released.
+    pub fn lease_guard<LD: Into<LeaseDuration>>(
+        &self,
+        lease_duration: LD,
+    ) -> AcquireLeaseGuardBuilder {
+        AcquireLeaseGuardBuilder::new(LeaseTarget::Blob(self.clone()), lease_duration.into())
+    }
+
+    /// Elect a leader among the clients leasing this blob.
+    pub fn leader_election(&self) -> LeaderElection {
+        LeaderElection::new(self.clone())
+    }
+
     /// End the lease but ensure that another client cannot acquire a new lease until the current lease period has expired.
     pub fn break_lease(&self) -> BreakLeaseBuilder {
         BreakLeaseBuilder::new(self.clone())
@@ -159,9 +250,31 @@ impl BlobClient {
         DeleteBlobVersionBuilder::new(self.clone(), version_id)
     }
 
+    /// Restore the contents and metadata of a soft-deleted blob and its snapshots.
+    pub fn undelete(&self) -> UndeleteBlobBuilder {
+        UndeleteBlobBuilder::new(self.clone())
+    }
+
+    /// Set or clear a legal hold on the blob.
+    pub fn set_legal_hold(&self, legal_hold: bool) -> SetLegalHoldBuilder {
+        SetLegalHoldBuilder::new(self.clone(), legal_hold)
+    }
+
+    /// Set the immutability policy of the blob, keeping it from being modified until `expiry`.
+    pub fn set_immutability_policy(&self, expiry: OffsetDateTime) -> SetImmutabilityPolicyBuilder {
+        SetImmutabilityPolicyBuilder::new(self.clone(), expiry)
+    }
+
+    /// Delete the unlocked immutability policy of the blob.
+    pub fn delete_immutability_policy(&self) -> DeleteImmutabilityPolicyBuilder {
+        DeleteImmutabilityPolicyBuilder::new(self.clone())
+    }
+
     /* Operations specific to certain blob types */
 
     /// Creates a new block to be committed as part of a block blob.
+    ///
+    /// Fails on clients using client-side encryption, as the content would be stored unencrypted.
     pub fn put_block(
         &self,
         block_id: impl Into<BlockId>,
@@ -197,25 +310,46 @@ impl BlobClient {
     /// Write a block blob by specifying the list of block IDs that make up the blob.
     ///
     /// In order to be written as part of a blob, a block must have been successfully written to the server in a prior Put Block operation.
+    ///
+    /// Fails on clients using client-side encryption, as the content would be stored unencrypted.
     pub fn put_block_list(&self, block_list: BlockList) -> PutBlockListBuilder {
         PutBlockListBuilder::new(self.clone(), block_list)
     }
 
     /// Write a range of pages to a page blob.
+    ///
+    /// Fails on clients using client-side encryption, as the content would be stored unencrypted.
     pub fn put_page(&self, ba512_range: BA512Range, content: impl Into<Body>) -> PutPageBuilder {
         PutPageBuilder::new(self.clone(), ba512_range, content.into())
     }
 
+    /// Write a range of pages to a page blob, reading them from a range of a source URL.
+    pub fn put_page_from_url(
+        &self,
+        ba512_range: BA512Range,
+        copy_source: Url,
+        source_range: BA512Range,
+    ) -> PutPageFromUrlBuilder {
+        PutPageFromUrlBuilder::new(self.clone(), ba512_range, copy_source, source_range)
+    }
+
     /// Return the list of valid page ranges for a page blob or snapshot of a page blob.
     pub fn get_page_ranges(&self) -> GetPageRangesBuilder {
         GetPageRangesBuilder::new(self.clone())
     }
 
     /// Commits a new block of data to the end of an existing append blob.
+    ///
+    /// Fails on clients using client-side encryption, as the content would be stored unencrypted.
     pub fn append_block(&self, body: impl Into<Body>) -> AppendBlockBuilder {
         AppendBlockBuilder::new(self.clone(), body.into())
     }
 
+    /// Commits a new block to the end of an existing append blob, reading it from a source URL.
+    pub fn append_block_from_url(&self, copy_source: Url) -> AppendBlockFromUrlBuilder {
+        AppendBlockFromUrlBuilder::new(self.clone(), copy_source)
+    }
+
     /// Clear range of pages in a page blob.
     pub fn clear_page(&self, ba512_range: BA512Range) -> ClearPageBuilder {
         ClearPageBuilder::new(self.clone(), ba512_range)
@@ -259,22 +393,18 @@ impl BlobClient {
         permissions: BlobSasPermissions,
         expiry: OffsetDateTime,
     ) -> azure_core::Result<BlobSharedAccessSignature> {
-        let creds = self.container_client.credentials().0.read().await;
-        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
-            return Err(Error::message(
-                ErrorKind::Credential,
-                "Shared access signature generation - SAS can be generated with access_key clients",
-            ));
-        };
-
-        let canonicalized_resource = format!(
-            "/blob/{}/{}/{}",
-            account,
-            self.container_client.container_name(),
-            self.blob_name()
-        );
+        let (canonicalized_resource, key) = service_sas_signing_key(
+            self.container_client.credentials(),
+            ServiceType::Blob,
+            &format!(
+                "{}/{}",
+                self.container_client.container_name(),
+                self.blob_name()
+            ),
+        )
+        .await?;
         Ok(BlobSharedAccessSignature::new(
-            key.clone(),
+            key,
             canonicalized_resource,
             permissions,
             expiry,
@@ -336,6 +466,70 @@ impl BlobClient {
         Ok(url)
     }
 
+    /// The customer-provided key of a read, set on the operation or else on the client.
+    pub(crate) fn server_side_encryption_key(
+        &self,
+        encryption_key: Option<CPKInfo>,
+    ) -> Option<CPKInfo> {
+        encryption_key.or_else(|| self.container_client.encryption_key().cloned())
+    }
+
+    /// The customer-provided key or encryption scope of a write, set on the operation or else on
+    /// the client. The service accepts at most one of them.
+    pub(crate) fn server_side_encryption(
+        &self,
+        encryption_key: Option<CPKInfo>,
+        encryption_scope: Option<EncryptionScope>,
+    ) -> azure_core::Result<(Option<CPKInfo>, Option<EncryptionScope>)> {
+        match (encryption_key, encryption_scope) {
+            (Some(_), Some(_)) => Err(Error::message(
+                ErrorKind::Other,
+                "an operation cannot set both an encryption key and an encryption scope",
+            )),
+            (None, None) => Ok((
+                self.container_client.encryption_key().cloned(),
+                self.container_client.encryption_scope().cloned(),
+            )),
+            (encryption_key, encryption_scope) => Ok((encryption_key, encryption_scope)),
+        }
+    }
+
+    #[cfg(feature = "client-side-encryption")]
+    pub(crate) fn client_side_encryption(&self) -> Option<&ClientSideEncryption> {
+        self.container_client.client_side_encryption()
+    }
+
+    /// The length of the content of `blob` as read by this client, which is the length of the
+    /// plaintext of blobs encrypted client-side.
+    #[cfg_attr(
+        not(feature = "client-side-encryption"),
+        allow(clippy::unnecessary_wraps)
+    )]
+    pub(crate) fn content_length(&self, blob: &Blob) -> azure_core::Result<u64> {
+        #[cfg(feature = "client-side-encryption")]
+        if let Some(encryption) = self.client_side_encryption() {
+            return encryption
+                .plaintext_length(blob.metadata.as_ref(), blob.properties.content_length);
+        }
+        Ok(blob.properties.content_length)
+    }
+
+    /// Fail operations uploading content as is, which would bypass the client-side encryption of
+    /// this client.
+    #[cfg_attr(
+        not(feature = "client-side-encryption"),
+        allow(unused_variables, clippy::unnecessary_wraps)
+    )]
+    pub(crate) fn check_unencrypted_upload(&self, operation: &str) -> azure_core::Result<()> {
+        #[cfg(feature = "client-side-encryption")]
+        if self.client_side_encryption().is_some() {
+            return Err(Error::with_message(ErrorKind::Other, || {
+                format!("{operation} does not support client-side encryption")
+            }));
+        }
+        Ok(())
+    }
+
     pub(crate) fn finalize_request(
         url: Url,
         method: Method,
@@ -455,4 +649,158 @@ mod tests {
             "http://127.0.0.1:10000/devstoreaccount1/a/b/c/d/hi%20there?fake_token"
         );
     }
+
+    #[test]
+    fn test_server_side_encryption() -> azure_core::Result<()> {
+        let key = CPKInfo::from(("key".to_owned(), "hash".to_owned()));
+        let scope = EncryptionScope::from("scope");
+        let blob_client = ClientBuilder::emulator()
+            .encryption_key(key.clone())
+            .blob_client("a", "b");
+
+        let (encryption_key, encryption_scope) = blob_client.server_side_encryption(None, None)?;
+        assert_eq!(encryption_key.unwrap().key_sha256(), "hash");
+        assert!(encryption_scope.is_none());
+
+        // a scope set on the operation replaces the key of the client
+        let (encryption_key, encryption_scope) =
+            blob_client.server_side_encryption(None, Some(scope.clone()))?;
+        assert!(encryption_key.is_none());
+        assert_eq!(encryption_scope, Some(scope.clone()));
+
+        // the service rejects requests with both
+        assert!(blob_client
+            .server_side_encryption(Some(key.clone()), Some(scope.clone()))
+            .is_err());
+
+        // the last of the key and scope set on the client is kept
+        let blob_client = ClientBuilder::emulator()
+            .encryption_key(key)
+            .encryption_scope(scope.clone())
+            .blob_client("a", "b");
+        let (encryption_key, encryption_scope) = blob_client.server_side_encryption(None, None)?;
+        assert!(encryption_key.is_none());
+        assert_eq!(encryption_scope, Some(scope));
+        assert!(blob_client.server_side_encryption_key(None).is_none());
+        Ok(())
+    }
+
+    /// Record the encryption headers of the blocks put.
+    #[derive(Debug, Default)]
+    struct EncryptionHeaders {
+        requests: std::sync::Mutex<Vec<(Option<String>, Option<String>)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl azure_core::Policy for EncryptionHeaders {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[std::sync::Arc<dyn azure_core::Policy>],
+        ) -> azure_core::PolicyResult {
+            use azure_core::headers::{
+                DATE, ENCRYPTION_KEY_SHA256, REQUEST_ID, REQUEST_SERVER_ENCRYPTED,
+            };
+
+            let key_sha256 = request
+                .headers()
+                .get_optional_string(&ENCRYPTION_KEY_SHA256);
+            let scope = request
+                .headers()
+                .get_optional_string(&azure_storage::headers::ENCRYPTION_SCOPE);
+            self.requests
+                .lock()
+                .unwrap()
+                .push((key_sha256.clone(), scope));
+
+            let mut headers = Headers::new();
+            headers.insert(REQUEST_ID, "00000000-0000-0000-0000-000000000000");
+            headers.insert(DATE, "Mon, 01 Jan 2024 00:00:00 GMT");
+            headers.insert(REQUEST_SERVER_ENCRYPTED, "true");
+            if let Some(key_sha256) = key_sha256 {
+                headers.insert(ENCRYPTION_KEY_SHA256, key_sha256);
+            }
+            Ok(Response::new(
+                StatusCode::Created,
+                headers,
+                Box::pin(azure_core::BytesStream::new_empty()),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_server_side_encryption_headers() -> azure_core::Result<()> {
+        let key = CPKInfo::from(("key".to_owned(), "hash".to_owned()));
+        let scope = EncryptionScope::from("scope");
+        let policy = std::sync::Arc::new(EncryptionHeaders::default());
+        let builder = ClientBuilder::emulator()
+            .retry(azure_core::RetryOptions::none())
+            .transport(azure_core::TransportOptions::new_custom_policy(
+                policy.clone(),
+            ));
+        let with_key = builder
+            .clone()
+            .encryption_key(key.clone())
+            .blob_client("a", "b");
+        let with_scope = builder
+            .encryption_scope(scope.clone())
+            .blob_client("a", "b");
+
+        with_key.put_block(BlockId::new("0"), "data").await?;
+        with_scope.put_block(BlockId::new("0"), "data").await?;
+        // the operation overrides the client
+        with_key
+            .put_block(BlockId::new("0"), "data")
+            .encryption_scope("other")
+            .await?;
+        with_scope
+            .put_block(BlockId::new("0"), "data")
+            .encryption_key(("other".to_owned(), "other hash".to_owned()))
+            .await?;
+        // Copy Blob does not support customer-provided keys
+        let source = Url::parse("https://account.blob.core.windows.net/c/source")?;
+        assert!(with_key.copy(source).await.is_err());
+
+        let requests = policy.requests.lock().unwrap().clone();
+        let expected = [
+            (Some("hash"), None),
+            (None, Some("scope")),
+            (None, Some("other")),
+            (Some("other hash"), None),
+        ];
+        assert_eq!(requests.len(), expected.len());
+        for ((key_sha256, scope), (expected_sha256, expected_scope)) in
+            requests.iter().zip(expected)
+        {
+            assert_eq!(key_sha256.as_deref(), expected_sha256);
+            assert_eq!(scope.as_deref(), expected_scope);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "client-side-encryption")]
+    #[tokio::test]
+    async fn test_unencrypted_uploads_rejected() {
+        use crate::encryption::{AesKeyEncryptionKey, ClientSideEncryption};
+        use std::sync::Arc;
+
+        let blob_client = ClientBuilder::emulator()
+            .client_side_encryption(ClientSideEncryption::new(Arc::new(
+                AesKeyEncryptionKey::new("key1", [7; 32]),
+            )))
+            .blob_client("a", "b");
+
+        let block_id = BlockId::new("block");
+        assert!(blob_client.put_block(block_id, "data").await.is_err());
+        assert!(blob_client
+            .put_block_list(BlockList::default())
+            .await
+            .is_err());
+        assert!(blob_client.append_block("data").await.is_err());
+        assert!(blob_client
+            .put_page(BA512Range::new(0, 511).unwrap(), vec![0; 512])
+            .await
+            .is_err());
+    }
 }
@@ -1,5 +1,7 @@
 use crate::{
+    change_feed::ChangeFeedBuilder,
     clients::{BlobClient, BlobLeaseClient, ContainerClient, ContainerLeaseClient},
+    options::{CPKInfo, EncryptionScope, TagFilter},
     service::operations::*,
 };
 use azure_core::{
@@ -10,16 +12,30 @@ use azure_storage::{
     clients::{new_pipeline_from_options, shared_access_signature, ServiceType},
     prelude::{AccountSasPermissions, AccountSasResource, AccountSasResourceType},
     shared_access_signature::account_sas::AccountSharedAccessSignature,
-    CloudLocation, StorageCredentials,
+    CloudLocation, ConnectionString, StorageCredentials,
 };
+use azure_svc_blobstorage::models::StorageServiceProperties;
 use time::OffsetDateTime;
 
+#[cfg(feature = "client-side-encryption")]
+use crate::encryption::ClientSideEncryption;
+
+/// How the blobs are encrypted on the server, the service accepts a key or a scope but not both.
+#[derive(Debug, Clone)]
+enum ServerSideEncryption {
+    Key(CPKInfo),
+    Scope(EncryptionScope),
+}
+
 /// A builder for the blob service client.
 #[derive(Debug, Clone)]
 pub struct ClientBuilder {
     cloud_location: CloudLocation,
     options: ClientOptions,
     credentials: StorageCredentials,
+    server_side_encryption: Option<ServerSideEncryption>,
+    #[cfg(feature = "client-side-encryption")]
+    client_side_encryption: Option<ClientSideEncryption>,
 }
 
 impl ClientBuilder {
@@ -48,9 +64,24 @@ impl ClientBuilder {
             options: ClientOptions::default(),
             cloud_location,
             credentials: credentials.into(),
+            server_side_encryption: None,
+            #[cfg(feature = "client-side-encryption")]
+            client_side_encryption: None,
         }
     }
 
+    /// Create a new instance of `ClientBuilder` from a storage connection string.
+    ///
+    /// The endpoint of the service, or else the account name, protocol and endpoint suffix, give
+    /// the location. The shared access signature or account key give the credentials.
+    pub fn from_connection_string(connection_string: &str) -> azure_core::Result<Self> {
+        let connection_string = ConnectionString::new(connection_string)?;
+        Ok(Self::with_location(
+            connection_string.cloud_location(ServiceType::Blob)?,
+            connection_string.storage_credentials()?,
+        ))
+    }
+
     /// Use the emulator with default settings
     #[must_use]
     pub fn emulator() -> Self {
@@ -70,12 +101,18 @@ impl ClientBuilder {
             cloud_location,
             options,
             credentials,
+            server_side_encryption,
+            #[cfg(feature = "client-side-encryption")]
+            client_side_encryption,
         } = self;
 
         BlobServiceClient {
             pipeline: new_pipeline_from_options(options, credentials.clone()),
             cloud_location,
             credentials,
+            server_side_encryption,
+            #[cfg(feature = "client-side-encryption")]
+            client_side_encryption,
         }
     }
 
@@ -144,6 +181,37 @@ impl ClientBuilder {
         self
     }
 
+    /// Encrypt the blobs written, and decrypt the blobs read, on the server with a key provided by
+    /// the client.
+    ///
+    /// The key is sent with every operation reading or writing the content, properties or metadata
+    /// of blobs, unless the operation sets its own key or encryption scope. It replaces the
+    /// encryption scope of the client, if any.
+    #[must_use]
+    pub fn encryption_key(mut self, encryption_key: impl Into<CPKInfo>) -> Self {
+        self.server_side_encryption = Some(ServerSideEncryption::Key(encryption_key.into()));
+        self
+    }
+
+    /// Encrypt the blobs written with an encryption scope of the account.
+    ///
+    /// The scope is sent with every operation writing blobs, unless the operation sets its own key
+    /// or encryption scope, and is the default encryption scope of the containers created. It
+    /// replaces the encryption key of the client, if any.
+    #[must_use]
+    pub fn encryption_scope(mut self, encryption_scope: impl Into<EncryptionScope>) -> Self {
+        self.server_side_encryption = Some(ServerSideEncryption::Scope(encryption_scope.into()));
+        self
+    }
+
+    /// Encrypt the block blobs written, and decrypt the blobs read, on the client.
+    #[cfg(feature = "client-side-encryption")]
+    #[must_use]
+    pub fn client_side_encryption(mut self, client_side_encryption: ClientSideEncryption) -> Self {
+        self.client_side_encryption = Some(client_side_encryption);
+        self
+    }
+
     /// Override all of the client options.
     ///
     /// *Warning!*: This overrides all client options that have been previously set on this builder.
@@ -160,6 +228,9 @@ pub struct BlobServiceClient {
     pipeline: Pipeline,
     cloud_location: CloudLocation,
     credentials: StorageCredentials,
+    server_side_encryption: Option<ServerSideEncryption>,
+    #[cfg(feature = "client-side-encryption")]
+    client_side_encryption: Option<ClientSideEncryption>,
 }
 
 impl BlobServiceClient {
@@ -169,6 +240,11 @@ impl BlobServiceClient {
         ClientBuilder::new(account, credentials).blob_service_client()
     }
 
+    /// Create a new `BlobServiceClient` from a storage connection string.
+    pub fn from_connection_string(connection_string: &str) -> azure_core::Result<Self> {
+        Ok(ClientBuilder::from_connection_string(connection_string)?.blob_service_client())
+    }
+
     /// Create a new `ClientBuilder`.
     #[must_use]
     pub fn builder(
@@ -192,19 +268,61 @@ impl BlobServiceClient {
         FindBlobsByTagsBuilder::new(self.clone(), expression)
     }
 
+    /// Get all the blobs matching a tag filter.
+    ///
+    /// Fails without sending a request if the filter is not supported by Find Blobs by Tags.
+    pub fn find_blobs_by_tag_filter(
+        &self,
+        filter: &TagFilter,
+    ) -> azure_core::Result<FindBlobsByTagsBuilder> {
+        Ok(self.find_blobs_by_tags(filter.find_expression()?))
+    }
+
     /// List all the containers in the blob account
     pub fn list_containers(&self) -> ListContainersBuilder {
         ListContainersBuilder::new(self.clone())
     }
 
+    /// Delete or set the tier of up to 256 blobs in the account with a single request
+    pub fn batch(&self) -> SubmitBatchBuilder {
+        SubmitBatchBuilder::new(self.clone(), None, Vec::new())
+    }
+
+    /// Get the properties of the blob service, such as logging, metrics, CORS and static website settings
     pub fn get_properties(&self) -> GetBlobServicePropertiesBuilder {
         GetBlobServicePropertiesBuilder::new(self.clone())
     }
 
+    /// Set the properties of the blob service
+    ///
+    /// Elements left as `None` keep their current setting on the service.
+    pub fn set_properties(
+        &self,
+        properties: StorageServiceProperties,
+    ) -> SetBlobServicePropertiesBuilder {
+        SetBlobServicePropertiesBuilder::new(self.clone(), properties)
+    }
+
+    /// Get the geo-replication status of the account from its secondary location
+    ///
+    /// Only available when read-access geo-redundant replication is enabled.
+    pub fn get_statistics(&self) -> GetBlobServiceStatisticsBuilder {
+        GetBlobServiceStatisticsBuilder::new(self.clone())
+    }
+
+    /// Read the change feed of the account.
+    pub fn change_feed(&self) -> ChangeFeedBuilder {
+        ChangeFeedBuilder::new(self.clone())
+    }
+
     pub fn url(&self) -> azure_core::Result<Url> {
         self.cloud_location.url(ServiceType::Blob)
     }
 
+    pub(crate) fn secondary_url(&self) -> azure_core::Result<Url> {
+        self.cloud_location.secondary_url(ServiceType::Blob)
+    }
+
     pub fn container_client<S: Into<String>>(&self, container_name: S) -> ContainerClient {
         ContainerClient::new(self.clone(), container_name.into())
     }
@@ -244,6 +362,25 @@ impl BlobServiceClient {
         &self.credentials
     }
 
+    pub(crate) fn encryption_key(&self) -> Option<&CPKInfo> {
+        match &self.server_side_encryption {
+            Some(ServerSideEncryption::Key(encryption_key)) => Some(encryption_key),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn encryption_scope(&self) -> Option<&EncryptionScope> {
+        match &self.server_side_encryption {
+            Some(ServerSideEncryption::Scope(encryption_scope)) => Some(encryption_scope),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "client-side-encryption")]
+    pub(crate) fn client_side_encryption(&self) -> Option<&ClientSideEncryption> {
+        self.client_side_encryption.as_ref()
+    }
+
     pub(crate) fn finalize_request(
         url: Url,
         method: Method,
@@ -293,4 +430,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn from_connection_string() -> azure_core::Result<()> {
+        let service_client = BlobServiceClient::from_connection_string(
+            "SharedAccessSignature=sv=2022-11-02&sig=c2lnbmF0dXJl;BlobEndpoint=https://blobs.example.com/account/",
+        )?;
+        assert_eq!(service_client.account(), "account");
+        assert_eq!(
+            service_client
+                .container_client("c")
+                .blob_client("b")
+                .url()?
+                .as_str(),
+            "https://blobs.example.com/account/c/b"
+        );
+
+        let service_client =
+            BlobServiceClient::from_connection_string("UseDevelopmentStorage=true")?;
+        assert_eq!(
+            service_client.url()?.as_str(),
+            "http://127.0.0.1:10000/devstoreaccount1"
+        );
+        Ok(())
+    }
 }
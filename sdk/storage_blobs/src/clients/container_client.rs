@@ -1,4 +1,11 @@
-use crate::{clients::*, container::operations::*, prelude::PublicAccess};
+use crate::{
+    clients::*,
+    container::operations::*,
+    lease::{AcquireLeaseGuardBuilder, LeaseTarget},
+    options::{CPKInfo, EncryptionScope},
+    prelude::PublicAccess,
+    service::operations::SubmitBatchBuilder,
+};
 use azure_core::{
     error::{Error, ErrorKind},
     headers::Headers,
@@ -6,6 +13,7 @@ use azure_core::{
     Body, Method, Request, Response, StatusCode, Url,
 };
 use azure_storage::{
+    clients::{service_sas_signing_key, ServiceType},
     prelude::BlobSasPermissions,
     shared_access_signature::{
         service_sas::{BlobSharedAccessSignature, BlobSignedResource, UserDeligationKey},
@@ -16,6 +24,9 @@ use azure_storage::{
 use std::ops::Deref;
 use time::OffsetDateTime;
 
+#[cfg(feature = "client-side-encryption")]
+use crate::encryption::ClientSideEncryption;
+
 #[derive(Debug, Clone)]
 pub struct ContainerClient {
     service_client: BlobServiceClient,
@@ -71,11 +82,57 @@ impl ContainerClient {
         GetPropertiesBuilder::new(self.clone())
     }
 
+    /// Replace the metadata of a container
+    pub fn set_metadata(&self, metadata: Metadata) -> SetMetadataBuilder {
+        SetMetadataBuilder::new(self.clone(), metadata)
+    }
+
+    /// Restore a soft-deleted container.
+    ///
+    /// The version of the deleted container is listed by `BlobServiceClient::list_containers`
+    /// when including deleted containers.
+    pub fn undelete(&self, deleted_container_version: impl Into<String>) -> UndeleteBuilder {
+        UndeleteBuilder::new(self.clone(), deleted_container_version.into())
+    }
+
+    /// Rename a container
+    pub fn rename(&self, new_container_name: impl Into<String>) -> RenameBuilder {
+        RenameBuilder::new(
+            self.service_client.container_client(new_container_name),
+            self.container_name.clone(),
+        )
+    }
+
     /// List the blobs in a container
     pub fn list_blobs(&self) -> ListBlobsBuilder {
         ListBlobsBuilder::new(self.clone())
     }
 
+    /// Walk the virtual directories below `prefix`, yielding blobs and directories as a stream
+    pub fn walk(&self, prefix: impl Into<String>) -> WalkBuilder {
+        WalkBuilder::new(self.clone(), prefix.into())
+    }
+
+    /// Copy the blobs below `source_prefix` in `source` to this container, server side
+    pub fn copy_directory(
+        &self,
+        source: ContainerClient,
+        source_prefix: impl Into<String>,
+    ) -> CopyDirectoryBuilder {
+        CopyDirectoryBuilder::new(self.clone(), source, source_prefix.into())
+    }
+
+    /// Copy the blobs below `source_prefix` in `source` to this container, skipping the blobs
+    /// already identical in this container
+    pub fn sync(
+        &self,
+        source: ContainerClient,
+        source_prefix: impl Into<String>,
+    ) -> CopyDirectoryBuilder {
+        self.copy_directory(source, source_prefix)
+            .skip_identical(true)
+    }
+
     /// Acquite a lease on a container
     pub fn acquire_lease<LD: Into<LeaseDuration>>(
         &self,
@@ -84,6 +141,14 @@ impl ContainerClient {
         AcquireLeaseBuilder::new(self.clone(), lease_duration.into())
     }
 
+    /// Acquire a lease on the container, and keep renewing it until it is released
+    pub fn lease_guard<LD: Into<LeaseDuration>>(
+        &self,
+        lease_duration: LD,
+    ) -> AcquireLeaseGuardBuilder {
+        AcquireLeaseGuardBuilder::new(LeaseTarget::Container(self.clone()), lease_duration.into())
+    }
+
     /// Break the lease on a container
     pub fn break_lease(&self) -> BreakLeaseBuilder {
         BreakLeaseBuilder::new(self.clone())
@@ -105,6 +170,11 @@ impl ContainerClient {
         }
     }
 
+    /// Delete or set the tier of up to 256 blobs in the container with a single request
+    pub fn batch(&self) -> SubmitBatchBuilder {
+        SubmitBatchBuilder::new(self.service_client(), Some(self.clone()), Vec::new())
+    }
+
     pub fn container_lease_client(&self, lease_id: LeaseId) -> ContainerLeaseClient {
         ContainerLeaseClient::new(self.clone(), lease_id)
     }
@@ -154,17 +224,11 @@ impl ContainerClient {
         permissions: BlobSasPermissions,
         expiry: OffsetDateTime,
     ) -> azure_core::Result<BlobSharedAccessSignature> {
-        let creds = self.service_client.credentials().0.read().await;
-        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
-            return Err(Error::message(
-                ErrorKind::Credential,
-                "Shared access signature generation - SAS can be generated with access_key clients",
-            ));
-        };
-
-        let canonicalized_resource = format!("/blob/{}/{}", account, self.container_name());
+        let (canonicalized_resource, key) =
+            service_sas_signing_key(self.credentials(), ServiceType::Blob, self.container_name())
+                .await?;
         Ok(BlobSharedAccessSignature::new(
-            key.clone(),
+            key,
             canonicalized_resource,
             permissions,
             expiry,
@@ -172,6 +236,22 @@ impl ContainerClient {
         ))
     }
 
+    /// Create a shared access signature governed by one of the container's stored access policies.
+    pub async fn stored_access_policy_signature(
+        &self,
+        identifier: impl Into<String>,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        let (canonicalized_resource, key) =
+            service_sas_signing_key(self.credentials(), ServiceType::Blob, self.container_name())
+                .await?;
+        Ok(BlobSharedAccessSignature::from_stored_access_policy(
+            key,
+            canonicalized_resource,
+            identifier.into(),
+            BlobSignedResource::Container,
+        ))
+    }
+
     pub fn generate_signed_container_url<T>(&self, signature: &T) -> azure_core::Result<Url>
     where
         T: SasToken,
@@ -194,6 +274,19 @@ impl ContainerClient {
         self.service_client.credentials()
     }
 
+    pub(crate) fn encryption_key(&self) -> Option<&CPKInfo> {
+        self.service_client.encryption_key()
+    }
+
+    pub(crate) fn encryption_scope(&self) -> Option<&EncryptionScope> {
+        self.service_client.encryption_scope()
+    }
+
+    #[cfg(feature = "client-side-encryption")]
+    pub(crate) fn client_side_encryption(&self) -> Option<&ClientSideEncryption> {
+        self.service_client.client_side_encryption()
+    }
+
     pub(crate) async fn send(
         &self,
         context: &mut Context,
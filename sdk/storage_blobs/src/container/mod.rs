@@ -12,7 +12,10 @@ use azure_core::{
     },
     LeaseDuration, LeaseState, LeaseStatus,
 };
-use azure_storage::parsing_xml::{cast_must, cast_optional, traverse};
+use azure_storage::{
+    headers::{DEFAULT_ENCRYPTION_SCOPE, DENY_ENCRYPTION_SCOPE_OVERRIDE},
+    parsing_xml::{cast_must, cast_optional, traverse},
+};
 use std::collections::HashMap;
 use time::OffsetDateTime;
 use xml::{Element, Xml};
@@ -55,6 +58,14 @@ pub struct Container {
     pub has_immutability_policy: bool,
     pub has_legal_hold: bool,
     pub metadata: HashMap<String, String>,
+    /// The encryption scope of the blobs written without one.
+    pub default_encryption_scope: Option<String>,
+    /// Whether blobs can only be written with the default encryption scope.
+    pub deny_encryption_scope_override: bool,
+    /// Whether the container is soft-deleted. Only set when listing with `include_deleted`.
+    pub deleted: bool,
+    /// The version of a soft-deleted container, needed to restore it.
+    pub version: Option<String>,
 }
 
 impl AsRef<str> for Container {
@@ -76,6 +87,10 @@ impl Container {
             has_immutability_policy: false,
             has_legal_hold: false,
             metadata: HashMap::new(),
+            default_encryption_scope: None,
+            deny_encryption_scope_override: false,
+            deleted: false,
+            version: None,
         }
     }
 
@@ -100,6 +115,10 @@ impl Container {
 
         let has_immutability_policy = headers.get_as(&HAS_IMMUTABILITY_POLICY)?;
         let has_legal_hold = headers.get_as(&HAS_LEGAL_HOLD)?;
+        let default_encryption_scope = headers.get_optional_string(&DEFAULT_ENCRYPTION_SCOPE);
+        let deny_encryption_scope_override = headers
+            .get_optional_as(&DENY_ENCRYPTION_SCOPE_OVERRIDE)?
+            .unwrap_or_default();
 
         let mut metadata: HashMap<String, String> = HashMap::new();
         for (key, value) in headers.iter() {
@@ -119,6 +138,10 @@ impl Container {
             has_immutability_policy,
             has_legal_hold,
             metadata,
+            default_encryption_scope,
+            deny_encryption_scope_override,
+            deleted: false,
+            version: None,
         })
     }
 
@@ -133,6 +156,13 @@ impl Container {
             cast_optional(elem, &["Properties", "PublicAccess"])?.unwrap_or(PublicAccess::None);
         let has_immutability_policy = cast_must(elem, &["Properties", "HasImmutabilityPolicy"])?;
         let has_legal_hold = cast_must(elem, &["Properties", "HasLegalHold"])?;
+        let default_encryption_scope =
+            cast_optional(elem, &["Properties", "DefaultEncryptionScope"])?;
+        let deny_encryption_scope_override =
+            cast_optional(elem, &["Properties", "DenyEncryptionScopeOverride"])?
+                .unwrap_or_default();
+        let deleted = cast_optional(elem, &["Deleted"])?.unwrap_or_default();
+        let version = cast_optional(elem, &["Version"])?;
         let metadata = {
             let mut hm = HashMap::new();
             let metadata = traverse(elem, &["Metadata"], true)?;
@@ -184,6 +214,39 @@ impl Container {
             has_immutability_policy,
             has_legal_hold,
             metadata,
+            default_encryption_scope,
+            deny_encryption_scope_override,
+            deleted,
+            version,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_deleted_container() {
+        let xml = "<Container>\
+            <Name>mycontainer</Name>\
+            <Deleted>true</Deleted>\
+            <Version>01D60F8BB59A4652</Version>\
+            <Properties>\
+                <Last-Modified>Fri, 03 Jan 2020 21:05:32 GMT</Last-Modified>\
+                <Etag>\"0x8D790A4B4E4A4D3\"</Etag>\
+                <LeaseStatus>unlocked</LeaseStatus>\
+                <LeaseState>available</LeaseState>\
+                <HasImmutabilityPolicy>false</HasImmutabilityPolicy>\
+                <HasLegalHold>false</HasLegalHold>\
+            </Properties>\
+        </Container>";
+        let elem: Element = xml.parse().unwrap();
+
+        let container = Container::parse(&elem).unwrap();
+
+        assert_eq!(container.name, "mycontainer");
+        assert!(container.deleted);
+        assert_eq!(container.version.as_deref(), Some("01D60F8BB59A4652"));
+    }
+}
@@ -0,0 +1,480 @@
+use crate::{
+    blob::{BlobType, CopyStatus},
+    prelude::*,
+};
+use azure_core::{
+    error::{Error, ErrorKind, ResultExt},
+    sleep, Context, Url,
+};
+use azure_storage::{
+    prelude::BlobSasPermissions, shared_access_signature::SasToken, StorageCredentialsInner,
+};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use time::OffsetDateTime;
+
+const DEFAULT_MAX_CONCURRENCY: usize = 8;
+pub(crate) const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How long an asynchronous copy may take.
+pub(crate) const DEFAULT_COPY_TIMEOUT: Duration = Duration::from_secs(8 * 60 * 60);
+/// Largest blob Copy Blob From URL copies synchronously.
+const MAX_SYNC_COPY_SIZE: u64 = 256 * 1024 * 1024;
+/// How much longer than the copy timeout the SAS signed to read a source blob is valid, to
+/// cover the time taken to start the copy and clock skew.
+const SOURCE_SAS_MARGIN: Duration = Duration::from_secs(15 * 60);
+const STORAGE_TOKEN_SCOPE: &str = "https://storage.azure.com/.default";
+
+operation! {
+    /// Copy the blobs below a prefix of a source container to this container, server side.
+    ///
+    /// The source container may be in another account. Reading it is authorized with the
+    /// credentials of its client: access keys sign a read-only SAS for each blob, SAS tokens are
+    /// added to the source URLs, and OAuth tokens are sent as the copy source authorization.
+    ///
+    /// Block blobs up to 256 MiB are copied synchronously. Larger blobs and other blob types are
+    /// copied asynchronously, polling their copy status every `poll_interval` until it completes.
+    /// Copies still pending after `copy_timeout`, 8 hours by default, are aborted.
+    /// Asynchronous copies fail on a destination client with a customer-provided key, which Copy
+    /// Blob does not support.
+    ///
+    /// With `skip_identical`, blobs whose destination has the same size and the same Content-MD5
+    /// are not copied again. When either of them has no Content-MD5, the destination is considered
+    /// identical if it was modified after the source. With `dry_run`, nothing is copied and the
+    /// response lists the blobs that would be.
+    CopyDirectory,
+    client: ContainerClient,
+    source: ContainerClient,
+    source_prefix: String,
+    ?destination_prefix: String,
+    ?max_concurrency: usize,
+    ?skip_identical: bool,
+    ?dry_run: bool,
+    ?poll_interval: Duration,
+    ?copy_timeout: Duration,
+    ?progress: TransferProgress
+}
+
+impl CopyDirectoryBuilder {
+    pub fn into_future(self) -> CopyDirectory {
+        Box::pin(async move {
+            let destination_prefix = self
+                .destination_prefix
+                .clone()
+                .unwrap_or_else(|| self.source_prefix.clone());
+            let existing = if self.skip_identical.unwrap_or_default() {
+                list(&self.client, &destination_prefix).await?
+            } else {
+                Vec::new()
+            };
+            let existing: HashMap<_, _> = existing
+                .into_iter()
+                .map(|blob| (blob.name.clone(), blob))
+                .collect();
+
+            let mut blobs = Vec::new();
+            let mut sources = HashMap::new();
+            for source in list(&self.source, &self.source_prefix).await? {
+                let destination =
+                    destination_name(&source.name, &self.source_prefix, &destination_prefix);
+                let outcome = match existing.get(&destination) {
+                    Some(existing) if identical(&source, existing) => CopyOutcome::Skipped,
+                    _ if self.dry_run.unwrap_or_default() => CopyOutcome::Planned,
+                    _ => CopyOutcome::Copied,
+                };
+                blobs.push(CopiedBlob {
+                    source: source.name.clone(),
+                    destination,
+                    size: source.properties.content_length,
+                    outcome,
+                });
+                sources.insert(source.name.clone(), source);
+            }
+
+            let this = &self;
+            let copied = Arc::new(AtomicU64::new(0));
+            let copies = blobs
+                .iter()
+                .filter(|blob| blob.outcome == CopyOutcome::Copied)
+                .map(|blob| {
+                    let blob = blob.clone();
+                    let blob_type = sources[&blob.source].properties.blob_type;
+                    let copied = copied.clone();
+                    async move {
+                        this.copy(&blob, blob_type).await?;
+                        let copied = copied.fetch_add(blob.size, Ordering::SeqCst) + blob.size;
+                        if let Some(progress) = &this.progress {
+                            progress.report(copied);
+                        }
+                        azure_core::Result::Ok(())
+                    }
+                })
+                .collect::<Vec<_>>();
+            stream::iter(copies)
+                .buffer_unordered(
+                    self.max_concurrency
+                        .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+                        .max(1),
+                )
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            Ok(CopyDirectoryResponse { blobs })
+        })
+    }
+
+    async fn copy(&self, blob: &CopiedBlob, blob_type: BlobType) -> azure_core::Result<()> {
+        copy_blob(
+            &self.source.blob_client(&blob.source),
+            &self.client.blob_client(&blob.destination),
+            blob_type,
+            blob.size,
+            self.poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL),
+            self.copy_timeout.unwrap_or(DEFAULT_COPY_TIMEOUT),
+            &self.context,
+        )
+        .await
+    }
+}
+
+/// The blobs of a directory copy, and what was done with them.
+#[derive(Debug, Clone)]
+pub struct CopyDirectoryResponse {
+    pub blobs: Vec<CopiedBlob>,
+}
+
+impl CopyDirectoryResponse {
+    /// The number of bytes copied, or that would be in a dry run.
+    pub fn bytes_copied(&self) -> u64 {
+        self.blobs
+            .iter()
+            .filter(|blob| blob.outcome != CopyOutcome::Skipped)
+            .map(|blob| blob.size)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopiedBlob {
+    /// The name of the blob in the source container.
+    pub source: String,
+    /// The name of the blob in the destination container.
+    pub destination: String,
+    pub size: u64,
+    pub outcome: CopyOutcome,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyOutcome {
+    Copied,
+    /// The destination was identical to the source.
+    Skipped,
+    /// The blob would have been copied, if not for the dry run.
+    Planned,
+}
+
+async fn list(container: &ContainerClient, prefix: &str) -> azure_core::Result<Vec<Blob>> {
+    let mut blobs = Vec::new();
+    let mut pages = container
+        .list_blobs()
+        .prefix(prefix.to_owned())
+        .into_stream();
+    while let Some(page) = pages.next().await {
+        blobs.extend(page?.blobs.blobs().cloned());
+    }
+    Ok(blobs)
+}
+
+fn destination_name(source: &str, source_prefix: &str, destination_prefix: &str) -> String {
+    let relative = source.strip_prefix(source_prefix).unwrap_or(source);
+    format!("{destination_prefix}{relative}")
+}
+
+fn identical(source: &Blob, destination: &Blob) -> bool {
+    let (source, destination) = (&source.properties, &destination.properties);
+    if source.content_length != destination.content_length {
+        return false;
+    }
+    match (&source.content_md5, &destination.content_md5) {
+        (Some(source), Some(destination)) => source == destination,
+        _ => destination.last_modified >= source.last_modified,
+    }
+}
+
+/// Copy a blob server side, polling the status of asynchronous copies until they complete, or
+/// aborting them after `timeout`.
+pub(crate) async fn copy_blob(
+    source: &BlobClient,
+    destination: &BlobClient,
+    blob_type: BlobType,
+    size: u64,
+    poll_interval: Duration,
+    timeout: Duration,
+    context: &Context,
+) -> azure_core::Result<()> {
+    let (source_url, source_authorization) = source_url(source, timeout).await?;
+
+    if blob_type == BlobType::BlockBlob && size <= MAX_SYNC_COPY_SIZE {
+        let mut copy = destination
+            .copy_from_url(source_url)
+            .is_synchronous(true)
+            .context(context.clone());
+        if let Some(source_authorization) = source_authorization {
+            copy = copy.source_authorization(source_authorization);
+        }
+        copy.await?;
+        return Ok(());
+    }
+
+    let mut copy = destination.copy(source_url).context(context.clone());
+    if let Some(source_authorization) = source_authorization {
+        copy = copy.source_authorization(source_authorization);
+    }
+    let deadline = OffsetDateTime::now_utc() + timeout;
+    let copy = copy.await?;
+    let mut status = copy.copy_status;
+    let mut description = None;
+    while status == CopyStatus::Pending {
+        if OffsetDateTime::now_utc() >= deadline {
+            destination
+                .abort_copy(copy.copy_id)
+                .context(context.clone())
+                .await?;
+            return Err(Error::with_message(ErrorKind::Other, || {
+                format!(
+                    "copy of {} to {} did not complete within {timeout:?}",
+                    source.blob_name(),
+                    destination.blob_name(),
+                )
+            }));
+        }
+        sleep(poll_interval).await;
+        let properties = destination
+            .get_properties()
+            .context(context.clone())
+            .await?
+            .blob
+            .properties;
+        status = properties.copy_status.unwrap_or(CopyStatus::Success);
+        description = properties.copy_status_description;
+    }
+    if status != CopyStatus::Success {
+        return Err(Error::with_message(ErrorKind::Other, || {
+            format!(
+                "copy of {} to {} {status}: {}",
+                source.blob_name(),
+                destination.blob_name(),
+                description.unwrap_or_default()
+            )
+        }));
+    }
+    Ok(())
+}
+
+/// The URL the service reads a source blob from during a copy of at most `timeout`, and the
+/// authorization to send with it.
+async fn source_url(
+    source: &BlobClient,
+    timeout: Duration,
+) -> azure_core::Result<(Url, Option<String>)> {
+    let credentials = source
+        .container_client()
+        .credentials()
+        .0
+        .read()
+        .await
+        .clone();
+    let mut url = source.url()?;
+    let authorization = match credentials {
+        StorageCredentialsInner::Key(..) => {
+            let signature = source
+                .shared_access_signature(
+                    BlobSasPermissions {
+                        read: true,
+                        ..Default::default()
+                    },
+                    OffsetDateTime::now_utc() + timeout + SOURCE_SAS_MARGIN,
+                )
+                .await?;
+            url.set_query(Some(&signature.token()?));
+            None
+        }
+        StorageCredentialsInner::SASToken(query_pairs) => {
+            url.query_pairs_mut().extend_pairs(query_pairs);
+            None
+        }
+        StorageCredentialsInner::BearerToken(token) => Some(format!("Bearer {}", token.secret())),
+        StorageCredentialsInner::TokenCredential(token_credential) => {
+            let token = token_credential
+                .get_token(&[STORAGE_TOKEN_SCOPE])
+                .await
+                .context(ErrorKind::Credential, "failed to get bearer token")?;
+            Some(format!("Bearer {}", token.token.secret()))
+        }
+        StorageCredentialsInner::Anonymous => None,
+    };
+    Ok((url, authorization))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::{
+        headers::{self, Headers},
+        BytesStream, Method, Policy, PolicyResult, Request, Response, RetryOptions, StatusCode,
+        TransportOptions,
+    };
+    use std::sync::Mutex;
+
+    /// Keep every asynchronous copy pending, recording the requests made.
+    #[derive(Debug, Default)]
+    struct PendingCopy {
+        requests: Mutex<Vec<&'static str>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Policy for PendingCopy {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            let (kind, status) = if request.method() == &Method::Head {
+                ("get_properties", StatusCode::Ok)
+            } else if request
+                .url()
+                .query()
+                .unwrap_or_default()
+                .contains("comp=copy")
+            {
+                ("abort_copy", StatusCode::NoContent)
+            } else {
+                ("copy", StatusCode::Accepted)
+            };
+            self.requests.lock().unwrap().push(kind);
+
+            let mut h = Headers::new();
+            h.insert(headers::CREATION_TIME, "Mon, 01 Jan 2024 00:00:00 GMT");
+            h.insert(headers::LAST_MODIFIED, "Mon, 01 Jan 2024 00:00:00 GMT");
+            h.insert(headers::DATE, "Mon, 01 Jan 2024 00:00:00 GMT");
+            h.insert(headers::ETAG, "\"0x8DC0\"");
+            h.insert(headers::REQUEST_ID, "00000000-0000-0000-0000-000000000000");
+            h.insert(headers::VERSION, "2022-11-02");
+            h.insert(headers::SERVER, "Windows-Azure-Blob/1.0");
+            h.insert(headers::CONTENT_LENGTH, "0");
+            h.insert(headers::BLOB_TYPE, "PageBlob");
+            h.insert(headers::SERVER_ENCRYPTED, "true");
+            h.insert(headers::COPY_STATUS, "pending");
+            h.insert(
+                azure_storage::headers::COPY_ID,
+                "00000000-0000-0000-0000-000000000001",
+            );
+            Ok(Response::new(status, h, Box::pin(BytesStream::new_empty())))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_copy_timeout_aborts_copy() {
+        let policy = Arc::new(PendingCopy::default());
+        let container = ClientBuilder::emulator()
+            .retry(RetryOptions::none())
+            .transport(TransportOptions::new_custom_policy(policy.clone()))
+            .container_client("container");
+
+        let result = copy_blob(
+            &container.blob_client("source"),
+            &container.blob_client("destination"),
+            BlobType::PageBlob,
+            1024 * 1024 * 1024,
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+            &Context::new(),
+        )
+        .await;
+        assert!(result.is_err());
+
+        let requests = policy.requests.lock().unwrap().clone();
+        assert_eq!(requests.first(), Some(&"copy"));
+        assert_eq!(requests.last(), Some(&"abort_copy"));
+        assert!(requests.contains(&"get_properties"));
+    }
+
+    #[tokio::test]
+    async fn test_source_sas_outlives_copy_timeout() -> azure_core::Result<()> {
+        let source = ClientBuilder::emulator().blob_client("container", "source");
+        let timeout = Duration::from_secs(24 * 60 * 60);
+        let (url, authorization) = source_url(&source, timeout).await?;
+        assert!(authorization.is_none());
+
+        let expiry = url
+            .query_pairs()
+            .find(|(key, _)| key == "se")
+            .map(|(_, value)| value.into_owned())
+            .unwrap();
+        let expiry =
+            OffsetDateTime::parse(&expiry, &time::format_description::well_known::Rfc3339).unwrap();
+        let expected = OffsetDateTime::now_utc() + timeout + SOURCE_SAS_MARGIN;
+        assert!((expected - expiry).abs() < time::Duration::minutes(1));
+        Ok(())
+    }
+
+    fn blob(length: u64, last_modified: &str, md5: Option<&str>) -> Blob {
+        let mut h = Headers::new();
+        h.insert(headers::CREATION_TIME, "Mon, 01 Jan 2024 00:00:00 GMT");
+        h.insert(headers::CONTENT_LENGTH, length.to_string());
+        h.insert(headers::LAST_MODIFIED, last_modified.to_owned());
+        h.insert(headers::ETAG, "\"0x8DC0\"");
+        h.insert(headers::BLOB_TYPE, "BlockBlob");
+        h.insert(headers::SERVER_ENCRYPTED, "true");
+        if let Some(md5) = md5 {
+            h.insert(headers::CONTENT_MD5, md5.to_owned());
+        }
+        Blob::from_headers("blob", &h).unwrap()
+    }
+
+    #[test]
+    fn test_identical() {
+        let old = "Mon, 01 Jan 2024 00:00:00 GMT";
+        let new = "Tue, 02 Jan 2024 00:00:00 GMT";
+        let md5 = "XrY7u+Ae7tCTyyK7j1rNww==";
+        let other_md5 = "mZFLkyvTelC5g8XnyQrpOw==";
+
+        assert!(identical(
+            &blob(10, old, Some(md5)),
+            &blob(10, old, Some(md5))
+        ));
+        assert!(!identical(
+            &blob(10, old, Some(md5)),
+            &blob(10, new, Some(other_md5))
+        ));
+        assert!(!identical(
+            &blob(10, old, Some(md5)),
+            &blob(11, new, Some(md5))
+        ));
+        assert!(identical(&blob(10, old, None), &blob(10, new, Some(md5))));
+        assert!(!identical(&blob(10, new, None), &blob(10, old, None)));
+    }
+
+    #[test]
+    fn test_destination_name() {
+        assert_eq!(
+            destination_name("logs/a.txt", "logs/", "archive/"),
+            "archive/a.txt"
+        );
+        assert_eq!(
+            destination_name("logs/a.txt", "logs/", "logs/"),
+            "logs/a.txt"
+        );
+        assert_eq!(
+            destination_name("logs/a.txt", "", "backup/"),
+            "backup/logs/a.txt"
+        );
+    }
+}
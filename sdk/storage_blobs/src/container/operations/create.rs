@@ -1,12 +1,19 @@
 use crate::prelude::*;
 use azure_core::Method;
 use azure_core::{headers::AsHeaders, headers::Headers, prelude::*};
+use azure_storage::headers::{DEFAULT_ENCRYPTION_SCOPE, DENY_ENCRYPTION_SCOPE_OVERRIDE};
 
 operation! {
+    /// Create a container.
+    ///
+    /// The default encryption scope of the container is the encryption scope of the client, unless
+    /// `default_encryption_scope` is set.
     Create,
     client: ContainerClient,
     ?public_access: PublicAccess,
-    ?metadata: Metadata
+    ?metadata: Metadata,
+    ?default_encryption_scope: EncryptionScope,
+    ?deny_encryption_scope_override: bool
 }
 
 impl CreateBuilder {
@@ -31,6 +38,22 @@ impl CreateBuilder {
                 headers.insert(name, value);
             }
 
+            let default_encryption_scope = self
+                .default_encryption_scope
+                .or_else(|| self.client.encryption_scope().cloned());
+            if let Some(default_encryption_scope) = default_encryption_scope {
+                headers.insert(
+                    DEFAULT_ENCRYPTION_SCOPE,
+                    default_encryption_scope.as_str().to_owned(),
+                );
+                if let Some(deny_encryption_scope_override) = self.deny_encryption_scope_override {
+                    headers.insert(
+                        DENY_ENCRYPTION_SCOPE_OVERRIDE,
+                        deny_encryption_scope_override.to_string(),
+                    );
+                }
+            }
+
             let mut request = ContainerClient::finalize_request(url, Method::Put, headers, None)?;
 
             let _response = self.client.send(&mut self.context, &mut request).await?;
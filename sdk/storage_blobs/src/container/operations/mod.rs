@@ -1,20 +1,30 @@
 pub mod acquire_lease;
 pub mod break_lease;
+pub mod copy_directory;
 pub mod create;
 pub mod delete;
 pub mod get_acl;
 pub mod get_properties;
 pub mod list_blobs;
 pub mod release_lease;
+pub mod rename;
 pub mod renew_lease;
 pub mod set_acl;
+pub mod set_metadata;
+pub mod undelete;
+pub mod walk;
 pub use self::acquire_lease::*;
 pub use self::break_lease::*;
+pub use self::copy_directory::*;
 pub use self::create::*;
 pub use self::delete::*;
 pub use self::get_acl::*;
 pub use self::get_properties::*;
 pub use self::list_blobs::*;
 pub use self::release_lease::*;
+pub use self::rename::*;
 pub use self::renew_lease::*;
 pub use self::set_acl::*;
+pub use self::set_metadata::*;
+pub use self::undelete::*;
+pub use self::walk::*;
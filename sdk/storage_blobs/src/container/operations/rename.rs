@@ -0,0 +1,38 @@
+use crate::prelude::*;
+use azure_core::{headers::*, prelude::*, Method, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    Rename,
+    client: ContainerClient,
+    source_container_name: String,
+    ?source_lease_id: SourceLeaseId
+}
+
+impl RenameBuilder {
+    pub fn into_future(mut self) -> Rename {
+        Box::pin(async move {
+            // the request targets the new name of the container
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut()
+                .append_pair("restype", "container")
+                .append_pair("comp", "rename");
+
+            let mut headers = Headers::new();
+            headers.insert(SOURCE_CONTAINER_NAME, self.source_container_name.clone());
+            headers.add(self.source_lease_id);
+
+            let mut request = ContainerClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            RenameResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(RenameResponse,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
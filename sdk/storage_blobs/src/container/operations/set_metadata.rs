@@ -0,0 +1,43 @@
+use crate::prelude::*;
+use azure_core::{headers::*, prelude::*, Method, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    SetMetadata,
+    client: ContainerClient,
+    metadata: Metadata,
+    ?lease_id: LeaseId,
+    ?if_modified_since: IfModifiedSince
+}
+
+impl SetMetadataBuilder {
+    pub fn into_future(mut self) -> SetMetadata {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut()
+                .append_pair("restype", "container")
+                .append_pair("comp", "metadata");
+
+            let mut headers = Headers::new();
+            for m in self.metadata.iter() {
+                headers.add(m);
+            }
+            headers.add(self.lease_id);
+            headers.add(self.if_modified_since);
+
+            let mut request = ContainerClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            SetMetadataResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(SetMetadataResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
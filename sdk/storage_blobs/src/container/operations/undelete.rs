@@ -0,0 +1,42 @@
+use crate::prelude::*;
+use azure_core::{headers::*, Method, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    Undelete,
+    client: ContainerClient,
+    deleted_container_version: String,
+}
+
+impl UndeleteBuilder {
+    pub fn into_future(mut self) -> Undelete {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut()
+                .append_pair("restype", "container")
+                .append_pair("comp", "undelete");
+
+            let mut headers = Headers::new();
+            headers.insert(
+                DELETED_CONTAINER_NAME,
+                self.client.container_name().to_owned(),
+            );
+            headers.insert(
+                DELETED_CONTAINER_VERSION,
+                self.deleted_container_version.clone(),
+            );
+
+            let mut request = ContainerClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            UndeleteResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(UndeleteResponse,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
@@ -0,0 +1,507 @@
+use crate::{
+    container::operations::{BlobItem, BlobPrefix, ListBlobsResponse},
+    prelude::*,
+};
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+};
+use futures::{
+    future::BoxFuture,
+    stream::{self, BoxStream, FuturesUnordered, StreamExt},
+};
+use std::collections::VecDeque;
+use time::OffsetDateTime;
+
+const DELIMITER: &str = "/";
+const DEFAULT_MAX_CONCURRENCY: usize = 8;
+
+operation! {
+    #[stream]
+    /// Walk the virtual directory tree below a prefix, listing sibling directories concurrently.
+    ///
+    /// Virtual directories are separated by `/`. Entries are yielded as soon as their page is
+    /// listed, so the order across directories is not deterministic. The `pattern`,
+    /// `modified_since` and `access_tier` filters only apply to blobs; every virtual directory
+    /// reached is yielded.
+    Walk,
+    client: ContainerClient,
+    prefix: String,
+    ?max_depth: usize,
+    ?max_concurrency: usize,
+    ?pattern: String,
+    ?modified_since: OffsetDateTime,
+    ?access_tier: AccessTier,
+    ?include_metadata: bool,
+    ?max_results: MaxResults
+}
+
+/// An entry found while walking a container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalkEntry {
+    /// How many virtual directories below the walked prefix the entry is, starting at 0.
+    pub depth: usize,
+    pub item: BlobItem,
+}
+
+impl WalkEntry {
+    pub fn blob(&self) -> Option<&Blob> {
+        match &self.item {
+            BlobItem::Blob(blob) => Some(blob),
+            BlobItem::BlobPrefix(_) => None,
+        }
+    }
+
+    pub fn prefix(&self) -> Option<&BlobPrefix> {
+        match &self.item {
+            BlobItem::BlobPrefix(prefix) => Some(prefix),
+            BlobItem::Blob(_) => None,
+        }
+    }
+}
+
+type Page = (String, usize, azure_core::Result<ListBlobsResponse>);
+
+struct WalkState {
+    builder: WalkBuilder,
+    pending: VecDeque<(String, usize, Option<NextMarker>)>,
+    in_flight: FuturesUnordered<BoxFuture<'static, Page>>,
+    ready: VecDeque<WalkEntry>,
+    failed: bool,
+}
+
+impl WalkBuilder {
+    pub fn into_stream(self) -> BoxStream<'static, azure_core::Result<WalkEntry>> {
+        let state = WalkState {
+            pending: VecDeque::from([(self.prefix.clone(), 0, None)]),
+            builder: self,
+            in_flight: FuturesUnordered::new(),
+            ready: VecDeque::new(),
+            failed: false,
+        };
+
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(entry) = state.ready.pop_front() {
+                    return Some((Ok(entry), state));
+                }
+                if state.failed {
+                    return None;
+                }
+
+                let max_concurrency = state
+                    .builder
+                    .max_concurrency
+                    .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+                    .max(1);
+                while state.in_flight.len() < max_concurrency {
+                    let Some((prefix, depth, marker)) = state.pending.pop_front() else {
+                        break;
+                    };
+                    state
+                        .in_flight
+                        .push(state.builder.list_page(prefix, depth, marker));
+                }
+
+                let (prefix, depth, page) = state.in_flight.next().await?;
+                match page {
+                    Ok(page) => state.push_page(prefix, depth, page),
+                    Err(err) => {
+                        state.failed = true;
+                        return Some((Err(err), state));
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+
+    fn list_page(
+        &self,
+        prefix: String,
+        depth: usize,
+        marker: Option<NextMarker>,
+    ) -> BoxFuture<'static, Page> {
+        let mut list_blobs = self
+            .client
+            .list_blobs()
+            .prefix(prefix.clone())
+            .delimiter(DELIMITER)
+            .include_metadata(self.include_metadata.unwrap_or_default())
+            .context(self.context.clone());
+        if let Some(marker) = marker {
+            list_blobs = list_blobs.marker(marker);
+        }
+        if let Some(max_results) = self.max_results {
+            list_blobs = list_blobs.max_results(max_results);
+        }
+
+        Box::pin(async move {
+            let page = list_blobs.into_stream().next().await.unwrap_or_else(|| {
+                Err(Error::message(
+                    ErrorKind::Other,
+                    "listing blobs returned no page",
+                ))
+            });
+            (prefix, depth, page)
+        })
+    }
+
+    fn matches(&self, blob: &Blob) -> bool {
+        if let Some(pattern) = &self.pattern {
+            let pattern = pattern.chars().collect::<Vec<_>>();
+            let name = blob.name.chars().collect::<Vec<_>>();
+            if !glob_match(&pattern, &name) {
+                return false;
+            }
+        }
+        if let Some(modified_since) = self.modified_since {
+            if blob.properties.last_modified <= modified_since {
+                return false;
+            }
+        }
+        if let Some(access_tier) = self.access_tier {
+            if blob.properties.access_tier != Some(access_tier) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl WalkState {
+    fn push_page(&mut self, prefix: String, depth: usize, page: ListBlobsResponse) {
+        if let Some(marker) = page.next_marker {
+            self.pending.push_back((prefix, depth, Some(marker)));
+        }
+
+        for item in page.blobs.items {
+            match &item {
+                BlobItem::Blob(blob) if !self.builder.matches(blob) => continue,
+                BlobItem::Blob(_) => {}
+                BlobItem::BlobPrefix(prefix) => {
+                    if self.builder.max_depth.map_or(true, |max| depth < max) {
+                        self.pending
+                            .push_back((prefix.name.clone(), depth + 1, None));
+                    }
+                }
+            }
+            self.ready.push_back(WalkEntry { depth, item });
+        }
+    }
+}
+
+/// Match a blob name against a glob pattern.
+///
+/// `?` matches a single character and `*` any run of characters, neither crossing a `/`.
+/// `**` matches across `/`, and `**/` also matches no directory at all.
+///
+/// Every way the pattern can match is followed at once, one name character at a time, instead of
+/// backtracking, so matching takes time proportional to the lengths of the pattern and the name.
+fn glob_match(pattern: &[char], name: &[char]) -> bool {
+    let tokens = glob_tokens(pattern);
+    // `states[i]` is whether the name read so far matches the first `i` tokens of the pattern
+    let mut states = vec![false; tokens.len() + 1];
+    states[0] = true;
+    skip_empty_tokens(&tokens, &mut states);
+    for c in name {
+        let mut next = vec![false; tokens.len() + 1];
+        for (i, token) in tokens.iter().enumerate() {
+            if !states[i] {
+                continue;
+            }
+            match token {
+                GlobToken::Char(p) if p == c => next[i + 1] = true,
+                GlobToken::Any if *c != '/' => next[i + 1] = true,
+                GlobToken::Star if *c != '/' => next[i] = true,
+                GlobToken::Globstar => next[i] = true,
+                _ => {}
+            }
+        }
+        skip_empty_tokens(&tokens, &mut next);
+        if !next.contains(&true) {
+            return false;
+        }
+        states = next;
+    }
+    states[tokens.len()]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GlobToken {
+    Char(char),
+    /// `?`
+    Any,
+    /// `*`
+    Star,
+    /// `**`
+    Globstar,
+    /// Before the `**` and `/` of a `**/`, which may match nothing at all.
+    SkipDirectories,
+}
+
+fn glob_tokens(pattern: &[char]) -> Vec<GlobToken> {
+    let mut tokens = Vec::with_capacity(pattern.len());
+    let mut i = 0;
+    while i < pattern.len() {
+        let token = match &pattern[i..] {
+            ['*', '*', '/', ..] => {
+                tokens.push(GlobToken::SkipDirectories);
+                tokens.push(GlobToken::Globstar);
+                i += 3;
+                GlobToken::Char('/')
+            }
+            ['*', '*', ..] => {
+                i += 2;
+                GlobToken::Globstar
+            }
+            ['*', ..] => {
+                i += 1;
+                GlobToken::Star
+            }
+            ['?', ..] => {
+                i += 1;
+                GlobToken::Any
+            }
+            [c, ..] => {
+                i += 1;
+                GlobToken::Char(*c)
+            }
+            [] => unreachable!(),
+        };
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Extend the matched states with the ones reached by tokens matching no character.
+fn skip_empty_tokens(tokens: &[GlobToken], states: &mut [bool]) {
+    for (i, token) in tokens.iter().enumerate() {
+        if !states[i] {
+            continue;
+        }
+        match token {
+            GlobToken::Star | GlobToken::Globstar => states[i + 1] = true,
+            GlobToken::SkipDirectories => {
+                states[i + 1] = true;
+                states[i + 3] = true;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        let pattern = pattern.chars().collect::<Vec<_>>();
+        let name = name.chars().collect::<Vec<_>>();
+        glob_match(&pattern, &name)
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(matches("logs/*.csv", "logs/a.csv"));
+        assert!(!matches("logs/*.csv", "logs/2023/a.csv"));
+        assert!(matches("logs/**/*.csv", "logs/2023/01/a.csv"));
+        assert!(matches("logs/**/*.csv", "logs/a.csv"));
+        assert!(matches("**", "a/b/c"));
+        assert!(matches("data?.bin", "data1.bin"));
+        assert!(!matches("data?.bin", "data/.bin"));
+        assert!(!matches("*.csv", "a.csv.gz"));
+        assert!(matches("logs/**/a.csv", "logs/a.csv"));
+        assert!(!matches("logs/**/a.csv", "logs/xa.csv"));
+        assert!(matches("**/*.csv", "a/b/c.csv"));
+        assert!(matches("*/b/*", "a/b/c"));
+        assert!(!matches("*b", "a/b"));
+        assert!(matches("a*b*c", "aXbYbZc"));
+        assert!(!matches("a*b*c", "aXb/c"));
+        assert!(matches("**b*c", "a/b/bxc"));
+        assert!(!matches("**b*c", "a/b/xc"));
+        assert!(matches("", ""));
+        assert!(!matches("", "a"));
+    }
+
+    #[test]
+    fn glob_long_names() {
+        // a recursive matcher backtracks exponentially on these
+        let name = "a".repeat(64);
+        assert!(!matches(&format!("{}b", "*a".repeat(16)), &name));
+        assert!(!matches(&format!("{}b", "**a".repeat(16)), &name));
+    }
+
+    fn blob(name: &str, last_modified: &str, access_tier: &str) -> BlobItem {
+        use azure_core::headers::{self, Headers};
+
+        let mut h = Headers::new();
+        h.insert(headers::CREATION_TIME, "Mon, 01 Jan 2024 00:00:00 GMT");
+        h.insert(headers::CONTENT_LENGTH, "10");
+        h.insert(headers::LAST_MODIFIED, last_modified.to_owned());
+        h.insert(headers::ETAG, "\"0x8DC0\"");
+        h.insert(headers::BLOB_TYPE, "BlockBlob");
+        h.insert(headers::SERVER_ENCRYPTED, "true");
+        h.insert(headers::BLOB_ACCESS_TIER, access_tier.to_owned());
+        BlobItem::Blob(Blob::from_headers(name, &h).unwrap())
+    }
+
+    fn prefix(name: &str) -> BlobItem {
+        BlobItem::BlobPrefix(BlobPrefix {
+            name: name.to_owned(),
+        })
+    }
+
+    fn page(items: Vec<BlobItem>, next_marker: Option<&str>) -> ListBlobsResponse {
+        ListBlobsResponse {
+            prefix: None,
+            max_results: None,
+            delimiter: Some(DELIMITER.to_owned()),
+            next_marker: next_marker.map(|marker| NextMarker::new(marker.to_owned())),
+            blobs: crate::container::operations::list_blobs::Blobs { items },
+            request_id: uuid::Uuid::nil(),
+            date: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    fn walk_state(builder: WalkBuilder) -> WalkState {
+        WalkState {
+            builder,
+            pending: VecDeque::new(),
+            in_flight: FuturesUnordered::new(),
+            ready: VecDeque::new(),
+            failed: false,
+        }
+    }
+
+    fn pending(state: &WalkState) -> Vec<(&str, usize, Option<&str>)> {
+        state
+            .pending
+            .iter()
+            .map(|(prefix, depth, marker)| {
+                (
+                    prefix.as_str(),
+                    *depth,
+                    marker.as_ref().map(NextMarker::as_str),
+                )
+            })
+            .collect()
+    }
+
+    fn ready(state: &WalkState) -> Vec<(&str, usize)> {
+        state
+            .ready
+            .iter()
+            .map(|entry| match &entry.item {
+                BlobItem::Blob(blob) => (blob.name.as_str(), entry.depth),
+                BlobItem::BlobPrefix(prefix) => (prefix.name.as_str(), entry.depth),
+            })
+            .collect()
+    }
+
+    const OLD: &str = "Mon, 01 Jan 2024 00:00:00 GMT";
+    const NEW: &str = "Mon, 01 Jul 2024 00:00:00 GMT";
+
+    #[test]
+    fn push_page_queues_directories_and_next_markers() {
+        let client = ClientBuilder::emulator().container_client("container");
+        let mut state = walk_state(client.walk("logs/"));
+
+        state.push_page(
+            "logs/".to_owned(),
+            0,
+            page(
+                vec![blob("logs/a.csv", OLD, "Hot"), prefix("logs/2024/")],
+                Some("marker"),
+            ),
+        );
+        assert_eq!(
+            pending(&state),
+            [("logs/", 0, Some("marker")), ("logs/2024/", 1, None)]
+        );
+        assert_eq!(ready(&state), [("logs/a.csv", 0), ("logs/2024/", 0)]);
+
+        // the next page of a directory keeps its depth
+        state.push_page(
+            "logs/".to_owned(),
+            0,
+            page(vec![blob("logs/b.csv", OLD, "Hot")], None),
+        );
+        assert_eq!(ready(&state)[2..], [("logs/b.csv", 0)]);
+        assert_eq!(pending(&state).len(), 2);
+    }
+
+    #[test]
+    fn push_page_limits_depth() {
+        let client = ClientBuilder::emulator().container_client("container");
+        let mut state = walk_state(client.walk("logs/").max_depth(1usize));
+
+        state.push_page(
+            "logs/".to_owned(),
+            0,
+            page(vec![prefix("logs/2024/")], None),
+        );
+        state.push_page(
+            "logs/2024/".to_owned(),
+            1,
+            page(
+                vec![prefix("logs/2024/01/"), blob("logs/2024/a.csv", OLD, "Hot")],
+                None,
+            ),
+        );
+        // directories beyond the maximum depth are yielded but not listed
+        assert_eq!(pending(&state), [("logs/2024/", 1, None)]);
+        assert_eq!(
+            ready(&state),
+            [
+                ("logs/2024/", 0),
+                ("logs/2024/01/", 1),
+                ("logs/2024/a.csv", 1)
+            ]
+        );
+
+        let mut state = walk_state(client.walk("logs/").max_depth(0usize));
+        state.push_page(
+            "logs/".to_owned(),
+            0,
+            page(vec![prefix("logs/2024/")], None),
+        );
+        assert!(pending(&state).is_empty());
+        assert_eq!(ready(&state), [("logs/2024/", 0)]);
+    }
+
+    #[test]
+    fn push_page_filters_blobs() {
+        let client = ClientBuilder::emulator().container_client("container");
+        let modified_since = OffsetDateTime::parse(
+            "Mon, 01 Apr 2024 00:00:00 GMT",
+            &time::format_description::well_known::Rfc2822,
+        )
+        .unwrap();
+        let mut state = walk_state(
+            client
+                .walk("logs/")
+                .pattern("logs/**/*.csv")
+                .modified_since(modified_since)
+                .access_tier(AccessTier::Hot),
+        );
+
+        state.push_page(
+            "logs/".to_owned(),
+            0,
+            page(
+                vec![
+                    blob("logs/new.csv", NEW, "Hot"),
+                    blob("logs/old.csv", OLD, "Hot"),
+                    blob("logs/cool.csv", NEW, "Cool"),
+                    blob("logs/new.json", NEW, "Hot"),
+                    prefix("logs/2024/"),
+                ],
+                None,
+            ),
+        );
+        // the filters only apply to blobs
+        assert_eq!(ready(&state), [("logs/new.csv", 0), ("logs/2024/", 0)]);
+        assert_eq!(pending(&state), [("logs/2024/", 1, None)]);
+    }
+}
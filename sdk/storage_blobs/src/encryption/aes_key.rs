@@ -0,0 +1,61 @@
+use super::KeyEncryptionKey;
+use aes_kw::KekAes256;
+use azure_core::error::{Error, ErrorKind};
+use std::fmt;
+
+const ALGORITHM: &str = "A256KW";
+
+/// A local AES-256 key encryption key, wrapping keys with the AES key wrap algorithm (RFC 3394).
+#[derive(Clone)]
+pub struct AesKeyEncryptionKey {
+    key_id: String,
+    kek: KekAes256,
+}
+
+impl AesKeyEncryptionKey {
+    pub fn new(key_id: impl Into<String>, key: [u8; 32]) -> Self {
+        Self {
+            key_id: key_id.into(),
+            kek: KekAes256::from(key),
+        }
+    }
+}
+
+impl fmt::Debug for AesKeyEncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AesKeyEncryptionKey")
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl KeyEncryptionKey for AesKeyEncryptionKey {
+    fn key_id(&self) -> String {
+        self.key_id.clone()
+    }
+
+    fn algorithm(&self) -> String {
+        ALGORITHM.to_owned()
+    }
+
+    async fn wrap_key(&self, key: &[u8]) -> azure_core::Result<Vec<u8>> {
+        self.kek.wrap_vec(key).map_err(|error| {
+            Error::with_message(ErrorKind::Other, || format!("failed to wrap key: {error}"))
+        })
+    }
+
+    async fn unwrap_key(&self, wrapped_key: &[u8], algorithm: &str) -> azure_core::Result<Vec<u8>> {
+        if algorithm != ALGORITHM {
+            return Err(Error::with_message(ErrorKind::Other, || {
+                format!("cannot unwrap a key wrapped with {algorithm} using {ALGORITHM}")
+            }));
+        }
+        self.kek.unwrap_vec(wrapped_key).map_err(|error| {
+            Error::with_message(ErrorKind::Credential, || {
+                format!("failed to unwrap key: {error}")
+            })
+        })
+    }
+}
@@ -0,0 +1,89 @@
+use super::KeyEncryptionKey;
+use azure_security_keyvault::prelude::{
+    CryptographParamtersEncryption, EncryptionAlgorithm, KeyClient, RsaEncryptionParameters,
+    UnwrapKeyParameters, WrapKeyParameters,
+};
+
+/// A key encryption key stored in Azure Key Vault, wrapping keys with its wrap and unwrap operations.
+#[derive(Debug, Clone)]
+pub struct KeyVaultKeyEncryptionKey {
+    client: KeyClient,
+    name: String,
+    version: String,
+    key_id: String,
+    algorithm: EncryptionAlgorithm,
+}
+
+impl KeyVaultKeyEncryptionKey {
+    /// Use a version of an RSA key, wrapping keys with `RSA-OAEP`.
+    ///
+    /// A version is required so that blobs can still be decrypted after the key is rotated.
+    pub fn new(client: KeyClient, name: impl Into<String>, version: impl Into<String>) -> Self {
+        let name = name.into();
+        let version = version.into();
+        let key_id = format!(
+            "{}/keys/{name}/{version}",
+            client.vault_url().as_str().trim_end_matches('/')
+        );
+        Self {
+            client,
+            name,
+            version,
+            key_id,
+            algorithm: EncryptionAlgorithm::RsaOaep,
+        }
+    }
+
+    setters! {
+        algorithm: EncryptionAlgorithm => algorithm,
+    }
+
+    fn parameters(algorithm: &str) -> azure_core::Result<CryptographParamtersEncryption> {
+        let algorithm = serde_json::from_value(serde_json::Value::String(algorithm.to_owned()))?;
+        Ok(CryptographParamtersEncryption::Rsa(
+            RsaEncryptionParameters::new(algorithm)?,
+        ))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl KeyEncryptionKey for KeyVaultKeyEncryptionKey {
+    /// The full identifier of the key version, `https://{vault}/keys/{name}/{version}`.
+    fn key_id(&self) -> String {
+        self.key_id.clone()
+    }
+
+    fn algorithm(&self) -> String {
+        serde_json::to_value(&self.algorithm)
+            .ok()
+            .and_then(|algorithm| algorithm.as_str().map(str::to_owned))
+            .unwrap_or_default()
+    }
+
+    async fn wrap_key(&self, key: &[u8]) -> azure_core::Result<Vec<u8>> {
+        let parameters = WrapKeyParameters {
+            encrypt_parameters_encryption: Self::parameters(&self.algorithm())?,
+            plaintext: key.to_vec(),
+        };
+        let result = self
+            .client
+            .wrap_key(&self.name, parameters)
+            .version(self.version.clone())
+            .await?;
+        Ok(result.result)
+    }
+
+    async fn unwrap_key(&self, wrapped_key: &[u8], algorithm: &str) -> azure_core::Result<Vec<u8>> {
+        let parameters = UnwrapKeyParameters {
+            decrypt_parameters_encryption: Self::parameters(algorithm)?,
+            ciphertext: wrapped_key.to_vec(),
+        };
+        let result = self
+            .client
+            .unwrap_key(&self.name, parameters)
+            .version(self.version.clone())
+            .await?;
+        Ok(result.result)
+    }
+}
@@ -0,0 +1,546 @@
+//! Client-side encryption of blob contents.
+//!
+//! Blobs are encrypted with the version 2.0 envelope format shared by the other Azure SDKs:
+//! the content is split into regions of 4 MiB, each encrypted with AES-256-GCM under a
+//! random content encryption key, and stored as the nonce, the ciphertext and the
+//! authentication tag of every region. The content encryption key is wrapped by a
+//! [`KeyEncryptionKey`] and stored, together with the layout of the regions, as JSON in
+//! the `encryptiondata` metadata of the blob.
+//!
+//! Client-side encryption is enabled with [`ClientBuilder::client_side_encryption`](crate::prelude::ClientBuilder::client_side_encryption).
+//! Block blobs written with `put_block_blob` or `upload` are then encrypted, and blobs read with
+//! `get` are decrypted, ranged reads included. Reading a blob that is not encrypted is an error,
+//! unless [`ClientSideEncryption::allow_unencrypted_blobs`] is set.
+//!
+//! ref: <https://docs.microsoft.com/azure/storage/common/storage-client-side-encryption>
+
+mod aes_key;
+#[cfg(feature = "keyvault")]
+mod keyvault;
+
+pub use aes_key::AesKeyEncryptionKey;
+#[cfg(feature = "keyvault")]
+pub use keyvault::KeyVaultKeyEncryptionKey;
+
+use crate::options::{ChecksumAlgorithm, Hash};
+use aes_gcm::{
+    aead::{Aead, KeyInit, OsRng},
+    AeadCore, Aes256Gcm, Key, Nonce,
+};
+use azure_core::{
+    base64,
+    error::{Error, ErrorKind, ResultExt},
+};
+use bytes::{Bytes, BytesMut};
+use std::{collections::HashMap, fmt, sync::Arc};
+
+/// The metadata holding the encryption data of a blob.
+pub const ENCRYPTION_DATA_METADATA: &str = "encryptiondata";
+
+const PROTOCOL_V2: &str = "2.0";
+const AES_GCM_256: &str = "AES_GCM_256";
+/// Plaintext bytes in every region but the last.
+const REGION_LENGTH: u64 = 4 * 1024 * 1024;
+const NONCE_LENGTH: u64 = 12;
+const TAG_LENGTH: u64 = 16;
+const CONTENT_KEY_LENGTH: usize = 32;
+/// The protocol version is wrapped along with the content key, padded to 8 bytes.
+const WRAPPED_KEY_PREFIX: &[u8; 8] = b"2.0\0\0\0\0\0";
+
+/// A key used to wrap the content encryption key of a blob.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait KeyEncryptionKey: Send + Sync + fmt::Debug {
+    /// The identifier stored with the wrapped key, used to find this key again to unwrap it.
+    fn key_id(&self) -> String;
+
+    /// The name of the algorithm used to wrap keys, such as `A256KW` or `RSA-OAEP`.
+    fn algorithm(&self) -> String;
+
+    /// Wrap a content encryption key.
+    async fn wrap_key(&self, key: &[u8]) -> azure_core::Result<Vec<u8>>;
+
+    /// Unwrap a content encryption key wrapped with `algorithm`.
+    async fn unwrap_key(&self, wrapped_key: &[u8], algorithm: &str) -> azure_core::Result<Vec<u8>>;
+}
+
+/// Finds the key encryption key a blob was encrypted with, from its identifier.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait KeyResolver: Send + Sync + fmt::Debug {
+    async fn resolve(&self, key_id: &str) -> azure_core::Result<Arc<dyn KeyEncryptionKey>>;
+}
+
+/// The client-side encryption settings of a client.
+#[derive(Debug, Clone)]
+pub struct ClientSideEncryption {
+    key_encryption_key: Arc<dyn KeyEncryptionKey>,
+    key_resolver: Option<Arc<dyn KeyResolver>>,
+    allow_unencrypted_blobs: bool,
+}
+
+impl ClientSideEncryption {
+    /// Encrypt blobs with `key_encryption_key`, and decrypt the blobs it encrypted.
+    pub fn new(key_encryption_key: Arc<dyn KeyEncryptionKey>) -> Self {
+        Self {
+            key_encryption_key,
+            key_resolver: None,
+            allow_unencrypted_blobs: false,
+        }
+    }
+
+    /// Decrypt blobs encrypted with other keys, such as previous versions of a rotated key.
+    #[must_use]
+    pub fn key_resolver(mut self, key_resolver: Arc<dyn KeyResolver>) -> Self {
+        self.key_resolver = Some(key_resolver);
+        self
+    }
+
+    /// Read blobs that are not encrypted as they are, instead of failing.
+    #[must_use]
+    pub fn allow_unencrypted_blobs(mut self, allow_unencrypted_blobs: bool) -> Self {
+        self.allow_unencrypted_blobs = allow_unencrypted_blobs;
+        self
+    }
+
+    /// The encryption data in the `metadata` of a blob, or `None` when the blob is not encrypted
+    /// and unencrypted blobs are allowed.
+    pub(crate) fn encryption_data<'a>(
+        &self,
+        metadata: Option<&'a HashMap<String, String>>,
+    ) -> azure_core::Result<Option<&'a str>> {
+        match metadata.and_then(|metadata| metadata.get(ENCRYPTION_DATA_METADATA)) {
+            Some(encryption_data) => Ok(Some(encryption_data)),
+            None if self.allow_unencrypted_blobs => Ok(None),
+            None => Err(Error::message(
+                ErrorKind::Other,
+                "blob is not client-side encrypted, allow unencrypted blobs to read it as is",
+            )),
+        }
+    }
+
+    /// Generate a content encryption key for a new blob.
+    pub(crate) async fn encryptor(&self) -> azure_core::Result<ContentEncryptor> {
+        let content_key = Aes256Gcm::generate_key(OsRng);
+
+        let mut key = WRAPPED_KEY_PREFIX.to_vec();
+        key.extend_from_slice(&content_key);
+        let wrapped_key = self.key_encryption_key.wrap_key(&key).await?;
+
+        let encryption_data = EncryptionData {
+            wrapped_content_key: WrappedContentKey {
+                key_id: self.key_encryption_key.key_id(),
+                encrypted_key: base64::encode(wrapped_key),
+                algorithm: self.key_encryption_key.algorithm(),
+            },
+            encryption_agent: EncryptionAgent {
+                protocol: PROTOCOL_V2.to_owned(),
+                encryption_algorithm: AES_GCM_256.to_owned(),
+            },
+            encrypted_region_info: Some(EncryptedRegionInfo {
+                data_length: REGION_LENGTH,
+                nonce_length: NONCE_LENGTH,
+            }),
+            key_wrapping_metadata: HashMap::from([(
+                "EncryptionLibrary".to_owned(),
+                format!("Rust {}", env!("CARGO_PKG_VERSION")),
+            )]),
+        };
+        let encryption_data = serde_json::to_string(&encryption_data)?;
+
+        Ok(ContentEncryptor {
+            cipher: Aes256Gcm::new(&content_key),
+            encryption_data,
+        })
+    }
+
+    /// The range of encrypted content holding the plaintext range `start..end`, for blobs
+    /// encrypted with the regions used by this client.
+    pub(crate) fn encrypted_range(&self, start: u64, end: u64) -> (u64, u64) {
+        encrypted_range(
+            &EncryptedRegionInfo {
+                data_length: REGION_LENGTH,
+                nonce_length: NONCE_LENGTH,
+            },
+            start,
+            end,
+        )
+    }
+
+    /// The length of the plaintext of a blob of `encrypted_length` bytes with the given
+    /// `metadata`, read without unwrapping its content encryption key.
+    pub(crate) fn plaintext_length(
+        &self,
+        metadata: Option<&HashMap<String, String>>,
+        encrypted_length: u64,
+    ) -> azure_core::Result<u64> {
+        match self.encryption_data(metadata)? {
+            Some(encryption_data) => {
+                let (_, region) = parse_encryption_data(encryption_data)?;
+                Ok(plaintext_length(&region, encrypted_length))
+            }
+            None => Ok(encrypted_length),
+        }
+    }
+
+    /// Unwrap the content encryption key of a blob from its `encryptiondata` metadata.
+    pub(crate) async fn decryptor(
+        &self,
+        encryption_data: &str,
+    ) -> azure_core::Result<ContentDecryptor> {
+        let (encryption_data, region) = parse_encryption_data(encryption_data)?;
+
+        let wrapped = &encryption_data.wrapped_content_key;
+        let key_encryption_key = match &self.key_resolver {
+            Some(key_resolver) if wrapped.key_id != self.key_encryption_key.key_id() => {
+                key_resolver.resolve(&wrapped.key_id).await?
+            }
+            _ => self.key_encryption_key.clone(),
+        };
+        if key_encryption_key.key_id() != wrapped.key_id {
+            return Err(Error::with_message(ErrorKind::Credential, || {
+                format!(
+                    "blob was encrypted with key {} but the client uses key {}",
+                    wrapped.key_id,
+                    key_encryption_key.key_id()
+                )
+            }));
+        }
+
+        let encrypted_key = base64::decode(&wrapped.encrypted_key)
+            .context(ErrorKind::DataConversion, "invalid wrapped content key")?;
+        let key = key_encryption_key
+            .unwrap_key(&encrypted_key, &wrapped.algorithm)
+            .await?;
+        let content_key = key
+            .strip_prefix(WRAPPED_KEY_PREFIX.as_slice())
+            .filter(|key| key.len() == CONTENT_KEY_LENGTH)
+            .ok_or_else(|| {
+                Error::message(
+                    ErrorKind::DataConversion,
+                    "unwrapped content key is not a version 2.0 AES-256 key",
+                )
+            })?;
+
+        Ok(ContentDecryptor {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(content_key)),
+            region,
+        })
+    }
+}
+
+/// Parse the `encryptiondata` metadata of a blob, checking that its protocol is supported.
+fn parse_encryption_data(
+    encryption_data: &str,
+) -> azure_core::Result<(EncryptionData, EncryptedRegionInfo)> {
+    let encryption_data: EncryptionData = serde_json::from_str(encryption_data)
+        .context(ErrorKind::DataConversion, "invalid blob encryption data")?;
+
+    let agent = &encryption_data.encryption_agent;
+    if agent.protocol != PROTOCOL_V2 || agent.encryption_algorithm != AES_GCM_256 {
+        return Err(Error::with_message(ErrorKind::Other, || {
+            format!(
+                "unsupported client-side encryption protocol {} with {}",
+                agent.protocol, agent.encryption_algorithm
+            )
+        }));
+    }
+    let region = encryption_data.encrypted_region_info.ok_or_else(|| {
+        Error::message(
+            ErrorKind::DataConversion,
+            "blob encryption data is missing the encrypted region info",
+        )
+    })?;
+    if region.data_length == 0 || region.nonce_length == 0 {
+        return Err(Error::message(
+            ErrorKind::DataConversion,
+            "invalid encrypted region info",
+        ));
+    }
+    Ok((encryption_data, region))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EncryptionData {
+    wrapped_content_key: WrappedContentKey,
+    encryption_agent: EncryptionAgent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encrypted_region_info: Option<EncryptedRegionInfo>,
+    #[serde(default)]
+    key_wrapping_metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct WrappedContentKey {
+    key_id: String,
+    encrypted_key: String,
+    algorithm: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EncryptionAgent {
+    protocol: String,
+    encryption_algorithm: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EncryptedRegionInfo {
+    data_length: u64,
+    nonce_length: u64,
+}
+
+impl EncryptedRegionInfo {
+    fn encrypted_length(&self) -> u64 {
+        self.nonce_length + self.data_length + TAG_LENGTH
+    }
+}
+
+/// Encrypts the content of a blob.
+pub(crate) struct ContentEncryptor {
+    cipher: Aes256Gcm,
+    encryption_data: String,
+}
+
+impl fmt::Debug for ContentEncryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContentEncryptor")
+            .field("encryption_data", &self.encryption_data)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ContentEncryptor {
+    /// The value of the `encryptiondata` metadata of the blob.
+    pub(crate) fn encryption_data(&self) -> &str {
+        &self.encryption_data
+    }
+
+    /// Encrypt content starting at a region boundary.
+    ///
+    /// Any content but the last of a blob must be made of whole regions.
+    pub(crate) fn encrypt(&self, plaintext: &[u8]) -> azure_core::Result<Bytes> {
+        let regions = plaintext.len().div_ceil(REGION_LENGTH as usize) as u64;
+        let mut encrypted = BytesMut::with_capacity(
+            plaintext.len() + (regions * (NONCE_LENGTH + TAG_LENGTH)) as usize,
+        );
+        for region in plaintext.chunks(REGION_LENGTH as usize) {
+            let nonce = Aes256Gcm::generate_nonce(OsRng);
+            let ciphertext = self
+                .cipher
+                .encrypt(&nonce, region)
+                .map_err(|_| Error::message(ErrorKind::Other, "failed to encrypt blob content"))?;
+            encrypted.extend_from_slice(&nonce);
+            encrypted.extend_from_slice(&ciphertext);
+        }
+        Ok(encrypted.freeze())
+    }
+
+    /// The length of the encrypted form of `plaintext_length` bytes.
+    pub(crate) fn encrypted_length(plaintext_length: u64) -> u64 {
+        plaintext_length + plaintext_length.div_ceil(REGION_LENGTH) * (NONCE_LENGTH + TAG_LENGTH)
+    }
+
+    /// Round a block size up so that every block holds whole regions.
+    pub(crate) fn align_block_size(block_size: u64) -> u64 {
+        block_size.max(1).next_multiple_of(REGION_LENGTH)
+    }
+}
+
+/// Decrypts the content of a blob.
+pub(crate) struct ContentDecryptor {
+    cipher: Aes256Gcm,
+    region: EncryptedRegionInfo,
+}
+
+impl fmt::Debug for ContentDecryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContentDecryptor")
+            .field("region", &self.region)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ContentDecryptor {
+    /// The range of encrypted content holding the plaintext range `start..end`.
+    pub(crate) fn encrypted_range(&self, start: u64, end: u64) -> (u64, u64) {
+        encrypted_range(&self.region, start, end)
+    }
+
+    /// The length of the plaintext of a blob of `encrypted_length` bytes.
+    pub(crate) fn plaintext_length(&self, encrypted_length: u64) -> u64 {
+        plaintext_length(&self.region, encrypted_length)
+    }
+
+    /// The plaintext offset of the region starting at `encrypted_offset`.
+    pub(crate) fn plaintext_offset(&self, encrypted_offset: u64) -> u64 {
+        encrypted_offset / self.region.encrypted_length() * self.region.data_length
+    }
+
+    /// Decrypt content starting at a region boundary and made of whole regions.
+    pub(crate) fn decrypt(&self, encrypted: &[u8]) -> azure_core::Result<Bytes> {
+        let nonce_length = self.region.nonce_length as usize;
+        let mut plaintext = BytesMut::with_capacity(encrypted.len());
+        for region in encrypted.chunks(self.region.encrypted_length() as usize) {
+            if region.len() < nonce_length + TAG_LENGTH as usize {
+                return Err(Error::message(
+                    ErrorKind::DataConversion,
+                    "encrypted blob content is truncated",
+                ));
+            }
+            let (nonce, ciphertext) = region.split_at(nonce_length);
+            let decrypted = self
+                .cipher
+                .decrypt(Nonce::from_slice(nonce), ciphertext)
+                .map_err(|_| {
+                    Error::message(
+                        ErrorKind::DataConversion,
+                        "failed to decrypt blob content, it may have been tampered with",
+                    )
+                })?;
+            plaintext.extend_from_slice(&decrypted);
+        }
+        Ok(plaintext.freeze())
+    }
+}
+
+fn plaintext_length(region: &EncryptedRegionInfo, encrypted_length: u64) -> u64 {
+    let regions = encrypted_length.div_ceil(region.encrypted_length());
+    encrypted_length.saturating_sub(regions * (region.nonce_length + TAG_LENGTH))
+}
+
+fn encrypted_range(region: &EncryptedRegionInfo, start: u64, end: u64) -> (u64, u64) {
+    let first = start / region.data_length;
+    let last = end.div_ceil(region.data_length).max(first + 1);
+    (
+        first * region.encrypted_length(),
+        last.saturating_mul(region.encrypted_length()),
+    )
+}
+
+/// Compute a transactional hash of the same kind as `hash`, over encrypted content.
+pub(crate) fn rehash(hash: &Hash, encrypted: &[u8]) -> azure_core::Result<Hash> {
+    match hash {
+        Hash::CRC64(_) => Ok(ChecksumAlgorithm::CRC64.hash(encrypted)),
+        #[cfg(feature = "md5")]
+        Hash::MD5(_) => Ok(ChecksumAlgorithm::MD5.hash(encrypted)),
+        #[cfg(not(feature = "md5"))]
+        Hash::MD5(_) => Err(Error::message(
+            ErrorKind::Other,
+            "an MD5 hash of encrypted content requires the md5 feature",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encryption() -> ClientSideEncryption {
+        ClientSideEncryption::new(Arc::new(AesKeyEncryptionKey::new("key1", [7; 32])))
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() -> azure_core::Result<()> {
+        let encryption = encryption();
+        let encryptor = encryption.encryptor().await?;
+
+        let plaintext: Vec<u8> = (0..(2 * REGION_LENGTH + 100))
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let encrypted = encryptor.encrypt(&plaintext)?;
+        assert_eq!(
+            encrypted.len() as u64,
+            ContentEncryptor::encrypted_length(plaintext.len() as u64)
+        );
+        assert_eq!(encrypted.len(), plaintext.len() + 3 * 28);
+
+        let decryptor = encryption.decryptor(encryptor.encryption_data()).await?;
+        assert_eq!(
+            decryptor.plaintext_length(encrypted.len() as u64),
+            plaintext.len() as u64
+        );
+        assert_eq!(decryptor.decrypt(&encrypted)?, plaintext);
+
+        // decrypt a range spanning the last two regions
+        let (start, end) = decryptor.encrypted_range(REGION_LENGTH + 10, 2 * REGION_LENGTH + 50);
+        assert_eq!(start, REGION_LENGTH + 28);
+        assert_eq!(end, 3 * (REGION_LENGTH + 28));
+        assert_eq!(
+            encryption.encrypted_range(REGION_LENGTH + 10, 2 * REGION_LENGTH + 50),
+            (start, end)
+        );
+        let end = end.min(encrypted.len() as u64);
+        let decrypted = decryptor.decrypt(&encrypted[start as usize..end as usize])?;
+        assert_eq!(decryptor.plaintext_offset(start), REGION_LENGTH);
+        assert_eq!(decrypted, plaintext[REGION_LENGTH as usize..]);
+
+        // tampering is detected
+        let mut tampered = encrypted.to_vec();
+        tampered[100] ^= 1;
+        assert!(decryptor.decrypt(&tampered).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_encryption_data() -> azure_core::Result<()> {
+        let encryptor = encryption().encryptor().await?;
+        let data: serde_json::Value = serde_json::from_str(encryptor.encryption_data())?;
+        assert_eq!(data["WrappedContentKey"]["KeyId"], "key1");
+        assert_eq!(data["WrappedContentKey"]["Algorithm"], "A256KW");
+        assert_eq!(data["EncryptionAgent"]["Protocol"], "2.0");
+        assert_eq!(
+            data["EncryptionAgent"]["EncryptionAlgorithm"],
+            "AES_GCM_256"
+        );
+        assert_eq!(data["EncryptedRegionInfo"]["DataLength"], 4194304);
+        assert_eq!(data["EncryptedRegionInfo"]["NonceLength"], 12);
+
+        // a different key cannot decrypt the blob
+        let other = ClientSideEncryption::new(Arc::new(AesKeyEncryptionKey::new("key2", [8; 32])));
+        assert!(other.decryptor(encryptor.encryption_data()).await.is_err());
+
+        // but the length of its plaintext is known without unwrapping the key
+        let metadata = HashMap::from([(
+            ENCRYPTION_DATA_METADATA.to_owned(),
+            encryptor.encryption_data().to_owned(),
+        )]);
+        let encrypted_length = ContentEncryptor::encrypted_length(REGION_LENGTH + 100);
+        assert_eq!(
+            other.plaintext_length(Some(&metadata), encrypted_length)?,
+            REGION_LENGTH + 100
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unencrypted_blobs() -> azure_core::Result<()> {
+        let metadata = HashMap::from([(ENCRYPTION_DATA_METADATA.to_owned(), "{}".to_owned())]);
+        assert_eq!(encryption().encryption_data(Some(&metadata))?, Some("{}"));
+        assert!(encryption().encryption_data(None).is_err());
+        assert!(encryption().encryption_data(Some(&HashMap::new())).is_err());
+        assert_eq!(
+            encryption()
+                .allow_unencrypted_blobs(true)
+                .encryption_data(None)?,
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_align_block_size() {
+        assert_eq!(ContentEncryptor::align_block_size(1), REGION_LENGTH);
+        assert_eq!(
+            ContentEncryptor::align_block_size(8 * 1024 * 1024),
+            2 * REGION_LENGTH
+        );
+        assert_eq!(
+            ContentEncryptor::align_block_size(9 * 1024 * 1024),
+            3 * REGION_LENGTH
+        );
+    }
+}
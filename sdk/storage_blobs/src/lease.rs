@@ -0,0 +1,556 @@
+//! Hold leases on blobs and containers for longer than their duration, and elect leaders with them.
+//!
+//! A [`LeaseGuard`] comes with a [`LeaseRenewal`] future, which renews the lease until the guard
+//! is released or dropped. The renewal does nothing unless it is polled, so it has to be spawned on
+//! the runtime of the application:
+//!
+//! ```no_run
+//! # async fn example(blob_client: azure_storage_blobs::prelude::BlobClient) -> azure_core::Result<()> {
+//! use std::time::Duration;
+//!
+//! let lease = blob_client.lease_guard(Duration::from_secs(30)).await?;
+//! let renewal = tokio::spawn(lease.renewal);
+//! // ... work while holding the lease, checking `lease.guard.is_lost()` ...
+//! lease.guard.release().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::prelude::*;
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+    sleep, StatusCode,
+};
+use futures::{
+    channel::oneshot,
+    future::{self, BoxFuture, Either, Shared},
+    FutureExt,
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use time::OffsetDateTime;
+use tracing::warn;
+
+const DEFAULT_LEADER_LEASE_DURATION: Duration = Duration::from_secs(30);
+const DEFAULT_CAMPAIGN_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+/// How long to wait before renewing a lease again after a transient failure.
+const RENEWAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// What a lease is acquired on.
+#[derive(Debug, Clone)]
+pub(crate) enum LeaseTarget {
+    Blob(BlobClient),
+    Container(ContainerClient),
+}
+
+#[derive(Debug, Clone)]
+enum LeaseClient {
+    Blob(BlobLeaseClient),
+    Container(ContainerLeaseClient),
+}
+
+impl LeaseClient {
+    fn lease_id(&self) -> LeaseId {
+        match self {
+            Self::Blob(client) => client.lease_id(),
+            Self::Container(client) => client.lease_id(),
+        }
+    }
+
+    async fn renew(&self) -> azure_core::Result<()> {
+        match self {
+            Self::Blob(client) => client.renew().await.map(|_| ()),
+            Self::Container(client) => client.renew().await.map(|_| ()),
+        }
+    }
+
+    async fn release(&self) -> azure_core::Result<()> {
+        match self {
+            Self::Blob(client) => client.release().await.map(|_| ()),
+            Self::Container(client) => client.release().await.map(|_| ()),
+        }
+    }
+}
+
+operation! {
+    /// Acquire a lease and keep renewing it.
+    ///
+    /// The lease is renewed every `renewal_interval`, by default half of the lease duration.
+    /// Transient failures are retried for as long as the lease has not expired. Infinite leases
+    /// are never renewed, only released.
+    AcquireLeaseGuard,
+    client: LeaseTarget,
+    lease_duration: LeaseDuration,
+    ?proposed_lease_id: ProposedLeaseId,
+    ?renewal_interval: Duration
+}
+
+impl AcquireLeaseGuardBuilder {
+    pub fn into_future(self) -> AcquireLeaseGuard {
+        Box::pin(async move {
+            let acquired_at = OffsetDateTime::now_utc();
+            let lease = match &self.client {
+                LeaseTarget::Blob(client) => {
+                    let mut acquire = client
+                        .acquire_lease(self.lease_duration.clone())
+                        .context(self.context.clone());
+                    if let Some(proposed_lease_id) = self.proposed_lease_id {
+                        acquire = acquire.proposed_lease_id(proposed_lease_id);
+                    }
+                    let lease_id = acquire.await?.lease_id;
+                    LeaseClient::Blob(client.blob_lease_client(lease_id))
+                }
+                LeaseTarget::Container(client) => {
+                    let mut acquire = client
+                        .acquire_lease(self.lease_duration.clone())
+                        .context(self.context.clone());
+                    if let Some(proposed_lease_id) = self.proposed_lease_id {
+                        acquire = acquire.proposed_lease_id(proposed_lease_id);
+                    }
+                    let lease_id = acquire.await?.lease_id;
+                    LeaseClient::Container(client.container_lease_client(lease_id))
+                }
+            };
+
+            let schedule = match self.lease_duration {
+                LeaseDuration::Infinite => None,
+                LeaseDuration::Seconds(seconds) => {
+                    let lease_duration = Duration::from_secs(u64::from(seconds));
+                    Some(RenewalSchedule {
+                        lease_duration,
+                        renewal_interval: self.renewal_interval.unwrap_or(lease_duration / 2),
+                    })
+                }
+            };
+            Ok(AcquireLeaseGuardResponse::new(lease, acquired_at, schedule))
+        })
+    }
+}
+
+/// A lease acquired, with the future renewing it.
+#[derive(Debug)]
+pub struct AcquireLeaseGuardResponse {
+    pub guard: LeaseGuard,
+    pub renewal: LeaseRenewal,
+}
+
+impl AcquireLeaseGuardResponse {
+    fn new(
+        lease: LeaseClient,
+        acquired_at: OffsetDateTime,
+        schedule: Option<RenewalSchedule>,
+    ) -> Self {
+        let (stop, stop_receiver) = oneshot::channel();
+        let (notify_lost, lost_receiver) = oneshot::channel();
+        let lost = Arc::new(AtomicBool::new(false));
+        let renewal = renew(
+            lease.clone(),
+            acquired_at,
+            schedule,
+            stop_receiver,
+            LostOnDrop {
+                lost: lost.clone(),
+                notify: Some(notify_lost),
+            },
+        );
+        Self {
+            guard: LeaseGuard {
+                lease,
+                stop: Some(stop),
+                lost,
+                lost_receiver: lost_receiver.shared(),
+            },
+            renewal: LeaseRenewal(renewal.boxed()),
+        }
+    }
+}
+
+/// How often a lease of a finite duration is renewed.
+#[derive(Debug, Clone, Copy)]
+struct RenewalSchedule {
+    lease_duration: Duration,
+    renewal_interval: Duration,
+}
+
+/// A lease renewed until it is released.
+///
+/// Dropping the guard stops the renewal, which then releases the lease, as long as the renewal is
+/// still being polled. Otherwise the lease is left to expire.
+#[derive(Debug)]
+pub struct LeaseGuard {
+    lease: LeaseClient,
+    stop: Option<oneshot::Sender<()>>,
+    lost: Arc<AtomicBool>,
+    lost_receiver: Shared<oneshot::Receiver<()>>,
+}
+
+impl LeaseGuard {
+    pub fn lease_id(&self) -> LeaseId {
+        self.lease.lease_id()
+    }
+
+    /// Whether the lease is no longer renewed, either because renewing it failed or because the
+    /// renewal future was dropped before the guard, in which case it may now be held by someone
+    /// else.
+    pub fn is_lost(&self) -> bool {
+        self.lost.load(Ordering::SeqCst)
+    }
+
+    /// Wait until the lease is lost, as reported by [`LeaseGuard::is_lost`].
+    ///
+    /// Never completes if the lease is released instead.
+    pub fn lost(&self) -> impl Future<Output = ()> + Send + 'static {
+        self.lost_receiver.clone().then(|result| match result {
+            Ok(()) => future::ready(()).left_future(),
+            // the guard was released or dropped
+            Err(oneshot::Canceled) => future::pending().right_future(),
+        })
+    }
+
+    /// Stop renewing the lease and release it.
+    pub async fn release(mut self) -> azure_core::Result<()> {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        self.lease.release().await
+    }
+}
+
+/// Renew a lease until its guard is released or dropped.
+///
+/// Completes with an error if the lease could not be renewed. Dropping the renewal before the
+/// guard marks the lease as lost.
+pub struct LeaseRenewal(BoxFuture<'static, azure_core::Result<()>>);
+
+impl Future for LeaseRenewal {
+    type Output = azure_core::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+impl std::fmt::Debug for LeaseRenewal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LeaseRenewal").finish_non_exhaustive()
+    }
+}
+
+/// Marks the lease as lost when the renewal stops before the guard is released or dropped,
+/// including when the renewal future itself is dropped.
+struct LostOnDrop {
+    lost: Arc<AtomicBool>,
+    notify: Option<oneshot::Sender<()>>,
+}
+
+impl LostOnDrop {
+    fn disarm(&mut self) {
+        self.notify = None;
+    }
+}
+
+impl Drop for LostOnDrop {
+    fn drop(&mut self) {
+        if let Some(notify) = self.notify.take() {
+            self.lost.store(true, Ordering::SeqCst);
+            let _ = notify.send(());
+        }
+    }
+}
+
+async fn renew(
+    lease: LeaseClient,
+    acquired_at: OffsetDateTime,
+    schedule: Option<RenewalSchedule>,
+    mut stop: oneshot::Receiver<()>,
+    mut lost: LostOnDrop,
+) -> azure_core::Result<()> {
+    let mut renewed_at = acquired_at;
+    let mut next_renewal = schedule.map(|schedule| acquired_at + schedule.renewal_interval);
+    loop {
+        let tick = match schedule.zip(next_renewal) {
+            Some((schedule, at)) => sleep(time_until(at)).map(move |_| schedule).boxed(),
+            None => future::pending().boxed(),
+        };
+        match future::select(tick, &mut stop).await {
+            Either::Left((schedule, _)) => {
+                let started_at = OffsetDateTime::now_utc();
+                match lease.renew().await {
+                    Ok(()) => {
+                        renewed_at = started_at;
+                        next_renewal = Some(started_at + schedule.renewal_interval);
+                    }
+                    Err(err) => {
+                        let retry_at = OffsetDateTime::now_utc() + RENEWAL_RETRY_DELAY;
+                        if !is_retriable(&err) || retry_at >= renewed_at + schedule.lease_duration {
+                            // dropping `lost` marks the lease as lost
+                            return Err(err);
+                        }
+                        warn!(
+                            "renewing lease {} failed, retrying: {err}",
+                            lease.lease_id()
+                        );
+                        next_renewal = Some(retry_at);
+                    }
+                }
+            }
+            // released by the guard
+            Either::Right((Ok(()), _)) => {
+                lost.disarm();
+                return Ok(());
+            }
+            // the guard was dropped
+            Either::Right((Err(oneshot::Canceled), _)) => {
+                lost.disarm();
+                return lease.release().await;
+            }
+        }
+    }
+}
+
+/// How long until `at`, or zero if it has passed.
+/// Whether a failed request is worth another attempt.
+///
+/// Expired or lost leases are not, renewing them again fails the same way.
+fn is_retriable(err: &Error) -> bool {
+    match err.as_http_error().map(|e| e.status()) {
+        Some(status) => {
+            status.is_server_error()
+                || status == StatusCode::RequestTimeout
+                || status == StatusCode::TooManyRequests
+        }
+        None => matches!(err.kind(), ErrorKind::Io),
+    }
+}
+
+fn time_until(at: OffsetDateTime) -> Duration {
+    (at - OffsetDateTime::now_utc())
+        .try_into()
+        .unwrap_or(Duration::ZERO)
+}
+
+/// Elect a leader among the clients holding a lease on the same blob.
+///
+/// The blob is created empty if it does not exist.
+#[derive(Debug, Clone)]
+pub struct LeaderElection {
+    client: BlobClient,
+    lease_duration: Duration,
+    retry_interval: Duration,
+}
+
+impl LeaderElection {
+    pub(crate) fn new(client: BlobClient) -> Self {
+        Self {
+            client,
+            lease_duration: DEFAULT_LEADER_LEASE_DURATION,
+            retry_interval: DEFAULT_CAMPAIGN_RETRY_INTERVAL,
+        }
+    }
+
+    setters! {
+        lease_duration: Duration => lease_duration,
+        retry_interval: Duration => retry_interval,
+    }
+
+    /// Wait until this client is the leader.
+    ///
+    /// Leadership lasts until the lease is released, or lost if it cannot be renewed.
+    pub async fn campaign(&self) -> azure_core::Result<AcquireLeaseGuardResponse> {
+        loop {
+            let acquired = AcquireLeaseGuardBuilder::new(
+                LeaseTarget::Blob(self.client.clone()),
+                self.lease_duration.into(),
+            )
+            .await;
+            match acquired {
+                Ok(response) => return Ok(response),
+                Err(err) => match err.kind() {
+                    ErrorKind::HttpResponse {
+                        status: StatusCode::Conflict,
+                        ..
+                    } => sleep(self.retry_interval).await,
+                    ErrorKind::HttpResponse {
+                        status: StatusCode::NotFound,
+                        ..
+                    } => self.create_blob().await?,
+                    _ => return Err(err),
+                },
+            }
+        }
+    }
+
+    /// Create the empty blob leases are acquired on, unless another client just did.
+    async fn create_blob(&self) -> azure_core::Result<()> {
+        let created = self
+            .client
+            .put_block_blob(Vec::new())
+            .if_match(IfMatchCondition::NotMatch("*".to_owned()))
+            .await;
+        match created {
+            Ok(_) => Ok(()),
+            Err(err) => match err.kind() {
+                ErrorKind::HttpResponse {
+                    status: StatusCode::Conflict | StatusCode::PreconditionFailed,
+                    ..
+                } => Ok(()),
+                _ => Err(Error::full(
+                    err.kind().clone(),
+                    err,
+                    "failed to create the leader election blob",
+                )),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::{
+        headers::{Headers, DATE, ETAG, LAST_MODIFIED, LEASE_ACTION, LEASE_ID, REQUEST_ID},
+        BytesStream, Policy, PolicyResult, Request, Response, RetryOptions, TransportOptions,
+    };
+    use futures::executor::block_on;
+    use std::{collections::VecDeque, sync::Mutex};
+
+    /// Answer the lease renewals with the statuses queued, and with success once they run out.
+    #[derive(Debug)]
+    struct MockLease {
+        statuses: Mutex<VecDeque<StatusCode>>,
+        renewals: Mutex<usize>,
+    }
+
+    impl MockLease {
+        fn new(statuses: &[StatusCode]) -> Arc<Self> {
+            Arc::new(Self {
+                statuses: Mutex::new(statuses.iter().copied().collect()),
+                renewals: Mutex::new(0),
+            })
+        }
+
+        fn renewals(&self) -> usize {
+            *self.renewals.lock().unwrap()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Policy for MockLease {
+        async fn send(
+            &self,
+            _ctx: &azure_core::Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            assert_eq!(request.headers().get_str(&LEASE_ACTION)?, "renew");
+            *self.renewals.lock().unwrap() += 1;
+            let status = self
+                .statuses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or(StatusCode::Ok);
+            let mut headers = Headers::new();
+            headers.insert(ETAG, "\"0x8DC0A\"");
+            headers.insert(LAST_MODIFIED, "Mon, 01 Jan 2024 00:00:00 GMT");
+            headers.insert(DATE, "Mon, 01 Jan 2024 00:00:00 GMT");
+            headers.insert(REQUEST_ID, "00000000-0000-0000-0000-000000000000");
+            headers.insert(LEASE_ID, uuid::Uuid::nil().to_string());
+            Ok(Response::new(
+                status,
+                headers,
+                Box::pin(BytesStream::new_empty()),
+            ))
+        }
+    }
+
+    fn lease_client(policy: Option<Arc<MockLease>>) -> LeaseClient {
+        let mut builder = ClientBuilder::emulator();
+        if let Some(policy) = policy {
+            builder = builder
+                .retry(RetryOptions::none())
+                .transport(TransportOptions::new_custom_policy(policy));
+        }
+        let client = builder.blob_client("container", "blob");
+        LeaseClient::Blob(client.blob_lease_client(LeaseId::from(uuid::Uuid::nil())))
+    }
+
+    /// A lease renewed every 10ms by `policy`.
+    fn renewed(policy: Arc<MockLease>, lease_duration: Duration) -> AcquireLeaseGuardResponse {
+        AcquireLeaseGuardResponse::new(
+            lease_client(Some(policy)),
+            OffsetDateTime::now_utc(),
+            Some(RenewalSchedule {
+                lease_duration,
+                renewal_interval: Duration::from_millis(10),
+            }),
+        )
+    }
+
+    #[test]
+    fn dropping_renewal_loses_lease() {
+        let AcquireLeaseGuardResponse { guard, renewal } =
+            AcquireLeaseGuardResponse::new(lease_client(None), OffsetDateTime::now_utc(), None);
+        assert!(!guard.is_lost());
+        drop(renewal);
+        assert!(guard.is_lost());
+        block_on(guard.lost());
+        assert_eq!(guard.lease_id(), LeaseId::from(uuid::Uuid::nil()));
+    }
+
+    #[test]
+    fn released_guard_stops_renewal() {
+        let AcquireLeaseGuardResponse { mut guard, renewal } =
+            AcquireLeaseGuardResponse::new(lease_client(None), OffsetDateTime::now_utc(), None);
+        let lost = guard.lost();
+        let _ = guard.stop.take().unwrap().send(());
+        assert!(block_on(renewal).is_ok());
+        assert!(!guard.is_lost());
+        assert!(lost.now_or_never().is_none());
+    }
+
+    #[tokio::test]
+    async fn renewal_retries_transient_failures() {
+        let policy = MockLease::new(&[StatusCode::InternalServerError]);
+        let AcquireLeaseGuardResponse { mut guard, renewal } =
+            renewed(policy.clone(), Duration::from_secs(15));
+        let renewal = tokio::spawn(renewal);
+        while policy.renewals() < 3 {
+            sleep(Duration::from_millis(10)).await;
+        }
+        assert!(!guard.is_lost());
+        let _ = guard.stop.take().unwrap().send(());
+        assert!(renewal.await.unwrap().is_ok());
+        assert!(!guard.is_lost());
+    }
+
+    #[tokio::test]
+    async fn renewal_failure_loses_lease() {
+        let policy = MockLease::new(&[StatusCode::Conflict]);
+        let AcquireLeaseGuardResponse { guard, renewal } =
+            renewed(policy.clone(), Duration::from_secs(15));
+        assert!(renewal.await.is_err());
+        assert_eq!(policy.renewals(), 1);
+        assert!(guard.is_lost());
+        guard.lost().await;
+    }
+
+    #[tokio::test]
+    async fn renewal_is_not_retried_past_expiry() {
+        let policy = MockLease::new(&[StatusCode::InternalServerError]);
+        let AcquireLeaseGuardResponse { guard, renewal } =
+            renewed(policy.clone(), Duration::from_millis(500));
+        assert!(renewal.await.is_err());
+        assert_eq!(policy.renewals(), 1);
+        assert!(guard.is_lost());
+    }
+}
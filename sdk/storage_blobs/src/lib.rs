@@ -52,8 +52,14 @@ extern crate serde_derive;
 #[macro_use]
 extern crate azure_core;
 
+mod avro;
 pub mod blob;
+pub mod change_feed;
 pub mod container;
+#[cfg(feature = "client-side-encryption")]
+pub mod encryption;
+pub mod lease;
+mod object_store;
 pub mod prelude;
 pub mod service;
 
@@ -0,0 +1,234 @@
+use crate::{
+    container::operations::copy_directory::{
+        copy_blob, DEFAULT_COPY_TIMEOUT, DEFAULT_POLL_INTERVAL,
+    },
+    prelude::*,
+};
+use azure_core::{error::ErrorKind, Context, StatusCode};
+use azure_storage::object_store::{
+    directory_prefix, ListResult, MultipartUpload, ObjectMeta, ObjectStore, DELIMITER,
+};
+use bytes::{Bytes, BytesMut};
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use std::ops::Range;
+use uuid::Uuid;
+
+fn object_meta(blob: &Blob) -> ObjectMeta {
+    ObjectMeta {
+        location: blob.name.clone(),
+        last_modified: blob.properties.last_modified,
+        size: blob.properties.content_length,
+        e_tag: Some(blob.properties.etag.to_string()),
+    }
+}
+
+/// Objects are block blobs, and prefixes are virtual directories.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl ObjectStore for ContainerClient {
+    async fn put(&self, location: &str, data: Bytes) -> azure_core::Result<()> {
+        self.blob_client(location).put_block_blob(data).await?;
+        Ok(())
+    }
+
+    async fn get(&self, location: &str) -> azure_core::Result<Bytes> {
+        Ok(self.blob_client(location).get_content().await?.into())
+    }
+
+    async fn get_range(&self, location: &str, range: Range<u64>) -> azure_core::Result<Bytes> {
+        if range.is_empty() {
+            return Ok(Bytes::new());
+        }
+        let mut chunks = self.blob_client(location).get().range(range).into_stream();
+        let mut data = BytesMut::new();
+        while let Some(chunk) = chunks.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                // the range starts after the end of the blob
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        ErrorKind::HttpResponse {
+                            status: StatusCode::RequestedRangeNotSatisfiable,
+                            ..
+                        }
+                    ) =>
+                {
+                    return Ok(Bytes::new())
+                }
+                Err(err) => return Err(err),
+            };
+            data.extend_from_slice(&chunk.data.collect().await?);
+        }
+        Ok(data.freeze())
+    }
+
+    async fn head(&self, location: &str) -> azure_core::Result<ObjectMeta> {
+        let client = self.blob_client(location);
+        let blob = client.get_properties().await?.blob;
+        // the size of blobs encrypted client-side is the size of their plaintext
+        Ok(ObjectMeta {
+            size: client.content_length(&blob)?,
+            ..object_meta(&blob)
+        })
+    }
+
+    async fn delete(&self, location: &str) -> azure_core::Result<()> {
+        self.blob_client(location).delete().await?;
+        Ok(())
+    }
+
+    fn list(&self, prefix: Option<&str>) -> BoxStream<'_, azure_core::Result<ObjectMeta>> {
+        self.list_blobs()
+            .prefix(directory_prefix(prefix))
+            .into_stream()
+            .map_ok(|page| {
+                let objects: Vec<_> = page.blobs.blobs().map(object_meta).collect();
+                futures::stream::iter(objects.into_iter().map(Ok))
+            })
+            .try_flatten()
+            .boxed()
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&str>) -> azure_core::Result<ListResult> {
+        let mut pages = self
+            .list_blobs()
+            .prefix(directory_prefix(prefix))
+            .delimiter(DELIMITER)
+            .into_stream();
+        let mut result = ListResult::default();
+        while let Some(page) = pages.next().await {
+            let page = page?;
+            result
+                .common_prefixes
+                .extend(page.blobs.prefixes().map(|prefix| prefix.name.clone()));
+            result.objects.extend(page.blobs.blobs().map(object_meta));
+        }
+        Ok(result)
+    }
+
+    /// Copy a blob server side, waiting for the copy to complete.
+    async fn copy(&self, from: &str, to: &str) -> azure_core::Result<()> {
+        let source = self.blob_client(from);
+        let properties = source.get_properties().await?.blob.properties;
+        copy_blob(
+            &source,
+            &self.blob_client(to),
+            properties.blob_type,
+            properties.content_length,
+            DEFAULT_POLL_INTERVAL,
+            DEFAULT_COPY_TIMEOUT,
+            &Context::new(),
+        )
+        .await
+    }
+
+    /// Stage the parts as blocks, and commit them when the upload completes.
+    ///
+    /// Fails on clients using client-side encryption, as the parts would be stored unencrypted.
+    async fn put_multipart(&self, location: &str) -> azure_core::Result<Box<dyn MultipartUpload>> {
+        let client = self.blob_client(location);
+        client.check_unencrypted_upload("a multipart upload")?;
+        Ok(Box::new(BlockBlobUpload {
+            client,
+            upload_id: Uuid::new_v4(),
+            block_list: BlockList::default(),
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct BlockBlobUpload {
+    client: BlobClient,
+    upload_id: Uuid,
+    block_list: BlockList,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl MultipartUpload for BlockBlobUpload {
+    async fn put_part(&mut self, data: Bytes) -> azure_core::Result<()> {
+        let index = self.block_list.blocks.len();
+        // the IDs of the blocks of a blob all have the same length
+        let block_id = BlockId::new(format!("{}-{index:05}", self.upload_id));
+        self.client.put_block(block_id.clone(), data).await?;
+        self.block_list
+            .blocks
+            .push(BlobBlockType::Uncommitted(block_id));
+        Ok(())
+    }
+
+    async fn complete(&mut self) -> azure_core::Result<()> {
+        self.client
+            .put_block_list(std::mem::take(&mut self.block_list))
+            .await?;
+        Ok(())
+    }
+
+    /// Uncommitted blocks are garbage collected by the service.
+    async fn abort(&mut self) -> azure_core::Result<()> {
+        self.block_list.blocks.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::headers::{self, Headers};
+
+    #[test]
+    fn test_object_meta() {
+        let mut h = Headers::new();
+        h.insert(headers::CREATION_TIME, "Mon, 01 Jan 2024 00:00:00 GMT");
+        h.insert(headers::CONTENT_LENGTH, "10");
+        h.insert(headers::LAST_MODIFIED, "Tue, 02 Jan 2024 00:00:00 GMT");
+        h.insert(headers::ETAG, "\"0x8DC0\"");
+        h.insert(headers::BLOB_TYPE, "BlockBlob");
+        h.insert(headers::SERVER_ENCRYPTED, "true");
+        let blob = Blob::from_headers("a/b", &h).unwrap();
+
+        let meta = object_meta(&blob);
+        assert_eq!(meta.location, "a/b");
+        assert_eq!(meta.size, 10);
+        assert_eq!(meta.last_modified, blob.properties.last_modified);
+        assert_eq!(meta.e_tag.as_deref(), Some("\"0x8DC0\""));
+    }
+
+    #[cfg(feature = "client-side-encryption")]
+    #[tokio::test]
+    async fn test_client_side_encryption() -> azure_core::Result<()> {
+        use crate::encryption::{
+            AesKeyEncryptionKey, ClientSideEncryption, ContentEncryptor, ENCRYPTION_DATA_METADATA,
+        };
+        use std::sync::Arc;
+
+        let encryption =
+            ClientSideEncryption::new(Arc::new(AesKeyEncryptionKey::new("key1", [7; 32])));
+        let encryptor = encryption.encryptor().await?;
+        let container_client = ClientBuilder::emulator()
+            .client_side_encryption(encryption)
+            .container_client("container");
+        assert!(container_client.put_multipart("a/b").await.is_err());
+
+        // blobs encrypted client-side have the size of their plaintext
+        let mut h = Headers::new();
+        h.insert(headers::CREATION_TIME, "Mon, 01 Jan 2024 00:00:00 GMT");
+        h.insert(
+            headers::CONTENT_LENGTH,
+            ContentEncryptor::encrypted_length(10).to_string(),
+        );
+        h.insert(headers::LAST_MODIFIED, "Tue, 02 Jan 2024 00:00:00 GMT");
+        h.insert(headers::ETAG, "\"0x8DC0\"");
+        h.insert(headers::BLOB_TYPE, "BlockBlob");
+        h.insert(headers::SERVER_ENCRYPTED, "true");
+        h.insert(
+            format!("x-ms-meta-{ENCRYPTION_DATA_METADATA}"),
+            encryptor.encryption_data().to_owned(),
+        );
+        let blob = Blob::from_headers("a/b", &h)?;
+        let blob_client = container_client.blob_client("a/b");
+        assert_eq!(blob_client.content_length(&blob)?, 10);
+        Ok(())
+    }
+}
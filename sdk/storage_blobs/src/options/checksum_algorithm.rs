@@ -0,0 +1,244 @@
+use super::Hash;
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::{self, HeaderName, Headers},
+    Body, Request,
+};
+#[cfg(feature = "md5")]
+use azure_storage::ConsistencyMD5;
+use azure_storage::{
+    crc64,
+    headers::{content_crc64_from_headers, STRUCTURED_BODY, STRUCTURED_CONTENT_LENGTH},
+    structured_message,
+};
+
+/// The checksum sent with uploaded content, so the service can validate it was not corrupted in transit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    #[cfg(feature = "md5")]
+    MD5,
+    CRC64,
+}
+
+/// Largest range the service returns a transactional checksum for.
+pub(crate) const MAX_CHECKSUM_RANGE: u64 = 4 * 1024 * 1024;
+
+impl ChecksumAlgorithm {
+    /// Compute the checksum of `data`.
+    pub fn hash(&self, data: &[u8]) -> Hash {
+        match self {
+            #[cfg(feature = "md5")]
+            ChecksumAlgorithm::MD5 => md5::compute(data).into(),
+            ChecksumAlgorithm::CRC64 => Hash::CRC64(crc64::checksum(data)),
+        }
+    }
+
+    /// The request header asking the service to return the checksum of a range.
+    pub(crate) fn range_request_header(&self) -> HeaderName {
+        match self {
+            #[cfg(feature = "md5")]
+            ChecksumAlgorithm::MD5 => headers::RANGE_GET_CONTENT_MD5,
+            ChecksumAlgorithm::CRC64 => headers::RANGE_GET_CONTENT_CRC64,
+        }
+    }
+
+    /// Add the checksum of an uploaded `body` to the request `headers`.
+    ///
+    /// Streamed bodies are sent as a structured message when using CRC64, so their checksum is
+    /// computed while they are sent. With MD5 they are read once to compute the hash, then reset.
+    pub(crate) async fn apply(
+        &self,
+        body: Body,
+        headers: &mut Headers,
+    ) -> azure_core::Result<(Body, SentChecksum)> {
+        match body {
+            Body::Bytes(bytes) => {
+                let hash = self.hash(&bytes);
+                headers.add(hash.clone());
+                Ok((Body::Bytes(bytes), SentChecksum::Hash(hash)))
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            Body::SeekableStream(stream) => match self {
+                #[cfg(feature = "md5")]
+                ChecksumAlgorithm::MD5 => {
+                    use futures::StreamExt;
+
+                    let mut stream = stream;
+                    let mut context = md5::Context::new();
+                    while let Some(bytes) = stream.next().await {
+                        context.consume(bytes?);
+                    }
+                    stream.reset().await?;
+                    let hash: Hash = context.compute().into();
+                    headers.add(hash.clone());
+                    Ok((Body::SeekableStream(stream), SentChecksum::Hash(hash)))
+                }
+                ChecksumAlgorithm::CRC64 => {
+                    let encoder = structured_message::StructuredMessageEncoder::new(stream);
+                    headers.insert(STRUCTURED_BODY, structured_message::STRUCTURED_BODY_CRC64);
+                    headers.insert(
+                        STRUCTURED_CONTENT_LENGTH,
+                        encoder.content_length().to_string(),
+                    );
+                    Ok((
+                        Body::SeekableStream(Box::new(encoder)),
+                        SentChecksum::StructuredMessage,
+                    ))
+                }
+            },
+        }
+    }
+
+    /// Check `data` against the checksum returned by the service in `headers`.
+    pub(crate) fn validate(&self, headers: &Headers, data: &[u8]) -> azure_core::Result<()> {
+        let expected = match self {
+            #[cfg(feature = "md5")]
+            ChecksumAlgorithm::MD5 => {
+                let md5: ConsistencyMD5 = headers.get_as(&headers::CONTENT_MD5)?;
+                Hash::MD5(*md5.as_slice())
+            }
+            ChecksumAlgorithm::CRC64 => {
+                let crc64 = content_crc64_from_headers(headers)?;
+                Hash::CRC64(u64::from_le_bytes(*crc64.as_slice()))
+            }
+        };
+        let actual = self.hash(data);
+        if actual != expected {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("checksum mismatch, expected {expected:?} but content has {actual:?}")
+            }));
+        }
+        Ok(())
+    }
+}
+
+/// The checksum sent with an upload, to compare with the one the service returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SentChecksum {
+    Hash(Hash),
+    StructuredMessage,
+}
+
+/// Add the `hash` of an uploaded `body` to `headers`, or else the checksum computed with `checksum`.
+pub(crate) async fn add_upload_checksum(
+    body: Body,
+    hash: Option<Hash>,
+    checksum: Option<ChecksumAlgorithm>,
+    headers: &mut Headers,
+) -> azure_core::Result<(Body, Option<SentChecksum>)> {
+    match (hash, checksum) {
+        (None, Some(checksum)) => {
+            let (body, sent) = checksum.apply(body, headers).await?;
+            Ok((body, Some(sent)))
+        }
+        (hash, _) => {
+            headers.add(hash);
+            Ok((body, None))
+        }
+    }
+}
+
+impl SentChecksum {
+    /// Set the service version needed to send a structured message on the finalized `request`.
+    pub(crate) fn prepare(&self, request: &mut Request) {
+        if let SentChecksum::StructuredMessage = self {
+            request.insert_header(headers::VERSION, structured_message::SERVICE_VERSION);
+        }
+    }
+
+    /// Check the response `headers` of an upload agree with the checksum that was sent.
+    pub(crate) fn verify(&self, headers: &Headers) -> azure_core::Result<()> {
+        let returned = match self {
+            #[cfg(feature = "md5")]
+            SentChecksum::Hash(Hash::MD5(_)) => headers
+                .get_optional_as::<ConsistencyMD5, _>(&headers::CONTENT_MD5)?
+                .map(|md5| Hash::MD5(*md5.as_slice())),
+            #[cfg(not(feature = "md5"))]
+            SentChecksum::Hash(Hash::MD5(_)) => None,
+            SentChecksum::Hash(Hash::CRC64(_)) => {
+                azure_storage::headers::content_crc64_from_headers_optional(headers)?
+                    .map(|crc64| Hash::CRC64(u64::from_le_bytes(*crc64.as_slice())))
+            }
+            SentChecksum::StructuredMessage => {
+                if headers.get_optional_str(&STRUCTURED_BODY).is_none() {
+                    return Err(Error::message(
+                        ErrorKind::DataConversion,
+                        "the service did not acknowledge the structured message checksums",
+                    ));
+                }
+                return Ok(());
+            }
+        };
+        match (self, returned) {
+            (SentChecksum::Hash(sent), Some(returned)) if *sent != returned => {
+                Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!(
+                        "checksum mismatch, sent {sent:?} but the service computed {returned:?}"
+                    )
+                }))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::base64;
+    use azure_storage::headers::CONTENT_CRC64;
+
+    #[test]
+    fn test_validate_crc64() {
+        let mut headers = Headers::new();
+        headers.insert(
+            CONTENT_CRC64,
+            base64::encode(crc64::checksum(b"hello").to_le_bytes()),
+        );
+        assert!(ChecksumAlgorithm::CRC64
+            .validate(&headers, b"hello")
+            .is_ok());
+        assert!(ChecksumAlgorithm::CRC64
+            .validate(&headers, b"world")
+            .is_err());
+        assert!(ChecksumAlgorithm::CRC64
+            .validate(&Headers::new(), b"hello")
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_sent_checksum() {
+        let sent = SentChecksum::Hash(ChecksumAlgorithm::CRC64.hash(b"hello"));
+        let mut headers = Headers::new();
+        assert!(sent.verify(&headers).is_ok());
+
+        headers.insert(
+            CONTENT_CRC64,
+            base64::encode(crc64::checksum(b"hello").to_le_bytes()),
+        );
+        assert!(sent.verify(&headers).is_ok());
+
+        let sent = SentChecksum::Hash(ChecksumAlgorithm::CRC64.hash(b"world"));
+        assert!(sent.verify(&headers).is_err());
+
+        assert!(SentChecksum::StructuredMessage.verify(&headers).is_err());
+        headers.insert(STRUCTURED_BODY, structured_message::STRUCTURED_BODY_CRC64);
+        assert!(SentChecksum::StructuredMessage.verify(&headers).is_ok());
+    }
+
+    #[test]
+    fn test_apply_streamed_crc64() {
+        let body = Body::SeekableStream(Box::new(azure_core::BytesStream::new("hello")));
+        let mut headers = Headers::new();
+        let (body, sent) =
+            futures::executor::block_on(ChecksumAlgorithm::CRC64.apply(body, &mut headers))
+                .unwrap();
+
+        assert_eq!(sent, SentChecksum::StructuredMessage);
+        assert_eq!(body.len() as u64, structured_message::encoded_length(5));
+        assert_eq!(
+            headers.get_optional_str(&STRUCTURED_CONTENT_LENGTH),
+            Some("5")
+        );
+    }
+}
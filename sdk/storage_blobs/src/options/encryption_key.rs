@@ -1,4 +1,7 @@
-use azure_core::headers::{self, AsHeaders, HeaderName, HeaderValue};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::{self, AsHeaders, HeaderName, HeaderValue, Headers},
+};
 
 const DEFAULT_ENCRYPTION_ALGORITHM: &str = "AES256";
 
@@ -20,6 +23,25 @@ impl CPKInfo {
             encryption_algorithm: algorithm,
         }
     }
+
+    /// The base64 encoded SHA-256 hash of the key.
+    pub fn key_sha256(&self) -> &str {
+        &self.encryption_key_sha256
+    }
+
+    /// Check that the service used this key, by the hash it returned.
+    pub(crate) fn verify(&self, headers: &Headers) -> azure_core::Result<()> {
+        let key_sha256 = headers.get_optional_str(&headers::ENCRYPTION_KEY_SHA256);
+        if key_sha256 != Some(self.key_sha256()) {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!(
+                    "the response was for encryption key SHA-256 {key_sha256:?}, not {}",
+                    self.encryption_key_sha256
+                )
+            }));
+        }
+        Ok(())
+    }
 }
 
 impl From<(String, String)> for CPKInfo {
@@ -62,3 +84,19 @@ impl AsHeaders for &CPKInfo {
         (*self).as_headers()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify() {
+        let key = CPKInfo::from(("key".to_owned(), "hash".to_owned()));
+        let mut headers = Headers::new();
+        assert!(key.verify(&headers).is_err());
+        headers.insert(headers::ENCRYPTION_KEY_SHA256, "other");
+        assert!(key.verify(&headers).is_err());
+        headers.insert(headers::ENCRYPTION_KEY_SHA256, "hash");
+        assert!(key.verify(&headers).is_ok());
+    }
+}
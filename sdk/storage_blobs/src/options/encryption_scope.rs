@@ -1,11 +1,18 @@
 use azure_core::headers::{self, Header};
+use azure_storage::headers::ENCRYPTION_SCOPE;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EncryptionScope(String);
 
+impl EncryptionScope {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
 impl Header for EncryptionScope {
     fn name(&self) -> headers::HeaderName {
-        "x-ms-encryption-scope".into()
+        ENCRYPTION_SCOPE
     }
 
     fn value(&self) -> headers::HeaderValue {
@@ -18,3 +25,9 @@ impl From<String> for EncryptionScope {
         Self(s)
     }
 }
+
+impl From<&str> for EncryptionScope {
+    fn from(s: &str) -> Self {
+        Self(s.to_owned())
+    }
+}
@@ -0,0 +1,17 @@
+use azure_core::headers::{self, Header};
+
+create_enum!(
+    ImmutabilityPolicyMode,
+    (Unlocked, "Unlocked"),
+    (Locked, "Locked")
+);
+
+impl Header for ImmutabilityPolicyMode {
+    fn name(&self) -> headers::HeaderName {
+        headers::IMMUTABILITY_POLICY_MODE
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        self.as_ref().to_owned().into()
+    }
+}
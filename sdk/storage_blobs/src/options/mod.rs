@@ -11,14 +11,18 @@ mod blob_content_type;
 mod blob_expiry;
 mod blob_versioning;
 mod block_id;
+mod checksum_algorithm;
 mod condition_append_position;
 mod condition_max_size;
 mod delete_snapshot_method;
 mod encryption_key;
 mod encryption_scope;
 mod hash;
+mod immutability_policy_mode;
 mod rehydrate_policy;
+mod tag_filter;
 mod tags;
+mod transfer_progress;
 
 pub use access_tier::AccessTier;
 pub use ba512_range::BA512Range;
@@ -31,14 +35,19 @@ pub use blob_content_type::BlobContentType;
 pub use blob_expiry::BlobExpiry;
 pub use blob_versioning::BlobVersioning;
 pub use block_id::BlockId;
+pub use checksum_algorithm::ChecksumAlgorithm;
+pub(crate) use checksum_algorithm::{add_upload_checksum, MAX_CHECKSUM_RANGE};
 pub use condition_append_position::ConditionAppendPosition;
 pub use condition_max_size::ConditionMaxSize;
 pub use delete_snapshot_method::DeleteSnapshotsMethod;
 pub use encryption_key::CPKInfo;
 pub use encryption_scope::EncryptionScope;
 pub use hash::Hash;
+pub use immutability_policy_mode::ImmutabilityPolicyMode;
 pub use rehydrate_policy::RehydratePriority;
+pub use tag_filter::{tag, TagFilter, TagKey};
 pub use tags::Tags;
+pub use transfer_progress::TransferProgress;
 
 use std::str::FromStr;
 
@@ -71,3 +80,6 @@ impl FromStr for Snapshot {
 }
 
 pub const SNAPSHOT: HeaderName = HeaderName::from_static("x-ms-snapshot");
+
+/// The MD5 of the whole blob, returned when a range of it is downloaded.
+pub(crate) const BLOB_CONTENT_MD5: HeaderName = HeaderName::from_static("x-ms-blob-content-md5");
@@ -0,0 +1,328 @@
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::IfTags,
+};
+use std::fmt;
+
+const MAX_KEY_LENGTH: usize = 128;
+const MAX_VALUE_LENGTH: usize = 256;
+
+/// Start a condition on the value of the blob index tag `key`.
+///
+/// ```
+/// use azure_storage_blobs::prelude::*;
+///
+/// let filter = tag("project").eq("x").and(tag("date").gt("2024-01-01"));
+/// assert_eq!(
+///     filter.find_expression().unwrap(),
+///     r#""project" = 'x' AND "date" > '2024-01-01'"#
+/// );
+/// ```
+pub fn tag(key: impl Into<String>) -> TagKey {
+    TagKey(key.into())
+}
+
+/// A blob index tag key, to compare with a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagKey(String);
+
+impl TagKey {
+    fn condition(self, operator: Operator, value: impl Into<String>) -> TagFilter {
+        TagFilter(Expression::Condition {
+            key: self.0,
+            operator,
+            value: value.into(),
+        })
+    }
+
+    pub fn eq(self, value: impl Into<String>) -> TagFilter {
+        self.condition(Operator::Eq, value)
+    }
+
+    /// Only supported by conditional headers, not by Find Blobs by Tags.
+    pub fn ne(self, value: impl Into<String>) -> TagFilter {
+        self.condition(Operator::Ne, value)
+    }
+
+    pub fn gt(self, value: impl Into<String>) -> TagFilter {
+        self.condition(Operator::Gt, value)
+    }
+
+    pub fn ge(self, value: impl Into<String>) -> TagFilter {
+        self.condition(Operator::Ge, value)
+    }
+
+    pub fn lt(self, value: impl Into<String>) -> TagFilter {
+        self.condition(Operator::Lt, value)
+    }
+
+    pub fn le(self, value: impl Into<String>) -> TagFilter {
+        self.condition(Operator::Le, value)
+    }
+}
+
+/// A filter on the blob index tags of blobs, for Find Blobs by Tags and for `x-ms-if-tags`
+/// conditional headers.
+///
+/// Tag values are compared as strings. The keys and values are checked against the rules of the
+/// service when the filter is turned into an expression, with
+/// [`find_expression`](TagFilter::find_expression) or [`if_tags`](TagFilter::if_tags), so that
+/// invalid filters fail before being sent.
+///
+/// ref: <https://learn.microsoft.com/azure/storage/blobs/storage-manage-find-blobs>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagFilter(Expression);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expression {
+    Condition {
+        key: String,
+        operator: Operator,
+        value: String,
+    },
+    Container(String),
+    And(Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Operator {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Eq => "=",
+            Self::Ne => "<>",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+        }
+    }
+}
+
+/// Where a filter is used, which decides what it may contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Usage {
+    FindBlobs,
+    IfTags,
+}
+
+impl TagFilter {
+    /// Only find the blobs of a container.
+    ///
+    /// Only supported by Find Blobs by Tags, not by conditional headers.
+    pub fn container(container_name: impl Into<String>) -> Self {
+        Self(Expression::Container(container_name.into()))
+    }
+
+    /// Match the blobs matching both filters.
+    pub fn and(self, other: TagFilter) -> Self {
+        Self(Expression::And(Box::new(self.0), Box::new(other.0)))
+    }
+
+    /// Match the blobs matching either filter.
+    ///
+    /// Only supported by conditional headers, not by Find Blobs by Tags.
+    pub fn or(self, other: TagFilter) -> Self {
+        Self(Expression::Or(Box::new(self.0), Box::new(other.0)))
+    }
+
+    /// The expression of the filter for Find Blobs by Tags.
+    pub fn find_expression(&self) -> azure_core::Result<String> {
+        let mut containers = 0;
+        self.0.validate(Usage::FindBlobs, &mut containers)?;
+        if containers > 1 {
+            return Err(invalid("a filter can only be scoped to one container"));
+        }
+        Ok(self.to_string())
+    }
+
+    /// The filter as an `x-ms-if-tags` condition, for the operations on blobs.
+    pub fn if_tags(&self) -> azure_core::Result<IfTags> {
+        self.0.validate(Usage::IfTags, &mut 0)?;
+        Ok(IfTags::new(self.to_string()))
+    }
+}
+
+impl Expression {
+    fn validate(&self, usage: Usage, containers: &mut usize) -> azure_core::Result<()> {
+        match self {
+            Self::Condition {
+                key,
+                operator,
+                value,
+            } => {
+                validate_tag("key", key, 1, MAX_KEY_LENGTH)?;
+                validate_tag("value", value, 0, MAX_VALUE_LENGTH)?;
+                if usage == Usage::FindBlobs && *operator == Operator::Ne {
+                    return Err(invalid(
+                        "Find Blobs by Tags does not support the <> operator",
+                    ));
+                }
+                Ok(())
+            }
+            Self::Container(name) => {
+                if usage == Usage::IfTags {
+                    return Err(invalid(
+                        "conditional headers cannot be scoped to a container",
+                    ));
+                }
+                validate_container_name(name)?;
+                *containers += 1;
+                Ok(())
+            }
+            Self::And(left, right) => {
+                left.validate(usage, containers)?;
+                right.validate(usage, containers)
+            }
+            Self::Or(left, right) => {
+                if usage == Usage::FindBlobs {
+                    return Err(invalid("Find Blobs by Tags does not support OR"));
+                }
+                left.validate(usage, containers)?;
+                right.validate(usage, containers)
+            }
+        }
+    }
+}
+
+/// The expression of the filter, without checking it.
+impl fmt::Display for TagFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Condition {
+                key,
+                operator,
+                value,
+            } => write!(f, "\"{key}\" {} '{value}'", operator.as_str()),
+            Self::Container(name) => write!(f, "@container = '{name}'"),
+            Self::And(left, right) => {
+                // AND takes precedence over OR
+                for (i, operand) in [left, right].into_iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" AND ")?;
+                    }
+                    match operand.as_ref() {
+                        Self::Or(..) => write!(f, "({operand})")?,
+                        _ => write!(f, "{operand}")?,
+                    }
+                }
+                Ok(())
+            }
+            Self::Or(left, right) => write!(f, "{left} OR {right}"),
+        }
+    }
+}
+
+/// Tag keys and values are made of alphanumeric characters and ` +-.:=_/`.
+fn validate_tag(
+    kind: &str,
+    tag: &str,
+    min_length: usize,
+    max_length: usize,
+) -> azure_core::Result<()> {
+    let length = tag.chars().count();
+    if length < min_length || length > max_length {
+        return Err(Error::with_message(ErrorKind::DataConversion, || {
+            format!(
+                "tag {kind} {tag:?} must be between {min_length} and {max_length} characters long"
+            )
+        }));
+    }
+    if let Some(c) = tag
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && !" +-.:=_/".contains(*c))
+    {
+        return Err(Error::with_message(ErrorKind::DataConversion, || {
+            format!("tag {kind} {tag:?} contains the invalid character {c:?}")
+        }));
+    }
+    Ok(())
+}
+
+/// Container names are 3 to 63 lowercase letters, digits and dashes.
+fn validate_container_name(name: &str) -> azure_core::Result<()> {
+    let valid = (3..=63).contains(&name.len())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid {
+        return Err(Error::with_message(ErrorKind::DataConversion, || {
+            format!("invalid container name {name:?}")
+        }));
+    }
+    Ok(())
+}
+
+fn invalid(message: &'static str) -> Error {
+    Error::message(ErrorKind::DataConversion, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::headers::Header;
+
+    #[test]
+    fn test_find_expression() {
+        let filter = TagFilter::container("logs")
+            .and(tag("project").eq("x"))
+            .and(
+                tag("date")
+                    .ge("2024-01-01")
+                    .and(tag("date").lt("2024-02-01")),
+            );
+        assert_eq!(
+            filter.find_expression().unwrap(),
+            r#"@container = 'logs' AND "project" = 'x' AND "date" >= '2024-01-01' AND "date" < '2024-02-01'"#
+        );
+
+        assert!(tag("a").ne("b").find_expression().is_err());
+        assert!(tag("a")
+            .eq("b")
+            .or(tag("c").eq("d"))
+            .find_expression()
+            .is_err());
+        assert!(TagFilter::container("a")
+            .and(TagFilter::container("b"))
+            .find_expression()
+            .is_err());
+        assert!(TagFilter::container("Logs").find_expression().is_err());
+    }
+
+    #[test]
+    fn test_if_tags() {
+        let filter = tag("status").eq("done").or(tag("status").eq("failed"));
+        let filter = tag("owner").ne("me").and(filter);
+        assert_eq!(
+            filter.if_tags().unwrap().value().as_str(),
+            r#""owner" <> 'me' AND ("status" = 'done' OR "status" = 'failed')"#
+        );
+
+        assert!(TagFilter::container("logs").if_tags().is_err());
+    }
+
+    #[test]
+    fn test_validate_tags() {
+        assert!(tag("key name+-.:=_/").eq("").if_tags().is_ok());
+        assert!(tag("").eq("a").if_tags().is_err());
+        assert!(tag("a".repeat(129)).eq("a").if_tags().is_err());
+        assert!(tag("a").eq("b".repeat(257)).if_tags().is_err());
+        assert!(tag("a").eq("it's").if_tags().is_err());
+        assert!(tag("a\"").eq("b").if_tags().is_err());
+    }
+}
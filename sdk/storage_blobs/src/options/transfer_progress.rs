@@ -0,0 +1,26 @@
+use std::{fmt, sync::Arc};
+
+/// Called with the total number of bytes transferred so far, each time a block or range completes.
+#[derive(Clone)]
+pub struct TransferProgress(Arc<dyn Fn(u64) + Send + Sync>);
+
+impl TransferProgress {
+    pub(crate) fn report(&self, transferred: u64) {
+        (self.0)(transferred)
+    }
+}
+
+impl<F> From<F> for TransferProgress
+where
+    F: Fn(u64) + Send + Sync + 'static,
+{
+    fn from(f: F) -> Self {
+        Self(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for TransferProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TransferProgress")
+    }
+}
@@ -10,4 +10,5 @@ pub use crate::{
 pub use azure_storage::{StoredAccessPolicy, StoredAccessPolicyList};
 pub use azure_svc_blobstorage::models::{
     storage_service_properties::Cors, CorsRule, Logging, Metrics, RetentionPolicy, StaticWebsite,
+    StorageServiceProperties,
 };
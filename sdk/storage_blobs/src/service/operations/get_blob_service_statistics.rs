@@ -0,0 +1,109 @@
+use crate::prelude::BlobServiceClient;
+use azure_core::{
+    date,
+    error::{ErrorKind, ResultExt},
+    headers::Headers,
+    Method, Response,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+operation! {
+    GetBlobServiceStatistics,
+    client: BlobServiceClient,
+}
+
+impl GetBlobServiceStatisticsBuilder {
+    pub fn into_future(mut self) -> GetBlobServiceStatistics {
+        Box::pin(async move {
+            let mut url = self.client.secondary_url()?;
+
+            url.query_pairs_mut()
+                .extend_pairs([("restype", "service"), ("comp", "stats")]);
+
+            let mut request =
+                BlobServiceClient::finalize_request(url, Method::Get, Headers::new(), None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            GetBlobServiceStatisticsResponse::try_from(response).await
+        })
+    }
+}
+
+/// The status of the secondary location of a geo-replicated account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GeoReplicationStatus {
+    Live,
+    Bootstrap,
+    Unavailable,
+}
+
+#[derive(Debug, Clone)]
+pub struct GetBlobServiceStatisticsResponse {
+    pub common: CommonStorageResponseHeaders,
+    pub status: GeoReplicationStatus,
+    /// All primary writes preceding this time are available for reads on the secondary.
+    pub last_sync_time: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StorageServiceStats {
+    geo_replication: GeoReplication,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GeoReplication {
+    status: GeoReplicationStatus,
+    last_sync_time: Option<String>,
+}
+
+impl GetBlobServiceStatisticsResponse {
+    pub(crate) async fn try_from(response: Response) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let stats: StorageServiceStats = body.xml().await?;
+
+        Ok(Self {
+            common: (&headers).try_into()?,
+            status: stats.geo_replication.status,
+            last_sync_time: stats
+                .geo_replication
+                .last_sync_time
+                .filter(|t| !t.is_empty())
+                .map(|t| {
+                    date::parse_rfc1123(&t)
+                        .context(ErrorKind::DataConversion, "failed to parse last sync time")
+                })
+                .transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_stats() {
+        let xml = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+            <StorageServiceStats><GeoReplication>\
+            <Status>bootstrap</Status><LastSyncTime />\
+            </GeoReplication></StorageServiceStats>";
+
+        let stats: StorageServiceStats = azure_core::xml::read_xml_str(xml).unwrap();
+
+        assert_eq!(
+            stats.geo_replication.status,
+            GeoReplicationStatus::Bootstrap
+        );
+        assert!(stats
+            .geo_replication
+            .last_sync_time
+            .filter(|t| !t.is_empty())
+            .is_none());
+    }
+}
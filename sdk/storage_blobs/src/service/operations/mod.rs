@@ -1,11 +1,17 @@
 mod find_blobs_by_tags;
 mod get_account_information;
 mod get_blob_service_properties;
+mod get_blob_service_statistics;
 mod get_user_delegation_key;
 mod list_containers;
+mod set_blob_service_properties;
+mod submit_batch;
 
 pub use find_blobs_by_tags::*;
 pub use get_account_information::*;
 pub use get_blob_service_properties::*;
+pub use get_blob_service_statistics::*;
 pub use get_user_delegation_key::*;
 pub use list_containers::*;
+pub use set_blob_service_properties::*;
+pub use submit_batch::*;
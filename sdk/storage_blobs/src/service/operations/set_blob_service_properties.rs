@@ -0,0 +1,81 @@
+use crate::prelude::BlobServiceClient;
+use azure_core::{headers::Headers, xml::to_xml_with_root, Method, Response};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use azure_svc_blobstorage::models::StorageServiceProperties;
+
+operation! {
+    SetBlobServiceProperties,
+    client: BlobServiceClient,
+    properties: StorageServiceProperties,
+}
+
+impl SetBlobServicePropertiesBuilder {
+    pub fn into_future(mut self) -> SetBlobServiceProperties {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut()
+                .extend_pairs([("restype", "service"), ("comp", "properties")]);
+
+            let body = to_xml_with_root("StorageServiceProperties", &self.properties)?;
+
+            let mut request = BlobServiceClient::finalize_request(
+                url,
+                Method::Put,
+                Headers::new(),
+                Some(body.into()),
+            )?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetBlobServicePropertiesResponse {
+    pub common: CommonStorageResponseHeaders,
+}
+
+impl TryFrom<Response> for SetBlobServicePropertiesResponse {
+    type Error = azure_core::Error;
+
+    fn try_from(response: Response) -> azure_core::Result<Self> {
+        Ok(SetBlobServicePropertiesResponse {
+            common: response.headers().try_into()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_svc_blobstorage::models::{RetentionPolicy, StaticWebsite};
+
+    #[test]
+    fn serialize_properties() {
+        let properties = StorageServiceProperties {
+            default_service_version: Some("2020-10-02".to_owned()),
+            delete_retention_policy: Some(RetentionPolicy {
+                days: Some(7),
+                ..RetentionPolicy::new(true)
+            }),
+            static_website: Some(StaticWebsite {
+                index_document: Some("index.html".to_owned()),
+                ..StaticWebsite::new(true)
+            }),
+            ..Default::default()
+        };
+
+        let body = to_xml_with_root("StorageServiceProperties", &properties).unwrap();
+        let body = std::str::from_utf8(&body).unwrap();
+
+        assert!(body.contains("<StorageServiceProperties>"));
+        assert!(body.contains("<DefaultServiceVersion>2020-10-02</DefaultServiceVersion>"));
+        assert!(body.contains(
+            "<DeleteRetentionPolicy><Enabled>true</Enabled><Days>7</Days></DeleteRetentionPolicy>"
+        ));
+        assert!(body.contains("<IndexDocument>index.html</IndexDocument>"));
+    }
+}
@@ -0,0 +1,530 @@
+use crate::prelude::*;
+use azure_core::{
+    date,
+    error::{Error, ErrorKind, ResultExt},
+    headers::*,
+    prelude::*,
+    Method, Request, RequestId, StatusCode, Url,
+};
+use azure_storage::clients::ServiceType;
+use bytes::Bytes;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// The maximum number of sub-requests a single batch request may contain.
+pub const MAX_BATCH_OPERATIONS: usize = 256;
+
+operation! {
+    /// Submit several delete or set tier operations as a single batch request.
+    ///
+    /// ref: <https://docs.microsoft.com/rest/api/storageservices/blob-batch>
+    SubmitBatch,
+    client: BlobServiceClient,
+    container_client: Option<ContainerClient>,
+    operations: Vec<BatchOperation>,
+}
+
+impl SubmitBatchBuilder {
+    /// Add an operation to the batch.
+    pub fn operation(mut self, operation: impl Into<BatchOperation>) -> Self {
+        self.operations.push(operation.into());
+        self
+    }
+
+    /// Add a delete operation to the batch.
+    pub fn delete(self, delete: BatchDelete) -> Self {
+        self.operation(delete)
+    }
+
+    /// Add a set tier operation to the batch.
+    pub fn set_tier(self, set_tier: BatchSetTier) -> Self {
+        self.operation(set_tier)
+    }
+
+    pub fn into_future(mut self) -> SubmitBatch {
+        Box::pin(async move {
+            if self.operations.is_empty() {
+                return Err(Error::message(
+                    ErrorKind::Other,
+                    "a batch must contain at least one operation",
+                ));
+            }
+            if self.operations.len() > MAX_BATCH_OPERATIONS {
+                return Err(Error::with_message(ErrorKind::Other, || {
+                    format!(
+                        "a batch can contain at most {MAX_BATCH_OPERATIONS} operations, got {}",
+                        self.operations.len()
+                    )
+                }));
+            }
+
+            let mut url = match &self.container_client {
+                Some(container_client) => {
+                    for operation in &self.operations {
+                        let blob_container = operation.blob_client().container_client();
+                        if blob_container.container_name() != container_client.container_name() {
+                            return Err(Error::with_message(ErrorKind::Other, || {
+                                format!(
+                                    "blob {} is not in container {}",
+                                    operation.blob_client().blob_name(),
+                                    container_client.container_name()
+                                )
+                            }));
+                        }
+                    }
+                    let mut url = container_client.url()?;
+                    url.query_pairs_mut().append_pair("restype", "container");
+                    url
+                }
+                None => self.client.url()?,
+            };
+            url.query_pairs_mut().append_pair("comp", "batch");
+
+            let mut requests = Vec::with_capacity(self.operations.len());
+            for operation in &self.operations {
+                let mut request = operation.to_request()?;
+                self.client
+                    .credentials()
+                    .authorize_request(&mut request, ServiceType::Blob)
+                    .await?;
+                requests.push(request);
+            }
+
+            let boundary = format!("batch_{}", Uuid::new_v4());
+            let body = serialize_batch(&boundary, &requests);
+
+            let mut headers = Headers::new();
+            headers.insert(
+                CONTENT_TYPE,
+                format!("multipart/mixed; boundary={boundary}"),
+            );
+
+            let mut request =
+                BlobServiceClient::finalize_request(url, Method::Post, headers, Some(body.into()))?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            let (_, headers, body) = response.deconstruct();
+            let body = body.collect().await?;
+
+            SubmitBatchResponse::from_response(&headers, &body)
+        })
+    }
+}
+
+/// A single operation within a batch request.
+#[derive(Debug, Clone)]
+pub enum BatchOperation {
+    Delete(BatchDelete),
+    SetTier(BatchSetTier),
+}
+
+impl BatchOperation {
+    fn blob_client(&self) -> &BlobClient {
+        match self {
+            BatchOperation::Delete(delete) => &delete.blob_client,
+            BatchOperation::SetTier(set_tier) => &set_tier.blob_client,
+        }
+    }
+
+    fn to_request(&self) -> azure_core::Result<Request> {
+        match self {
+            BatchOperation::Delete(delete) => delete.to_request(),
+            BatchOperation::SetTier(set_tier) => set_tier.to_request(),
+        }
+    }
+}
+
+impl From<BatchDelete> for BatchOperation {
+    fn from(delete: BatchDelete) -> Self {
+        Self::Delete(delete)
+    }
+}
+
+impl From<BatchSetTier> for BatchOperation {
+    fn from(set_tier: BatchSetTier) -> Self {
+        Self::SetTier(set_tier)
+    }
+}
+
+/// Delete a blob, or one of its snapshots or versions, as part of a batch.
+///
+/// Deleting the base blob also deletes its snapshots, unless `delete_snapshots_method` says otherwise.
+#[derive(Debug, Clone)]
+pub struct BatchDelete {
+    blob_client: BlobClient,
+    blob_versioning: Option<BlobVersioning>,
+    if_modified_since: Option<IfModifiedSinceCondition>,
+    if_match: Option<IfMatchCondition>,
+    if_tags: Option<IfTags>,
+    delete_snapshots_method: Option<DeleteSnapshotsMethod>,
+    lease_id: Option<LeaseId>,
+}
+
+impl BatchDelete {
+    pub fn new(blob_client: BlobClient) -> Self {
+        Self {
+            blob_client,
+            blob_versioning: None,
+            if_modified_since: None,
+            if_match: None,
+            if_tags: None,
+            delete_snapshots_method: None,
+            lease_id: None,
+        }
+    }
+
+    setters! {
+        blob_versioning: BlobVersioning => Some(blob_versioning),
+        if_modified_since: IfModifiedSinceCondition => Some(if_modified_since),
+        if_match: IfMatchCondition => Some(if_match),
+        if_tags: IfTags => Some(if_tags),
+        delete_snapshots_method: DeleteSnapshotsMethod => Some(delete_snapshots_method),
+        lease_id: LeaseId => Some(lease_id),
+    }
+
+    fn to_request(&self) -> azure_core::Result<Request> {
+        let mut url = self.blob_client.url()?;
+        self.blob_versioning.append_to_url_query(&mut url);
+
+        let mut headers = Headers::new();
+        headers.add(self.lease_id);
+        // snapshots and versions have no snapshots of their own to delete
+        match &self.blob_versioning {
+            Some(_) => headers.add(self.delete_snapshots_method),
+            None => headers.add(
+                self.delete_snapshots_method
+                    .unwrap_or(DeleteSnapshotsMethod::Include),
+            ),
+        }
+        headers.add(self.if_modified_since);
+        headers.add(self.if_match.clone());
+        headers.add(self.if_tags.clone());
+
+        Ok(sub_request(url, Method::Delete, headers))
+    }
+}
+
+/// Set the access tier of a blob as part of a batch.
+#[derive(Debug, Clone)]
+pub struct BatchSetTier {
+    blob_client: BlobClient,
+    access_tier: AccessTier,
+    rehydrate_priority: Option<RehydratePriority>,
+    blob_versioning: Option<BlobVersioning>,
+    if_tags: Option<IfTags>,
+}
+
+impl BatchSetTier {
+    pub fn new(blob_client: BlobClient, access_tier: AccessTier) -> Self {
+        Self {
+            blob_client,
+            access_tier,
+            rehydrate_priority: None,
+            blob_versioning: None,
+            if_tags: None,
+        }
+    }
+
+    setters! {
+        rehydrate_priority: RehydratePriority => Some(rehydrate_priority),
+        blob_versioning: BlobVersioning => Some(blob_versioning),
+        if_tags: IfTags => Some(if_tags),
+    }
+
+    fn to_request(&self) -> azure_core::Result<Request> {
+        let mut url = self.blob_client.url()?;
+        url.query_pairs_mut().append_pair("comp", "tier");
+        self.blob_versioning.append_to_url_query(&mut url);
+
+        let mut headers = Headers::new();
+        headers.add(self.access_tier);
+        headers.add(self.rehydrate_priority);
+        headers.add(self.if_tags.clone());
+
+        Ok(sub_request(url, Method::Put, headers))
+    }
+}
+
+/// Build a sub-request. Unlike top level requests, sub-requests must not carry `x-ms-version`.
+fn sub_request(url: Url, method: Method, headers: Headers) -> Request {
+    let mut request = Request::new(url, method);
+    for (name, value) in headers {
+        request.insert_header(name, value);
+    }
+    request.insert_header(CONTENT_LENGTH, "0");
+    request.insert_header(MS_DATE, date::to_rfc1123(&OffsetDateTime::now_utc()));
+    request
+}
+
+fn serialize_batch(boundary: &str, requests: &[Request]) -> Bytes {
+    let mut body = String::new();
+    for (index, request) in requests.iter().enumerate() {
+        body.push_str(&format!("--{boundary}\r\n"));
+        body.push_str("Content-Type: application/http\r\n");
+        body.push_str("Content-Transfer-Encoding: binary\r\n");
+        body.push_str(&format!("Content-ID: {index}\r\n\r\n"));
+        body.push_str(&format!(
+            "{} {} HTTP/1.1\r\n",
+            request.method().as_ref(),
+            request.path_and_query()
+        ));
+        for (name, value) in request.headers().iter() {
+            body.push_str(&format!("{}: {}\r\n", name.as_str(), value.as_str()));
+        }
+        body.push_str("\r\n");
+    }
+    body.push_str(&format!("--{boundary}--\r\n"));
+    Bytes::from(body)
+}
+
+/// The outcome of a single operation within a batch.
+#[derive(Debug, Clone)]
+pub struct BatchResult {
+    /// The position of the operation in the batch.
+    pub index: usize,
+    pub status: StatusCode,
+    pub request_id: Option<RequestId>,
+    pub error_code: Option<String>,
+    pub headers: Headers,
+    pub body: Bytes,
+}
+
+impl BatchResult {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&(self.status as u16))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SubmitBatchResponse {
+    pub request_id: RequestId,
+    pub date: OffsetDateTime,
+    /// The results of the operations, in the order they were added to the batch.
+    pub results: Vec<BatchResult>,
+}
+
+impl SubmitBatchResponse {
+    fn from_response(headers: &Headers, body: &[u8]) -> azure_core::Result<Self> {
+        let content_type = headers.get_str(&CONTENT_TYPE)?;
+        let boundary = content_type
+            .split(';')
+            .filter_map(|param| param.trim().strip_prefix("boundary="))
+            .next()
+            .map(|boundary| boundary.trim_matches('"'))
+            .ok_or_else(|| {
+                Error::with_message(ErrorKind::DataConversion, || {
+                    format!("batch response content type has no boundary: {content_type}")
+                })
+            })?;
+
+        Ok(Self {
+            request_id: request_id_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+            results: parse_batch_results(boundary, body)?,
+        })
+    }
+}
+
+fn parse_batch_results(boundary: &str, body: &[u8]) -> azure_core::Result<Vec<BatchResult>> {
+    let body = std::str::from_utf8(body).context(
+        ErrorKind::DataConversion,
+        "batch response is not valid UTF-8",
+    )?;
+    let delimiter = format!("--{boundary}");
+
+    let mut results = Vec::new();
+    for (position, part) in body.split(delimiter.as_str()).skip(1).enumerate() {
+        if part.starts_with("--") {
+            break;
+        }
+        let part = part.trim_start_matches(['\r', '\n']);
+        let (part_headers, http) = split_head(part)?;
+        let index = parse_header_lines(part_headers)?
+            .get_optional_as::<usize, _>(&HeaderName::from_static("content-id"))?
+            .unwrap_or(position);
+
+        let (head, body) = split_head(http)?;
+        let (status_line, header_lines) = head.split_once('\n').unwrap_or((head, ""));
+        let status = parse_status_line(status_line.trim_end())?;
+        let headers = parse_header_lines(header_lines)?;
+        let body = body.strip_suffix("\r\n").unwrap_or(body);
+
+        results.push(BatchResult {
+            index,
+            status,
+            request_id: headers.get_optional_as(&REQUEST_ID)?,
+            error_code: headers.get_optional_string(&ERROR_CODE),
+            headers,
+            body: Bytes::copy_from_slice(body.as_bytes()),
+        });
+    }
+
+    results.sort_by_key(|result| result.index);
+    Ok(results)
+}
+
+/// Split an HTTP message into its head and body.
+fn split_head(message: &str) -> azure_core::Result<(&str, &str)> {
+    message
+        .split_once("\r\n\r\n")
+        .or_else(|| message.split_once("\n\n"))
+        .ok_or_else(|| {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!("malformed batch response part: {message}")
+            })
+        })
+}
+
+fn parse_status_line(line: &str) -> azure_core::Result<StatusCode> {
+    let code = line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!("malformed batch response status line: {line}")
+            })
+        })?;
+    StatusCode::try_from(code).map_err(|_| {
+        Error::with_message(ErrorKind::DataConversion, || {
+            format!("invalid status code in batch response: {code}")
+        })
+    })
+}
+
+fn parse_header_lines(lines: &str) -> azure_core::Result<Headers> {
+    let mut headers = Headers::new();
+    for line in lines.lines().map(str::trim_end).filter(|l| !l.is_empty()) {
+        let (name, value) = line.split_once(':').ok_or_else(|| {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!("malformed header in batch response: {line}")
+            })
+        })?;
+        headers.insert(name.trim().to_owned(), value.trim().to_owned());
+    }
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_storage::StorageCredentials;
+
+    #[tokio::test]
+    async fn test_serialize_batch() -> azure_core::Result<()> {
+        let container_client =
+            ClientBuilder::new("account", StorageCredentials::anonymous()).container_client("c");
+        let operations: Vec<BatchOperation> = vec![
+            BatchDelete::new(container_client.blob_client("a.txt")).into(),
+            BatchSetTier::new(container_client.blob_client("b.txt"), AccessTier::Cool).into(),
+        ];
+        let requests = operations
+            .iter()
+            .map(BatchOperation::to_request)
+            .collect::<azure_core::Result<Vec<_>>>()?;
+
+        let body = serialize_batch("batch_test", &requests);
+        let body = std::str::from_utf8(&body).unwrap();
+
+        assert!(body.starts_with("--batch_test\r\nContent-Type: application/http\r\n"));
+        assert!(body.contains("Content-ID: 0\r\n\r\nDELETE /c/a.txt HTTP/1.1\r\n"));
+        assert!(body.contains("Content-ID: 1\r\n\r\nPUT /c/b.txt?comp=tier HTTP/1.1\r\n"));
+        assert!(body.contains("x-ms-access-tier: Cool\r\n"));
+        assert!(body.contains("x-ms-delete-snapshots: include\r\n"));
+        assert!(!body.contains("x-ms-version"));
+        assert!(body.ends_with("--batch_test--\r\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_snapshot_and_version() -> azure_core::Result<()> {
+        let blob_client = ClientBuilder::new("account", StorageCredentials::anonymous())
+            .blob_client("c", "a.txt");
+
+        let request = BatchDelete::new(blob_client.clone())
+            .blob_versioning(Snapshot::new("2024-01-01T00:00:00.0000000Z".to_owned()))
+            .to_request()?;
+        assert_eq!(
+            request.path_and_query(),
+            "/c/a.txt?snapshot=2024-01-01T00%3A00%3A00.0000000Z"
+        );
+        assert!(request
+            .headers()
+            .get_optional_str(&DELETE_SNAPSHOTS)
+            .is_none());
+
+        let request = BatchDelete::new(blob_client)
+            .blob_versioning(VersionId::new("2024-01-01T00:00:00.0000000Z".to_owned()))
+            .to_request()?;
+        assert_eq!(
+            request.path_and_query(),
+            "/c/a.txt?versionid=2024-01-01T00%3A00%3A00.0000000Z"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_batch_results() -> azure_core::Result<()> {
+        let body = concat!(
+            "--batchresponse_66925647\r\n",
+            "Content-Type: application/http\r\n",
+            "Content-ID: 1\r\n",
+            "\r\n",
+            "HTTP/1.1 404 The specified blob does not exist.\r\n",
+            "x-ms-error-code: BlobNotFound\r\n",
+            "x-ms-request-id: 778fdc83-801e-0000-62ff-0334671e2852\r\n",
+            "x-ms-version: 2018-11-09\r\n",
+            "Content-Length: 216\r\n",
+            "Content-Type: application/xml\r\n",
+            "\r\n",
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?><Error><Code>BlobNotFound</Code></Error>\r\n",
+            "--batchresponse_66925647\r\n",
+            "Content-Type: application/http\r\n",
+            "Content-ID: 0\r\n",
+            "\r\n",
+            "HTTP/1.1 202 Accepted\r\n",
+            "x-ms-delete-type-permanent: true\r\n",
+            "x-ms-request-id: 778fdc83-801e-0000-62ff-0334671e284f\r\n",
+            "x-ms-version: 2018-11-09\r\n",
+            "\r\n",
+            "--batchresponse_66925647--\r\n",
+        );
+
+        let results = parse_batch_results("batchresponse_66925647", body.as_bytes())?;
+        assert_eq!(results.len(), 2);
+
+        assert_eq!(results[0].index, 0);
+        assert_eq!(results[0].status, StatusCode::Accepted);
+        assert!(results[0].is_success());
+        assert!(results[0].error_code.is_none());
+        assert!(results[0].body.is_empty());
+
+        assert_eq!(results[1].index, 1);
+        assert_eq!(results[1].status, StatusCode::NotFound);
+        assert!(!results[1].is_success());
+        assert_eq!(results[1].error_code.as_deref(), Some("BlobNotFound"));
+        assert_eq!(
+            results[1].request_id,
+            Some("778fdc83-801e-0000-62ff-0334671e2852".parse().unwrap())
+        );
+        assert!(results[1].body.starts_with(b"<?xml"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_response_boundary() -> azure_core::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert(
+            CONTENT_TYPE,
+            "multipart/mixed; boundary=batchresponse_66925647",
+        );
+        headers.insert(REQUEST_ID, "778fdc83-801e-0000-62ff-033467000000");
+        headers.insert(DATE, "Mon, 04 Nov 2019 18:33:21 GMT");
+        let body = "--batchresponse_66925647\r\nContent-Type: application/http\r\n\r\nHTTP/1.1 202 Accepted\r\n\r\n--batchresponse_66925647--\r\n";
+
+        let response = SubmitBatchResponse::from_response(&headers, body.as_bytes())?;
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.results[0].index, 0);
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use azure_core::{ClientOptions, Pipeline, Url};
 use azure_storage::{
     clients::{new_pipeline_from_options, ServiceType},
     prelude::StorageCredentials,
-    CloudLocation,
+    CloudLocation, ConnectionString,
 };
 
 /// A builder for the blob service client.
@@ -43,6 +43,18 @@ impl DataLakeClientBuilder {
         }
     }
 
+    /// Create a new instance of `DataLakeClientBuilder` from a storage connection string.
+    ///
+    /// The endpoint of the service, or else the account name, protocol and endpoint suffix, give
+    /// the location. The shared access signature or account key give the credentials.
+    pub fn from_connection_string(connection_string: &str) -> azure_core::Result<Self> {
+        let connection_string = ConnectionString::new(connection_string)?;
+        Ok(Self::with_location(
+            connection_string.cloud_location(ServiceType::DataLake)?,
+            connection_string.storage_credentials()?,
+        ))
+    }
+
     /// Convert the builder into a `DataLakeClient` instance.
     #[must_use]
     pub fn build(self) -> DataLakeClient {
@@ -99,6 +111,11 @@ impl DataLakeClient {
         DataLakeClientBuilder::new(account, credentials).build()
     }
 
+    /// Create a new `DataLakeClient` from a storage connection string.
+    pub fn from_connection_string(connection_string: &str) -> azure_core::Result<Self> {
+        Ok(DataLakeClientBuilder::from_connection_string(connection_string)?.build())
+    }
+
     /// Create a new `DataLakeClientBuilder`.
     #[must_use]
     pub fn builder(
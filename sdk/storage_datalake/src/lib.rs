@@ -12,6 +12,7 @@ extern crate azure_core;
 
 pub mod clients;
 pub mod file_system;
+mod object_store;
 pub mod operations;
 pub mod prelude;
 mod properties;
@@ -0,0 +1,227 @@
+use crate::{clients::*, file_system::Path};
+use azure_core::{error::ErrorKind, StatusCode};
+use azure_storage::object_store::{
+    directory_prefix, is_not_found, ListResult, MultipartUpload, ObjectMeta, ObjectStore, DELIMITER,
+};
+use bytes::Bytes;
+use futures::future;
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use std::ops::Range;
+use uuid::Uuid;
+
+fn object_meta(path: &Path) -> ObjectMeta {
+    ObjectMeta {
+        location: path.name.clone(),
+        last_modified: path.last_modified,
+        size: path.content_length.max(0) as u64,
+        e_tag: Some(path.etag.to_string()),
+    }
+}
+
+/// The directory a location is in, if it is not the root of the file system.
+fn parent(location: &str) -> Option<&str> {
+    location.rsplit_once(DELIMITER).map(|(parent, _)| parent)
+}
+
+/// A temporary file next to a location, renamed over it once it is written.
+fn temporary_location(location: &str) -> String {
+    match parent(location) {
+        Some(parent) => format!("{parent}{DELIMITER}.{}.upload", Uuid::new_v4()),
+        None => format!(".{}.upload", Uuid::new_v4()),
+    }
+}
+
+fn is_status(err: &azure_core::Error, expected: StatusCode) -> bool {
+    matches!(err.kind(), ErrorKind::HttpResponse { status, .. } if *status == expected)
+}
+
+impl FileSystemClient {
+    /// Create the directory a location is in, unless it already exists.
+    async fn create_parent(&self, location: &str) -> azure_core::Result<()> {
+        let Some(parent) = parent(location) else {
+            return Ok(());
+        };
+        match self
+            .get_directory_client(parent)
+            .create_if_not_exists()
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) if is_status(&err, StatusCode::Conflict) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// The files directly in a directory, and its subdirectories, or everything below it when
+    /// `recursive`, listed a page at a time.
+    fn list_directory(
+        &self,
+        prefix: Option<&str>,
+        recursive: bool,
+    ) -> BoxStream<'static, azure_core::Result<Path>> {
+        let mut list = self.list_paths().recursive(recursive);
+        let directory = directory_prefix(prefix);
+        if let Some(directory) = directory.strip_suffix(DELIMITER) {
+            list = list.directory(directory.to_owned());
+        }
+        list.into_stream()
+            // the directory does not exist
+            .take_while(|page| future::ready(!matches!(page, Err(err) if is_not_found(err))))
+            .map_ok(|page| stream::iter(page.paths.into_iter().map(Ok)))
+            .try_flatten()
+            .boxed()
+    }
+}
+
+/// Objects are files, and prefixes are directories.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl ObjectStore for FileSystemClient {
+    /// Write a temporary file next to the object and rename it over the object, so readers never
+    /// see a partially written file.
+    async fn put(&self, location: &str, data: Bytes) -> azure_core::Result<()> {
+        let mut upload = self.put_multipart(location).await?;
+        let written = match upload.put_part(data).await {
+            Ok(()) => upload.complete().await,
+            Err(err) => Err(err),
+        };
+        if written.is_err() {
+            // best effort, the write error is more useful than a failed clean up
+            let _ = upload.abort().await;
+        }
+        written
+    }
+
+    async fn get(&self, location: &str) -> azure_core::Result<Bytes> {
+        Ok(self.get_file_client(location).read().await?.data)
+    }
+
+    async fn get_range(&self, location: &str, range: Range<u64>) -> azure_core::Result<Bytes> {
+        if range.is_empty() {
+            return Ok(Bytes::new());
+        }
+        match self.get_file_client(location).read().range(range).await {
+            Ok(response) => Ok(response.data),
+            // the range starts after the end of the file
+            Err(err) if is_status(&err, StatusCode::RequestedRangeNotSatisfiable) => {
+                Ok(Bytes::new())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn head(&self, location: &str) -> azure_core::Result<ObjectMeta> {
+        let properties = self.get_file_client(location).get_properties().await?;
+        Ok(ObjectMeta {
+            location: location.to_owned(),
+            last_modified: properties.last_modified,
+            size: properties.content_length.unwrap_or_default().max(0) as u64,
+            e_tag: Some(properties.etag),
+        })
+    }
+
+    async fn delete(&self, location: &str) -> azure_core::Result<()> {
+        self.get_file_client(location).delete().await?;
+        Ok(())
+    }
+
+    fn list(&self, prefix: Option<&str>) -> BoxStream<'_, azure_core::Result<ObjectMeta>> {
+        self.list_directory(prefix, true)
+            .try_filter(|path| future::ready(!path.is_directory))
+            .map_ok(|path| object_meta(&path))
+            .boxed()
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&str>) -> azure_core::Result<ListResult> {
+        let mut result = ListResult::default();
+        let mut paths = self.list_directory(prefix, false);
+        while let Some(path) = paths.try_next().await? {
+            if path.is_directory {
+                result
+                    .common_prefixes
+                    .push(format!("{}{DELIMITER}", path.name));
+            } else {
+                result.objects.push(object_meta(&path));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Data Lake Storage cannot copy files, so the file is downloaded and uploaded again.
+    async fn copy(&self, from: &str, to: &str) -> azure_core::Result<()> {
+        let data = self.get(from).await?;
+        self.put(to, data).await
+    }
+
+    /// Rename the file atomically.
+    async fn rename(&self, from: &str, to: &str) -> azure_core::Result<()> {
+        self.create_parent(to).await?;
+        self.get_file_client(from).rename(to).await
+    }
+
+    /// Append the parts to a temporary file next to the object, renamed over it when the upload
+    /// completes.
+    async fn put_multipart(&self, location: &str) -> azure_core::Result<Box<dyn MultipartUpload>> {
+        let file = self.get_file_client(temporary_location(location));
+        file.create().await?;
+        Ok(Box::new(FileUpload {
+            file,
+            location: location.to_owned(),
+            position: 0,
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct FileUpload {
+    /// The temporary file the parts are appended to.
+    file: FileClient,
+    location: String,
+    position: i64,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl MultipartUpload for FileUpload {
+    async fn put_part(&mut self, data: Bytes) -> azure_core::Result<()> {
+        let length = data.len() as i64;
+        if length > 0 {
+            self.file.append(self.position, data).await?;
+            self.position += length;
+        }
+        Ok(())
+    }
+
+    async fn complete(&mut self) -> azure_core::Result<()> {
+        self.file.flush(self.position).await?;
+        self.file.rename(self.location.as_str()).await
+    }
+
+    async fn abort(&mut self) -> azure_core::Result<()> {
+        match self.file.delete().await {
+            Ok(_) => Ok(()),
+            Err(err) if is_not_found(&err) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parent() {
+        assert_eq!(parent("a/b/c"), Some("a/b"));
+        assert_eq!(parent("a"), None);
+    }
+
+    #[test]
+    fn test_temporary_location() {
+        let temporary = temporary_location("a/b/c");
+        assert!(temporary.starts_with("a/b/."), "{temporary}");
+        assert!(temporary.ends_with(".upload"), "{temporary}");
+        assert!(temporary_location("c").starts_with('.'));
+        assert_ne!(temporary_location("c"), temporary_location("c"));
+    }
+}
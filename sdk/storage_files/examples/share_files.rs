@@ -0,0 +1,57 @@
+use azure_storage::prelude::*;
+use azure_storage_files::prelude::*;
+use futures::stream::StreamExt;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    // First we retrieve the account name and access key from environment variables.
+    let account =
+        std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let access_key =
+        std::env::var("STORAGE_ACCESS_KEY").expect("Set env variable STORAGE_ACCESS_KEY first!");
+    let share_name = std::env::args()
+        .nth(1)
+        .expect("please specify the share name as command line parameter");
+
+    let storage_credentials = StorageCredentials::access_key(account.clone(), access_key);
+    let share_service = ShareServiceClient::new(account, storage_credentials);
+    let share = share_service.share_client(&share_name);
+
+    println!("creating share {share_name}");
+    share.create().quota(ShareQuota(1)).await?;
+
+    let directory = share.directory_client("reports");
+    directory
+        .create()
+        .smb_properties(SmbProperties::new().attributes(FileAttributes {
+            directory: true,
+            ..Default::default()
+        }))
+        .await?;
+
+    let content = "first line\nsecond line\n";
+    let file = directory.file_client("report.txt");
+    file.create(content.len() as u64)
+        .content_type("text/plain")
+        .await?;
+    file.put_range(0..content.len(), content).await?;
+
+    let snapshot = share.snapshot().await?.snapshot;
+    println!("created snapshot {snapshot}");
+
+    println!("listing the directory");
+    let mut stream = directory.list().into_stream();
+    while let Some(response) = stream.next().await {
+        for file in response?.entries.files() {
+            println!("{} ({:?} bytes)", file.name, file.properties.content_length);
+        }
+    }
+
+    let downloaded = file.get_content().await?;
+    println!("downloaded {:?}", String::from_utf8_lossy(&downloaded));
+
+    println!("deleting share {share_name} and its snapshots");
+    share.delete().include_snapshots(true).await?;
+
+    Ok(())
+}
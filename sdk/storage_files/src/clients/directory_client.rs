@@ -0,0 +1,211 @@
+use crate::{operations::*, FileClient, ShareClient};
+use azure_core::{prelude::*, Request, Response, StatusCode, Url};
+
+#[derive(Debug, Clone)]
+pub struct DirectoryClient {
+    share_client: ShareClient,
+    directory_path: String,
+}
+
+impl DirectoryClient {
+    pub(crate) fn new(share_client: ShareClient, directory_path: String) -> Self {
+        Self {
+            share_client,
+            directory_path,
+        }
+    }
+
+    /// Create the directory. Its parent directory must exist.
+    pub fn create(&self) -> CreateDirectoryBuilder {
+        CreateDirectoryBuilder::new(self.clone())
+    }
+
+    /// Delete the directory, which must be empty.
+    pub fn delete(&self) -> DeleteDirectoryBuilder {
+        DeleteDirectoryBuilder::new(self.clone())
+    }
+
+    /// Get the directory properties, metadata and SMB properties.
+    pub fn get_properties(&self) -> GetDirectoryPropertiesBuilder {
+        GetDirectoryPropertiesBuilder::new(self.clone())
+    }
+
+    /// Replace the directory metadata.
+    pub fn set_metadata(&self, metadata: Metadata) -> SetDirectoryMetadataBuilder {
+        SetDirectoryMetadataBuilder::new(self.clone(), metadata)
+    }
+
+    /// List the files and directories directly within the directory.
+    pub fn list(&self) -> ListDirectoriesAndFilesBuilder {
+        ListDirectoriesAndFilesBuilder::new(self.clone())
+    }
+
+    /// List the SMB handles open on the directory, and optionally on its content.
+    pub fn list_handles(&self) -> ListHandlesBuilder {
+        ListHandlesBuilder::new(self.into())
+    }
+
+    /// Close SMB handles open on the directory, and optionally on its content.
+    ///
+    /// Use `"*"` to close all of them.
+    pub fn force_close_handles<H: Into<String>>(&self, handle_id: H) -> ForceCloseHandlesBuilder {
+        ForceCloseHandlesBuilder::new(self.into(), handle_id.into())
+    }
+
+    /// Check whether the directory exists.
+    pub async fn exists(&self) -> azure_core::Result<bool> {
+        match self.get_properties().await {
+            Ok(_) => Ok(true),
+            Err(err)
+                if err
+                    .as_http_error()
+                    .map(|e| e.status() == StatusCode::NotFound)
+                    .unwrap_or_default() =>
+            {
+                Ok(false)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// A directory within this directory.
+    pub fn directory_client<N: AsRef<str>>(&self, directory_name: N) -> DirectoryClient {
+        DirectoryClient::new(
+            self.share_client(),
+            self.child_path(directory_name.as_ref()),
+        )
+    }
+
+    /// A file within this directory.
+    pub fn file_client<N: AsRef<str>>(&self, file_name: N) -> FileClient {
+        FileClient::new(self.share_client(), self.child_path(file_name.as_ref()))
+    }
+
+    pub fn share_client(&self) -> ShareClient {
+        self.share_client.clone()
+    }
+
+    /// The path of the directory from the root of the share, empty for the root directory.
+    pub fn directory_path(&self) -> &str {
+        &self.directory_path
+    }
+
+    fn child_path(&self, name: &str) -> String {
+        let parent = self.directory_path.trim_end_matches('/');
+        if parent.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{parent}/{name}")
+        }
+    }
+
+    /// Full URL for the directory.
+    pub fn url(&self) -> azure_core::Result<Url> {
+        self.share_client.path_url(&self.directory_path)
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: azure_core::Method,
+        headers: azure_core::headers::Headers,
+        request_body: Option<azure_core::Body>,
+    ) -> azure_core::Result<Request> {
+        ShareClient::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.share_client.send(context, request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock_transport::MockTransport;
+    use azure_core::{Method, StatusCode};
+    use futures::StreamExt;
+    use std::num::NonZeroU32;
+
+    #[tokio::test]
+    async fn test_list() -> azure_core::Result<()> {
+        let transport = MockTransport::new();
+        transport.respond(
+            StatusCode::Ok,
+            &[("content-type", "application/xml")],
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://account.file.core.windows.net/" ShareName="share" DirectoryPath="dir">
+  <Prefix>re</Prefix>
+  <MaxResults>1</MaxResults>
+  <DirectoryId>13835128424026341376</DirectoryId>
+  <Entries>
+    <File>
+      <FileId>13835093239654252544</FileId>
+      <Name>report.csv</Name>
+      <Properties>
+        <Content-Length>1024</Content-Length>
+      </Properties>
+    </File>
+  </Entries>
+  <NextMarker>2!88!MDAwMDA</NextMarker>
+</EnumerationResults>"#,
+        );
+        transport.respond(
+            StatusCode::Ok,
+            &[("content-type", "application/xml")],
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://account.file.core.windows.net/" ShareName="share" DirectoryPath="dir">
+  <Prefix>re</Prefix>
+  <MaxResults>1</MaxResults>
+  <Entries>
+    <Directory>
+      <FileId>13835163608398430208</FileId>
+      <Name>reports</Name>
+      <Properties />
+    </Directory>
+  </Entries>
+  <NextMarker />
+</EnumerationResults>"#,
+        );
+        let directory = transport
+            .client()
+            .share_client("share")
+            .directory_client("dir");
+
+        let mut pages = directory
+            .list()
+            .prefix("re")
+            .max_results(NonZeroU32::new(1).unwrap())
+            .into_stream();
+        let first = pages.next().await.unwrap()?;
+        assert_eq!(first.prefix.as_deref(), Some("re"));
+        assert_eq!(first.directory_id.as_deref(), Some("13835128424026341376"));
+        let files: Vec<_> = first.entries.files().collect();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "report.csv");
+        assert_eq!(files[0].properties.content_length, Some(1024));
+        let second = pages.next().await.unwrap()?;
+        let directories: Vec<_> = second.entries.directories().collect();
+        assert_eq!(directories.len(), 1);
+        assert_eq!(directories[0].name, "reports");
+        assert!(second.next_marker.is_none());
+        assert!(pages.next().await.is_none());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        for request in &requests {
+            assert_eq!(request.method, Method::Get);
+            assert_eq!(request.url.path(), "/share/dir");
+            assert_eq!(request.query("restype").as_deref(), Some("directory"));
+            assert_eq!(request.query("comp").as_deref(), Some("list"));
+            assert_eq!(request.query("prefix").as_deref(), Some("re"));
+            assert_eq!(request.query("maxresults").as_deref(), Some("1"));
+            assert_eq!(request.header("x-ms-file-extended-info"), Some("true"));
+        }
+        assert_eq!(requests[0].query("marker"), None);
+        assert_eq!(requests[1].query("marker").as_deref(), Some("2!88!MDAwMDA"));
+        Ok(())
+    }
+}
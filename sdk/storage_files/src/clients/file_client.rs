@@ -0,0 +1,285 @@
+use crate::{operations::*, FileLeaseClient, ShareClient};
+use azure_core::{prelude::*, Body, Request, Response, StatusCode, Url};
+use azure_storage::{
+    prelude::{FileSasPermissions, FileSignedResource},
+    shared_access_signature::{service_sas::FileSharedAccessSignature, SasToken},
+    CopyId,
+};
+use futures::StreamExt;
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone)]
+pub struct FileClient {
+    share_client: ShareClient,
+    file_path: String,
+}
+
+impl FileClient {
+    pub(crate) fn new(share_client: ShareClient, file_path: String) -> Self {
+        Self {
+            share_client,
+            file_path,
+        }
+    }
+
+    /// Create the file with the given size, or replace it.
+    ///
+    /// This only allocates the file: its content is written with `put_range`.
+    pub fn create(&self, size: u64) -> CreateFileBuilder {
+        CreateFileBuilder::new(self.clone(), size)
+    }
+
+    /// Delete the file.
+    pub fn delete(&self) -> DeleteFileBuilder {
+        DeleteFileBuilder::new(self.clone())
+    }
+
+    /// Stream the content of the file.
+    ///
+    /// The content is downloaded in chunks, each chunk being a separate request.
+    pub fn get(&self) -> GetFileBuilder {
+        GetFileBuilder::new(self.clone())
+    }
+
+    /// Get the whole content of the file.
+    pub async fn get_content(&self) -> azure_core::Result<Vec<u8>> {
+        let mut content = Vec::new();
+        let mut stream = self.get().into_stream();
+        while let Some(response) = stream.next().await {
+            content.extend(response?.data.collect().await?);
+        }
+        Ok(content)
+    }
+
+    /// Get the file properties, metadata and SMB properties.
+    pub fn get_properties(&self) -> GetFilePropertiesBuilder {
+        GetFilePropertiesBuilder::new(self.clone())
+    }
+
+    /// Set the file HTTP and SMB properties, or resize the file.
+    ///
+    /// Content headers left unset are cleared.
+    pub fn set_properties(&self) -> SetFilePropertiesBuilder {
+        SetFilePropertiesBuilder::new(self.clone())
+    }
+
+    /// Replace the file metadata.
+    pub fn set_metadata(&self, metadata: Metadata) -> SetFileMetadataBuilder {
+        SetFileMetadataBuilder::new(self.clone(), metadata)
+    }
+
+    /// Write up to 4 MiB of content at the given range of the file.
+    pub fn put_range(&self, range: impl Into<Range>, content: impl Into<Body>) -> PutRangeBuilder {
+        PutRangeBuilder::new(self.clone(), range.into(), content.into())
+    }
+
+    /// Clear the given range of the file, releasing its storage.
+    pub fn clear_range(&self, range: impl Into<Range>) -> ClearRangeBuilder {
+        ClearRangeBuilder::new(self.clone(), range.into())
+    }
+
+    /// List the ranges of the file holding data.
+    pub fn list_ranges(&self) -> ListRangesBuilder {
+        ListRangesBuilder::new(self.clone())
+    }
+
+    /// Start copying a file or a blob into this file.
+    ///
+    /// The source must be in the same account, or be readable through a shared access signature.
+    pub fn start_copy(&self, copy_source: Url) -> StartCopyBuilder {
+        StartCopyBuilder::new(self.clone(), copy_source)
+    }
+
+    /// Abort a pending copy into this file, leaving it empty.
+    pub fn abort_copy(&self, copy_id: impl Into<CopyId>) -> AbortCopyBuilder {
+        AbortCopyBuilder::new(self.clone(), copy_id.into())
+    }
+
+    /// Acquire an infinite lease on the file, preventing writes and deletion without the lease.
+    pub fn acquire_lease(&self) -> AcquireFileLeaseBuilder {
+        AcquireFileLeaseBuilder::new(self.clone())
+    }
+
+    /// Break the lease on the file, whatever its lease id.
+    pub fn break_lease(&self) -> BreakFileLeaseBuilder {
+        BreakFileLeaseBuilder::new(self.clone())
+    }
+
+    /// List the SMB handles open on the file.
+    pub fn list_handles(&self) -> ListHandlesBuilder {
+        ListHandlesBuilder::new(self.into())
+    }
+
+    /// Close SMB handles open on the file.
+    ///
+    /// Use `"*"` to close all of them.
+    pub fn force_close_handles<H: Into<String>>(&self, handle_id: H) -> ForceCloseHandlesBuilder {
+        ForceCloseHandlesBuilder::new(self.into(), handle_id.into())
+    }
+
+    /// Check whether the file exists.
+    pub async fn exists(&self) -> azure_core::Result<bool> {
+        match self.get_properties().await {
+            Ok(_) => Ok(true),
+            Err(err)
+                if err
+                    .as_http_error()
+                    .map(|e| e.status() == StatusCode::NotFound)
+                    .unwrap_or_default() =>
+            {
+                Ok(false)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn file_lease_client(&self, lease_id: LeaseId) -> FileLeaseClient {
+        FileLeaseClient::new(self.clone(), lease_id)
+    }
+
+    pub fn share_client(&self) -> ShareClient {
+        self.share_client.clone()
+    }
+
+    /// The path of the file from the root of the share.
+    pub fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    /// Create a shared access signature for the file.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: FileSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<FileSharedAccessSignature> {
+        let (canonicalized_resource, key) = self.share_client.signing_key(&self.file_path).await?;
+        Ok(FileSharedAccessSignature::new(
+            key,
+            canonicalized_resource,
+            permissions,
+            expiry,
+            FileSignedResource::File,
+        ))
+    }
+
+    pub fn generate_signed_file_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
+    /// Full URL for the file.
+    pub fn url(&self) -> azure_core::Result<Url> {
+        self.share_client.path_url(&self.file_path)
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: azure_core::Method,
+        headers: azure_core::headers::Headers,
+        request_body: Option<azure_core::Body>,
+    ) -> azure_core::Result<Request> {
+        ShareClient::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.share_client.send(context, request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock_transport::MockTransport;
+    use azure_core::{Method, StatusCode};
+    use futures::StreamExt;
+
+    const FILE_HEADERS: &[(&str, &str)] = &[
+        ("etag", "\"0x8DB4A3B2C1D0E0F\""),
+        ("last-modified", "Mon, 01 May 2023 10:20:30 GMT"),
+        ("x-ms-file-attributes", "Archive"),
+        ("x-ms-file-creation-time", "2023-05-01T10:20:30.1234567Z"),
+        ("x-ms-file-last-write-time", "2023-05-01T10:20:30.1234567Z"),
+        ("x-ms-file-permission-key", "4066528134148476695*1"),
+        ("x-ms-file-id", "13835093239654252544"),
+        ("x-ms-file-parent-id", "13835128424026341376"),
+    ];
+
+    #[tokio::test]
+    async fn test_put_range() -> azure_core::Result<()> {
+        let transport = MockTransport::new();
+        transport.respond(
+            StatusCode::Created,
+            &[
+                ("etag", "\"0x8DB4A3B2C1D0E0F\""),
+                ("last-modified", "Mon, 01 May 2023 10:20:30 GMT"),
+                ("content-md5", "XUFAKrxLKna5cZ2REBfFkg=="),
+            ],
+            "",
+        );
+        let file = transport
+            .client()
+            .share_client("share")
+            .file_client("dir/hello.txt");
+
+        let response = file.put_range(5u64..10, "hello").await?;
+        assert_eq!(response.etag, "\"0x8DB4A3B2C1D0E0F\"");
+        assert!(response.content_md5.is_some());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        let request = &requests[0];
+        assert_eq!(request.method, Method::Put);
+        assert_eq!(request.url.path(), "/share/dir/hello.txt");
+        assert_eq!(request.query("comp").as_deref(), Some("range"));
+        assert_eq!(request.header("x-ms-range"), Some("bytes=5-9"));
+        assert_eq!(request.header("x-ms-write"), Some("update"));
+        assert_eq!(request.body, "hello");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_in_chunks() -> azure_core::Result<()> {
+        let transport = MockTransport::new();
+        let mut headers = FILE_HEADERS.to_vec();
+        headers.push(("content-range", "bytes 0-3/6"));
+        transport.respond(StatusCode::PartialContent, &headers, "hell");
+        headers.pop();
+        headers.push(("content-range", "bytes 4-5/6"));
+        transport.respond(StatusCode::PartialContent, &headers, "o!");
+        let file = transport
+            .client()
+            .share_client("share")
+            .file_client("hello.txt");
+
+        let mut content = Vec::new();
+        let mut stream = file.get().chunk_size(4u64).into_stream();
+        while let Some(response) = stream.next().await {
+            let response = response?;
+            assert_eq!(response.properties.etag, "\"0x8DB4A3B2C1D0E0F\"");
+            assert_eq!(response.properties.smb_info.file_id, "13835093239654252544");
+            content.extend(response.data.collect().await?);
+        }
+        assert_eq!(content, b"hello!");
+
+        let ranges: Vec<_> = transport
+            .requests()
+            .iter()
+            .map(|request| {
+                assert_eq!(request.method, Method::Get);
+                request.header("x-ms-range").map(ToOwned::to_owned)
+            })
+            .collect();
+        assert_eq!(
+            ranges,
+            [Some("bytes=0-3".to_owned()), Some("bytes=4-5".to_owned())]
+        );
+        Ok(())
+    }
+}
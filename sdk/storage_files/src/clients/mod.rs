@@ -0,0 +1,11 @@
+mod directory_client;
+mod file_client;
+mod file_lease_client;
+mod share_client;
+mod share_service_client;
+
+pub use directory_client::DirectoryClient;
+pub use file_client::FileClient;
+pub use file_lease_client::FileLeaseClient;
+pub use share_client::ShareClient;
+pub use share_service_client::{ShareServiceClient, ShareServiceClientBuilder};
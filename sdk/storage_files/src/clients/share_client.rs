@@ -0,0 +1,248 @@
+use crate::{operations::*, DirectoryClient, FileClient, ShareServiceClient, ShareSnapshot};
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+    Request, Response, StatusCode, Url,
+};
+use azure_storage::{
+    clients::{service_sas_signing_key, ServiceType},
+    prelude::{FileSasPermissions, FileSignedResource},
+    shared_access_signature::{service_sas::FileSharedAccessSignature, SasToken},
+};
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone)]
+pub struct ShareClient {
+    service_client: ShareServiceClient,
+    share_name: String,
+    snapshot: Option<ShareSnapshot>,
+}
+
+impl ShareClient {
+    pub(crate) fn new(service_client: ShareServiceClient, share_name: String) -> Self {
+        Self {
+            service_client,
+            share_name,
+            snapshot: None,
+        }
+    }
+
+    /// Create the share.
+    pub fn create(&self) -> CreateShareBuilder {
+        CreateShareBuilder::new(self.clone())
+    }
+
+    /// Delete the share, or the snapshot this client points to.
+    pub fn delete(&self) -> DeleteShareBuilder {
+        DeleteShareBuilder::new(self.clone())
+    }
+
+    /// Get the share properties and metadata.
+    pub fn get_properties(&self) -> GetSharePropertiesBuilder {
+        GetSharePropertiesBuilder::new(self.clone())
+    }
+
+    /// Replace the share metadata.
+    pub fn set_metadata(&self, metadata: Metadata) -> SetShareMetadataBuilder {
+        SetShareMetadataBuilder::new(self.clone(), metadata)
+    }
+
+    /// Create a read-only snapshot of the share.
+    pub fn snapshot(&self) -> CreateShareSnapshotBuilder {
+        CreateShareSnapshotBuilder::new(self.clone())
+    }
+
+    /// Check whether the share exists.
+    pub async fn exists(&self) -> azure_core::Result<bool> {
+        match self.get_properties().await {
+            Ok(_) => Ok(true),
+            Err(err)
+                if err
+                    .as_http_error()
+                    .map(|e| e.status() == StatusCode::NotFound)
+                    .unwrap_or_default() =>
+            {
+                Ok(false)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Turn into a client of a snapshot of the share.
+    ///
+    /// Reads through the returned client and its directory and file clients target the snapshot.
+    pub fn with_snapshot(&self, snapshot: impl Into<ShareSnapshot>) -> ShareClient {
+        Self {
+            snapshot: Some(snapshot.into()),
+            ..self.clone()
+        }
+    }
+
+    /// The root directory of the share.
+    pub fn root_directory_client(&self) -> DirectoryClient {
+        DirectoryClient::new(self.clone(), String::new())
+    }
+
+    /// A directory of the share, by its path from the root, such as `dir/subdir`.
+    pub fn directory_client<P: Into<String>>(&self, directory_path: P) -> DirectoryClient {
+        DirectoryClient::new(self.clone(), directory_path.into())
+    }
+
+    /// A file of the share, by its path from the root, such as `dir/file.txt`.
+    pub fn file_client<P: Into<String>>(&self, file_path: P) -> FileClient {
+        FileClient::new(self.clone(), file_path.into())
+    }
+
+    pub fn service_client(&self) -> ShareServiceClient {
+        self.service_client.clone()
+    }
+
+    pub fn share_name(&self) -> &str {
+        &self.share_name
+    }
+
+    pub fn snapshot_id(&self) -> Option<&ShareSnapshot> {
+        self.snapshot.as_ref()
+    }
+
+    /// Create a shared access signature for the share.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: FileSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<FileSharedAccessSignature> {
+        let (canonicalized_resource, key) = self.signing_key("").await?;
+        Ok(FileSharedAccessSignature::new(
+            key,
+            canonicalized_resource,
+            permissions,
+            expiry,
+            FileSignedResource::Share,
+        ))
+    }
+
+    /// The canonicalized resource of a path of the share, and the key to sign it with.
+    pub(crate) async fn signing_key(
+        &self,
+        path: &str,
+    ) -> azure_core::Result<(String, azure_core::auth::Secret)> {
+        let path = if path.is_empty() {
+            self.share_name().to_owned()
+        } else {
+            format!("{}/{path}", self.share_name())
+        };
+        service_sas_signing_key(self.service_client.credentials(), ServiceType::File, &path).await
+    }
+
+    pub fn generate_signed_share_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
+    /// Full URL for the share.
+    pub fn url(&self) -> azure_core::Result<Url> {
+        let mut url = self.service_client.url()?;
+        url.path_segments_mut()
+            .map_err(|()| Error::message(ErrorKind::DataConversion, "Invalid url"))?
+            .push(self.share_name());
+        Ok(url)
+    }
+
+    /// URL of a path of the share, pointing to the snapshot if any.
+    pub(crate) fn path_url(&self, path: &str) -> azure_core::Result<Url> {
+        let mut url = self.url()?;
+        url.path_segments_mut()
+            .map_err(|()| Error::message(ErrorKind::DataConversion, "Invalid url"))?
+            .extend(path.split('/').filter(|segment| !segment.is_empty()));
+        self.snapshot.append_to_url_query(&mut url);
+        Ok(url)
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: azure_core::Method,
+        headers: azure_core::headers::Headers,
+        request_body: Option<azure_core::Body>,
+    ) -> azure_core::Result<Request> {
+        ShareServiceClient::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.service_client.send(context, request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock_transport::MockTransport, ShareQuota};
+    use azure_core::Method;
+    use azure_storage::StorageCredentials;
+
+    #[test]
+    fn test_path_url() -> azure_core::Result<()> {
+        let share = ShareServiceClient::new("account", StorageCredentials::anonymous())
+            .share_client("share");
+        assert_eq!(
+            share.path_url("dir/sub dir/")?.as_str(),
+            "https://account.file.core.windows.net/share/dir/sub%20dir"
+        );
+        assert_eq!(
+            share
+                .with_snapshot("2023-05-01T10:20:30.0000000Z")
+                .path_url("file.txt")?
+                .as_str(),
+            "https://account.file.core.windows.net/share/file.txt?sharesnapshot=2023-05-01T10%3A20%3A30.0000000Z"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_and_delete() -> azure_core::Result<()> {
+        let transport = MockTransport::new();
+        transport.respond(
+            StatusCode::Created,
+            &[
+                ("etag", "\"0x8DB4A3B2C1D0E0F\""),
+                ("last-modified", "Mon, 01 May 2023 10:20:30 GMT"),
+            ],
+            "",
+        );
+        transport.respond(StatusCode::Accepted, &[], "");
+        let share = transport.client().share_client("share");
+
+        let created = share
+            .create()
+            .quota(ShareQuota(5))
+            .metadata({
+                let mut metadata = Metadata::new();
+                metadata.insert("owner", "reports");
+                metadata
+            })
+            .await?;
+        assert_eq!(created.etag, "\"0x8DB4A3B2C1D0E0F\"");
+        share.delete().include_snapshots(true).await?;
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        let (create, delete) = (&requests[0], &requests[1]);
+        assert_eq!(create.method, Method::Put);
+        assert_eq!(create.url.path(), "/share");
+        assert_eq!(create.query("restype").as_deref(), Some("share"));
+        assert_eq!(create.header("x-ms-share-quota"), Some("5"));
+        assert_eq!(create.header("x-ms-meta-owner"), Some("reports"));
+        assert_eq!(delete.method, Method::Delete);
+        assert_eq!(delete.url.path(), "/share");
+        assert_eq!(delete.query("restype").as_deref(), Some("share"));
+        assert_eq!(delete.header("x-ms-delete-snapshots"), Some("include"));
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{operations::*, ShareClient};
+use azure_core::{ClientOptions, Context, Pipeline, Request, Response, Url};
+use azure_storage::{
+    clients::{new_pipeline_from_options, ServiceType},
+    prelude::StorageCredentials,
+    CloudLocation, ConnectionString,
+};
+use std::fmt::Debug;
+
+/// A builder for the file share service client.
+#[derive(Debug, Clone)]
+pub struct ShareServiceClientBuilder {
+    cloud_location: CloudLocation,
+    options: ClientOptions,
+    credentials: StorageCredentials,
+}
+
+impl ShareServiceClientBuilder {
+    /// Create a new instance of `ShareServiceClientBuilder`.
+    #[must_use]
+    pub fn new<A, C>(account: A, credentials: C) -> Self
+    where
+        A: Into<String>,
+        C: Into<StorageCredentials>,
+    {
+        Self::with_location(
+            CloudLocation::Public {
+                account: account.into(),
+            },
+            credentials,
+        )
+    }
+
+    /// Create a new instance of `ShareServiceClientBuilder` with a cloud location.
+    #[must_use]
+    pub fn with_location<C>(cloud_location: CloudLocation, credentials: C) -> Self
+    where
+        C: Into<StorageCredentials>,
+    {
+        Self {
+            options: ClientOptions::default(),
+            cloud_location,
+            credentials: credentials.into(),
+        }
+    }
+
+    /// Create a new instance of `ShareServiceClientBuilder` from a storage connection string.
+    ///
+    /// The endpoint of the service, or else the account name, protocol and endpoint suffix, give
+    /// the location. The shared access signature or account key give the credentials.
+    pub fn from_connection_string(connection_string: &str) -> azure_core::Result<Self> {
+        let connection_string = ConnectionString::new(connection_string)?;
+        Ok(Self::with_location(
+            connection_string.cloud_location(ServiceType::File)?,
+            connection_string.storage_credentials()?,
+        ))
+    }
+
+    /// Convert the builder into a `ShareServiceClient` instance.
+    #[must_use]
+    pub fn build(self) -> ShareServiceClient {
+        let Self {
+            cloud_location,
+            options,
+            credentials,
+        } = self;
+
+        ShareServiceClient {
+            pipeline: new_pipeline_from_options(options, credentials.clone()),
+            cloud_location,
+            credentials,
+        }
+    }
+
+    /// Set the cloud location.
+    #[must_use]
+    pub fn cloud_location(mut self, cloud_location: CloudLocation) -> Self {
+        self.cloud_location = cloud_location;
+        self
+    }
+
+    /// Set the retry options.
+    #[must_use]
+    pub fn retry(mut self, retry: impl Into<azure_core::RetryOptions>) -> Self {
+        self.options = self.options.retry(retry);
+        self
+    }
+
+    /// Set the transport options.
+    #[must_use]
+    pub fn transport(mut self, transport: impl Into<azure_core::TransportOptions>) -> Self {
+        self.options = self.options.transport(transport);
+        self
+    }
+
+    /// Override all of the client options.
+    ///
+    /// *Warning!*: This overrides all client options that have been previously set on this builder.
+    #[must_use]
+    pub fn client_options(mut self, options: impl Into<azure_core::ClientOptions>) -> Self {
+        self.options = options.into();
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ShareServiceClient {
+    pipeline: Pipeline,
+    cloud_location: CloudLocation,
+    credentials: StorageCredentials,
+}
+
+impl ShareServiceClient {
+    /// Create a new `ShareServiceClient` which connects to the account's instance in the public Azure cloud.
+    #[must_use]
+    pub fn new(account: impl Into<String>, credentials: impl Into<StorageCredentials>) -> Self {
+        ShareServiceClientBuilder::new(account, credentials).build()
+    }
+
+    /// Create a new `ShareServiceClient` from a storage connection string.
+    pub fn from_connection_string(connection_string: &str) -> azure_core::Result<Self> {
+        Ok(ShareServiceClientBuilder::from_connection_string(connection_string)?.build())
+    }
+
+    /// List the shares of the account.
+    pub fn list_shares(&self) -> ListSharesBuilder {
+        ListSharesBuilder::new(self.clone())
+    }
+
+    pub fn share_client<S: Into<String>>(&self, share_name: S) -> ShareClient {
+        ShareClient::new(self.clone(), share_name.into())
+    }
+
+    pub fn url(&self) -> azure_core::Result<Url> {
+        self.cloud_location.url(ServiceType::File)
+    }
+
+    pub(crate) fn credentials(&self) -> &StorageCredentials {
+        &self.credentials
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: azure_core::Method,
+        headers: azure_core::headers::Headers,
+        request_body: Option<azure_core::Body>,
+    ) -> azure_core::Result<Request> {
+        azure_storage::clients::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.pipeline
+            .send(context.insert(ServiceType::File), request)
+            .await
+    }
+}
@@ -0,0 +1,59 @@
+use crate::SmbInfo;
+use azure_core::{
+    headers::{
+        etag_from_headers, last_modified_from_headers, Headers, CACHE_CONTROL, CONTENT_DISPOSITION,
+        CONTENT_ENCODING, CONTENT_LANGUAGE, CONTENT_TYPE, COPY_PROGRESS, COPY_SOURCE, COPY_STATUS,
+        LEASE_STATE,
+    },
+    prelude::Metadata,
+};
+use azure_storage::{headers::COPY_ID, CopyId, CopyProgress};
+use time::OffsetDateTime;
+
+create_enum!(
+    CopyStatus,
+    (Pending, "pending"),
+    (Success, "success"),
+    (Aborted, "aborted"),
+    (Failed, "failed")
+);
+
+/// The properties of a file, as returned when getting it or its properties.
+#[derive(Debug, Clone)]
+pub struct FileProperties {
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub content_type: Option<String>,
+    pub content_encoding: Option<String>,
+    pub content_language: Option<String>,
+    pub content_disposition: Option<String>,
+    pub cache_control: Option<String>,
+    pub metadata: Metadata,
+    pub smb_info: SmbInfo,
+    pub lease_state: Option<String>,
+    pub copy_id: Option<CopyId>,
+    pub copy_status: Option<CopyStatus>,
+    pub copy_progress: Option<CopyProgress>,
+    pub copy_source: Option<String>,
+}
+
+impl FileProperties {
+    pub(crate) fn from_headers(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            content_type: headers.get_optional_string(&CONTENT_TYPE),
+            content_encoding: headers.get_optional_string(&CONTENT_ENCODING),
+            content_language: headers.get_optional_string(&CONTENT_LANGUAGE),
+            content_disposition: headers.get_optional_string(&CONTENT_DISPOSITION),
+            cache_control: headers.get_optional_string(&CACHE_CONTROL),
+            metadata: headers.into(),
+            smb_info: SmbInfo::from_headers(headers)?,
+            lease_state: headers.get_optional_string(&LEASE_STATE),
+            copy_id: headers.get_optional_as(&COPY_ID)?,
+            copy_status: headers.get_optional_as(&COPY_STATUS)?,
+            copy_progress: headers.get_optional_as(&COPY_PROGRESS)?,
+            copy_source: headers.get_optional_string(&COPY_SOURCE),
+        })
+    }
+}
@@ -0,0 +1,57 @@
+/*!
+# The Azure Storage Files crate
+
+This crate is from the [Azure SDK for Rust](https://github.com/azure/azure-sdk-for-rust).
+It supports [Azure Files](https://docs.microsoft.com/azure/storage/files/storage-files-introduction).
+
+# Example
+```no_run
+use azure_storage::prelude::*;
+use azure_storage_files::prelude::*;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    let account = std::env::var("STORAGE_ACCOUNT").expect("missing STORAGE_ACCOUNT");
+    let access_key = std::env::var("STORAGE_ACCESS_KEY").expect("missing STORAGE_ACCESS_KEY");
+    let share_name = std::env::var("STORAGE_SHARE_NAME").expect("missing STORAGE_SHARE_NAME");
+
+    let storage_credentials = StorageCredentials::access_key(account.clone(), access_key);
+    let share = ShareServiceClient::new(account, storage_credentials).share_client(share_name);
+
+    share.create().quota(ShareQuota(1)).await?;
+    share.directory_client("reports").create().await?;
+
+    // a file is allocated first, then its content is written by ranges
+    let content = "hello, world";
+    let file = share.file_client("reports/hello.txt");
+    file.create(content.len() as u64).await?;
+    file.put_range(0..content.len(), content).await?;
+
+    let downloaded = file.get_content().await?;
+    println!("{}", String::from_utf8_lossy(&downloaded));
+
+    Ok(())
+}
+
+```
+
+*/
+
+#[macro_use]
+extern crate azure_core;
+
+mod clients;
+mod file_properties;
+#[cfg(test)]
+mod mock_transport;
+pub mod operations;
+mod options;
+pub mod prelude;
+
+pub use clients::*;
+pub use file_properties::{CopyStatus, FileProperties};
+pub use options::{
+    ContentMd5, FileAttributes, FileCacheControl, FileContentDisposition, FileContentEncoding,
+    FileContentLanguage, FileContentType, FilePermission, FileTime, ShareAccessTier, ShareQuota,
+    ShareSnapshot, SmbInfo, SmbProperties,
+};
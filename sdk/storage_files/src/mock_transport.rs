@@ -0,0 +1,117 @@
+//! A transport answering requests with canned responses, to test operations without a service.
+
+use crate::{ShareServiceClient, ShareServiceClientBuilder};
+use azure_core::{
+    headers::{HeaderName, Headers, DATE, REQUEST_ID, SERVER, VERSION},
+    Body, BytesStream, Context, Method, Policy, PolicyResult, Request, Response, RetryOptions,
+    StatusCode, TransportOptions, Url,
+};
+use azure_storage::StorageCredentials;
+use bytes::Bytes;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// A request received by the mock transport.
+#[derive(Debug, Clone)]
+pub(crate) struct SentRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: Headers,
+    pub body: Bytes,
+}
+
+impl SentRequest {
+    pub fn header(&self, name: &'static str) -> Option<&str> {
+        self.headers
+            .get_optional_str(&HeaderName::from_static(name))
+    }
+
+    pub fn query(&self, key: &str) -> Option<String> {
+        self.url
+            .query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.into_owned())
+    }
+}
+
+/// Answer every request with the next queued response, recording the requests.
+#[derive(Debug, Default)]
+pub(crate) struct MockTransport {
+    responses: Mutex<VecDeque<(StatusCode, Headers, Bytes)>>,
+    requests: Mutex<Vec<SentRequest>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// A client of a storage account named `account`, sending its requests to this transport.
+    pub fn client(self: &Arc<Self>) -> ShareServiceClient {
+        ShareServiceClientBuilder::new("account", StorageCredentials::anonymous())
+            .retry(RetryOptions::none())
+            .transport(TransportOptions::new_custom_policy(self.clone()))
+            .build()
+    }
+
+    /// Queue a response with the given headers, on top of the ones of every storage response.
+    pub fn respond(
+        &self,
+        status: StatusCode,
+        headers: &[(&'static str, &str)],
+        body: impl Into<Bytes>,
+    ) {
+        let mut h = Headers::new();
+        h.insert(REQUEST_ID, "00000000-0000-0000-0000-000000000000");
+        h.insert(VERSION, "2022-11-02");
+        h.insert(DATE, "Mon, 01 May 2023 10:20:30 GMT");
+        h.insert(SERVER, "Windows-Azure-File/1.0");
+        for (name, value) in headers {
+            h.insert(HeaderName::from_static(name), (*value).to_owned());
+        }
+        self.responses
+            .lock()
+            .unwrap()
+            .push_back((status, h, body.into()));
+    }
+
+    pub fn requests(&self) -> Vec<SentRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl Policy for MockTransport {
+    async fn send(
+        &self,
+        _ctx: &Context,
+        request: &mut Request,
+        _next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let body = match request.body() {
+            Body::Bytes(bytes) => bytes.clone(),
+            #[allow(unreachable_patterns)]
+            _ => Bytes::new(),
+        };
+        self.requests.lock().unwrap().push(SentRequest {
+            method: *request.method(),
+            url: request.url().clone(),
+            headers: request.headers().clone(),
+            body,
+        });
+
+        let (status, headers, body) = self
+            .responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("no response queued for the request");
+        Ok(Response::new(
+            status,
+            headers,
+            Box::pin(BytesStream::new(body)),
+        ))
+    }
+}
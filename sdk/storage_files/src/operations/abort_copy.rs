@@ -0,0 +1,47 @@
+use crate::{options::COPY_ACTION, FileClient};
+use azure_core::{error::Error, headers::Headers, prelude::*, Method, Response as AzureResponse};
+use azure_storage::{headers::CommonStorageResponseHeaders, CopyId};
+
+operation! {
+    AbortCopy,
+    client: FileClient,
+    copy_id: CopyId,
+    ?lease_id: LeaseId
+}
+
+impl AbortCopyBuilder {
+    pub fn into_future(mut self) -> AbortCopy {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut()
+                .append_pair("comp", "copy")
+                .append_pair("copyid", &self.copy_id.to_string());
+
+            let mut headers = Headers::new();
+            headers.insert(COPY_ACTION, "abort");
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AbortCopyResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+}
+
+impl TryFrom<AzureResponse> for AbortCopyResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        Ok(AbortCopyResponse {
+            common_storage_response_headers: response.headers().try_into()?,
+        })
+    }
+}
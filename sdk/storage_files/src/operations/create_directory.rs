@@ -0,0 +1,64 @@
+use crate::{DirectoryClient, SmbInfo, SmbProperties};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use time::OffsetDateTime;
+
+operation! {
+    CreateDirectory,
+    client: DirectoryClient,
+    ?smb_properties: SmbProperties,
+    ?metadata: Metadata
+}
+
+impl CreateDirectoryBuilder {
+    pub fn into_future(mut self) -> CreateDirectory {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "directory");
+
+            let mut headers = Headers::new();
+            if let Some(smb_properties) = &self.smb_properties {
+                smb_properties.add_to(&mut headers);
+            }
+            if let Some(metadata) = &self.metadata {
+                for m in metadata.iter() {
+                    headers.add(m);
+                }
+            }
+
+            let mut request = DirectoryClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateDirectoryResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub smb_info: SmbInfo,
+}
+
+impl TryFrom<AzureResponse> for CreateDirectoryResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(CreateDirectoryResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            smb_info: SmbInfo::from_headers(headers)?,
+        })
+    }
+}
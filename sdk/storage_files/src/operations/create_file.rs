@@ -0,0 +1,81 @@
+use crate::{
+    options::{CONTENT_LENGTH, FILE_TYPE},
+    FileCacheControl, FileClient, FileContentDisposition, FileContentEncoding, FileContentLanguage,
+    FileContentType, SmbInfo, SmbProperties,
+};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use time::OffsetDateTime;
+
+operation! {
+    CreateFile,
+    client: FileClient,
+    size: u64,
+    ?content_type: FileContentType,
+    ?content_encoding: FileContentEncoding,
+    ?content_language: FileContentLanguage,
+    ?content_disposition: FileContentDisposition,
+    ?cache_control: FileCacheControl,
+    ?smb_properties: SmbProperties,
+    ?metadata: Metadata,
+    ?lease_id: LeaseId
+}
+
+impl CreateFileBuilder {
+    pub fn into_future(mut self) -> CreateFile {
+        Box::pin(async move {
+            let url = self.client.url()?;
+
+            let mut headers = Headers::new();
+            headers.insert(FILE_TYPE, "file");
+            headers.insert(CONTENT_LENGTH, self.size.to_string());
+            headers.add(self.content_type);
+            headers.add(self.content_encoding);
+            headers.add(self.content_language);
+            headers.add(self.content_disposition);
+            headers.add(self.cache_control);
+            if let Some(smb_properties) = &self.smb_properties {
+                smb_properties.add_to(&mut headers);
+            }
+            if let Some(metadata) = &self.metadata {
+                for m in metadata.iter() {
+                    headers.add(m);
+                }
+            }
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateFileResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub smb_info: SmbInfo,
+}
+
+impl TryFrom<AzureResponse> for CreateFileResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(CreateFileResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            smb_info: SmbInfo::from_headers(headers)?,
+        })
+    }
+}
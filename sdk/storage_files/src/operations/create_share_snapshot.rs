@@ -0,0 +1,63 @@
+use crate::{options::SNAPSHOT, ShareClient, ShareSnapshot};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use time::OffsetDateTime;
+
+operation! {
+    CreateShareSnapshot,
+    client: ShareClient,
+    ?metadata: Metadata
+}
+
+impl CreateShareSnapshotBuilder {
+    pub fn into_future(mut self) -> CreateShareSnapshot {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut()
+                .append_pair("restype", "share")
+                .append_pair("comp", "snapshot");
+
+            let mut headers = Headers::new();
+            if let Some(metadata) = &self.metadata {
+                for m in metadata.iter() {
+                    headers.add(m);
+                }
+            }
+
+            let mut request = ShareClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateShareSnapshotResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    /// The snapshot, to read it with `ShareClient::with_snapshot`.
+    pub snapshot: ShareSnapshot,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+}
+
+impl TryFrom<AzureResponse> for CreateShareSnapshotResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(CreateShareSnapshotResponse {
+            common_storage_response_headers: headers.try_into()?,
+            snapshot: headers.get_str(&SNAPSHOT)?.into(),
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use crate::ShareClient;
+use azure_core::{
+    error::Error,
+    headers::{Headers, DELETE_SNAPSHOTS},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+
+operation! {
+    DeleteShare,
+    client: ShareClient,
+    ?include_snapshots: bool,
+    ?lease_id: LeaseId
+}
+
+impl DeleteShareBuilder {
+    pub fn into_future(mut self) -> DeleteShare {
+        Box::pin(async move {
+            let mut url = self.client.path_url("")?;
+
+            url.query_pairs_mut().append_pair("restype", "share");
+
+            let mut headers = Headers::new();
+            if self.include_snapshots.unwrap_or(false) {
+                headers.insert(DELETE_SNAPSHOTS, "include");
+            }
+            headers.add(self.lease_id);
+
+            let mut request = ShareClient::finalize_request(url, Method::Delete, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteShareResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+}
+
+impl TryFrom<AzureResponse> for DeleteShareResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        Ok(DeleteShareResponse {
+            common_storage_response_headers: response.headers().try_into()?,
+        })
+    }
+}
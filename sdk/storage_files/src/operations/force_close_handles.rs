@@ -0,0 +1,68 @@
+use crate::{
+    operations::HandleOwner,
+    options::{HANDLE_ID, MARKER, NUMBER_OF_HANDLES_CLOSED, NUMBER_OF_HANDLES_FAILED, RECURSIVE},
+    FileClient,
+};
+use azure_core::{headers::Headers, Method};
+use azure_storage::headers::CommonStorageResponseHeaders;
+
+operation! {
+    ForceCloseHandles,
+    client: HandleOwner,
+    handle_id: String,
+    ?recursive: bool
+}
+
+impl ForceCloseHandlesBuilder {
+    /// Close the handles, following the service's markers until all of them have been processed.
+    pub fn into_future(mut self) -> ForceCloseHandles {
+        Box::pin(async move {
+            let mut closed = 0;
+            let mut failed = 0;
+            let mut marker: Option<String> = None;
+            loop {
+                let mut url = self.client.url()?;
+
+                url.query_pairs_mut()
+                    .append_pair("comp", "forceclosehandles");
+                if let Some(marker) = &marker {
+                    url.query_pairs_mut().append_pair("marker", marker);
+                }
+
+                let mut headers = Headers::new();
+                headers.insert(HANDLE_ID, self.handle_id.clone());
+                if let Some(recursive) = self.recursive {
+                    headers.insert(RECURSIVE, recursive.to_string());
+                }
+
+                let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+                let response = self.client.send(&mut self.context, &mut request).await?;
+
+                let headers = response.headers();
+                closed += headers.get_as::<u64, _>(&NUMBER_OF_HANDLES_CLOSED)?;
+                failed += headers
+                    .get_optional_as::<u64, _>(&NUMBER_OF_HANDLES_FAILED)?
+                    .unwrap_or_default();
+                marker = headers
+                    .get_optional_string(&MARKER)
+                    .filter(|marker| !marker.is_empty());
+                if marker.is_none() {
+                    return Ok(ForceCloseHandlesResponse {
+                        common_storage_response_headers: headers.try_into()?,
+                        number_of_handles_closed: closed,
+                        number_of_handles_failed: failed,
+                    });
+                }
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ForceCloseHandlesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub number_of_handles_closed: u64,
+    /// The handles which could not be closed, which should be retried.
+    pub number_of_handles_failed: u64,
+}
@@ -0,0 +1,180 @@
+use crate::{FileClient, FileProperties};
+use azure_core::{
+    error::Error,
+    headers::{Headers, CONTENT_RANGE, MS_RANGE},
+    prelude::*,
+    Method, Pageable, Response as AzureResponse, ResponseBody, StatusCode,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+
+const DEFAULT_CHUNK_SIZE: u64 = 0x1000 * 0x1000;
+
+operation! {
+    #[stream]
+    GetFile,
+    client: FileClient,
+    ?range: Range,
+    ?chunk_size: u64,
+    ?lease_id: LeaseId,
+}
+
+impl GetFileBuilder {
+    pub fn into_stream(self) -> Pageable<GetFileResponse, Error> {
+        let make_request = move |continuation: Option<Range>| {
+            let this = self.clone();
+            let mut ctx = self.context.clone();
+            async move {
+                let chunk_size = this.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+                let whole_file = continuation.is_none() && this.range.is_none();
+                let range = match continuation {
+                    Some(range) => range,
+                    None => initial_range(chunk_size, this.range.clone()),
+                };
+
+                let response = match this.send(&mut ctx, Some(&range)).await {
+                    // an empty file has no range to read
+                    Err(err)
+                        if whole_file
+                            && err.as_http_error().map(|e| e.status())
+                                == Some(StatusCode::RequestedRangeNotSatisfiable) =>
+                    {
+                        this.send(&mut ctx, None).await?
+                    }
+                    response => response?,
+                };
+
+                GetFileResponse::try_from(chunk_size, this.range, response)
+            }
+        };
+        Pageable::new(make_request)
+    }
+
+    async fn send(
+        &self,
+        ctx: &mut Context,
+        range: Option<&Range>,
+    ) -> azure_core::Result<AzureResponse> {
+        let url = self.client.url()?;
+
+        let mut headers = Headers::new();
+        if let Some(range) = range {
+            headers.insert(MS_RANGE, range.to_string());
+        }
+        headers.add(self.lease_id);
+
+        let mut request = FileClient::finalize_request(url, Method::Get, headers, None)?;
+
+        self.client.send(ctx, &mut request).await
+    }
+}
+
+#[derive(Debug)]
+pub struct GetFileResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub properties: FileProperties,
+    pub data: ResponseBody,
+    pub content_range: Option<ContentRange>,
+    pub remaining_range: Option<Range>,
+}
+
+impl GetFileResponse {
+    fn try_from(
+        chunk_size: u64,
+        requested_range: Option<Range>,
+        response: AzureResponse,
+    ) -> azure_core::Result<Self> {
+        let headers = response.headers();
+
+        let common_storage_response_headers = headers.try_into()?;
+        let properties = FileProperties::from_headers(headers)?;
+        let content_range = headers.get_optional_as(&CONTENT_RANGE)?;
+        let remaining_range = remaining_range(chunk_size, requested_range, content_range);
+
+        Ok(Self {
+            common_storage_response_headers,
+            properties,
+            data: response.into_body(),
+            content_range,
+            remaining_range,
+        })
+    }
+}
+
+impl Continuable for GetFileResponse {
+    type Continuation = Range;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.remaining_range.clone()
+    }
+}
+
+// the first chunk of the requested range.
+fn initial_range(chunk_size: u64, requested_range: Option<Range>) -> Range {
+    match requested_range {
+        Some(Range::Range(x)) => {
+            let len = std::cmp::min(x.end - x.start, chunk_size);
+            (x.start..x.start + len).into()
+        }
+        Some(Range::RangeFrom(x)) => (x.start..x.start + chunk_size).into(),
+        None => Range::new(0, chunk_size),
+    }
+}
+
+// the next chunk of the requested range, knowing the range the last response held and the
+// current size of the file from its Content-Range.
+fn remaining_range(
+    chunk_size: u64,
+    requested_range: Option<Range>,
+    content_range: Option<ContentRange>,
+) -> Option<Range> {
+    // without a content range, the response held the whole file.
+    let content_range = content_range?;
+
+    let after = content_range.end() + 1;
+    if after >= content_range.total_length() {
+        return None;
+    }
+
+    let end = match requested_range {
+        Some(Range::Range(x)) => std::cmp::min(x.end, content_range.total_length()),
+        Some(Range::RangeFrom(_)) | None => content_range.total_length(),
+    };
+    if after >= end {
+        return None;
+    }
+
+    Some(Range::new(after, std::cmp::min(end, after + chunk_size)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_ranges() {
+        assert_eq!(initial_range(3, None), Range::new(0, 3));
+        assert_eq!(initial_range(3, Some(Range::new(5, 7))), Range::new(5, 7));
+        assert_eq!(initial_range(3, Some((5u64..).into())), Range::new(5, 8));
+
+        assert_eq!(remaining_range(3, None, None), None);
+        assert_eq!(
+            remaining_range(3, None, Some(ContentRange::new(0, 2, 10))),
+            Some(Range::new(3, 6))
+        );
+        assert_eq!(
+            remaining_range(3, None, Some(ContentRange::new(6, 8, 10))),
+            Some(Range::new(9, 10))
+        );
+        assert_eq!(
+            remaining_range(3, None, Some(ContentRange::new(9, 9, 10))),
+            None
+        );
+        assert_eq!(
+            remaining_range(3, Some(Range::new(2, 7)), Some(ContentRange::new(2, 4, 10))),
+            Some(Range::new(5, 7))
+        );
+        assert_eq!(
+            remaining_range(3, Some(Range::new(2, 7)), Some(ContentRange::new(5, 6, 10))),
+            None
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{FileClient, FileProperties};
+use azure_core::{
+    error::Error,
+    headers::{Headers, CONTENT_LENGTH},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+
+operation! {
+    GetFileProperties,
+    client: FileClient,
+    ?lease_id: LeaseId
+}
+
+impl GetFilePropertiesBuilder {
+    pub fn into_future(mut self) -> GetFileProperties {
+        Box::pin(async move {
+            let url = self.client.url()?;
+
+            let mut headers = Headers::new();
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Head, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetFilePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    /// The size of the file.
+    pub content_length: u64,
+    pub properties: FileProperties,
+}
+
+impl TryFrom<AzureResponse> for GetFilePropertiesResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(GetFilePropertiesResponse {
+            common_storage_response_headers: headers.try_into()?,
+            content_length: headers.get_as(&CONTENT_LENGTH)?,
+            properties: FileProperties::from_headers(headers)?,
+        })
+    }
+}
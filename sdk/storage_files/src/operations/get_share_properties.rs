@@ -0,0 +1,60 @@
+use crate::{options::ACCESS_TIER, options::SHARE_QUOTA, ShareClient};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use time::OffsetDateTime;
+
+operation! {
+    GetShareProperties,
+    client: ShareClient,
+    ?lease_id: LeaseId
+}
+
+impl GetSharePropertiesBuilder {
+    pub fn into_future(mut self) -> GetShareProperties {
+        Box::pin(async move {
+            let mut url = self.client.path_url("")?;
+
+            url.query_pairs_mut().append_pair("restype", "share");
+
+            let mut headers = Headers::new();
+            headers.add(self.lease_id);
+
+            let mut request = ShareClient::finalize_request(url, Method::Get, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetSharePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub quota: u64,
+    pub access_tier: Option<String>,
+    pub metadata: Metadata,
+}
+
+impl TryFrom<AzureResponse> for GetSharePropertiesResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(GetSharePropertiesResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            quota: headers.get_as(&SHARE_QUOTA)?,
+            access_tier: headers.get_optional_string(&ACCESS_TIER),
+            metadata: headers.into(),
+        })
+    }
+}
@@ -0,0 +1,212 @@
+use crate::{options::FILE_EXTENDED_INFO, DirectoryClient};
+use azure_core::{
+    date, error::Error, headers::Headers, prelude::*, Method, Pageable, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+operation! {
+    #[stream]
+    ListDirectoriesAndFiles,
+    client: DirectoryClient,
+    ?prefix: Prefix,
+    ?max_results: MaxResults,
+}
+
+impl ListDirectoriesAndFilesBuilder {
+    pub fn into_stream(self) -> Pageable<ListDirectoriesAndFilesResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let mut this = self.clone();
+            async move {
+                let mut url = this.client.url()?;
+
+                url.query_pairs_mut()
+                    .append_pair("restype", "directory")
+                    .append_pair("comp", "list")
+                    .append_pair("include", "Timestamps,ETag,Attributes,PermissionKey");
+
+                this.prefix.append_to_url_query(&mut url);
+
+                if let Some(next_marker) = continuation {
+                    next_marker.append_to_url_query(&mut url);
+                }
+
+                this.max_results.append_to_url_query(&mut url);
+
+                let mut headers = Headers::new();
+                headers.insert(FILE_EXTENDED_INFO, "true");
+
+                let mut request =
+                    DirectoryClient::finalize_request(url, Method::Get, headers, None)?;
+
+                let response = this.client.send(&mut this.context, &mut request).await?;
+
+                ListDirectoriesAndFilesResponse::try_from(response).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListDirectoriesAndFilesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub prefix: Option<String>,
+    pub max_results: Option<u32>,
+    /// The SMB file id of the listed directory.
+    pub directory_id: Option<String>,
+    pub entries: Entries,
+    pub next_marker: Option<NextMarker>,
+}
+
+impl Continuable for ListDirectoriesAndFilesResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.next_marker.clone()
+    }
+}
+
+impl ListDirectoriesAndFilesResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let response: ListDirectoriesAndFilesResponseInternal = body.xml().await?;
+
+        Ok(ListDirectoriesAndFilesResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            prefix: response.prefix,
+            max_results: response.max_results,
+            directory_id: response.directory_id,
+            entries: response.entries,
+            next_marker: NextMarker::from_possibly_empty_string(response.next_marker),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListDirectoriesAndFilesResponseInternal {
+    prefix: Option<String>,
+    max_results: Option<u32>,
+    directory_id: Option<String>,
+    #[serde(default)]
+    entries: Entries,
+    next_marker: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Entries {
+    #[serde(rename = "$value", default)]
+    pub items: Vec<Entry>,
+}
+
+impl Entries {
+    pub fn files(&self) -> impl Iterator<Item = &EntryInfo> {
+        self.items.iter().filter_map(|item| match item {
+            Entry::File(file) => Some(file),
+            Entry::Directory(_) => None,
+        })
+    }
+
+    pub fn directories(&self) -> impl Iterator<Item = &EntryInfo> {
+        self.items.iter().filter_map(|item| match item {
+            Entry::Directory(directory) => Some(directory),
+            Entry::File(_) => None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum Entry {
+    File(EntryInfo),
+    Directory(EntryInfo),
+}
+
+/// A file or a directory, as listed.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EntryInfo {
+    pub name: String,
+    pub file_id: Option<String>,
+    #[serde(default)]
+    pub properties: EntryProperties,
+    pub attributes: Option<String>,
+    pub permission_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EntryProperties {
+    /// The size of a file, absent for directories.
+    #[serde(rename = "Content-Length")]
+    pub content_length: Option<u64>,
+    #[serde(default, with = "date::iso8601::option")]
+    pub creation_time: Option<OffsetDateTime>,
+    #[serde(default, with = "date::iso8601::option")]
+    pub last_access_time: Option<OffsetDateTime>,
+    #[serde(default, with = "date::iso8601::option")]
+    pub last_write_time: Option<OffsetDateTime>,
+    #[serde(default, with = "date::iso8601::option")]
+    pub change_time: Option<OffsetDateTime>,
+    #[serde(rename = "Last-Modified", default, with = "date::rfc1123::option")]
+    pub last_modified: Option<OffsetDateTime>,
+    pub etag: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_parse_list_directories_and_files() -> azure_core::Result<()> {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://account.file.core.windows.net/" ShareName="share" DirectoryPath="dir">
+  <MaxResults>10</MaxResults>
+  <DirectoryId>13835128424026341376</DirectoryId>
+  <Entries>
+    <File>
+      <FileId>13835093239654252544</FileId>
+      <Name>report.csv</Name>
+      <Properties>
+        <Content-Length>1024</Content-Length>
+        <CreationTime>2023-05-01T10:20:30.1234567Z</CreationTime>
+        <LastAccessTime>2023-05-01T10:20:30.1234567Z</LastAccessTime>
+        <LastWriteTime>2023-05-01T10:20:30.1234567Z</LastWriteTime>
+        <ChangeTime>2023-05-01T10:20:30.1234567Z</ChangeTime>
+        <Last-Modified>Mon, 01 May 2023 10:20:30 GMT</Last-Modified>
+        <Etag>"0x8DB4A3B2C1D0E0F"</Etag>
+      </Properties>
+      <Attributes>Archive</Attributes>
+      <PermissionKey>4066528134148476695*1</PermissionKey>
+    </File>
+    <Directory>
+      <FileId>13835163608398430208</FileId>
+      <Name>archive</Name>
+      <Properties />
+    </Directory>
+  </Entries>
+  <NextMarker>2!88!MDAwMDA</NextMarker>
+</EnumerationResults>"#;
+
+        let response: ListDirectoriesAndFilesResponseInternal = read_xml(xml.as_bytes())?;
+        let files: Vec<_> = response.entries.files().collect();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "report.csv");
+        assert_eq!(files[0].properties.content_length, Some(1024));
+        assert_eq!(
+            files[0].properties.creation_time,
+            Some(datetime!(2023-05-01 10:20:30.1234567 UTC))
+        );
+        assert_eq!(files[0].attributes.as_deref(), Some("Archive"));
+        let directories: Vec<_> = response.entries.directories().collect();
+        assert_eq!(directories.len(), 1);
+        assert_eq!(directories[0].name, "archive");
+        assert_eq!(directories[0].properties, EntryProperties::default());
+        assert_eq!(response.next_marker.as_deref(), Some("2!88!MDAwMDA"));
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{options::RECURSIVE, DirectoryClient, FileClient};
+use azure_core::{
+    date, error::Error, headers::Headers, prelude::*, Method, Pageable, Request,
+    Response as AzureResponse, Url,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+/// The directory or file whose SMB handles are listed or closed.
+#[derive(Debug, Clone)]
+pub(crate) enum HandleOwner {
+    Directory(DirectoryClient),
+    File(FileClient),
+}
+
+impl HandleOwner {
+    pub(crate) fn url(&self) -> azure_core::Result<Url> {
+        match self {
+            Self::Directory(client) => client.url(),
+            Self::File(client) => client.url(),
+        }
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<AzureResponse> {
+        match self {
+            Self::Directory(client) => client.send(context, request).await,
+            Self::File(client) => client.send(context, request).await,
+        }
+    }
+}
+
+impl From<&DirectoryClient> for HandleOwner {
+    fn from(client: &DirectoryClient) -> Self {
+        Self::Directory(client.clone())
+    }
+}
+
+impl From<&FileClient> for HandleOwner {
+    fn from(client: &FileClient) -> Self {
+        Self::File(client.clone())
+    }
+}
+
+operation! {
+    #[stream]
+    ListHandles,
+    client: HandleOwner,
+    ?max_results: MaxResults,
+    ?recursive: bool,
+}
+
+impl ListHandlesBuilder {
+    pub fn into_stream(self) -> Pageable<ListHandlesResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let mut this = self.clone();
+            async move {
+                let mut url = this.client.url()?;
+
+                url.query_pairs_mut().append_pair("comp", "listhandles");
+
+                if let Some(next_marker) = continuation {
+                    next_marker.append_to_url_query(&mut url);
+                }
+
+                this.max_results.append_to_url_query(&mut url);
+
+                let mut headers = Headers::new();
+                if let Some(recursive) = this.recursive {
+                    headers.insert(RECURSIVE, recursive.to_string());
+                }
+
+                let mut request = FileClient::finalize_request(url, Method::Get, headers, None)?;
+
+                let response = this.client.send(&mut this.context, &mut request).await?;
+
+                ListHandlesResponse::try_from(response).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListHandlesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub handles: Vec<Handle>,
+    pub next_marker: Option<NextMarker>,
+}
+
+impl Continuable for ListHandlesResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.next_marker.clone()
+    }
+}
+
+impl ListHandlesResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let response: ListHandlesResponseInternal = body.xml().await?;
+
+        Ok(ListHandlesResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            handles: response.handle_list.handles,
+            next_marker: NextMarker::from_possibly_empty_string(response.next_marker),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListHandlesResponseInternal {
+    #[serde(default)]
+    handle_list: HandleList,
+    next_marker: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct HandleList {
+    #[serde(rename = "Handle", default)]
+    handles: Vec<Handle>,
+}
+
+/// An SMB handle open on a file or a directory.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Handle {
+    pub handle_id: String,
+    /// The path of the file or directory from the root of the share.
+    pub path: String,
+    pub file_id: String,
+    pub parent_id: Option<String>,
+    pub session_id: String,
+    pub client_ip: String,
+    pub client_name: Option<String>,
+    #[serde(with = "date::rfc1123")]
+    pub open_time: OffsetDateTime,
+    #[serde(default, with = "date::rfc1123::option")]
+    pub last_reconnect_time: Option<OffsetDateTime>,
+    #[serde(default)]
+    pub access_right_list: AccessRightList,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct AccessRightList {
+    #[serde(rename = "AccessRight", default)]
+    pub access_rights: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    fn test_parse_list_handles() -> azure_core::Result<()> {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults>
+  <HandleList>
+    <Handle>
+      <HandleId>83591297600</HandleId>
+      <Path>dir/report.csv</Path>
+      <FileId>13835093239654252544</FileId>
+      <ParentId>13835128424026341376</ParentId>
+      <SessionId>9385737614310506553</SessionId>
+      <ClientIp>10.0.0.4:49160</ClientIp>
+      <ClientName>worker-1</ClientName>
+      <OpenTime>Mon, 01 May 2023 10:20:30 GMT</OpenTime>
+      <AccessRightList><AccessRight>Read</AccessRight><AccessRight>Write</AccessRight></AccessRightList>
+    </Handle>
+  </HandleList>
+  <NextMarker />
+</EnumerationResults>"#;
+
+        let response: ListHandlesResponseInternal = read_xml(xml.as_bytes())?;
+        let handles = response.handle_list.handles;
+        assert_eq!(handles.len(), 1);
+        assert_eq!(handles[0].path, "dir/report.csv");
+        assert_eq!(handles[0].client_ip, "10.0.0.4:49160");
+        assert_eq!(handles[0].last_reconnect_time, None);
+        assert_eq!(
+            handles[0].access_right_list.access_rights,
+            vec!["Read", "Write"]
+        );
+        Ok(())
+    }
+}
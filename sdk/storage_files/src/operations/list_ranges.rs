@@ -0,0 +1,132 @@
+use crate::{options::CONTENT_LENGTH, FileClient, ShareSnapshot};
+use azure_core::{
+    headers::{etag_from_headers, last_modified_from_headers, Headers, MS_RANGE},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+operation! {
+    ListRanges,
+    client: FileClient,
+    ?range: Range,
+    ?previous_snapshot: ShareSnapshot,
+    ?lease_id: LeaseId
+}
+
+impl ListRangesBuilder {
+    pub fn into_future(mut self) -> ListRanges {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "rangelist");
+            if let Some(previous_snapshot) = &self.previous_snapshot {
+                url.query_pairs_mut()
+                    .append_pair("prevsharesnapshot", previous_snapshot.as_str());
+            }
+
+            let mut headers = Headers::new();
+            if let Some(range) = &self.range {
+                headers.insert(MS_RANGE, range.to_string());
+            }
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Get, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            ListRangesResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListRangesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    /// The size of the file.
+    pub content_length: u64,
+    /// The ranges holding data, or which changed since the previous snapshot.
+    pub ranges: Vec<std::ops::Range<u64>>,
+    /// The ranges cleared since the previous snapshot.
+    pub clear_ranges: Vec<std::ops::Range<u64>>,
+}
+
+impl ListRangesResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let ranges: RangesInternal = body.xml().await?;
+
+        let (ranges, clear_ranges) = ranges.split();
+        Ok(ListRangesResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            etag: etag_from_headers(&headers)?,
+            last_modified: last_modified_from_headers(&headers)?,
+            content_length: headers.get_as(&CONTENT_LENGTH)?,
+            ranges,
+            clear_ranges,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct RangesInternal {
+    #[serde(rename = "$value", default)]
+    items: Vec<RangeItem>,
+}
+
+impl RangesInternal {
+    fn split(self) -> (Vec<std::ops::Range<u64>>, Vec<std::ops::Range<u64>>) {
+        let mut ranges = Vec::new();
+        let mut clear_ranges = Vec::new();
+        for item in self.items {
+            match item {
+                RangeItem::Range(range) => ranges.push(range.start..range.end + 1),
+                RangeItem::ClearRange(range) => clear_ranges.push(range.start..range.end + 1),
+            }
+        }
+        (ranges, clear_ranges)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+enum RangeItem {
+    Range(RangeInternal),
+    ClearRange(RangeInternal),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RangeInternal {
+    start: u64,
+    end: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    fn test_parse_ranges() -> azure_core::Result<()> {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<Ranges>
+  <Range><Start>0</Start><End>511</End></Range>
+  <ClearRange><Start>512</Start><End>1023</End></ClearRange>
+  <Range><Start>1048576</Start><End>1049087</End></Range>
+</Ranges>"#;
+
+        let (ranges, clear_ranges) = read_xml::<RangesInternal>(xml.as_bytes())?.split();
+        assert_eq!(ranges, vec![0..512, 1_048_576..1_049_088]);
+        assert_eq!(clear_ranges, vec![512..1024]);
+
+        let (ranges, clear_ranges) =
+            read_xml::<RangesInternal>(b"<?xml version=\"1.0\" encoding=\"utf-8\"?><Ranges />")?
+                .split();
+        assert!(ranges.is_empty() && clear_ranges.is_empty());
+        Ok(())
+    }
+}
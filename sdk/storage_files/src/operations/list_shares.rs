@@ -0,0 +1,185 @@
+use crate::ShareServiceClient;
+use azure_core::{
+    date, error::Error, headers::Headers, prelude::*, Method, Pageable, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use serde::Deserialize;
+use std::collections::HashMap;
+use time::OffsetDateTime;
+
+operation! {
+    #[stream]
+    ListShares,
+    client: ShareServiceClient,
+    ?prefix: Prefix,
+    ?max_results: MaxResults,
+    ?include_metadata: bool,
+    ?include_snapshots: bool,
+    ?include_deleted: bool,
+}
+
+impl ListSharesBuilder {
+    pub fn into_stream(self) -> Pageable<ListSharesResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let mut this = self.clone();
+            async move {
+                let mut url = this.client.url()?;
+
+                url.query_pairs_mut().append_pair("comp", "list");
+
+                this.prefix.append_to_url_query(&mut url);
+
+                if let Some(next_marker) = continuation {
+                    next_marker.append_to_url_query(&mut url);
+                }
+
+                this.max_results.append_to_url_query(&mut url);
+
+                let include: Vec<_> = [
+                    (this.include_metadata, "metadata"),
+                    (this.include_snapshots, "snapshots"),
+                    (this.include_deleted, "deleted"),
+                ]
+                .into_iter()
+                .filter_map(|(include, name)| include.unwrap_or(false).then_some(name))
+                .collect();
+                if !include.is_empty() {
+                    url.query_pairs_mut()
+                        .append_pair("include", &include.join(","));
+                }
+
+                let mut request =
+                    ShareServiceClient::finalize_request(url, Method::Get, Headers::new(), None)?;
+
+                let response = this.client.send(&mut this.context, &mut request).await?;
+
+                ListSharesResponse::try_from(response).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListSharesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub prefix: Option<String>,
+    pub max_results: Option<u32>,
+    pub shares: Vec<Share>,
+    pub next_marker: Option<NextMarker>,
+}
+
+impl Continuable for ListSharesResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.next_marker.clone()
+    }
+}
+
+impl ListSharesResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let response: ListSharesResponseInternal = body.xml().await?;
+
+        Ok(ListSharesResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            prefix: response.prefix,
+            max_results: response.max_results,
+            shares: response.shares.shares,
+            next_marker: NextMarker::from_possibly_empty_string(response.next_marker),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListSharesResponseInternal {
+    prefix: Option<String>,
+    max_results: Option<u32>,
+    #[serde(default)]
+    shares: Shares,
+    next_marker: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct Shares {
+    #[serde(rename = "Share", default)]
+    shares: Vec<Share>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Share {
+    pub name: String,
+    pub snapshot: Option<String>,
+    #[serde(default)]
+    pub deleted: bool,
+    pub version: Option<String>,
+    pub properties: ShareProperties,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ShareProperties {
+    #[serde(rename = "Last-Modified", with = "date::rfc1123")]
+    pub last_modified: OffsetDateTime,
+    #[serde(rename = "Etag")]
+    pub etag: String,
+    pub quota: u64,
+    pub access_tier: Option<String>,
+    #[serde(default, with = "date::rfc1123::option")]
+    pub deleted_time: Option<OffsetDateTime>,
+    pub remaining_retention_days: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    fn test_parse_list_shares() -> azure_core::Result<()> {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://account.file.core.windows.net/">
+  <Prefix>sh</Prefix>
+  <MaxResults>2</MaxResults>
+  <Shares>
+    <Share>
+      <Name>share</Name>
+      <Properties>
+        <Last-Modified>Mon, 01 May 2023 10:20:30 GMT</Last-Modified>
+        <Etag>"0x8DB4A3B2C1D0E0F"</Etag>
+        <Quota>5120</Quota>
+        <AccessTier>TransactionOptimized</AccessTier>
+      </Properties>
+      <Metadata><owner>ops</owner></Metadata>
+    </Share>
+    <Share>
+      <Name>share</Name>
+      <Snapshot>2023-05-01T10:20:30.0000000Z</Snapshot>
+      <Properties>
+        <Last-Modified>Mon, 01 May 2023 10:20:30 GMT</Last-Modified>
+        <Etag>"0x8DB4A3B2C1D0E0F"</Etag>
+        <Quota>5120</Quota>
+      </Properties>
+    </Share>
+  </Shares>
+  <NextMarker />
+</EnumerationResults>"#;
+
+        let response: ListSharesResponseInternal = read_xml(xml.as_bytes())?;
+        assert_eq!(response.shares.shares.len(), 2);
+        let share = &response.shares.shares[0];
+        assert_eq!(share.properties.quota, 5120);
+        assert_eq!(share.metadata.get("owner").map(String::as_str), Some("ops"));
+        assert_eq!(
+            response.shares.shares[1].snapshot.as_deref(),
+            Some("2023-05-01T10:20:30.0000000Z")
+        );
+        assert!(NextMarker::from_possibly_empty_string(response.next_marker).is_none());
+        Ok(())
+    }
+}
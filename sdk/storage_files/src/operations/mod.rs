@@ -0,0 +1,61 @@
+mod abort_copy;
+mod acquire_file_lease;
+mod break_file_lease;
+mod change_file_lease;
+mod clear_range;
+mod create_directory;
+mod create_file;
+mod create_share;
+mod create_share_snapshot;
+mod delete_directory;
+mod delete_file;
+mod delete_share;
+mod force_close_handles;
+mod get_directory_properties;
+mod get_file;
+mod get_file_properties;
+mod get_share_properties;
+mod list_directories_and_files;
+mod list_handles;
+mod list_ranges;
+mod list_shares;
+mod put_range;
+mod release_file_lease;
+mod set_directory_metadata;
+mod set_file_metadata;
+mod set_file_properties;
+mod set_share_metadata;
+mod start_copy;
+
+pub use abort_copy::{AbortCopyBuilder, AbortCopyResponse};
+pub use acquire_file_lease::{AcquireFileLeaseBuilder, AcquireFileLeaseResponse};
+pub use break_file_lease::{BreakFileLeaseBuilder, BreakFileLeaseResponse};
+pub use change_file_lease::{ChangeFileLeaseBuilder, ChangeFileLeaseResponse};
+pub use clear_range::{ClearRangeBuilder, ClearRangeResponse};
+pub use create_directory::{CreateDirectoryBuilder, CreateDirectoryResponse};
+pub use create_file::{CreateFileBuilder, CreateFileResponse};
+pub use create_share::{CreateShareBuilder, CreateShareResponse};
+pub use create_share_snapshot::{CreateShareSnapshotBuilder, CreateShareSnapshotResponse};
+pub use delete_directory::{DeleteDirectoryBuilder, DeleteDirectoryResponse};
+pub use delete_file::{DeleteFileBuilder, DeleteFileResponse};
+pub use delete_share::{DeleteShareBuilder, DeleteShareResponse};
+pub use force_close_handles::{ForceCloseHandlesBuilder, ForceCloseHandlesResponse};
+pub use get_directory_properties::{GetDirectoryPropertiesBuilder, GetDirectoryPropertiesResponse};
+pub use get_file::{GetFileBuilder, GetFileResponse};
+pub use get_file_properties::{GetFilePropertiesBuilder, GetFilePropertiesResponse};
+pub use get_share_properties::{GetSharePropertiesBuilder, GetSharePropertiesResponse};
+pub use list_directories_and_files::{
+    Entries, Entry, EntryInfo, EntryProperties, ListDirectoriesAndFilesBuilder,
+    ListDirectoriesAndFilesResponse,
+};
+pub(crate) use list_handles::HandleOwner;
+pub use list_handles::{AccessRightList, Handle, ListHandlesBuilder, ListHandlesResponse};
+pub use list_ranges::{ListRangesBuilder, ListRangesResponse};
+pub use list_shares::{ListSharesBuilder, ListSharesResponse, Share, ShareProperties};
+pub use put_range::{PutRangeBuilder, PutRangeResponse};
+pub use release_file_lease::{ReleaseFileLeaseBuilder, ReleaseFileLeaseResponse};
+pub use set_directory_metadata::{SetDirectoryMetadataBuilder, SetDirectoryMetadataResponse};
+pub use set_file_metadata::{SetFileMetadataBuilder, SetFileMetadataResponse};
+pub use set_file_properties::{SetFilePropertiesBuilder, SetFilePropertiesResponse};
+pub use set_share_metadata::{SetShareMetadataBuilder, SetShareMetadataResponse};
+pub use start_copy::{StartCopyBuilder, StartCopyResponse};
@@ -0,0 +1,66 @@
+use crate::{options::WRITE, ContentMd5, FileClient};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers, MS_RANGE},
+    prelude::*,
+    Body, Method, Response as AzureResponse,
+};
+use azure_storage::{
+    headers::{content_md5_from_headers_optional, CommonStorageResponseHeaders},
+    ConsistencyMD5,
+};
+use time::OffsetDateTime;
+
+operation! {
+    PutRange,
+    client: FileClient,
+    range: Range,
+    content: Body,
+    ?content_md5: ContentMd5,
+    ?lease_id: LeaseId
+}
+
+impl PutRangeBuilder {
+    pub fn into_future(mut self) -> PutRange {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "range");
+
+            let mut headers = Headers::new();
+            headers.insert(WRITE, "update");
+            headers.insert(MS_RANGE, self.range.to_string());
+            headers.add(self.content_md5);
+            headers.add(self.lease_id);
+
+            let mut request =
+                FileClient::finalize_request(url, Method::Put, headers, Some(self.content))?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PutRangeResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub content_md5: Option<ConsistencyMD5>,
+}
+
+impl TryFrom<AzureResponse> for PutRangeResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(PutRangeResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            content_md5: content_md5_from_headers_optional(headers)?,
+        })
+    }
+}
@@ -0,0 +1,55 @@
+use crate::FileClient;
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, Headers},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+
+operation! {
+    SetFileMetadata,
+    client: FileClient,
+    metadata: Metadata,
+    ?lease_id: LeaseId
+}
+
+impl SetFileMetadataBuilder {
+    pub fn into_future(mut self) -> SetFileMetadata {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "metadata");
+
+            let mut headers = Headers::new();
+            for m in self.metadata.iter() {
+                headers.add(m);
+            }
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetFileMetadataResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+}
+
+impl TryFrom<AzureResponse> for SetFileMetadataResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(SetFileMetadataResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+        })
+    }
+}
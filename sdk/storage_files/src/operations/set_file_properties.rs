@@ -0,0 +1,77 @@
+use crate::{
+    options::CONTENT_LENGTH, FileCacheControl, FileClient, FileContentDisposition,
+    FileContentEncoding, FileContentLanguage, FileContentType, SmbInfo, SmbProperties,
+};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use time::OffsetDateTime;
+
+operation! {
+    SetFileProperties,
+    client: FileClient,
+    ?size: u64,
+    ?content_type: FileContentType,
+    ?content_encoding: FileContentEncoding,
+    ?content_language: FileContentLanguage,
+    ?content_disposition: FileContentDisposition,
+    ?cache_control: FileCacheControl,
+    ?smb_properties: SmbProperties,
+    ?lease_id: LeaseId
+}
+
+impl SetFilePropertiesBuilder {
+    pub fn into_future(mut self) -> SetFileProperties {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "properties");
+
+            let mut headers = Headers::new();
+            if let Some(size) = self.size {
+                headers.insert(CONTENT_LENGTH, size.to_string());
+            }
+            headers.add(self.content_type);
+            headers.add(self.content_encoding);
+            headers.add(self.content_language);
+            headers.add(self.content_disposition);
+            headers.add(self.cache_control);
+            if let Some(smb_properties) = &self.smb_properties {
+                smb_properties.add_to(&mut headers);
+            }
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetFilePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub smb_info: SmbInfo,
+}
+
+impl TryFrom<AzureResponse> for SetFilePropertiesResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(SetFilePropertiesResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            smb_info: SmbInfo::from_headers(headers)?,
+        })
+    }
+}
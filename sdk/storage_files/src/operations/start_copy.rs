@@ -0,0 +1,68 @@
+use crate::{CopyStatus, FileClient, SmbProperties};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers, COPY_SOURCE, COPY_STATUS},
+    prelude::*,
+    Method, Response as AzureResponse, Url,
+};
+use azure_storage::{copy_id_from_headers, headers::CommonStorageResponseHeaders, CopyId};
+use time::OffsetDateTime;
+
+operation! {
+    StartCopy,
+    client: FileClient,
+    copy_source: Url,
+    ?smb_properties: SmbProperties,
+    ?metadata: Metadata,
+    ?lease_id: LeaseId
+}
+
+impl StartCopyBuilder {
+    pub fn into_future(mut self) -> StartCopy {
+        Box::pin(async move {
+            let url = self.client.url()?;
+
+            let mut headers = Headers::new();
+            headers.insert(COPY_SOURCE, self.copy_source.to_string());
+            if let Some(smb_properties) = &self.smb_properties {
+                smb_properties.add_to(&mut headers);
+            }
+            if let Some(metadata) = &self.metadata {
+                for m in metadata.iter() {
+                    headers.add(m);
+                }
+            }
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StartCopyResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub copy_id: CopyId,
+    pub copy_status: CopyStatus,
+}
+
+impl TryFrom<AzureResponse> for StartCopyResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(StartCopyResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            copy_id: copy_id_from_headers(headers)?,
+            copy_status: headers.get_as(&COPY_STATUS)?,
+        })
+    }
+}
@@ -0,0 +1,400 @@
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::{Header, HeaderName, HeaderValue, Headers},
+    AppendToUrlQuery, Url,
+};
+use std::{fmt, str::FromStr};
+use time::OffsetDateTime;
+
+/// Define a request option sent as a header of the File service.
+macro_rules! file_header {
+    ($(#[$outer:meta])* $name:ident, $header:ident) => {
+        azure_core::request_option!($(#[$outer])* $name);
+
+        impl Header for $name {
+            fn name(&self) -> HeaderName {
+                $header
+            }
+
+            fn value(&self) -> HeaderValue {
+                HeaderValue::from_cow(self.0.clone())
+            }
+        }
+    };
+}
+
+pub(crate) const CONTENT_TYPE: HeaderName = HeaderName::from_static("x-ms-content-type");
+pub(crate) const CONTENT_ENCODING: HeaderName = HeaderName::from_static("x-ms-content-encoding");
+pub(crate) const CONTENT_LANGUAGE: HeaderName = HeaderName::from_static("x-ms-content-language");
+pub(crate) const CONTENT_DISPOSITION: HeaderName =
+    HeaderName::from_static("x-ms-content-disposition");
+pub(crate) const CACHE_CONTROL: HeaderName = HeaderName::from_static("x-ms-cache-control");
+pub(crate) const FILE_TYPE: HeaderName = HeaderName::from_static("x-ms-type");
+pub(crate) const CONTENT_LENGTH: HeaderName = HeaderName::from_static("x-ms-content-length");
+pub(crate) const SHARE_QUOTA: HeaderName = HeaderName::from_static("x-ms-share-quota");
+pub(crate) const ACCESS_TIER: HeaderName = HeaderName::from_static("x-ms-access-tier");
+pub(crate) const SNAPSHOT: HeaderName = HeaderName::from_static("x-ms-snapshot");
+pub(crate) const FILE_ATTRIBUTES: HeaderName = HeaderName::from_static("x-ms-file-attributes");
+pub(crate) const FILE_CREATION_TIME: HeaderName =
+    HeaderName::from_static("x-ms-file-creation-time");
+pub(crate) const FILE_LAST_WRITE_TIME: HeaderName =
+    HeaderName::from_static("x-ms-file-last-write-time");
+pub(crate) const FILE_CHANGE_TIME: HeaderName = HeaderName::from_static("x-ms-file-change-time");
+pub(crate) const FILE_PERMISSION: HeaderName = HeaderName::from_static("x-ms-file-permission");
+pub(crate) const FILE_PERMISSION_KEY: HeaderName =
+    HeaderName::from_static("x-ms-file-permission-key");
+pub(crate) const FILE_ID: HeaderName = HeaderName::from_static("x-ms-file-id");
+pub(crate) const FILE_PARENT_ID: HeaderName = HeaderName::from_static("x-ms-file-parent-id");
+pub(crate) const HANDLE_ID: HeaderName = HeaderName::from_static("x-ms-handle-id");
+pub(crate) const RECURSIVE: HeaderName = HeaderName::from_static("x-ms-recursive");
+pub(crate) const MARKER: HeaderName = HeaderName::from_static("x-ms-marker");
+pub(crate) const NUMBER_OF_HANDLES_CLOSED: HeaderName =
+    HeaderName::from_static("x-ms-number-of-handles-closed");
+pub(crate) const NUMBER_OF_HANDLES_FAILED: HeaderName =
+    HeaderName::from_static("x-ms-number-of-handles-failed");
+pub(crate) const FILE_EXTENDED_INFO: HeaderName =
+    HeaderName::from_static("x-ms-file-extended-info");
+pub(crate) const WRITE: HeaderName = HeaderName::from_static("x-ms-write");
+pub(crate) const COPY_ACTION: HeaderName = HeaderName::from_static("x-ms-copy-action");
+
+file_header!(FileContentType, CONTENT_TYPE);
+file_header!(FileContentEncoding, CONTENT_ENCODING);
+file_header!(FileContentLanguage, CONTENT_LANGUAGE);
+file_header!(FileContentDisposition, CONTENT_DISPOSITION);
+file_header!(FileCacheControl, CACHE_CONTROL);
+file_header!(
+    /// The access tier of a share: `TransactionOptimized`, `Hot`, `Cool` or `Premium`.
+    ShareAccessTier,
+    ACCESS_TIER
+);
+
+/// The maximum size of a share, in GiB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShareQuota(pub u64);
+
+impl Header for ShareQuota {
+    fn name(&self) -> HeaderName {
+        SHARE_QUOTA
+    }
+
+    fn value(&self) -> HeaderValue {
+        self.0.to_string().into()
+    }
+}
+
+impl From<u64> for ShareQuota {
+    fn from(quota: u64) -> Self {
+        Self(quota)
+    }
+}
+
+/// The MD5 hash of the content of a range, checked by the service on upload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentMd5(pub [u8; 16]);
+
+impl Header for ContentMd5 {
+    fn name(&self) -> HeaderName {
+        azure_core::headers::CONTENT_MD5
+    }
+
+    fn value(&self) -> HeaderValue {
+        azure_core::base64::encode(self.0).into()
+    }
+}
+
+impl From<[u8; 16]> for ContentMd5 {
+    fn from(md5: [u8; 16]) -> Self {
+        Self(md5)
+    }
+}
+
+/// A snapshot of a share, identified by its creation time as returned by the service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareSnapshot(String);
+
+impl ShareSnapshot {
+    pub fn new(snapshot: impl Into<String>) -> Self {
+        Self(snapshot.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ShareSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AppendToUrlQuery for ShareSnapshot {
+    fn append_to_url_query(&self, url: &mut Url) {
+        url.query_pairs_mut().append_pair("sharesnapshot", &self.0);
+    }
+}
+
+impl From<String> for ShareSnapshot {
+    fn from(snapshot: String) -> Self {
+        Self(snapshot)
+    }
+}
+
+impl From<&str> for ShareSnapshot {
+    fn from(snapshot: &str) -> Self {
+        Self(snapshot.to_owned())
+    }
+}
+
+/// The SMB attributes of a file or a directory.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileAttributes {
+    pub read_only: bool,
+    pub hidden: bool,
+    pub system: bool,
+    pub directory: bool,
+    pub archive: bool,
+    pub temporary: bool,
+    pub offline: bool,
+    pub not_content_indexed: bool,
+    pub no_scrub_data: bool,
+}
+
+impl FileAttributes {
+    fn names(&self) -> impl Iterator<Item = &'static str> {
+        [
+            (self.read_only, "ReadOnly"),
+            (self.hidden, "Hidden"),
+            (self.system, "System"),
+            (self.directory, "Directory"),
+            (self.archive, "Archive"),
+            (self.temporary, "Temporary"),
+            (self.offline, "Offline"),
+            (self.not_content_indexed, "NotContentIndexed"),
+            (self.no_scrub_data, "NoScrubData"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+    }
+}
+
+impl fmt::Display for FileAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<_> = self.names().collect();
+        if names.is_empty() {
+            f.write_str("None")
+        } else {
+            f.write_str(&names.join(" | "))
+        }
+    }
+}
+
+impl FromStr for FileAttributes {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut attributes = Self::default();
+        for name in s.split('|').map(str::trim).filter(|name| !name.is_empty()) {
+            let attribute = match name {
+                "None" | "Normal" => continue,
+                "ReadOnly" => &mut attributes.read_only,
+                "Hidden" => &mut attributes.hidden,
+                "System" => &mut attributes.system,
+                "Directory" => &mut attributes.directory,
+                "Archive" => &mut attributes.archive,
+                "Temporary" => &mut attributes.temporary,
+                "Offline" => &mut attributes.offline,
+                "NotContentIndexed" => &mut attributes.not_content_indexed,
+                "NoScrubData" => &mut attributes.no_scrub_data,
+                _ => {
+                    return Err(Error::with_message(ErrorKind::DataConversion, || {
+                        format!("unknown file attribute: {name}")
+                    }))
+                }
+            };
+            *attribute = true;
+        }
+        Ok(attributes)
+    }
+}
+
+/// A time of a file or a directory to set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileTime {
+    /// The time of the request.
+    Now,
+    /// Keep the current value.
+    Preserve,
+    At(OffsetDateTime),
+}
+
+impl fmt::Display for FileTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Now => f.write_str("now"),
+            Self::Preserve => f.write_str("preserve"),
+            Self::At(time) => f.write_str(&format_file_time(*time)),
+        }
+    }
+}
+
+impl From<OffsetDateTime> for FileTime {
+    fn from(time: OffsetDateTime) -> Self {
+        Self::At(time)
+    }
+}
+
+/// Format a time the way the File service expects it, with seven fractional digits.
+fn format_file_time(time: OffsetDateTime) -> String {
+    let time = time.to_offset(time::UtcOffset::UTC);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:07}Z",
+        time.year(),
+        u8::from(time.month()),
+        time.day(),
+        time.hour(),
+        time.minute(),
+        time.second(),
+        time.nanosecond() / 100
+    )
+}
+
+/// The security descriptor of a file or a directory to set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilePermission {
+    /// Inherit the permission of the parent directory.
+    Inherit,
+    /// Keep the current permission.
+    Preserve,
+    /// A permission in the Security Descriptor Definition Language.
+    Sddl(String),
+    /// A permission stored in the share, by its key.
+    Key(String),
+}
+
+/// The SMB properties of a file or a directory to set on creation or update.
+///
+/// Properties left unset take their default value on creation, and are preserved on update.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SmbProperties {
+    attributes: Option<FileAttributes>,
+    creation_time: Option<FileTime>,
+    last_write_time: Option<FileTime>,
+    change_time: Option<FileTime>,
+    permission: Option<FilePermission>,
+}
+
+impl SmbProperties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    setters! {
+        attributes: FileAttributes => Some(attributes),
+        creation_time: FileTime => Some(creation_time),
+        last_write_time: FileTime => Some(last_write_time),
+        change_time: FileTime => Some(change_time),
+        permission: FilePermission => Some(permission),
+    }
+
+    pub(crate) fn add_to(&self, headers: &mut Headers) {
+        if let Some(attributes) = &self.attributes {
+            headers.insert(FILE_ATTRIBUTES, attributes.to_string());
+        }
+        for (name, time) in [
+            (FILE_CREATION_TIME, &self.creation_time),
+            (FILE_LAST_WRITE_TIME, &self.last_write_time),
+            (FILE_CHANGE_TIME, &self.change_time),
+        ] {
+            if let Some(time) = time {
+                headers.insert(name, time.to_string());
+            }
+        }
+        match &self.permission {
+            Some(FilePermission::Inherit) => headers.insert(FILE_PERMISSION, "inherit"),
+            Some(FilePermission::Preserve) => headers.insert(FILE_PERMISSION, "preserve"),
+            Some(FilePermission::Sddl(sddl)) => headers.insert(FILE_PERMISSION, sddl.clone()),
+            Some(FilePermission::Key(key)) => headers.insert(FILE_PERMISSION_KEY, key.clone()),
+            None => {}
+        }
+    }
+}
+
+/// The SMB properties of a file or a directory, as returned by the service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmbInfo {
+    pub attributes: FileAttributes,
+    pub creation_time: OffsetDateTime,
+    pub last_write_time: OffsetDateTime,
+    pub change_time: Option<OffsetDateTime>,
+    pub permission_key: String,
+    pub file_id: String,
+    pub parent_id: String,
+}
+
+impl SmbInfo {
+    pub(crate) fn from_headers(headers: &Headers) -> azure_core::Result<Self> {
+        let time = |name| azure_core::date::parse_rfc3339(headers.get_str(name)?);
+        Ok(Self {
+            attributes: headers.get_as(&FILE_ATTRIBUTES)?,
+            creation_time: time(&FILE_CREATION_TIME)?,
+            last_write_time: time(&FILE_LAST_WRITE_TIME)?,
+            change_time: headers
+                .get_optional_str(&FILE_CHANGE_TIME)
+                .map(azure_core::date::parse_rfc3339)
+                .transpose()?,
+            permission_key: headers.get_as(&FILE_PERMISSION_KEY)?,
+            file_id: headers.get_as(&FILE_ID)?,
+            parent_id: headers.get_as(&FILE_PARENT_ID)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_file_attributes() -> azure_core::Result<()> {
+        let attributes: FileAttributes = "ReadOnly | Archive".parse()?;
+        assert!(attributes.read_only && attributes.archive && !attributes.hidden);
+        assert_eq!(attributes.to_string(), "ReadOnly | Archive");
+        assert_eq!(
+            "Hidden|System".parse::<FileAttributes>()?.to_string(),
+            "Hidden | System"
+        );
+        assert_eq!(FileAttributes::default().to_string(), "None");
+        assert_eq!("None".parse::<FileAttributes>()?, FileAttributes::default());
+        assert!("Sticky".parse::<FileAttributes>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_smb_properties_headers() {
+        let mut headers = Headers::new();
+        SmbProperties::new()
+            .attributes(FileAttributes {
+                hidden: true,
+                ..Default::default()
+            })
+            .creation_time(datetime!(2023-05-01 10:20:30.123456789 UTC))
+            .last_write_time(FileTime::Now)
+            .permission(FilePermission::Key("1234*5678".to_owned()))
+            .add_to(&mut headers);
+
+        assert_eq!(headers.get_optional_str(&FILE_ATTRIBUTES), Some("Hidden"));
+        assert_eq!(
+            headers.get_optional_str(&FILE_CREATION_TIME),
+            Some("2023-05-01T10:20:30.1234567Z")
+        );
+        assert_eq!(headers.get_optional_str(&FILE_LAST_WRITE_TIME), Some("now"));
+        assert_eq!(headers.get_optional_str(&FILE_CHANGE_TIME), None);
+        assert_eq!(headers.get_optional_str(&FILE_PERMISSION), None);
+        assert_eq!(
+            headers.get_optional_str(&FILE_PERMISSION_KEY),
+            Some("1234*5678")
+        );
+    }
+}
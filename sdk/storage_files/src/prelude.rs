@@ -0,0 +1,6 @@
+pub use crate::{
+    ContentMd5, DirectoryClient, FileAttributes, FileCacheControl, FileClient,
+    FileContentDisposition, FileContentEncoding, FileContentLanguage, FileContentType,
+    FileLeaseClient, FilePermission, FileTime, ShareAccessTier, ShareClient, ShareQuota,
+    ShareServiceClient, ShareSnapshot, SmbProperties,
+};
@@ -2,7 +2,13 @@ use crate::{
     operations::*, PopReceipt, PopReceiptClient, QueueServiceClient, QueueStoredAccessPolicy,
 };
 use azure_core::{prelude::*, Request, Response, Url};
+use azure_storage::{
+    clients::{service_sas_signing_key, ServiceType},
+    prelude::QueueSasPermissions,
+    shared_access_signature::{service_sas::QueueSharedAccessSignature, SasToken},
+};
 use std::fmt::Debug;
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone)]
 pub struct QueueClient {
@@ -90,6 +96,52 @@ impl QueueClient {
         PopReceiptClient::new(self.clone(), pop_receipt.into())
     }
 
+    /// Create a shared access signature for the queue.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: QueueSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<QueueSharedAccessSignature> {
+        let (canonicalized_resource, key) = self.signing_key().await?;
+        Ok(QueueSharedAccessSignature::new(
+            key,
+            canonicalized_resource,
+            permissions,
+            expiry,
+        ))
+    }
+
+    /// Create a shared access signature for the queue, governed by one of its stored access policies.
+    pub async fn stored_access_policy_signature(
+        &self,
+        identifier: impl Into<String>,
+    ) -> azure_core::Result<QueueSharedAccessSignature> {
+        let (canonicalized_resource, key) = self.signing_key().await?;
+        Ok(QueueSharedAccessSignature::from_stored_access_policy(
+            key,
+            canonicalized_resource,
+            identifier.into(),
+        ))
+    }
+
+    async fn signing_key(&self) -> azure_core::Result<(String, azure_core::auth::Secret)> {
+        service_sas_signing_key(
+            self.service_client.credentials(),
+            ServiceType::Queue,
+            self.queue_name(),
+        )
+        .await
+    }
+
+    pub fn generate_signed_queue_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
     pub fn queue_name(&self) -> &str {
         &self.queue_name
     }
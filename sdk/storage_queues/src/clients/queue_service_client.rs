@@ -3,7 +3,7 @@ use azure_core::{ClientOptions, Context, Pipeline, Request, Response, Url};
 use azure_storage::{
     clients::{new_pipeline_from_options, ServiceType},
     prelude::StorageCredentials,
-    CloudLocation,
+    CloudLocation, ConnectionString,
 };
 use std::fmt::Debug;
 
@@ -44,6 +44,18 @@ impl QueueServiceClientBuilder {
         }
     }
 
+    /// Create a new instance of `QueueServiceClientBuilder` from a storage connection string.
+    ///
+    /// The endpoint of the service, or else the account name, protocol and endpoint suffix, give
+    /// the location. The shared access signature or account key give the credentials.
+    pub fn from_connection_string(connection_string: &str) -> azure_core::Result<Self> {
+        let connection_string = ConnectionString::new(connection_string)?;
+        Ok(Self::with_location(
+            connection_string.cloud_location(ServiceType::Queue)?,
+            connection_string.storage_credentials()?,
+        ))
+    }
+
     /// Use the emulator with default settings
     #[must_use]
     pub fn emulator() -> Self {
@@ -66,8 +78,9 @@ impl QueueServiceClientBuilder {
         } = self;
 
         QueueServiceClient {
-            pipeline: new_pipeline_from_options(options, credentials),
+            pipeline: new_pipeline_from_options(options, credentials.clone()),
             cloud_location,
+            credentials,
         }
     }
 
@@ -106,6 +119,7 @@ impl QueueServiceClientBuilder {
 pub struct QueueServiceClient {
     pipeline: Pipeline,
     cloud_location: CloudLocation,
+    credentials: StorageCredentials,
 }
 
 impl QueueServiceClient {
@@ -115,6 +129,11 @@ impl QueueServiceClient {
         QueueServiceClientBuilder::new(account, credentials).build()
     }
 
+    /// Create a new `QueueServiceClient` from a storage connection string.
+    pub fn from_connection_string(connection_string: &str) -> azure_core::Result<Self> {
+        Ok(QueueServiceClientBuilder::from_connection_string(connection_string)?.build())
+    }
+
     pub fn list_queues(&self) -> ListQueuesBuilder {
         ListQueuesBuilder::new(self.clone())
     }
@@ -146,6 +165,10 @@ impl QueueServiceClient {
         self.cloud_location.url(ServiceType::Queue)
     }
 
+    pub(crate) fn credentials(&self) -> &StorageCredentials {
+        &self.credentials
+    }
+
     pub(crate) fn finalize_request(
         url: Url,
         method: azure_core::Method,